@@ -146,6 +146,23 @@ contract AndeConsensus is
     /// @notice Bloques de gracia antes de jailing por downtime
     uint256 public constant DOWNTIME_JAIL_BLOCKS = 1000;
 
+    /// @notice Bloques que un validator permanece encarcelado antes de poder salir (~1 día a 2s)
+    uint256 public constant JAIL_DURATION_BLOCKS = 43_200;
+
+    /// @notice Penalty por proponer fuera de turno (10% del stake)
+    uint256 public constant UNSCHEDULED_PROPOSAL_SLASH_BPS = 1000;
+
+    /// @notice Dominio de las firmas de atestación de los nodos
+    bytes public constant ATTESTATION_DOMAIN = "ANDE_ATTESTATION_V2";
+
+    /// @notice Dominio de las firmas de propuesta de los nodos
+    bytes public constant PROPOSAL_DOMAIN = "ANDE_PROPOSAL_V1";
+
+    /// @notice Tipos de evidencia de slashing
+    uint8 public constant EVIDENCE_DUPLICATE_ATTESTATION = 0;
+    uint8 public constant EVIDENCE_DUPLICATE_PROPOSAL = 1;
+    uint8 public constant EVIDENCE_UNSCHEDULED_PROPOSAL = 2;
+
     // ============================================
     // STATE VARIABLES
     // ============================================
@@ -192,6 +209,12 @@ contract AndeConsensus is
     /// @notice Referencia al contrato de sequencer registry
     address public sequencerRegistry;
 
+    /// @notice Evidencias de slashing ya procesadas (evita doble slash)
+    mapping(bytes32 => bool) public processedEvidence;
+
+    /// @notice Bloque a partir del cual un validator encarcelado puede salir
+    mapping(address => uint256) public jailReleaseBlock;
+
     // ============================================
     // EVENTS
     // ============================================
@@ -241,6 +264,13 @@ contract AndeConsensus is
         uint256 timestamp
     );
 
+    event EvidenceSubmitted(
+        bytes32 indexed evidenceId,
+        address indexed validator,
+        uint8 kind,
+        uint256 blockNumber
+    );
+
     event ValidatorSlashed(
         address indexed validator,
         uint256 amount,
@@ -275,7 +305,11 @@ contract AndeConsensus is
     error EpochNotEnded();
     error InvalidBlockNumber(uint256 expected, uint256 actual);
     error DoubleSign(address validator, uint256 blockNumber);
+    error EvidenceAlreadyProcessed(bytes32 evidenceId);
+    error InvalidEvidence();
     error InvalidVotingPower();
+    error ValidatorNotJailed(address validator);
+    error JailPeriodNotOver(uint256 releaseBlock, uint256 currentBlock);
 
     // ============================================
     // CONSTRUCTOR & INITIALIZER
@@ -679,8 +713,7 @@ contract AndeConsensus is
 
         // Aplicar slash
         val.stake -= slashAmount;
-        val.jailed = true;
-        val.active = false;
+        _jail(val);
 
         emit ValidatorSlashed(validator, slashAmount, "Double sign", block.timestamp);
         emit ValidatorJailed(validator, "Double sign", block.timestamp);
@@ -705,13 +738,162 @@ contract AndeConsensus is
 
         // Aplicar slash
         val.stake -= slashAmount;
-        val.jailed = true;
-        val.active = false;
+        _jail(val);
 
         emit ValidatorSlashed(validator, slashAmount, "Downtime", block.timestamp);
         emit ValidatorJailed(validator, "Downtime", block.timestamp);
     }
 
+    /**
+     * @notice Slash por equivocación: dos firmas del mismo validator para la
+     *         misma altura con hashes distintos
+     * @dev Verifica las firmas que producen los nodos ande-consensus:
+     *      propuestas: keccak256(dominio ‖ uint64 chainId ‖ uint64 blockNumber ‖ blockHash)
+     *      atestaciones: keccak256(dominio ‖ uint64 chainId ‖ uint64 epoch ‖ uint64 blockNumber ‖ blockHash)
+     * @param proposal true para propuestas, false para atestaciones
+     * @param epoch Época a la que se comprometen las atestaciones; se ignora para propuestas
+     */
+    function slashEquivocation(
+        address validator,
+        bool proposal,
+        uint64 blockNumber,
+        uint64 epoch,
+        bytes32 blockHash1,
+        bytes calldata signature1,
+        bytes32 blockHash2,
+        bytes calldata signature2
+    ) external onlyRole(SLASHER_ROLE) {
+        ValidatorInfo storage val = validators[validator];
+        if (val.validator == address(0)) revert ValidatorNotFound(validator);
+        if (blockHash1 == blockHash2) revert InvalidEvidence();
+
+        bytes memory prefix = proposal
+            ? abi.encodePacked(PROPOSAL_DOMAIN, uint64(block.chainid))
+            : abi.encodePacked(ATTESTATION_DOMAIN, uint64(block.chainid), epoch);
+        if (
+            _nodeSigner(prefix, blockNumber, blockHash1, signature1) != validator
+                || _nodeSigner(prefix, blockNumber, blockHash2, signature2) != validator
+        ) {
+            revert InvalidSignature();
+        }
+
+        uint8 kind = proposal ? EVIDENCE_DUPLICATE_PROPOSAL : EVIDENCE_DUPLICATE_ATTESTATION;
+        (bytes32 low, bytes32 high) = blockHash1 < blockHash2 ? (blockHash1, blockHash2) : (blockHash2, blockHash1);
+        _recordEvidence(validator, kind, blockNumber, low, high);
+
+        _slashAndJail(val, DOUBLE_SIGN_SLASH_BPS, "Double sign");
+    }
+
+    /**
+     * @notice Slash por una propuesta firmada de un validator que no era el
+     *         proposer programado para esa altura
+     * @dev Diseño de reporter confiable: la firma se verifica on-chain, pero
+     *      el proposer programado lo calcula off-chain el slasher a partir del
+     *      validator set de la época y el contrato confía en `scheduledProposer`.
+     *      Un SLASHER_ROLE malicioso podría slashear a cualquier validator que
+     *      haya firmado una propuesta, así que el rol solo se otorga a los nodos
+     *      secuenciadores operados (ver scripts/deploy-testnet.sh) y se revoca
+     *      al retirarlos. On-chain solo se exige que `scheduledProposer` sea un
+     *      validator registrado distinto del acusado.
+     */
+    function slashUnscheduledProposal(
+        address validator,
+        uint64 blockNumber,
+        bytes32 blockHash,
+        bytes calldata signature,
+        address scheduledProposer
+    ) external onlyRole(SLASHER_ROLE) {
+        ValidatorInfo storage val = validators[validator];
+        if (val.validator == address(0)) revert ValidatorNotFound(validator);
+        if (validator == scheduledProposer) revert InvalidEvidence();
+        if (validators[scheduledProposer].validator == address(0)) revert ValidatorNotFound(scheduledProposer);
+
+        bytes memory prefix = abi.encodePacked(PROPOSAL_DOMAIN, uint64(block.chainid));
+        if (_nodeSigner(prefix, blockNumber, blockHash, signature) != validator) {
+            revert InvalidSignature();
+        }
+
+        _recordEvidence(validator, EVIDENCE_UNSCHEDULED_PROPOSAL, blockNumber, blockHash, blockHash);
+
+        _slashAndJail(val, UNSCHEDULED_PROPOSAL_SLASH_BPS, "Unscheduled proposal");
+    }
+
+    /**
+     * @notice Identificador de una evidencia, igual al que calculan los nodos
+     */
+    function evidenceId(
+        address validator,
+        uint8 kind,
+        uint64 blockNumber,
+        bytes32 blockHash1,
+        bytes32 blockHash2
+    ) public pure returns (bytes32) {
+        return keccak256(abi.encodePacked(validator, kind, blockNumber, blockHash1, blockHash2));
+    }
+
+    /**
+     * @notice Firmante de un mensaje de nodo (atestación o propuesta)
+     */
+    function _nodeSigner(
+        bytes memory prefix,
+        uint64 blockNumber,
+        bytes32 blockHash,
+        bytes calldata signature
+    ) internal pure returns (address) {
+        bytes32 digest = keccak256(abi.encodePacked(prefix, blockNumber, blockHash));
+        return ECDSA.recover(digest, signature);
+    }
+
+    /**
+     * @notice Marca una evidencia como procesada o revierte si ya lo estaba
+     */
+    function _recordEvidence(
+        address validator,
+        uint8 kind,
+        uint64 blockNumber,
+        bytes32 blockHash1,
+        bytes32 blockHash2
+    ) internal {
+        bytes32 id = evidenceId(validator, kind, blockNumber, blockHash1, blockHash2);
+        if (processedEvidence[id]) revert EvidenceAlreadyProcessed(id);
+        processedEvidence[id] = true;
+
+        emit EvidenceSubmitted(id, validator, kind, blockNumber);
+    }
+
+    /**
+     * @notice Aplica un slash en basis points del stake y encarcela al validator
+     */
+    function _slashAndJail(ValidatorInfo storage val, uint256 slashBps, string memory reason) internal {
+        uint256 slashAmount = (val.stake * slashBps) / BASIS_POINTS;
+
+        val.stake -= slashAmount;
+        _jail(val);
+
+        emit ValidatorSlashed(val.validator, slashAmount, reason, block.timestamp);
+        emit ValidatorJailed(val.validator, reason, block.timestamp);
+    }
+
+    /**
+     * @notice Encarcela a un validator durante JAIL_DURATION_BLOCKS
+     */
+    function _jail(ValidatorInfo storage val) internal {
+        val.jailed = true;
+        val.active = false;
+        jailReleaseBlock[val.validator] = block.number + JAIL_DURATION_BLOCKS;
+    }
+
+    /**
+     * @notice Saca de la cárcel a un validator y lo reactiva
+     */
+    function _unjail(ValidatorInfo storage val) internal {
+        val.jailed = false;
+        val.active = true;
+        delete jailReleaseBlock[val.validator];
+
+        emit ValidatorUnjailed(val.validator, block.timestamp);
+    }
+
     /**
      * @notice Unjail a un validator
      */
@@ -722,10 +904,22 @@ contract AndeConsensus is
         if (val.validator == address(0)) revert ValidatorNotFound(validator);
         if (!val.jailed) return;
 
-        val.jailed = false;
-        val.active = true;
+        _unjail(val);
+    }
+
+    /**
+     * @notice El propio validator sale de la cárcel una vez cumplido el periodo
+     * @dev Lo llama el nodo del validator (auto-unjail) desde su dirección de sequencer
+     */
+    function unjail() external {
+        ValidatorInfo storage val = validators[msg.sender];
+        if (val.validator == address(0)) revert ValidatorNotFound(msg.sender);
+        if (!val.jailed) revert ValidatorNotJailed(msg.sender);
+
+        uint256 releaseBlock = jailReleaseBlock[msg.sender];
+        if (block.number < releaseBlock) revert JailPeriodNotOver(releaseBlock, block.number);
 
-        emit ValidatorUnjailed(validator, block.timestamp);
+        _unjail(val);
     }
 
     // ============================================
@@ -402,6 +402,147 @@ contract AndeConsensusTest is Test {
         vm.stopPrank();
     }
     
+    function testSelfUnjailAfterJailPeriod() public {
+        vm.startPrank(admin);
+        consensus.registerValidator(
+            validator2,
+            bytes32(uint256(2)),
+            "https://validator2.ande.network",
+            100000 * 1e18,
+            100
+        );
+        consensus.slashDowntime(validator2);
+        vm.stopPrank();
+
+        uint256 releaseBlock = consensus.jailReleaseBlock(validator2);
+        assertEq(releaseBlock, block.number + consensus.JAIL_DURATION_BLOCKS());
+
+        // Antes de cumplir el periodo no puede salir
+        vm.prank(validator2);
+        vm.expectRevert(
+            abi.encodeWithSelector(AndeConsensus.JailPeriodNotOver.selector, releaseBlock, block.number)
+        );
+        consensus.unjail();
+
+        vm.roll(releaseBlock);
+        vm.prank(validator2);
+        consensus.unjail();
+
+        AndeConsensus.ValidatorInfo memory info = consensus.getValidatorInfo(validator2);
+        assertFalse(info.jailed);
+        assertTrue(info.active);
+        assertEq(consensus.jailReleaseBlock(validator2), 0);
+
+        // Un validator libre no puede volver a salir
+        vm.prank(validator2);
+        vm.expectRevert(abi.encodeWithSelector(AndeConsensus.ValidatorNotJailed.selector, validator2));
+        consensus.unjail();
+    }
+    
+    function _nodeSignature(uint256 key, bytes memory domain, uint64 blockNumber, bytes32 blockHash)
+        internal
+        view
+        returns (bytes memory)
+    {
+        bytes32 digest = keccak256(abi.encodePacked(domain, uint64(block.chainid), blockNumber, blockHash));
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(key, digest);
+        return abi.encodePacked(r, s, v);
+    }
+    
+    function _attestationSignature(uint256 key, uint64 epoch, uint64 blockNumber, bytes32 blockHash)
+        internal
+        view
+        returns (bytes memory)
+    {
+        bytes32 digest = keccak256(
+            abi.encodePacked(consensus.ATTESTATION_DOMAIN(), uint64(block.chainid), epoch, blockNumber, blockHash)
+        );
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(key, digest);
+        return abi.encodePacked(r, s, v);
+    }
+    
+    function testSlashEquivocation() public {
+        uint256 key = 0xA11CE;
+        address signer = vm.addr(key);
+        
+        vm.startPrank(admin);
+        consensus.registerValidator(
+            signer,
+            bytes32(uint256(4)),
+            "https://signer.ande.network",
+            100000 * 1e18,
+            100
+        );
+        
+        bytes32 hash1 = keccak256("block-a");
+        bytes32 hash2 = keccak256("block-b");
+        bytes memory sig1 = _attestationSignature(key, 3, 42, hash1);
+        bytes memory sig2 = _attestationSignature(key, 3, 42, hash2);
+        
+        // Same hash twice is not equivocation
+        vm.expectRevert(AndeConsensus.InvalidEvidence.selector);
+        consensus.slashEquivocation(signer, false, 42, 3, hash1, sig1, hash1, sig1);
+        
+        // Attestation signatures do not count as proposals
+        vm.expectRevert(AndeConsensus.InvalidSignature.selector);
+        consensus.slashEquivocation(signer, true, 42, 3, hash1, sig1, hash2, sig2);
+        
+        // Attestation signatures are bound to their epoch
+        vm.expectRevert(AndeConsensus.InvalidSignature.selector);
+        consensus.slashEquivocation(signer, false, 42, 4, hash1, sig1, hash2, sig2);
+        
+        consensus.slashEquivocation(signer, false, 42, 3, hash1, sig1, hash2, sig2);
+        
+        AndeConsensus.ValidatorInfo memory info = consensus.getValidatorInfo(signer);
+        assertTrue(info.jailed);
+        assertEq(info.stake, 50000 * 1e18); // 100k - 50% = 50k
+        
+        // The same evidence, in either order, is only processed once
+        (bytes32 low, bytes32 high) = hash1 < hash2 ? (hash1, hash2) : (hash2, hash1);
+        bytes32 id = consensus.evidenceId(signer, 0, 42, low, high);
+        assertTrue(consensus.processedEvidence(id));
+        vm.expectRevert(abi.encodeWithSelector(AndeConsensus.EvidenceAlreadyProcessed.selector, id));
+        consensus.slashEquivocation(signer, false, 42, 3, hash2, sig2, hash1, sig1);
+        
+        vm.stopPrank();
+    }
+    
+    function testSlashUnscheduledProposal() public {
+        uint256 key = 0xB0B;
+        address signer = vm.addr(key);
+        
+        vm.startPrank(admin);
+        consensus.registerValidator(
+            signer,
+            bytes32(uint256(5)),
+            "https://signer.ande.network",
+            100000 * 1e18,
+            100
+        );
+        
+        bytes32 blockHash = keccak256("block");
+        bytes memory sig = _nodeSignature(key, consensus.PROPOSAL_DOMAIN(), 7, blockHash);
+        
+        vm.expectRevert(AndeConsensus.InvalidEvidence.selector);
+        consensus.slashUnscheduledProposal(signer, 7, blockHash, sig, signer);
+        
+        // The claimed scheduled proposer must be a registered validator
+        vm.expectRevert(abi.encodeWithSelector(AndeConsensus.ValidatorNotFound.selector, address(0xdead)));
+        consensus.slashUnscheduledProposal(signer, 7, blockHash, sig, address(0xdead));
+        
+        consensus.slashUnscheduledProposal(signer, 7, blockHash, sig, genesisValidator);
+        
+        AndeConsensus.ValidatorInfo memory info = consensus.getValidatorInfo(signer);
+        assertTrue(info.jailed);
+        assertEq(info.stake, 90000 * 1e18); // 100k - 10% = 90k
+        vm.stopPrank();
+        
+        // Only slashers may submit evidence
+        vm.prank(validator2);
+        vm.expectRevert();
+        consensus.slashUnscheduledProposal(signer, 8, blockHash, sig, genesisValidator);
+    }
+    
     // ============================================
     // EPOCH MANAGEMENT TESTS
     // ============================================
@@ -0,0 +1,829 @@
+//! Block attestations and quorum certificates
+//!
+//! Every active validator signs `(chain_id, epoch, block_number, block_hash)`
+//! for each canonical block, with the epoch of the validator set in force
+//! at the block, and gossips the signature to the other validators.
+//! Attestations are collected per block until the signers hold 2/3+1 of the
+//! voting power of the validator set in force at that block, at which point
+//! they are aggregated into a [`QuorumCertificate`] and the block is final.
+//!
+//! ```text
+//! canonical block ─→ AttestationSigner::sign ─→ gossip ─→ AttestationPool::add
+//!                                                              │  epoch of the set at that block
+//!                                                              │  recover signer, power from that set
+//!                                                              ↓  Σ power ≥ bft_threshold
+//!                                                     QuorumCertificate (finalized)
+//! ```
+//!
+//! "Final" means certified: reth's forkchoice finalized head is not moved by
+//! certificates. It is still set by the Engine API driver through
+//! `engine_forkchoiceUpdated`, which should pass the block of
+//! `ande_getFinalizedBlock` as `finalizedBlockHash`. On import, a header that
+//! conflicts with a certificate is rejected; a block without one is accepted.
+//!
+//! Certificates are self-contained: [`QuorumCertificate::verify`] recovers
+//! every signature and recomputes the voting power against a validator set,
+//! without trusting the power recorded in the certificate.
+
+use crate::{
+    error::{ConsensusError, Result},
+    finality::ValidatorSetSignature,
+    seal,
+    signer::{LocalKey, SigningBackend},
+    slashing_protection::{SigningKind, SlashingProtection},
+    types::{AttestationInfo, BlockProposal, ValidatorSetUpdate},
+};
+use alloy::consensus::Header;
+use alloy_primitives::{keccak256, Address, Bytes, Signature, B256};
+use ande_light_client::{validator_set_message, ValidatorSet as LightValidatorSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Domain separator of the attestation digest
+const ATTESTATION_DOMAIN: &[u8] = b"ANDE_ATTESTATION_V2";
+
+/// Domain separator of the proposal digest
+const PROPOSAL_DOMAIN: &[u8] = b"ANDE_PROPOSAL_V1";
+
+/// Number of quorum certificates kept in memory
+pub const MAX_CERTIFICATES: usize = 4096;
+
+/// Blocks past the local canonical head an attestation may be for
+///
+/// Validators attest as they import, so honest votes run at most a few
+/// blocks ahead of a node that is slightly behind.
+pub const MAX_FUTURE_BLOCKS: u64 = 16;
+
+/// Timeout of a single gossip request
+const GOSSIP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Digest signed by the validators of `epoch` for a block
+///
+/// Matches `abi.encodePacked(domain, uint64 chainId, uint64 epoch, uint64
+/// number, hash)`, so AndeConsensus can verify these signatures as slashing
+/// evidence and light clients only count them for the epoch's set.
+pub fn attestation_digest(chain_id: u64, epoch: u64, block_number: u64, block_hash: B256) -> B256 {
+    keccak256(attestation_message(chain_id, epoch, block_number, block_hash))
+}
+
+/// Preimage of [`attestation_digest`], as handed to the signing backend
+pub fn attestation_message(chain_id: u64, epoch: u64, block_number: u64, block_hash: B256) -> Vec<u8> {
+    let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 56);
+    message.extend_from_slice(ATTESTATION_DOMAIN);
+    message.extend_from_slice(&chain_id.to_be_bytes());
+    message.extend_from_slice(&epoch.to_be_bytes());
+    message.extend_from_slice(&block_number.to_be_bytes());
+    message.extend_from_slice(block_hash.as_slice());
+    message
+}
+
+/// Digest signed by a proposer for a block it produced
+pub fn proposal_digest(chain_id: u64, block_number: u64, block_hash: B256) -> B256 {
+    signing_digest(PROPOSAL_DOMAIN, chain_id, block_number, block_hash)
+}
+
+/// Address that signed `(epoch, block_number, block_hash)`
+///
+/// # Errors
+///
+/// Returns error if the signature is malformed or does not recover
+pub fn recover_attester(
+    chain_id: u64,
+    epoch: u64,
+    block_number: u64,
+    block_hash: B256,
+    signature: &[u8],
+) -> Result<Address> {
+    recover_signer(attestation_digest(chain_id, epoch, block_number, block_hash), signature)
+}
+
+/// Address that signed a proposal for `(block_number, block_hash)`
+///
+/// # Errors
+///
+/// Returns error if the signature is malformed or does not recover
+pub fn recover_proposer(
+    chain_id: u64,
+    block_number: u64,
+    block_hash: B256,
+    signature: &[u8],
+) -> Result<Address> {
+    recover_signer(proposal_digest(chain_id, block_number, block_hash), signature)
+}
+
+/// Domain-separated digest of a block
+pub(crate) fn signing_digest(domain: &[u8], chain_id: u64, block_number: u64, block_hash: B256) -> B256 {
+    keccak256(signing_message(domain, chain_id, block_number, block_hash))
+}
+
+/// Preimage of [`signing_digest`]
+pub(crate) fn signing_message(domain: &[u8], chain_id: u64, block_number: u64, block_hash: B256) -> Vec<u8> {
+    let mut message = Vec::with_capacity(domain.len() + 48);
+    message.extend_from_slice(domain);
+    message.extend_from_slice(&chain_id.to_be_bytes());
+    message.extend_from_slice(&block_number.to_be_bytes());
+    message.extend_from_slice(block_hash.as_slice());
+    message
+}
+
+/// Address that signed `digest`
+pub(crate) fn recover_signer(digest: B256, signature: &[u8]) -> Result<Address> {
+    Signature::try_from(signature)
+        .ok()
+        .and_then(|sig| sig.recover_address_from_prehash(&digest).ok())
+        .ok_or(ConsensusError::InvalidSignature { signer: Address::ZERO })
+}
+
+/// Signs attestations and proposals with this validator's key
+///
+/// With slashing protection, every height is recorded before it is signed
+/// and a second hash at the same height is refused.
+pub struct AttestationSigner {
+    /// Key holder
+    backend: Arc<dyn SigningBackend>,
+
+    /// Address of the key
+    address: Address,
+
+    /// Chain the attestations are for
+    chain_id: u64,
+
+    /// Records of what was signed, if protected
+    protection: Option<Arc<SlashingProtection>>,
+}
+
+impl std::fmt::Debug for AttestationSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttestationSigner")
+            .field("backend", &self.backend.name())
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .field("protected", &self.protection.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AttestationSigner {
+    /// Create a signer using `backend`, without slashing protection
+    pub fn new(backend: Arc<dyn SigningBackend>, chain_id: u64) -> Self {
+        let address = backend.address();
+        Self { backend, address, chain_id, protection: None }
+    }
+
+    /// Create a signer from a raw 32-byte secret key
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the key is not a valid secp256k1 scalar
+    pub fn from_bytes(secret: &[u8], chain_id: u64) -> Result<Self> {
+        Ok(Self::new(Arc::new(LocalKey::from_bytes(secret)?), chain_id))
+    }
+
+    /// Load a hex-encoded secret key from `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or does not hold a key
+    pub fn from_key_file(path: &Path, chain_id: u64) -> Result<Self> {
+        Ok(Self::new(Arc::new(LocalKey::from_key_file(path)?), chain_id))
+    }
+
+    /// Refuse conflicting signatures using `protection`
+    #[must_use]
+    pub fn with_slashing_protection(mut self, protection: Arc<SlashingProtection>) -> Self {
+        self.protection = Some(protection);
+        self
+    }
+
+    /// Address of the signing key
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Name of the signing backend
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Sign an attestation for `(block_number, block_hash)` as a validator
+    /// of `epoch`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if slashing protection refuses or signing fails
+    pub async fn sign(
+        &self,
+        epoch: u64,
+        block_number: u64,
+        block_hash: B256,
+        voting_power: u64,
+    ) -> Result<AttestationInfo> {
+        self.protect(SigningKind::Attestation, block_number, block_hash)?;
+        let message = attestation_message(self.chain_id, epoch, block_number, block_hash);
+
+        Ok(AttestationInfo {
+            validator: self.address,
+            block_number,
+            block_hash,
+            epoch,
+            signature: self.sign_message(&message).await?,
+            timestamp: unix_now(),
+            voting_power,
+        })
+    }
+
+    /// Sign a proposal for a block this validator produced
+    ///
+    /// # Errors
+    ///
+    /// Returns error if slashing protection refuses or signing fails
+    pub async fn sign_proposal(&self, block_number: u64, block_hash: B256) -> Result<BlockProposal> {
+        self.protect(SigningKind::Proposal, block_number, block_hash)?;
+        let message = signing_message(PROPOSAL_DOMAIN, self.chain_id, block_number, block_hash);
+
+        Ok(BlockProposal {
+            block_number,
+            block_hash,
+            producer: self.address,
+            signature: self.sign_message(&message).await?,
+            timestamp: unix_now(),
+            verified: true,
+        })
+    }
+
+    /// Sign the handover to `set`, the validator set of the next epoch
+    ///
+    /// # Errors
+    ///
+    /// Returns error if slashing protection refuses or signing fails
+    pub async fn sign_validator_set(&self, set: &LightValidatorSet) -> Result<ValidatorSetSignature> {
+        let set_hash = set.hash();
+        self.protect(SigningKind::ValidatorSet, set.epoch, set_hash)?;
+        let message = validator_set_message(self.chain_id, set.epoch, set_hash);
+
+        Ok(ValidatorSetSignature {
+            epoch: set.epoch,
+            set_hash,
+            validator: self.address,
+            signature: self.sign_message(&message).await?,
+        })
+    }
+
+    /// Seal a header this validator built
+    ///
+    /// # Errors
+    ///
+    /// Returns error if slashing protection refuses or signing fails
+    pub async fn seal_header(&self, header: &mut Header) -> Result<()> {
+        if let Some(protection) = &self.protection {
+            protection.check_seal(header.number)?;
+        }
+        let signature = self.backend.sign_message(&seal::seal_message(self.chain_id, header)).await?;
+        seal::apply_seal(header, &signature);
+        Ok(())
+    }
+
+    /// Raw secret key, for signing contract transactions with the same key
+    ///
+    /// `None` if the key is not held in process.
+    pub(crate) fn secret_bytes(&self) -> Option<[u8; 32]> {
+        self.backend.secret_bytes()
+    }
+
+    /// Check and record `(block_number, block_hash)` before signing it
+    fn protect(&self, kind: SigningKind, block_number: u64, block_hash: B256) -> Result<()> {
+        match &self.protection {
+            Some(protection) => protection.check_and_record(kind, block_number, block_hash),
+            None => Ok(()),
+        }
+    }
+
+    /// 65-byte recoverable signature over `keccak256(message)`
+    async fn sign_message(&self, message: &[u8]) -> Result<Bytes> {
+        let signature = self.backend.sign_message(message).await?;
+        Ok(Bytes::copy_from_slice(&signature.as_bytes()))
+    }
+}
+
+/// Current unix time in seconds
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Signature of one validator inside a certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSignature {
+    /// Signing validator
+    pub validator: Address,
+
+    /// 65-byte recoverable signature over the attestation digest
+    pub signature: Bytes,
+}
+
+/// Proof that 2/3+1 of the voting power attested a block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuorumCertificate {
+    /// Finalized block number
+    pub block_number: u64,
+
+    /// Finalized block hash
+    pub block_hash: B256,
+
+    /// Epoch of the validator set that signed
+    pub epoch: u64,
+
+    /// Validator signatures, sorted by validator address
+    pub signatures: Vec<CertificateSignature>,
+
+    /// Voting power of the signers
+    pub voting_power: u64,
+
+    /// Total voting power of the validator set
+    pub total_power: u64,
+}
+
+impl QuorumCertificate {
+    /// Verify every signature against `set` and return the signed power
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the epoch differs, a signature is invalid or from a
+    /// validator outside the set, a validator signs twice, or the signers
+    /// hold less than the BFT threshold of `set`
+    pub fn verify(&self, chain_id: u64, set: &ValidatorSetUpdate) -> Result<u64> {
+        if self.epoch != set.epoch {
+            return Err(ConsensusError::InvalidQuorumCertificate(format!(
+                "signed by epoch {} but block {} is in epoch {}",
+                self.epoch, self.block_number, set.epoch
+            )));
+        }
+
+        let mut signers = HashSet::with_capacity(self.signatures.len());
+        let mut power = 0u64;
+        for entry in &self.signatures {
+            let signer = recover_attester(chain_id, self.epoch, self.block_number, self.block_hash, &entry.signature)?;
+            if signer != entry.validator {
+                return Err(ConsensusError::InvalidSignature { signer: entry.validator });
+            }
+            if !signers.insert(signer) {
+                return Err(ConsensusError::InvalidQuorumCertificate(format!(
+                    "duplicate signature from {signer}"
+                )));
+            }
+            power += set.power_of(&signer).ok_or(ConsensusError::ValidatorNotFound(signer))?;
+        }
+
+        let need = set.bft_threshold();
+        if power < need {
+            return Err(ConsensusError::InsufficientVotingPower { have: power, need });
+        }
+        Ok(power)
+    }
+}
+
+/// Collects attestations and forms quorum certificates
+#[derive(Debug)]
+pub struct AttestationPool {
+    /// Chain attestations are for
+    chain_id: u64,
+
+    /// Validator sets by the first block they apply to
+    sets: BTreeMap<u64, ValidatorSetUpdate>,
+
+    /// Verified attestations by block number, block hash and validator
+    pending: BTreeMap<u64, HashMap<B256, BTreeMap<Address, AttestationInfo>>>,
+
+    /// Certificates of finalized blocks by block number
+    certificates: BTreeMap<u64, QuorumCertificate>,
+
+    /// Highest attested block seen, at most `MAX_FUTURE_BLOCKS` past
+    /// `canonical_head`
+    head: u64,
+
+    /// Highest block imported by this node
+    canonical_head: u64,
+
+    /// Attestations older than this many blocks below `head` are rejected
+    max_age: u64,
+}
+
+impl AttestationPool {
+    /// Create an empty pool
+    pub fn new(chain_id: u64, max_age: u64) -> Self {
+        Self {
+            chain_id,
+            sets: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            certificates: BTreeMap::new(),
+            head: 0,
+            canonical_head: 0,
+            max_age,
+        }
+    }
+
+    /// Record the validator set in force from `set.block_number`
+    ///
+    /// A set identical to the one already in force is ignored.
+    pub fn record_validator_set(&mut self, set: ValidatorSetUpdate) {
+        if let Some(current) = self.validator_set_at(set.block_number)
+            && current.epoch == set.epoch
+            && current.validators == set.validators
+            && current.powers == set.powers
+        {
+            return;
+        }
+
+        debug!(epoch = set.epoch, from_block = set.block_number, "Recording validator set for attestations");
+        self.sets.insert(set.block_number, set);
+    }
+
+    /// Advance the local canonical head that bounds future attestations
+    pub fn set_canonical_head(&mut self, block_number: u64) {
+        self.canonical_head = self.canonical_head.max(block_number);
+    }
+
+    /// Validator set in force at `block_number`
+    pub fn validator_set_at(&self, block_number: u64) -> Option<&ValidatorSetUpdate> {
+        self.sets.range(..=block_number).next_back().map(|(_, set)| set)
+    }
+
+    /// Voting power of `validator` in the set in force at `block_number`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the height is stale, too far past the local
+    /// canonical head, has no validator set, or `validator` is not a member
+    /// of its set
+    pub fn member_power(&self, block_number: u64, validator: &Address) -> Result<u64> {
+        if block_number.saturating_add(self.max_age) < self.head {
+            return Err(ConsensusError::StaleAttestation { block_number, head: self.head });
+        }
+        // A far-future vote would raise `head` and make every real one stale
+        if block_number > self.canonical_head.saturating_add(MAX_FUTURE_BLOCKS) {
+            return Err(ConsensusError::FutureAttestation { block_number, head: self.canonical_head });
+        }
+
+        self.validator_set_at(block_number)
+            .ok_or(ConsensusError::UnknownValidatorSet(block_number))?
+            .power_of(validator)
+            .ok_or(ConsensusError::ValidatorNotFound(*validator))
+    }
+
+    /// Check an attestation without collecting it
+    ///
+    /// Returns the voting power of its validator.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`Self::member_power`] rejects the validator, the
+    /// attestation names another epoch than the set at its block, or the
+    /// signature is not theirs
+    pub fn verify(&self, attestation: &AttestationInfo) -> Result<u64> {
+        let (block_number, validator) = (attestation.block_number, attestation.validator);
+        let power = self.member_power(block_number, &validator)?;
+
+        let epoch = self.validator_set_at(block_number).map_or(0, |set| set.epoch);
+        if attestation.epoch != epoch {
+            return Err(ConsensusError::WrongEpoch { block_number, expected: epoch, actual: attestation.epoch });
+        }
+        let signer =
+            recover_attester(self.chain_id, epoch, block_number, attestation.block_hash, &attestation.signature)?;
+        if signer != validator {
+            return Err(ConsensusError::InvalidSignature { signer: validator });
+        }
+        Ok(power)
+    }
+
+    /// Verify and collect an attestation
+    ///
+    /// Returns the certificate if this attestation completed the quorum.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`Self::verify`] rejects the attestation or it
+    /// conflicts with a finalized block
+    pub fn add(&mut self, mut attestation: AttestationInfo) -> Result<Option<QuorumCertificate>> {
+        attestation.voting_power = self.verify(&attestation)?;
+
+        let block_number = attestation.block_number;
+        if let Some(certificate) = self.certificates.get(&block_number) {
+            if certificate.block_hash == attestation.block_hash {
+                return Ok(None);
+            }
+            return Err(ConsensusError::InvalidQuorumCertificate(format!(
+                "attestation for {} conflicts with finalized block {block_number}",
+                attestation.block_hash
+            )));
+        }
+
+        let signer = attestation.validator;
+        let set = self
+            .validator_set_at(block_number)
+            .ok_or(ConsensusError::UnknownValidatorSet(block_number))?;
+        let (epoch, total_power, threshold) = (set.epoch, set.total_power, set.bft_threshold());
+
+        let block_hash = attestation.block_hash;
+        let votes = self.pending.entry(block_number).or_default().entry(block_hash).or_default();
+        votes.insert(signer, attestation);
+        self.head = self.head.max(block_number);
+
+        let power: u64 = votes.values().map(|a| a.voting_power).sum();
+        if power < threshold {
+            debug!(block = block_number, power, threshold, "Attestation collected");
+            return Ok(None);
+        }
+
+        let certificate = QuorumCertificate {
+            block_number,
+            block_hash,
+            epoch,
+            signatures: votes
+                .values()
+                .map(|a| CertificateSignature { validator: a.validator, signature: a.signature.clone() })
+                .collect(),
+            voting_power: power,
+            total_power,
+        };
+
+        info!(
+            block = block_number,
+            hash = ?block_hash,
+            signers = certificate.signatures.len(),
+            power,
+            threshold,
+            "Quorum certificate formed"
+        );
+
+        self.store_certificate(certificate.clone());
+        self.prune();
+        Ok(Some(certificate))
+    }
+
+    /// Verify and store a certificate formed elsewhere
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no validator set is known for the block, the
+    /// certificate does not verify, or it conflicts with a stored one
+    pub fn import_certificate(&mut self, certificate: QuorumCertificate) -> Result<()> {
+        if let Some(existing) = self.certificates.get(&certificate.block_number) {
+            if existing.block_hash == certificate.block_hash {
+                return Ok(());
+            }
+            return Err(ConsensusError::InvalidQuorumCertificate(format!(
+                "conflicts with finalized block {}",
+                certificate.block_number
+            )));
+        }
+
+        let set = self
+            .validator_set_at(certificate.block_number)
+            .ok_or(ConsensusError::UnknownValidatorSet(certificate.block_number))?;
+        certificate.verify(self.chain_id, set)?;
+
+        let bound = self.canonical_head.saturating_add(MAX_FUTURE_BLOCKS);
+        self.head = self.head.max(certificate.block_number.min(bound));
+        self.store_certificate(certificate);
+        self.prune();
+        Ok(())
+    }
+
+    /// Certificate of a finalized block
+    pub fn certificate(&self, block_number: u64) -> Option<&QuorumCertificate> {
+        self.certificates.get(&block_number)
+    }
+
+    /// Whether `(block_number, block_hash)` has a certificate
+    pub fn is_finalized(&self, block_number: u64, block_hash: B256) -> bool {
+        self.certificate(block_number).is_some_and(|c| c.block_hash == block_hash)
+    }
+
+    /// Certificate of the highest finalized block
+    pub fn latest_finalized(&self) -> Option<&QuorumCertificate> {
+        self.certificates.values().next_back()
+    }
+
+    /// Voting power attested so far for `(block_number, block_hash)`
+    pub fn attested_power(&self, block_number: u64, block_hash: B256) -> u64 {
+        self.pending
+            .get(&block_number)
+            .and_then(|blocks| blocks.get(&block_hash))
+            .map_or(0, |votes| votes.values().map(|a| a.voting_power).sum())
+    }
+
+    /// Store a certificate and drop the votes it supersedes
+    fn store_certificate(&mut self, certificate: QuorumCertificate) {
+        self.pending.remove(&certificate.block_number);
+        self.certificates.insert(certificate.block_number, certificate);
+    }
+
+    /// Drop stale votes, old certificates and superseded validator sets
+    fn prune(&mut self) {
+        let cutoff = self.head.saturating_sub(self.max_age);
+        self.pending = self.pending.split_off(&cutoff);
+
+        while self.certificates.len() > MAX_CERTIFICATES {
+            self.certificates.pop_first();
+        }
+
+        // Keep the set in force at the oldest block still of interest
+        let oldest = self.certificates.keys().next().copied().unwrap_or(cutoff).min(cutoff);
+        if let Some(&keep_from) = self.sets.range(..=oldest).next_back().map(|(block, _)| block) {
+            self.sets = self.sets.split_off(&keep_from);
+        }
+    }
+}
+
+/// Best-effort delivery of attestations to other validators
+///
+/// Attestations are posted as `ande_submitAttestation` JSON-RPC calls to the
+/// `rpc_endpoint` each validator registered in AndeConsensus; proposals,
+/// slashing evidence and validator set handovers travel the same way.
+#[derive(Debug, Clone)]
+pub struct AttestationGossip {
+    /// HTTP client shared by all requests
+    client: reqwest::Client,
+}
+
+impl AttestationGossip {
+    /// Create a gossip client
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(GOSSIP_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+
+    /// Send `attestation` to every endpoint in the background
+    pub fn broadcast(&self, endpoints: Vec<String>, attestation: &AttestationInfo) {
+        self.post_all(endpoints, "ande_submitAttestation", attestation);
+    }
+
+    /// Send a signed block proposal to every endpoint in the background
+    pub fn broadcast_proposal(&self, endpoints: Vec<String>, proposal: &BlockProposal) {
+        self.post_all(endpoints, "ande_submitProposal", proposal);
+    }
+
+    /// Send slashing evidence to every endpoint in the background
+    pub fn broadcast_evidence<T: Serialize>(&self, endpoints: Vec<String>, evidence: &T) {
+        self.post_all(endpoints, "ande_submitEvidence", evidence);
+    }
+
+    /// Send a validator set handover signature to every endpoint in the background
+    pub fn broadcast_validator_set_signature(&self, endpoints: Vec<String>, signature: &ValidatorSetSignature) {
+        self.post_all(endpoints, "ande_submitValidatorSetSignature", signature);
+    }
+
+    /// Post a single-parameter JSON-RPC call to every endpoint
+    fn post_all<T: Serialize>(&self, endpoints: Vec<String>, method: &'static str, param: &T) {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": [param],
+        });
+
+        for endpoint in endpoints {
+            let client = self.client.clone();
+            let request = request.clone();
+            tokio::spawn(async move {
+                if let Err(e) = client.post(&endpoint).json(&request).send().await {
+                    debug!(endpoint, method, error = %e, "Gossip failed");
+                }
+            });
+        }
+    }
+}
+
+impl Default for AttestationGossip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN_ID: u64 = 6174;
+
+    fn signers(n: u8) -> Vec<AttestationSigner> {
+        (1..=n).map(|i| AttestationSigner::from_bytes(&[i; 32], CHAIN_ID).unwrap()).collect()
+    }
+
+    fn set_of(signers: &[AttestationSigner], powers: &[u64]) -> ValidatorSetUpdate {
+        ValidatorSetUpdate {
+            epoch: 1,
+            validators: signers.iter().map(AttestationSigner::address).collect(),
+            powers: powers.to_vec(),
+            total_power: powers.iter().sum(),
+            block_number: 0,
+            timestamp: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quorum_reached_at_two_thirds_plus_one() {
+        let signers = signers(4);
+        let mut pool = AttestationPool::new(CHAIN_ID, 100);
+        pool.record_validator_set(set_of(&signers, &[30, 30, 30, 10]));
+        let hash = B256::repeat_byte(0xab);
+
+        // threshold = 100 * 2 / 3 + 1 = 67
+        assert!(pool.add(signers[0].sign(1, 5, hash, 0).await.unwrap()).unwrap().is_none());
+        assert!(pool.add(signers[3].sign(1, 5, hash, 0).await.unwrap()).unwrap().is_none());
+        assert!(pool.add(signers[0].sign(1, 5, hash, 0).await.unwrap()).unwrap().is_none());
+        assert_eq!(pool.attested_power(5, hash), 40);
+
+        let certificate = pool.add(signers[1].sign(1, 5, hash, 0).await.unwrap()).unwrap().unwrap();
+        assert_eq!(certificate.voting_power, 70);
+        assert!(pool.is_finalized(5, hash));
+        assert_eq!(certificate.verify(CHAIN_ID, &set_of(&signers, &[30, 30, 30, 10])).unwrap(), 70);
+
+        // Votes for a competing block at a finalized height are rejected
+        assert!(pool.add(signers[2].sign(1, 5, B256::ZERO, 0).await.unwrap()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_outsiders_and_forged_signatures_rejected() {
+        let signers = signers(3);
+        let mut pool = AttestationPool::new(CHAIN_ID, 100);
+        pool.record_validator_set(set_of(&signers[..2], &[50, 50]));
+        let hash = B256::repeat_byte(1);
+
+        assert!(matches!(
+            pool.add(signers[2].sign(1, 1, hash, 0).await.unwrap()),
+            Err(ConsensusError::ValidatorNotFound(_))
+        ));
+
+        let mut forged = signers[2].sign(1, 1, hash, 0).await.unwrap();
+        forged.validator = signers[0].address();
+        assert!(matches!(pool.add(forged), Err(ConsensusError::InvalidSignature { .. })));
+
+        let wrong_chain = AttestationSigner::from_bytes(&[1; 32], 1).unwrap();
+        assert!(pool.add(wrong_chain.sign(1, 1, hash, 0).await.unwrap()).is_err());
+
+        // Votes cast for another epoch do not count in this one
+        assert!(matches!(
+            pool.add(signers[0].sign(2, 1, hash, 0).await.unwrap()),
+            Err(ConsensusError::WrongEpoch { expected: 1, actual: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_far_future_attestation_does_not_advance_head() {
+        let signers = signers(3);
+        let mut pool = AttestationPool::new(CHAIN_ID, 100);
+        pool.record_validator_set(set_of(&signers, &[40, 40, 20]));
+        pool.set_canonical_head(1_000);
+
+        let far = 1_000 + MAX_FUTURE_BLOCKS + 1;
+        assert!(matches!(
+            pool.add(signers[0].sign(1, far, B256::repeat_byte(3), 0).await.unwrap()),
+            Err(ConsensusError::FutureAttestation { head: 1_000, .. })
+        ));
+
+        // Votes at the real head are still collected
+        let hash = B256::repeat_byte(4);
+        pool.add(signers[0].sign(1, 1_000, hash, 0).await.unwrap()).unwrap();
+        assert_eq!(pool.attested_power(1_000, hash), 40);
+    }
+
+    #[tokio::test]
+    async fn test_certificate_verified_against_epoch_set() {
+        let signers = signers(3);
+        let set = set_of(&signers, &[40, 40, 20]);
+        let mut pool = AttestationPool::new(CHAIN_ID, 100);
+        pool.record_validator_set(set.clone());
+
+        let hash = B256::repeat_byte(2);
+        pool.add(signers[0].sign(1, 9, hash, 0).await.unwrap()).unwrap();
+        let certificate = pool.add(signers[1].sign(1, 9, hash, 0).await.unwrap()).unwrap().unwrap();
+
+        // A later epoch with different powers does not accept it
+        let mut next_epoch = set_of(&signers, &[10, 10, 80]);
+        next_epoch.epoch = 2;
+        assert!(certificate.verify(CHAIN_ID, &next_epoch).is_err());
+        next_epoch.epoch = 1;
+        assert!(matches!(
+            certificate.verify(CHAIN_ID, &next_epoch),
+            Err(ConsensusError::InsufficientVotingPower { have: 20, need: 67 })
+        ));
+
+        // Another node imports it against the same set
+        let mut other = AttestationPool::new(CHAIN_ID, 100);
+        assert!(other.import_certificate(certificate.clone()).is_err());
+        other.record_validator_set(set);
+        other.import_certificate(certificate).unwrap();
+        assert!(other.is_finalized(9, hash));
+    }
+}
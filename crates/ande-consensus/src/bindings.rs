@@ -0,0 +1,151 @@
+//! Solidity bindings of the consensus contracts
+//!
+//! Shared by the JSON-RPC [`ContractClient`](crate::contract_client::ContractClient)
+//! and the in-process [`StateReader`](crate::data_source::StateReader), so
+//! both decode exactly the same ABI.
+
+use alloy::sol;
+
+sol! {
+    /// AndeConsensus.sol
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IAndeConsensus {
+        struct ValidatorInfo {
+            address validator;
+            bytes32 p2pPeerId;
+            string rpcEndpoint;
+            uint256 stake;
+            uint256 power;
+            int256 accumulatedPriority;
+            uint256 totalBlocksProduced;
+            uint256 totalBlocksMissed;
+            uint256 uptime;
+            uint256 lastBlockProduced;
+            uint256 registeredAt;
+            bool jailed;
+            bool active;
+            bool isPermanent;
+        }
+
+        struct EpochInfo {
+            uint256 epochNumber;
+            uint256 startBlock;
+            uint256 endBlock;
+            uint256 startTime;
+            uint256 endTime;
+            address[] validators;
+            uint256 totalVotingPower;
+        }
+
+        struct BlockProposal {
+            uint256 blockNumber;
+            bytes32 blockHash;
+            address producer;
+            bytes signature;
+            uint256 timestamp;
+            bool verified;
+        }
+
+        struct Attestation {
+            address validator;
+            bytes32 blockHash;
+            bytes signature;
+            uint256 timestamp;
+        }
+
+        error ValidatorNotFound(address validator);
+        error ValidatorNotActive(address validator);
+        error ValidatorIsJailed(address validator);
+        error InvalidSignature();
+        error BlockAlreadyProposed(uint256 blockNumber);
+        error NotDesignatedProposer(address expected, address actual);
+        error InvalidBlockNumber(uint256 expected, uint256 actual);
+        error InvalidEvidence();
+        error EvidenceAlreadyProcessed(bytes32 evidenceId);
+        error ValidatorNotJailed(address validator);
+        error JailPeriodNotOver(uint256 releaseBlock, uint256 currentBlock);
+        error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+        error EnforcedPause();
+
+        event ValidatorSetUpdated(uint256 indexed epoch, address[] validators, uint256[] powers, uint256 totalPower);
+        event ValidatorJailed(address indexed validator, string reason, uint256 timestamp);
+        event ValidatorUnjailed(address indexed validator, uint256 timestamp);
+
+        function getActiveValidators() external view returns (address[] memory);
+        function getValidatorInfo(address validator) external view returns (ValidatorInfo memory);
+        function getCurrentProposer() external view returns (address);
+        function isValidator(address validator) external view returns (bool);
+        function currentEpoch() external view returns (uint256);
+        function getEpochInfo(uint256 epoch) external view returns (EpochInfo memory);
+        function totalVotingPower() external view returns (uint256);
+        function processedEvidence(bytes32 evidenceId) external view returns (bool);
+        function jailReleaseBlock(address validator) external view returns (uint256);
+        function currentBlockNumber() external view returns (uint256);
+        function getBlockProducer(uint256 blockNumber) external view returns (address);
+        function getBlockProposal(uint256 blockNumber) external view returns (BlockProposal memory);
+        function getAttestations(bytes32 blockHash) external view returns (Attestation[] memory);
+        function getAttestationPower(bytes32 blockHash) external view returns (uint256);
+        function isBlockFinalized(bytes32 blockHash) external view returns (bool);
+
+        function proposeBlock(uint256 blockNumber, bytes32 blockHash, bytes signature) external;
+        function attestBlock(uint256 blockNumber, bytes32 blockHash, bytes signature) external;
+
+        function slashEquivocation(
+            address validator,
+            bool proposal,
+            uint64 blockNumber,
+            uint64 epoch,
+            bytes32 blockHash1,
+            bytes signature1,
+            bytes32 blockHash2,
+            bytes signature2
+        ) external;
+        function slashUnscheduledProposal(
+            address validator,
+            uint64 blockNumber,
+            bytes32 blockHash,
+            bytes signature,
+            address scheduledProposer
+        ) external;
+        function unjail() external;
+    }
+
+    /// AndeSequencerCoordinator.sol
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IAndeSequencerCoordinator {
+        error SequencerNotFound();
+        error SequencerNotActive();
+        error NotCurrentLeader();
+        error TimeoutNotReached();
+        error NoActiveSequencers();
+        error EnforcedPause();
+        error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+
+        event LeaderRotated(
+            uint256 indexed rotationNumber,
+            address indexed oldLeader,
+            address indexed newLeader,
+            uint256 blockNumber,
+            string reason
+        );
+
+        function currentLeader() external view returns (address);
+        function currentRotationNumber() external view returns (uint256);
+        function rotations(uint256 rotationNumber) external view returns (
+            uint256 number,
+            uint256 startBlock,
+            uint256 endBlock,
+            address leader,
+            uint256 blocksProduced,
+            uint256 missedBlocks,
+            bool completedSuccessfully
+        );
+        function getActiveSequencers() external view returns (address[] memory);
+        function isTimeoutReached() external view returns (bool);
+
+        function recordBlockProduced(address sequencer, uint256 blockNumber, uint256 gasUsed) external;
+        function checkTimeout() external;
+    }
+}
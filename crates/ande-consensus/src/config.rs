@@ -3,15 +3,73 @@
 use alloy_primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
 
+/// Where a node hosting the engine reads the consensus contracts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataSourceKind {
+    /// JSON-RPC calls to `rpc_url`
+    Rpc,
+
+    /// The hosting node's own state, without a network hop
+    #[default]
+    State,
+}
+
+impl FromStr for DataSourceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rpc" => Ok(Self::Rpc),
+            "state" => Ok(Self::State),
+            other => Err(format!("unknown data source {other:?}, expected \"rpc\" or \"state\"")),
+        }
+    }
+}
+
+/// Where the validator signing key is kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignerKind {
+    /// Hex-encoded secret in `private_key_path`
+    #[default]
+    Key,
+
+    /// Web3 Secret Storage keystore in `keystore_path`
+    Keystore,
+
+    /// Web3Signer-compatible remote signer at `remote_signer_url`
+    Web3Signer,
+}
+
+impl FromStr for SignerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "key" => Ok(Self::Key),
+            "keystore" => Ok(Self::Keystore),
+            "web3signer" => Ok(Self::Web3Signer),
+            other => Err(format!(
+                "unknown signer {other:?}, expected \"key\", \"keystore\" or \"web3signer\""
+            )),
+        }
+    }
+}
+
 /// Complete configuration for the consensus engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusConfig {
     /// Ethereum RPC endpoint URL
     pub rpc_url: String,
 
-    /// WebSocket RPC URL (for event subscriptions)
+    /// Where the hosting node reads the consensus contracts from
+    pub data_source: DataSourceKind,
+
+    /// WebSocket RPC URL (for event subscriptions, empty to poll only)
     pub ws_url: String,
 
     /// Address of AndeConsensus contract
@@ -26,9 +84,21 @@ pub struct ConsensusConfig {
     /// This node's sequencer address
     pub sequencer_address: Address,
 
+    /// Where the signing key is kept
+    pub signer: SignerKind,
+
     /// Path to private key file
     pub private_key_path: PathBuf,
 
+    /// Path to the encrypted keystore, with `SignerKind::Keystore`
+    pub keystore_path: PathBuf,
+
+    /// File holding the keystore password
+    pub keystore_password_file: PathBuf,
+
+    /// Remote signer base URL, with `SignerKind::Web3Signer`
+    pub remote_signer_url: String,
+
     /// Chain ID
     pub chain_id: u64,
 
@@ -53,9 +123,18 @@ pub struct ConsensusConfig {
     /// Maximum attestation age (blocks)
     pub max_attestation_age: u64,
 
+    /// Canonical blocks validator performance is computed over
+    pub performance_window: u64,
+
     /// Whether to auto-unjail after jail period
     pub auto_unjail: bool,
 
+    /// Hand block production over through AndeSequencerCoordinator rotations
+    pub leader_handoff: bool,
+
+    /// Highest fee per gas paid for coordinator transactions (gwei)
+    pub max_fee_per_gas_gwei: u64,
+
     /// Data directory for state persistence
     pub data_dir: PathBuf,
 
@@ -74,6 +153,11 @@ impl ConsensusConfig {
             rpc_url: std::env::var("CONSENSUS_RPC_URL")
                 .unwrap_or_else(|_| "http://localhost:8545".to_string()),
 
+            data_source: std::env::var("CONSENSUS_DATA_SOURCE")
+                .unwrap_or_else(|_| "state".to_string())
+                .parse()
+                .map_err(|e| format!("Invalid CONSENSUS_DATA_SOURCE: {e}"))?,
+
             ws_url: std::env::var("CONSENSUS_WS_URL")
                 .unwrap_or_else(|_| "ws://localhost:8546".to_string()),
 
@@ -97,10 +181,25 @@ impl ConsensusConfig {
                 .parse()
                 .map_err(|e| format!("Invalid SEQUENCER_ADDRESS: {e}"))?,
 
+            signer: std::env::var("SIGNER_TYPE")
+                .unwrap_or_else(|_| "key".to_string())
+                .parse()
+                .map_err(|e| format!("Invalid SIGNER_TYPE: {e}"))?,
+
             private_key_path: std::env::var("PRIVATE_KEY_PATH")
                 .map(PathBuf::from)
                 .unwrap_or_else(|_| PathBuf::from("./sequencer.key")),
 
+            keystore_path: std::env::var("KEYSTORE_PATH")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("./sequencer.keystore.json")),
+
+            keystore_password_file: std::env::var("KEYSTORE_PASSWORD_FILE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("./sequencer.password")),
+
+            remote_signer_url: std::env::var("REMOTE_SIGNER_URL").unwrap_or_default(),
+
             chain_id: std::env::var("CHAIN_ID")
                 .unwrap_or_else(|_| "6174".to_string())
                 .parse()
@@ -143,11 +242,26 @@ impl ConsensusConfig {
                 .parse()
                 .map_err(|e| format!("Invalid MAX_ATTESTATION_AGE: {e}"))?,
 
+            performance_window: std::env::var("PERFORMANCE_WINDOW")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .map_err(|e| format!("Invalid PERFORMANCE_WINDOW: {e}"))?,
+
             auto_unjail: std::env::var("AUTO_UNJAIL")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .map_err(|e| format!("Invalid AUTO_UNJAIL: {e}"))?,
 
+            leader_handoff: std::env::var("LEADER_HANDOFF")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .map_err(|e| format!("Invalid LEADER_HANDOFF: {e}"))?,
+
+            max_fee_per_gas_gwei: std::env::var("MAX_FEE_PER_GAS_GWEI")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .map_err(|e| format!("Invalid MAX_FEE_PER_GAS_GWEI: {e}"))?,
+
             data_dir: std::env::var("CONSENSUS_DATA_DIR")
                 .map(PathBuf::from)
                 .unwrap_or_else(|_| PathBuf::from("./consensus-data")),
@@ -181,6 +295,18 @@ impl ConsensusConfig {
             return Err("max_attestation_age must be > 0".to_string());
         }
 
+        if self.performance_window == 0 {
+            return Err("performance_window must be > 0".to_string());
+        }
+
+        if self.signer == SignerKind::Web3Signer && self.remote_signer_url.is_empty() {
+            return Err("remote_signer_url must be set for the web3signer signer".to_string());
+        }
+
+        if self.leader_handoff && self.max_fee_per_gas_gwei == 0 {
+            return Err("max_fee_per_gas_gwei must be > 0 with leader_handoff".to_string());
+        }
+
         Ok(())
     }
 }
@@ -189,12 +315,17 @@ impl Default for ConsensusConfig {
     fn default() -> Self {
         Self {
             rpc_url: "http://localhost:8545".to_string(),
+            data_source: DataSourceKind::State,
             ws_url: "ws://localhost:8546".to_string(),
             consensus_contract: Address::ZERO,
             coordinator_contract: Address::ZERO,
             registry_contract: Address::ZERO,
             sequencer_address: Address::ZERO,
+            signer: SignerKind::Key,
             private_key_path: PathBuf::from("./sequencer.key"),
+            keystore_path: PathBuf::from("./sequencer.keystore.json"),
+            keystore_password_file: PathBuf::from("./sequencer.password"),
+            remote_signer_url: String::new(),
             chain_id: 6174,
             blocks_per_rotation: 100,
             timeout_blocks: 10,
@@ -203,7 +334,10 @@ impl Default for ConsensusConfig {
             metrics_addr: "0.0.0.0:9090".to_string(),
             strict_validation: true,
             max_attestation_age: 100,
+            performance_window: 1000,
             auto_unjail: true,
+            leader_handoff: false,
+            max_fee_per_gas_gwei: 100,
             data_dir: PathBuf::from("./consensus-data"),
             log_level: "info".to_string(),
         }
@@ -227,6 +361,23 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_parse_data_source() {
+        assert_eq!("rpc".parse::<DataSourceKind>().unwrap(), DataSourceKind::Rpc);
+        assert_eq!("State".parse::<DataSourceKind>().unwrap(), DataSourceKind::State);
+        assert!("ipc".parse::<DataSourceKind>().is_err());
+    }
+
+    #[test]
+    fn test_web3signer_needs_url() {
+        let mut config = ConsensusConfig::default();
+        config.signer = "Web3Signer".parse().unwrap();
+        assert!(config.validate().is_err());
+
+        config.remote_signer_url = "http://localhost:9000".to_string();
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_timeout_must_be_less_than_rotation() {
         let mut config = ConsensusConfig::default();
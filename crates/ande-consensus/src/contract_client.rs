@@ -1,62 +1,60 @@
 //! Ethereum contract client for consensus on-chain interactions
+//!
+//! Built on alloy providers and the [`bindings`](crate::bindings) shared
+//! with the in-process state reader. Reads go through a plain HTTP provider
+//! and validator events through a WebSocket subscription. Slashing evidence
+//! and unjail requests are submitted with the validator's own key when one
+//! is configured; evidence needs the validator's address to hold
+//! `SLASHER_ROLE` in AndeConsensus.
 
 use crate::{
+    bindings::{
+        IAndeConsensus::{self, IAndeConsensusErrors, IAndeConsensusEvents, IAndeConsensusInstance},
+        IAndeSequencerCoordinator::{self, IAndeSequencerCoordinatorInstance},
+    },
+    data_source::{next_sequencer, ConsensusDataSource, MAX_FORCED_ROTATIONS},
     error::{ConsensusError, Result},
-    types::ValidatorInfo,
+    evidence::Evidence,
+    types::{ForcedRotation, LeaderRotation, ValidatorInfo, ValidatorSetChange, ValidatorSetEvent, ValidatorSetUpdate},
 };
-use alloy_primitives::{Address, B256};
-use ethers::{
-    contract::abigen,
-    providers::{Http, Middleware, Provider, Ws},
-    types::H160,
+use alloy::{
+    eips::BlockId,
+    network::EthereumWallet,
+    providers::{DynProvider, Provider, ProviderBuilder, WsConnect},
+    rpc::types::{Filter, Log},
+    signers::{local::PrivateKeySigner, Signer},
+    sol_types::{SolEvent, SolEventInterface},
+    transports::http::reqwest::Url,
 };
+use alloy_primitives::{Address, B256, U256};
+use async_trait::async_trait;
+use futures::StreamExt;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, error, info};
-
-// Generate contract bindings
-abigen!(
-    AndeConsensus,
-    r#"[
-        function getActiveValidators() external view returns (address[])
-        function getValidatorInfo(address) external view returns (tuple(address,bytes32,string,uint256,uint256,int256,uint256,uint256,uint256,uint256,uint256,bool,bool,bool))
-        function getCurrentProposer() external view returns (address)
-        function isValidator(address) external view returns (bool)
-        function currentEpoch() external view returns (uint256)
-        function currentBlockNumber() external view returns (uint256)
-        function totalVotingPower() external view returns (uint256)
-        event ValidatorSetUpdated(uint256 indexed epoch, address[] validators, uint256[] powers, uint256 totalPower)
-        event BlockProposed(uint256 indexed blockNumber, bytes32 indexed blockHash, address indexed producer, uint256 timestamp)
-        event BlockFinalized(uint256 indexed blockNumber, bytes32 indexed blockHash, uint256 totalPower, uint256 threshold)
-    ]"#
-);
-
-abigen!(
-    AndeSequencerCoordinator,
-    r#"[
-        function currentLeader() external view returns (address)
-        function getActiveSequencers() external view returns (address[])
-        function isTimeoutReached() external view returns (bool)
-        function checkTimeout() external
-        function recordBlockProduced(address,uint256,uint256) external
-        event LeaderRotated(uint256 indexed rotationNumber, address indexed oldLeader, address indexed newLeader, uint256 blockNumber, string reason)
-        event TimeoutDetected(address indexed sequencer, uint256 missedBlocks, uint256 slashedAmount)
-    ]"#
-);
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, error, info, warn};
 
 /// Client for interacting with consensus contracts
 pub struct ContractClient {
+    /// HTTP endpoint, reused by the signing provider
+    rpc_url: Url,
+
     /// HTTP provider for queries
-    provider: Arc<Provider<Http>>,
+    provider: DynProvider,
+
+    /// WebSocket endpoint, kept for reconnecting
+    ws_url: Option<String>,
 
-    /// WebSocket provider for events
-    ws_provider: Option<Arc<Provider<Ws>>>,
+    /// WebSocket provider for events, dropped when its subscription ends
+    ws_provider: RwLock<Option<DynProvider>>,
 
     /// AndeConsensus contract
-    consensus: AndeConsensus<Provider<Http>>,
+    consensus: IAndeConsensusInstance<DynProvider>,
 
     /// AndeSequencerCoordinator contract
-    coordinator: AndeSequencerCoordinator<Provider<Http>>,
+    coordinator: IAndeSequencerCoordinatorInstance<DynProvider>,
+
+    /// AndeConsensus with the validator key, for evidence and unjail transactions
+    signed: Option<IAndeConsensusInstance<DynProvider>>,
 
     /// Last synced block number
     last_synced_block: Arc<RwLock<u64>>,
@@ -67,7 +65,7 @@ impl ContractClient {
     ///
     /// # Errors
     ///
-    /// Returns error if provider connection fails
+    /// Returns error if the RPC URL is invalid
     pub async fn new(
         rpc_url: &str,
         ws_url: Option<&str>,
@@ -75,14 +73,15 @@ impl ContractClient {
         coordinator_address: Address,
     ) -> Result<Self> {
         // Connect HTTP provider
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| ConsensusError::RpcError(e.to_string()))?;
-        let provider = Arc::new(provider);
+        let rpc_url: Url = rpc_url
+            .parse()
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid RPC URL {rpc_url}: {e}")))?;
+        let provider = ProviderBuilder::new().connect_http(rpc_url.clone()).erased();
 
         // Connect WebSocket provider for events (optional)
         let ws_provider = if let Some(url) = ws_url {
-            match Provider::<Ws>::connect(url).await {
-                Ok(ws) => Some(Arc::new(ws)),
+            match connect_ws(url).await {
+                Ok(ws) => Some(ws),
                 Err(e) => {
                     error!(error = %e, "Failed to connect WebSocket provider, events disabled");
                     None
@@ -92,12 +91,8 @@ impl ContractClient {
             None
         };
 
-        // Create contract instances
-        let consensus_addr: H160 = H160::from_slice(consensus_address.as_slice());
-        let coordinator_addr: H160 = H160::from_slice(coordinator_address.as_slice());
-
-        let consensus = AndeConsensus::new(consensus_addr, provider.clone());
-        let coordinator = AndeSequencerCoordinator::new(coordinator_addr, provider.clone());
+        let consensus = IAndeConsensus::new(consensus_address, provider.clone());
+        let coordinator = IAndeSequencerCoordinator::new(coordinator_address, provider.clone());
 
         info!(
             consensus = ?consensus_address,
@@ -106,27 +101,158 @@ impl ContractClient {
         );
 
         Ok(Self {
+            rpc_url,
             provider,
-            ws_provider,
+            ws_url: ws_url.map(str::to_string),
+            ws_provider: RwLock::new(ws_provider),
             consensus,
             coordinator,
+            signed: None,
             last_synced_block: Arc::new(RwLock::new(0)),
         })
     }
 
+    /// Sign evidence and unjail transactions with `secret` on `chain_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the key is invalid
+    pub fn with_signer(mut self, secret: &[u8], chain_id: u64) -> Result<Self> {
+        let mut signer = PrivateKeySigner::from_slice(secret)
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid signing key: {e}")))?;
+        signer.set_chain_id(Some(chain_id));
+
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .connect_http(self.rpc_url.clone())
+            .erased();
+        self.signed = Some(IAndeConsensus::new(*self.consensus.address(), provider));
+        Ok(self)
+    }
+
+    /// Whether AndeConsensus already processed the evidence `id`
+    pub async fn is_evidence_processed(&self, id: B256) -> Result<bool> {
+        Ok(self.consensus.processedEvidence(id).call().await?)
+    }
+
+    /// Submit slashing evidence to AndeConsensus
+    ///
+    /// Returns `false` without sending a transaction if the contract already
+    /// processed this evidence.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no signer is configured, or the transaction fails or
+    /// reverts
+    pub async fn submit_evidence(&self, evidence: &Evidence) -> Result<bool> {
+        let slasher = self.signed_consensus()?;
+        if self.is_evidence_processed(evidence.id()).await? {
+            return Ok(false);
+        }
+
+        let validator = evidence.validator();
+        let sent = match evidence {
+            Evidence::DuplicateAttestation { block_number, first, second, .. }
+            | Evidence::DuplicateProposal { block_number, first, second, .. } => {
+                slasher
+                    .slashEquivocation(
+                        validator,
+                        matches!(evidence, Evidence::DuplicateProposal { .. }),
+                        *block_number,
+                        evidence.epoch().unwrap_or_default(),
+                        first.block_hash,
+                        first.signature.clone(),
+                        second.block_hash,
+                        second.signature.clone(),
+                    )
+                    .send()
+                    .await
+            }
+            Evidence::UnscheduledProposal { block_number, proposal, scheduled, .. } => {
+                slasher
+                    .slashUnscheduledProposal(
+                        validator,
+                        *block_number,
+                        proposal.block_hash,
+                        proposal.signature.clone(),
+                        *scheduled,
+                    )
+                    .send()
+                    .await
+            }
+        };
+
+        // Another validator may land the same evidence between the check and
+        // our gas estimation
+        let pending = match sent.map_err(ConsensusError::from) {
+            Ok(pending) => pending,
+            Err(ConsensusError::ContractReverted(IAndeConsensusErrors::EvidenceAlreadyProcessed(_))) => {
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let receipt = pending.get_receipt().await?;
+        if !receipt.status() {
+            return Err(ConsensusError::ContractError(format!(
+                "Evidence transaction {:?} reverted",
+                receipt.transaction_hash
+            )));
+        }
+
+        info!(tx = ?receipt.transaction_hash, validator = ?validator, block = evidence.block_number(), "Slashing evidence submitted");
+        Ok(true)
+    }
+
+    /// Get the block from which a jailed validator can unjail
+    pub async fn get_jail_release_block(&self, validator: Address) -> Result<u64> {
+        let release = self.consensus.jailReleaseBlock(validator).call().await?;
+
+        Ok(release.saturating_to())
+    }
+
+    /// Unjail this validator once its jail period is over
+    ///
+    /// Returns `false` without an error if the validator is not jailed,
+    /// e.g. because the validator manager already unjailed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no signer is configured, the jail period is not
+    /// over, or the transaction fails or reverts
+    pub async fn submit_unjail(&self) -> Result<bool> {
+        let signed = self.signed_consensus()?;
+
+        let pending = match signed.unjail().send().await.map_err(ConsensusError::from) {
+            Ok(pending) => pending,
+            Err(ConsensusError::ContractReverted(IAndeConsensusErrors::ValidatorNotJailed(_))) => {
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let receipt = pending.get_receipt().await?;
+        if !receipt.status() {
+            return Err(ConsensusError::ContractError(format!(
+                "Unjail transaction {:?} reverted",
+                receipt.transaction_hash
+            )));
+        }
+
+        info!(tx = ?receipt.transaction_hash, block = ?receipt.block_number, "Unjail transaction confirmed");
+        Ok(true)
+    }
+
+    /// AndeConsensus with the validator key
+    fn signed_consensus(&self) -> Result<&IAndeConsensusInstance<DynProvider>> {
+        self.signed
+            .as_ref()
+            .ok_or_else(|| ConsensusError::ConfigError("No signing key for transactions".to_string()))
+    }
+
     /// Get list of active validator addresses
     pub async fn get_active_validators(&self) -> Result<Vec<Address>> {
-        let addresses = self
-            .consensus
-            .get_active_validators()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
-
-        let validators: Vec<Address> = addresses
-            .into_iter()
-            .map(|addr| Address::from_slice(addr.as_bytes()))
-            .collect();
+        let validators = self.consensus.getActiveValidators().call().await?;
 
         debug!(count = validators.len(), "Fetched active validators");
         Ok(validators)
@@ -134,39 +260,7 @@ impl ContractClient {
 
     /// Get detailed validator information
     pub async fn get_validator_info(&self, address: Address) -> Result<ValidatorInfo> {
-        let addr: H160 = H160::from_slice(address.as_slice());
-
-        let info = self
-            .consensus
-            .get_validator_info(addr)
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
-
-        // Convert from contract tuple to ValidatorInfo
-        // Note: Solidity returns I256 for accumulated_priority, convert to i64
-        let accumulated_priority = if info.5 >= ethers::types::I256::zero() {
-            info.5.as_u128() as i64
-        } else {
-            -((-info.5).as_u128() as i64)
-        };
-
-        Ok(ValidatorInfo {
-            validator: Address::from_slice(info.0.as_bytes()),
-            p2p_peer_id: B256::from_slice(&info.1),
-            rpc_endpoint: info.2,
-            stake: alloy_primitives::U256::from_limbs(info.3.0),
-            power: info.4.as_u64(),
-            accumulated_priority,
-            total_blocks_produced: info.6.as_u64(),
-            total_blocks_missed: info.7.as_u64(),
-            uptime: info.8.as_u64() as u16,
-            last_block_produced: info.9.as_u64(),
-            registered_at: info.10.as_u64(),
-            jailed: info.11,
-            active: info.12,
-            is_permanent: info.13,
-        })
+        Ok(self.consensus.getValidatorInfo(address).call().await?.into())
     }
 
     /// Get all active validators with full info
@@ -188,104 +282,178 @@ impl ContractClient {
 
     /// Get current proposer address
     pub async fn get_current_proposer(&self) -> Result<Address> {
-        let proposer = self
-            .consensus
-            .get_current_proposer()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
-
-        Ok(Address::from_slice(proposer.as_bytes()))
+        Ok(self.consensus.getCurrentProposer().call().await?)
     }
 
     /// Check if address is a validator
     pub async fn is_validator(&self, address: Address) -> Result<bool> {
-        let addr: H160 = H160::from_slice(address.as_slice());
-
-        let is_val = self
-            .consensus
-            .is_validator(addr)
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
-
-        Ok(is_val)
+        Ok(self.consensus.isValidator(address).call().await?)
     }
 
     /// Get current epoch number
     pub async fn get_current_epoch(&self) -> Result<u64> {
-        let epoch = self
-            .consensus
-            .current_epoch()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
+        let epoch = self.consensus.currentEpoch().call().await?;
+
+        Ok(epoch.saturating_to())
+    }
 
-        Ok(epoch.as_u64())
+    /// Get the validator set an epoch started with
+    ///
+    /// Powers are read at the epoch's start block so every node derives the
+    /// same set; nodes that pruned that state fall back to current powers.
+    pub async fn get_epoch_validator_set(&self, epoch: u64) -> Result<ValidatorSetUpdate> {
+        let info = self.consensus.getEpochInfo(U256::from(epoch)).call().await?;
+        let start_block: u64 = info.startBlock.saturating_to();
+
+        let mut powers = Vec::with_capacity(info.validators.len());
+        for validator in &info.validators {
+            let at_start = self
+                .consensus
+                .getValidatorInfo(*validator)
+                .block(BlockId::number(start_block))
+                .call()
+                .await;
+            let validator_info = match at_start {
+                Ok(validator_info) => validator_info,
+                Err(e) => {
+                    warn!(epoch, start_block, error = %e, "Epoch start state unavailable, using current power");
+                    self.consensus.getValidatorInfo(*validator).call().await?
+                }
+            };
+            powers.push(validator_info.power.saturating_to());
+        }
+
+        debug!(epoch, start_block, count = powers.len(), "Fetched epoch validator set");
+        Ok(ValidatorSetUpdate {
+            epoch,
+            validators: info.validators,
+            total_power: powers.iter().sum(),
+            powers,
+            block_number: start_block,
+            timestamp: info.startTime.saturating_to(),
+        })
     }
 
     /// Get total voting power
     pub async fn get_total_voting_power(&self) -> Result<u64> {
-        let power = self
-            .consensus
-            .total_voting_power()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
+        let power = self.consensus.totalVotingPower().call().await?;
 
-        Ok(power.as_u64())
+        Ok(power.saturating_to())
     }
 
     /// Get current leader from sequencer coordinator
     pub async fn get_current_leader(&self) -> Result<Address> {
-        let leader = self
-            .coordinator
-            .current_leader()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
-
-        Ok(Address::from_slice(leader.as_bytes()))
+        Ok(self.coordinator.currentLeader().call().await?)
     }
 
     /// Check if timeout is reached for current leader
     pub async fn is_timeout_reached(&self) -> Result<bool> {
-        let timeout = self
-            .coordinator
-            .is_timeout_reached()
-            .call()
-            .await
-            .map_err(|e| ConsensusError::ContractError(e.to_string()))?;
+        Ok(self.coordinator.isTimeoutReached().call().await?)
+    }
+
+    /// Get the coordinator's leader and rotation number at `block`
+    pub async fn get_coordinator_leader(&self, block: u64) -> Result<(Address, u64)> {
+        let at = BlockId::number(block);
+        let leader = self.coordinator.currentLeader().block(at).call().await?;
+        let rotation = self.coordinator.currentRotationNumber().block(at).call().await?;
 
-        Ok(timeout)
+        Ok((leader, rotation.saturating_to()))
+    }
+
+    /// Get the sequencer a forced rotation at `block` would hand over to
+    pub async fn get_next_leader(&self, block: u64) -> Result<Address> {
+        let at = BlockId::number(block);
+        let leader = self.coordinator.currentLeader().block(at).call().await?;
+        let sequencers = self.coordinator.getActiveSequencers().block(at).call().await?;
+
+        next_sequencer(leader, &sequencers)
+    }
+
+    /// Get the `LeaderRotated` events emitted in blocks `from..=to`, in order
+    pub async fn get_leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>> {
+        let filter = Filter::new()
+            .address(*self.coordinator.address())
+            .event_signature(IAndeSequencerCoordinator::LeaderRotated::SIGNATURE_HASH)
+            .from_block(from)
+            .to_block(to);
+
+        let logs = self.provider.get_logs(&filter).await?;
+        let rotations: Vec<LeaderRotation> = logs
+            .iter()
+            .filter_map(|log| {
+                let rotation = decode_leader_rotation(log);
+                if rotation.is_none() {
+                    warn!(tx = ?log.transaction_hash, "Undecodable LeaderRotated log");
+                }
+                rotation
+            })
+            .collect();
+
+        debug!(from, to, count = rotations.len(), "Fetched leader rotations");
+        Ok(rotations)
     }
 
     /// Get current block number from provider
     pub async fn get_block_number(&self) -> Result<u64> {
-        let block = self
-            .provider
-            .get_block_number()
-            .await
-            .map_err(|e| ConsensusError::RpcError(e.to_string()))?;
-
-        Ok(block.as_u64())
+        Ok(self.provider.get_block_number().await?)
     }
 
-    /// Subscribe to ValidatorSetUpdated events
+    /// Stream AndeConsensus validator set events into `events`
     ///
-    /// # Errors
+    /// Subscribes to `ValidatorSetUpdated`, `ValidatorJailed` and
+    /// `ValidatorUnjailed` logs over WebSocket and runs until the
+    /// subscription ends or `events` is closed. Logs removed by a reorg are
+    /// forwarded with `removed = true`.
     ///
-    /// Returns error if WebSocket provider is not available
+    /// # Errors
     ///
-    /// TODO: Implement event subscription with proper type annotations
-    /// Currently disabled pending ethers-rs type compatibility fixes
-    #[allow(dead_code)]
+    /// Returns error if no WebSocket provider is available, the subscription
+    /// fails, or the subscription stream ends
     pub async fn subscribe_validator_set_updates(
         &self,
+        events: mpsc::Sender<ValidatorSetEvent>,
     ) -> Result<()> {
-        Err(ConsensusError::Internal(
-            "Event subscription not yet implemented".to_string(),
-        ))
+        let ws = self.connected_ws().await?;
+
+        let filter = Filter::new().address(*self.consensus.address()).event_signature(vec![
+            IAndeConsensus::ValidatorSetUpdated::SIGNATURE_HASH,
+            IAndeConsensus::ValidatorJailed::SIGNATURE_HASH,
+            IAndeConsensus::ValidatorUnjailed::SIGNATURE_HASH,
+        ]);
+
+        let mut stream = ws.subscribe_logs(&filter).await?.into_stream();
+        info!(contract = ?self.consensus.address(), "Subscribed to validator set events");
+
+        while let Some(log) = stream.next().await {
+            let Some(event) = decode_validator_event(&log) else {
+                warn!(tx = ?log.transaction_hash, "Undecodable AndeConsensus log");
+                continue;
+            };
+
+            if events.send(event).await.is_err() {
+                return Ok(());
+            }
+        }
+
+        // Reconnect on the next subscription attempt
+        *self.ws_provider.write().await = None;
+        Err(ConsensusError::RpcError("Validator event subscription closed".to_string()))
+    }
+
+    /// Connected WebSocket provider, reconnecting if needed
+    async fn connected_ws(&self) -> Result<DynProvider> {
+        if let Some(ws) = self.ws_provider.read().await.as_ref() {
+            return Ok(ws.clone());
+        }
+
+        let url = self
+            .ws_url
+            .as_deref()
+            .ok_or_else(|| ConsensusError::ConfigError("No WebSocket URL configured".to_string()))?;
+        let ws = connect_ws(url).await?;
+
+        *self.ws_provider.write().await = Some(ws.clone());
+        Ok(ws)
     }
 
     /// Get the last synced block number
@@ -299,6 +467,139 @@ impl ContractClient {
     }
 }
 
+#[async_trait]
+impl ConsensusDataSource for ContractClient {
+    fn name(&self) -> &'static str {
+        "rpc"
+    }
+
+    async fn latest_block(&self) -> Result<u64> {
+        self.get_block_number().await
+    }
+
+    async fn active_validators(&self, block: u64) -> Result<Vec<ValidatorInfo>> {
+        let at = BlockId::number(block);
+        let addresses = self.consensus.getActiveValidators().block(at).call().await?;
+
+        let mut validators = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            match self.consensus.getValidatorInfo(address).block(at).call().await {
+                Ok(info) => validators.push(info.into()),
+                Err(e) => {
+                    error!(validator = ?address, block, error = %e, "Failed to fetch validator info");
+                }
+            }
+        }
+
+        Ok(validators)
+    }
+
+    async fn current_epoch(&self, block: u64) -> Result<u64> {
+        let epoch = self.consensus.currentEpoch().block(BlockId::number(block)).call().await?;
+
+        Ok(epoch.saturating_to())
+    }
+
+    async fn epoch_validator_set(&self, epoch: u64) -> Result<ValidatorSetUpdate> {
+        self.get_epoch_validator_set(epoch).await
+    }
+
+    async fn is_timeout_reached(&self, block: u64) -> Result<bool> {
+        Ok(self.coordinator.isTimeoutReached().block(BlockId::number(block)).call().await?)
+    }
+
+    async fn forced_rotations(&self, after: u64, block: u64) -> Result<Vec<ForcedRotation>> {
+        let at = BlockId::number(block);
+        let current: u64 = self.coordinator.currentRotationNumber().block(at).call().await?.saturating_to();
+        let from = after.max(current.saturating_sub(MAX_FORCED_ROTATIONS)) + 1;
+        if from > current {
+            return Ok(Vec::new());
+        }
+
+        let mut forced = Vec::new();
+        let mut previous = self.coordinator.rotations(U256::from(from - 1)).block(at).call().await?;
+        for number in from..=current {
+            let next = self.coordinator.rotations(U256::from(number)).block(at).call().await?;
+            // `_forceRotation` closes the previous rotation as failed
+            if !previous.completedSuccessfully {
+                forced.push(ForcedRotation { rotation_number: number, block_number: next.startBlock.saturating_to() });
+            }
+            previous = next;
+        }
+        Ok(forced)
+    }
+
+    async fn jail_release_block(&self, validator: Address, block: u64) -> Result<u64> {
+        let release = self.consensus.jailReleaseBlock(validator).block(BlockId::number(block)).call().await?;
+
+        Ok(release.saturating_to())
+    }
+
+    async fn coordinator_leader(&self, block: u64) -> Result<(Address, u64)> {
+        self.get_coordinator_leader(block).await
+    }
+
+    async fn leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>> {
+        self.get_leader_rotations(from, to).await
+    }
+
+    async fn next_leader(&self, block: u64) -> Result<Address> {
+        self.get_next_leader(block).await
+    }
+}
+
+/// Connect a WebSocket provider
+async fn connect_ws(url: &str) -> Result<DynProvider> {
+    Ok(ProviderBuilder::new().connect_ws(WsConnect::new(url)).await?.erased())
+}
+
+/// Decode an AndeConsensus validator set log
+fn decode_validator_event(log: &Log) -> Option<ValidatorSetEvent> {
+    let block_number = log.block_number?;
+
+    let change = match IAndeConsensusEvents::decode_raw_log(log.topics(), &log.data().data).ok()? {
+        IAndeConsensusEvents::ValidatorSetUpdated(event) => {
+            ValidatorSetChange::Updated(ValidatorSetUpdate {
+                epoch: event.epoch.saturating_to(),
+                validators: event.validators,
+                powers: event.powers.iter().map(|power| power.saturating_to()).collect(),
+                total_power: event.totalPower.saturating_to(),
+                block_number,
+                timestamp: 0,
+            })
+        }
+        IAndeConsensusEvents::ValidatorJailed(event) => ValidatorSetChange::Jailed {
+            validator: event.validator,
+            reason: event.reason,
+        },
+        IAndeConsensusEvents::ValidatorUnjailed(event) => ValidatorSetChange::Unjailed {
+            validator: event.validator,
+        },
+    };
+
+    Some(ValidatorSetEvent {
+        change,
+        block_number,
+        block_hash: log.block_hash?,
+        log_index: log.log_index.unwrap_or(0),
+        removed: log.removed,
+    })
+}
+
+/// Decode an AndeSequencerCoordinator `LeaderRotated` log
+fn decode_leader_rotation(log: &Log) -> Option<LeaderRotation> {
+    let block_number = log.block_number?;
+    let event = IAndeSequencerCoordinator::LeaderRotated::decode_raw_log(log.topics(), &log.data().data).ok()?;
+
+    Some(LeaderRotation {
+        rotation_number: event.rotationNumber.saturating_to(),
+        old_leader: event.oldLeader,
+        new_leader: event.newLeader,
+        block_number,
+        reason: event.reason,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,4 +618,52 @@ mod tests {
         // Should succeed even with zero addresses (contracts might not exist)
         assert!(client.is_ok());
     }
+
+    #[test]
+    fn test_decode_removed_jail_log() {
+        let jailed = IAndeConsensus::ValidatorJailed {
+            validator: Address::repeat_byte(7),
+            reason: "double sign".to_string(),
+            timestamp: U256::from(1),
+        };
+        let log = Log {
+            inner: alloy_primitives::Log { address: Address::ZERO, data: jailed.encode_log_data() },
+            block_hash: Some(B256::repeat_byte(2)),
+            block_number: Some(42),
+            log_index: Some(3),
+            removed: true,
+            ..Default::default()
+        };
+
+        let event = decode_validator_event(&log).unwrap();
+        assert_eq!(
+            event.change,
+            ValidatorSetChange::Jailed {
+                validator: Address::repeat_byte(7),
+                reason: "double sign".to_string(),
+            }
+        );
+        assert_eq!((event.block_number, event.log_index, event.removed), (42, 3, true));
+    }
+
+    #[test]
+    fn test_decode_leader_rotation() {
+        let rotated = IAndeSequencerCoordinator::LeaderRotated {
+            rotationNumber: U256::from(5),
+            oldLeader: Address::repeat_byte(1),
+            newLeader: Address::repeat_byte(2),
+            blockNumber: U256::from(99),
+            reason: "Timeout detected".to_string(),
+        };
+        let log = Log {
+            inner: alloy_primitives::Log { address: Address::ZERO, data: rotated.encode_log_data() },
+            block_number: Some(100),
+            ..Default::default()
+        };
+
+        let rotation = decode_leader_rotation(&log).unwrap();
+        assert_eq!((rotation.rotation_number, rotation.block_number), (5, 100));
+        assert_eq!((rotation.old_leader, rotation.new_leader), (Address::repeat_byte(1), Address::repeat_byte(2)));
+        assert_eq!(rotation.reason, "Timeout detected");
+    }
 }
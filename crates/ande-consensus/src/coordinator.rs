@@ -0,0 +1,293 @@
+//! Transactions to AndeSequencerCoordinator
+//!
+//! Leader handoff needs two coordinator calls from the sequencer's own key:
+//! `recordBlockProduced` after each block it produces (the sequencer needs
+//! `ORACLE_ROLE`) and `checkTimeout` when the leader stalls. They are sent
+//! one at a time with locally tracked nonces and explicit gas:
+//!
+//! ```text
+//! nonce: cached, refetched (pending) after any failed send
+//! gas:   estimate × GAS_LIMIT_MARGIN_PERCENT
+//! fees:  EIP-1559 estimate, capped ──no receipt in RECEIPT_TIMEOUT──→ same nonce,
+//!                                     fees × FEE_BUMP_PERCENT (≤ MAX_FEE_BUMPS times)
+//! ```
+
+use crate::{
+    bindings::IAndeSequencerCoordinator::{self, IAndeSequencerCoordinatorErrors},
+    error::{ConsensusError, Result},
+};
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::{EthereumWallet, TransactionBuilder},
+    providers::{DynProvider, PendingTransactionError, Provider, ProviderBuilder, WatchTxError},
+    rpc::types::{TransactionReceipt, TransactionRequest},
+    signers::{local::PrivateKeySigner, Signer},
+    sol_types::SolCall,
+    transports::{http::reqwest::Url, TransportError},
+};
+use alloy_primitives::{Address, U256};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Gas limit as a percentage of the estimate
+pub const GAS_LIMIT_MARGIN_PERCENT: u64 = 130;
+
+/// Fee increase when resending a transaction that was not mined
+pub const FEE_BUMP_PERCENT: u128 = 125;
+
+/// Resends with bumped fees before giving up
+pub const MAX_FEE_BUMPS: usize = 3;
+
+/// Time to wait for a receipt before bumping fees
+pub const RECEIPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// EIP-1559 fees of one send attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    /// Maximum total fee per gas
+    pub max_fee_per_gas: u128,
+
+    /// Maximum priority fee per gas
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Fees {
+    /// Limit both fees to `cap`
+    pub fn capped(self, cap: u128) -> Self {
+        Self {
+            max_fee_per_gas: self.max_fee_per_gas.min(cap),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.min(cap),
+        }
+    }
+
+    /// Fees for replacing a pending transaction, limited to `cap`
+    ///
+    /// Nodes only accept a replacement that raises both fees, so at least
+    /// by one wei each.
+    pub fn bumped(self, cap: u128) -> Self {
+        let bump = |fee: u128| (fee.saturating_mul(FEE_BUMP_PERCENT) / 100).max(fee + 1);
+        Self {
+            max_fee_per_gas: bump(self.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(self.max_priority_fee_per_gas),
+        }
+        .capped(cap)
+    }
+}
+
+/// Sends AndeSequencerCoordinator transactions from the sequencer's key
+pub struct CoordinatorTransactor {
+    /// Provider signing with the sequencer's key, without fillers
+    provider: DynProvider,
+
+    /// AndeSequencerCoordinator contract
+    coordinator: Address,
+
+    /// Sequencer address the transactions are sent from
+    sender: Address,
+
+    /// Chain ID
+    chain_id: u64,
+
+    /// Highest fee per gas paid, in wei
+    max_fee_per_gas: u128,
+
+    /// Next nonce, `None` until read from the node; held while a
+    /// transaction is in flight so sends never interleave
+    nonce: Mutex<Option<u64>>,
+}
+
+impl CoordinatorTransactor {
+    /// Send coordinator transactions to `rpc_url` signed with `secret`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the RPC URL or the key is invalid
+    pub fn new(
+        rpc_url: &str,
+        coordinator: Address,
+        secret: &[u8],
+        chain_id: u64,
+        max_fee_per_gas: u128,
+    ) -> Result<Self> {
+        let url: Url = rpc_url
+            .parse()
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid RPC URL {rpc_url}: {e}")))?;
+        let mut signer = PrivateKeySigner::from_slice(secret)
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid signing key: {e}")))?;
+        signer.set_chain_id(Some(chain_id));
+        let sender = signer.address();
+
+        // Nonce, gas and fees are set here rather than by fillers
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .wallet(EthereumWallet::from(signer))
+            .connect_http(url)
+            .erased();
+
+        info!(coordinator = ?coordinator, sender = ?sender, "Coordinator transactor initialized");
+        Ok(Self { provider, coordinator, sender, chain_id, max_fee_per_gas, nonce: Mutex::new(None) })
+    }
+
+    /// Address the transactions are sent from
+    pub const fn sender(&self) -> Address {
+        self.sender
+    }
+
+    /// Report a block this sequencer produced
+    ///
+    /// Returns `false` if the coordinator no longer considers this sequencer
+    /// the leader, i.e. a rotation landed first.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the block cannot be read or the transaction fails
+    pub async fn record_block_produced(&self, block_number: u64) -> Result<bool> {
+        let gas_used = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(block_number))
+            .await?
+            .map_or(0, |block| block.header.gas_used);
+
+        let call = IAndeSequencerCoordinator::recordBlockProducedCall {
+            sequencer: self.sender,
+            blockNumber: U256::from(block_number),
+            gasUsed: U256::from(gas_used),
+        };
+        match self.send(&call).await {
+            Ok(_) => Ok(true),
+            Err(ConsensusError::CoordinatorReverted(IAndeSequencerCoordinatorErrors::NotCurrentLeader(_))) => {
+                debug!(block = block_number, "Not the coordinator leader anymore, block not recorded");
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Ask the coordinator to rotate away from a stalled leader
+    ///
+    /// Returns `false` if the coordinator does not see a timeout (yet).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the transaction fails
+    pub async fn check_timeout(&self) -> Result<bool> {
+        match self.send(&IAndeSequencerCoordinator::checkTimeoutCall {}).await {
+            Ok(_) => Ok(true),
+            Err(ConsensusError::CoordinatorReverted(IAndeSequencerCoordinatorErrors::TimeoutNotReached(_))) => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Send `call` and wait for it to be mined, bumping fees while it is not
+    async fn send<C: SolCall>(&self, call: &C) -> Result<TransactionReceipt> {
+        let mut nonce = self.nonce.lock().await;
+        let next = match *nonce {
+            Some(next) => next,
+            None => self.provider.get_transaction_count(self.sender).pending().await?,
+        };
+
+        let request = TransactionRequest::default()
+            .with_from(self.sender)
+            .with_to(self.coordinator)
+            .with_input(call.abi_encode())
+            .with_chain_id(self.chain_id)
+            .with_nonce(next);
+
+        // Reverts surface here, before anything is sent
+        let gas = self.provider.estimate_gas(request.clone()).await.map_err(reverted)?;
+        let request = request.with_gas_limit(gas.saturating_mul(GAS_LIMIT_MARGIN_PERCENT) / 100);
+
+        let estimate = self.provider.estimate_eip1559_fees().await?;
+        let mut fees = Fees {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        }
+        .capped(self.max_fee_per_gas);
+
+        for attempt in 0..=MAX_FEE_BUMPS {
+            let attempt_request = request
+                .clone()
+                .with_max_fee_per_gas(fees.max_fee_per_gas)
+                .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+            let pending = match self.provider.send_transaction(attempt_request).await {
+                Ok(pending) => pending,
+                Err(e) => {
+                    // The cached nonce may be what the node rejected
+                    *nonce = None;
+                    return Err(reverted(e));
+                }
+            };
+            *nonce = Some(next + 1);
+
+            match pending.with_timeout(Some(RECEIPT_TIMEOUT)).get_receipt().await {
+                Ok(receipt) if receipt.status() => {
+                    debug!(call = C::SIGNATURE, tx = ?receipt.transaction_hash, nonce = next, "Coordinator transaction mined");
+                    return Ok(receipt);
+                }
+                Ok(receipt) => {
+                    return Err(ConsensusError::ContractError(format!(
+                        "{} transaction {:?} reverted",
+                        C::SIGNATURE,
+                        receipt.transaction_hash
+                    )));
+                }
+                Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) if attempt < MAX_FEE_BUMPS => {
+                    fees = fees.bumped(self.max_fee_per_gas);
+                    warn!(
+                        call = C::SIGNATURE,
+                        nonce = next,
+                        max_fee_per_gas = fees.max_fee_per_gas,
+                        "Coordinator transaction not mined, resending with higher fees"
+                    );
+                }
+                Err(e) => {
+                    *nonce = None;
+                    return Err(e.into());
+                }
+            }
+        }
+
+        *nonce = None;
+        Err(ConsensusError::RpcError(format!(
+            "{} not mined after {MAX_FEE_BUMPS} fee bumps",
+            C::SIGNATURE
+        )))
+    }
+}
+
+/// Decode a coordinator revert carried by an RPC error
+fn reverted(err: TransportError) -> ConsensusError {
+    match err
+        .as_error_resp()
+        .and_then(|resp| resp.as_decoded_interface_error::<IAndeSequencerCoordinatorErrors>())
+    {
+        Some(revert) => ConsensusError::CoordinatorReverted(revert),
+        None => err.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_bumps_raise_both_fees_up_to_cap() {
+        let fees = Fees { max_fee_per_gas: 100, max_priority_fee_per_gas: 2 };
+
+        let bumped = fees.bumped(1_000);
+        assert_eq!(bumped, Fees { max_fee_per_gas: 125, max_priority_fee_per_gas: 3 });
+
+        let capped = Fees { max_fee_per_gas: 900, max_priority_fee_per_gas: 900 }.bumped(1_000);
+        assert_eq!(capped, Fees { max_fee_per_gas: 1_000, max_priority_fee_per_gas: 1_000 });
+    }
+
+    #[test]
+    fn test_transactor_rejects_bad_key() {
+        let result = CoordinatorTransactor::new("http://localhost:8545", Address::ZERO, &[0u8; 32], 6174, 1);
+        assert!(matches!(result, Err(ConsensusError::ConfigError(_))));
+    }
+}
@@ -0,0 +1,502 @@
+//! Sources of on-chain consensus data
+//!
+//! The engine reads validator sets, epochs, coordinator leaders and leader
+//! timeouts through a [`ConsensusDataSource`], so it does not care whether the contracts are
+//! reached over JSON-RPC or read from the state of the node it runs in:
+//!
+//! ```text
+//!                      ┌─→ ContractClient ──HTTP──→ remote node
+//! ConsensusEngine ─────┤
+//!   (reads pinned      └─→ StateReader<C> ──ViewCaller──→ local state at block N
+//!    to one block)                              (ande-reth: EVM over the state provider)
+//! ```
+//!
+//! Every read takes the block it applies to, so a validator set sync sees
+//! one consistent state even if blocks arrive while it runs.
+
+use crate::{
+    bindings::{IAndeConsensus, IAndeSequencerCoordinator},
+    error::{ConsensusError, Result},
+    types::{ForcedRotation, LeaderRotation, ValidatorInfo, ValidatorSetUpdate},
+};
+use alloy::sol_types::SolCall;
+use alloy_primitives::{Address, Bytes, U256};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Latest coordinator rotations checked on one read
+pub const MAX_FORCED_ROTATIONS: u64 = 64;
+
+/// On-chain consensus data, read at explicit block heights
+#[async_trait]
+pub trait ConsensusDataSource: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    /// Latest block the source can read
+    async fn latest_block(&self) -> Result<u64>;
+
+    /// Active validators with full info at `block`
+    async fn active_validators(&self, block: u64) -> Result<Vec<ValidatorInfo>>;
+
+    /// Current epoch at `block`
+    async fn current_epoch(&self, block: u64) -> Result<u64>;
+
+    /// Validator set `epoch` started with, powers read at its start block
+    async fn epoch_validator_set(&self, epoch: u64) -> Result<ValidatorSetUpdate>;
+
+    /// Whether the coordinator reports a leader timeout at `block`
+    async fn is_timeout_reached(&self, block: u64) -> Result<bool>;
+
+    /// Coordinator rotations numbered above `after` that were forced
+    /// rather than completed, read at `block`, oldest first
+    ///
+    /// At most [`MAX_FORCED_ROTATIONS`] of the latest rotations are read.
+    async fn forced_rotations(&self, after: u64, block: u64) -> Result<Vec<ForcedRotation>>;
+
+    /// Block from which jailed `validator` can unjail, read at `block`
+    async fn jail_release_block(&self, validator: Address, block: u64) -> Result<u64>;
+
+    /// Coordinator leader and rotation number at `block`
+    async fn coordinator_leader(&self, block: u64) -> Result<(Address, u64)>;
+
+    /// Coordinator handoffs that took place in blocks `from..=to`, oldest
+    /// first
+    async fn leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>>;
+
+    /// Sequencer a forced rotation at `block` would hand over to
+    async fn next_leader(&self, block: u64) -> Result<Address>;
+}
+
+/// Sequencer after `leader` among `sequencers`, wrapping around
+///
+/// Mirrors `_forceRotation`, which hands over to the next active sequencer.
+pub(crate) fn next_sequencer(leader: Address, sequencers: &[Address]) -> Result<Address> {
+    let index = sequencers.iter().position(|sequencer| *sequencer == leader).unwrap_or(sequencers.len());
+    sequencers
+        .get((index + 1) % sequencers.len().max(1))
+        .copied()
+        .ok_or_else(|| ConsensusError::ContractError("No active sequencers".to_string()))
+}
+
+/// Executes read-only contract calls against the state at a block
+pub trait ViewCaller: Send + Sync {
+    /// Latest block whose state is available
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the chain head cannot be read
+    fn latest_block(&self) -> Result<u64>;
+
+    /// Call `contract` with `input` on the state after `block`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the state is unavailable or the call reverts
+    fn call(&self, block: u64, contract: Address, input: Bytes) -> Result<Bytes>;
+}
+
+/// [`ConsensusDataSource`] over a [`ViewCaller`], without any network hop
+pub struct StateReader<C> {
+    /// Call executor
+    caller: Arc<C>,
+
+    /// AndeConsensus contract
+    consensus: Address,
+
+    /// AndeSequencerCoordinator contract
+    coordinator: Address,
+}
+
+impl<C> Clone for StateReader<C> {
+    fn clone(&self) -> Self {
+        Self { caller: Arc::clone(&self.caller), consensus: self.consensus, coordinator: self.coordinator }
+    }
+}
+
+impl<C> std::fmt::Debug for StateReader<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateReader")
+            .field("consensus", &self.consensus)
+            .field("coordinator", &self.coordinator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: ViewCaller + 'static> StateReader<C> {
+    /// Read the consensus contracts through `caller`
+    pub fn new(caller: C, consensus: Address, coordinator: Address) -> Self {
+        Self { caller: Arc::new(caller), consensus, coordinator }
+    }
+
+    /// Call a view and decode its return value
+    fn view<T: SolCall>(&self, block: u64, contract: Address, call: &T) -> Result<T::Return> {
+        let output = self.caller.call(block, contract, call.abi_encode().into())?;
+        T::abi_decode_returns(&output).map_err(|e| {
+            ConsensusError::ContractError(format!("Invalid return data of {}: {e}", T::SIGNATURE))
+        })
+    }
+
+    /// Active validators with full info at `block`
+    fn read_validators(&self, block: u64) -> Result<Vec<ValidatorInfo>> {
+        let addresses = self.view(block, self.consensus, &IAndeConsensus::getActiveValidatorsCall {})?;
+
+        let mut validators = Vec::with_capacity(addresses.len());
+        for validator in addresses {
+            let info = self.view(block, self.consensus, &IAndeConsensus::getValidatorInfoCall { validator })?;
+            validators.push(info.into());
+        }
+
+        debug!(block, count = validators.len(), "Read validators from state");
+        Ok(validators)
+    }
+
+    /// Validator set of `epoch` from its start block
+    fn read_epoch_set(&self, epoch: u64) -> Result<ValidatorSetUpdate> {
+        let head = self.caller.latest_block()?;
+        let info = self.view(head, self.consensus, &IAndeConsensus::getEpochInfoCall { epoch: U256::from(epoch) })?;
+        let start_block: u64 = info.startBlock.saturating_to();
+
+        let mut powers = Vec::with_capacity(info.validators.len());
+        for validator in &info.validators {
+            let call = IAndeConsensus::getValidatorInfoCall { validator: *validator };
+            let validator_info = match self.view(start_block, self.consensus, &call) {
+                Ok(validator_info) => validator_info,
+                Err(e) => {
+                    warn!(epoch, start_block, error = %e, "Epoch start state unavailable, using current power");
+                    self.view(head, self.consensus, &call)?
+                }
+            };
+            powers.push(validator_info.power.saturating_to());
+        }
+
+        Ok(ValidatorSetUpdate {
+            epoch,
+            validators: info.validators,
+            total_power: powers.iter().sum(),
+            powers,
+            block_number: start_block,
+            timestamp: info.startTime.saturating_to(),
+        })
+    }
+
+    /// Forced coordinator rotations above `after` at `block`
+    fn read_forced_rotations(&self, after: u64, block: u64) -> Result<Vec<ForcedRotation>> {
+        let current: u64 = self
+            .view(block, self.coordinator, &IAndeSequencerCoordinator::currentRotationNumberCall {})?
+            .saturating_to();
+        let from = after.max(current.saturating_sub(MAX_FORCED_ROTATIONS)) + 1;
+        if from > current {
+            return Ok(Vec::new());
+        }
+
+        let rotation = |number: u64| {
+            let call = IAndeSequencerCoordinator::rotationsCall { rotationNumber: U256::from(number) };
+            self.view(block, self.coordinator, &call)
+        };
+        let mut forced = Vec::new();
+        let mut previous = rotation(from - 1)?;
+        for number in from..=current {
+            let next = rotation(number)?;
+            // `_forceRotation` closes the previous rotation as failed
+            if !previous.completedSuccessfully {
+                forced.push(ForcedRotation { rotation_number: number, block_number: next.startBlock.saturating_to() });
+            }
+            previous = next;
+        }
+        Ok(forced)
+    }
+
+    /// Handoffs in blocks `from..=to` from the coordinator's rotation
+    /// records at `to`
+    ///
+    /// State holds no logs, so each handoff is rebuilt from the rotation it
+    /// started and the one before. The reason string is not stored; forced
+    /// rotations are told apart by the previous rotation not completing.
+    fn read_leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>> {
+        let rotation = |number: u64| {
+            let call = IAndeSequencerCoordinator::rotationsCall { rotationNumber: U256::from(number) };
+            self.view(to, self.coordinator, &call)
+        };
+        let current: u64 = self
+            .view(to, self.coordinator, &IAndeSequencerCoordinator::currentRotationNumberCall {})?
+            .saturating_to();
+        let oldest = current.saturating_sub(MAX_FORCED_ROTATIONS).max(1);
+
+        // Rotation 0 is the coordinator's first leader, not a handoff
+        let mut rotations = Vec::new();
+        let mut next = rotation(current)?;
+        for number in (oldest..=current).rev() {
+            let started_at: u64 = next.startBlock.saturating_to();
+            if started_at < from {
+                break;
+            }
+            let previous = rotation(number - 1)?;
+            rotations.push(LeaderRotation {
+                rotation_number: number,
+                old_leader: previous.leader,
+                new_leader: next.leader,
+                block_number: started_at,
+                reason: if previous.completedSuccessfully { "Round-robin" } else { "Forced" }.to_string(),
+            });
+            next = previous;
+        }
+        rotations.reverse();
+        Ok(rotations)
+    }
+
+    /// Run blocking state reads off the async runtime
+    async fn blocking<T, F>(&self, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T> + Send + 'static,
+    {
+        let reader = self.clone();
+        tokio::task::spawn_blocking(move || read(&reader))
+            .await
+            .map_err(|e| ConsensusError::Internal(format!("State read task failed: {e}")))?
+    }
+}
+
+#[async_trait]
+impl<C: ViewCaller + 'static> ConsensusDataSource for StateReader<C> {
+    fn name(&self) -> &'static str {
+        "state"
+    }
+
+    async fn latest_block(&self) -> Result<u64> {
+        self.blocking(|reader| reader.caller.latest_block()).await
+    }
+
+    async fn active_validators(&self, block: u64) -> Result<Vec<ValidatorInfo>> {
+        self.blocking(move |reader| reader.read_validators(block)).await
+    }
+
+    async fn current_epoch(&self, block: u64) -> Result<u64> {
+        self.blocking(move |reader| {
+            let epoch = reader.view(block, reader.consensus, &IAndeConsensus::currentEpochCall {})?;
+            Ok(epoch.saturating_to())
+        })
+        .await
+    }
+
+    async fn epoch_validator_set(&self, epoch: u64) -> Result<ValidatorSetUpdate> {
+        self.blocking(move |reader| reader.read_epoch_set(epoch)).await
+    }
+
+    async fn is_timeout_reached(&self, block: u64) -> Result<bool> {
+        self.blocking(move |reader| {
+            reader.view(block, reader.coordinator, &IAndeSequencerCoordinator::isTimeoutReachedCall {})
+        })
+        .await
+    }
+
+    async fn forced_rotations(&self, after: u64, block: u64) -> Result<Vec<ForcedRotation>> {
+        self.blocking(move |reader| reader.read_forced_rotations(after, block)).await
+    }
+
+    async fn jail_release_block(&self, validator: Address, block: u64) -> Result<u64> {
+        self.blocking(move |reader| {
+            let release = reader.view(block, reader.consensus, &IAndeConsensus::jailReleaseBlockCall { validator })?;
+            Ok(release.saturating_to())
+        })
+        .await
+    }
+
+    async fn coordinator_leader(&self, block: u64) -> Result<(Address, u64)> {
+        self.blocking(move |reader| {
+            let leader = reader.view(block, reader.coordinator, &IAndeSequencerCoordinator::currentLeaderCall {})?;
+            let rotation =
+                reader.view(block, reader.coordinator, &IAndeSequencerCoordinator::currentRotationNumberCall {})?;
+            Ok((leader, rotation.saturating_to()))
+        })
+        .await
+    }
+
+    async fn leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>> {
+        self.blocking(move |reader| reader.read_leader_rotations(from, to)).await
+    }
+
+    async fn next_leader(&self, block: u64) -> Result<Address> {
+        self.blocking(move |reader| {
+            let leader = reader.view(block, reader.coordinator, &IAndeSequencerCoordinator::currentLeaderCall {})?;
+            let sequencers =
+                reader.view(block, reader.coordinator, &IAndeSequencerCoordinator::getActiveSequencersCall {})?;
+            next_sequencer(leader, &sequencers)
+        })
+        .await
+    }
+}
+
+impl From<IAndeConsensus::ValidatorInfo> for ValidatorInfo {
+    fn from(info: IAndeConsensus::ValidatorInfo) -> Self {
+        let accumulated_priority = i64::try_from(info.accumulatedPriority)
+            .unwrap_or(if info.accumulatedPriority.is_negative() { i64::MIN } else { i64::MAX });
+
+        Self {
+            validator: info.validator,
+            p2p_peer_id: info.p2pPeerId,
+            rpc_endpoint: info.rpcEndpoint,
+            stake: info.stake,
+            power: info.power.saturating_to(),
+            accumulated_priority,
+            total_blocks_produced: info.totalBlocksProduced.saturating_to(),
+            total_blocks_missed: info.totalBlocksMissed.saturating_to(),
+            uptime: info.uptime.saturating_to(),
+            last_block_produced: info.lastBlockProduced.saturating_to(),
+            registered_at: info.registeredAt.saturating_to(),
+            jailed: info.jailed,
+            active: info.active,
+            is_permanent: info.isPermanent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, I256};
+    use alloy::sol_types::SolValue;
+    use std::collections::HashMap;
+
+    /// Contract state per block, answering the views the reader uses
+    #[derive(Default)]
+    struct MockState {
+        /// Validator powers by block
+        powers: HashMap<u64, Vec<(Address, u64)>>,
+        /// Coordinator rotations by number: leader, start block and whether it completed
+        rotations: Vec<(Address, u64, bool)>,
+        /// Head block
+        head: u64,
+    }
+
+    impl ViewCaller for MockState {
+        fn latest_block(&self) -> Result<u64> {
+            Ok(self.head)
+        }
+
+        fn call(&self, block: u64, _contract: Address, input: Bytes) -> Result<Bytes> {
+            let powers = self
+                .powers
+                .get(&block)
+                .ok_or_else(|| ConsensusError::ContractError(format!("state of block {block} pruned")))?;
+            let selector: [u8; 4] = input[..4].try_into().unwrap();
+
+            let output = match selector {
+                IAndeConsensus::getActiveValidatorsCall::SELECTOR => {
+                    powers.iter().map(|(validator, _)| *validator).collect::<Vec<_>>().abi_encode()
+                }
+                IAndeConsensus::getValidatorInfoCall::SELECTOR => {
+                    let call = IAndeConsensus::getValidatorInfoCall::abi_decode(&input).unwrap();
+                    let power = powers.iter().find(|(v, _)| *v == call.validator).map_or(0, |(_, p)| *p);
+                    IAndeConsensus::ValidatorInfo {
+                        validator: call.validator,
+                        p2pPeerId: B256::ZERO,
+                        rpcEndpoint: "http://validator:8545".to_string(),
+                        stake: U256::from(power),
+                        power: U256::from(power),
+                        accumulatedPriority: I256::try_from(-5).unwrap(),
+                        totalBlocksProduced: U256::ZERO,
+                        totalBlocksMissed: U256::ZERO,
+                        uptime: U256::from(10_000),
+                        lastBlockProduced: U256::ZERO,
+                        registeredAt: U256::ZERO,
+                        jailed: false,
+                        active: true,
+                        isPermanent: false,
+                    }
+                    .abi_encode()
+                }
+                IAndeConsensus::currentEpochCall::SELECTOR => U256::from(block / 100).abi_encode(),
+                IAndeConsensus::getEpochInfoCall::SELECTOR => IAndeConsensus::EpochInfo {
+                    epochNumber: U256::from(1),
+                    startBlock: U256::from(100),
+                    endBlock: U256::ZERO,
+                    startTime: U256::from(1_700_000_000),
+                    endTime: U256::ZERO,
+                    validators: self.powers[&100].iter().map(|(validator, _)| *validator).collect(),
+                    totalVotingPower: U256::ZERO,
+                }
+                .abi_encode(),
+                IAndeSequencerCoordinator::currentRotationNumberCall::SELECTOR => {
+                    U256::from(self.rotations.len() - 1).abi_encode()
+                }
+                IAndeSequencerCoordinator::rotationsCall::SELECTOR => {
+                    let call = IAndeSequencerCoordinator::rotationsCall::abi_decode(&input).unwrap();
+                    let number: usize = call.rotationNumber.saturating_to();
+                    let (leader, start_block, completed) = self.rotations[number];
+                    IAndeSequencerCoordinator::rotationsCall::abi_encode_returns(
+                        &IAndeSequencerCoordinator::rotationsReturn {
+                            number: call.rotationNumber,
+                            startBlock: U256::from(start_block),
+                            endBlock: U256::ZERO,
+                            leader,
+                            blocksProduced: U256::ZERO,
+                            missedBlocks: U256::ZERO,
+                            completedSuccessfully: completed,
+                        },
+                    )
+                }
+                _ => return Err(ConsensusError::ContractError("unknown selector".to_string())),
+            };
+            Ok(output.into())
+        }
+    }
+
+    fn reader(state: MockState) -> StateReader<MockState> {
+        StateReader::new(state, Address::repeat_byte(0xc0), Address::repeat_byte(0xc1))
+    }
+
+    #[tokio::test]
+    async fn test_reads_pinned_to_block() {
+        let mut state = MockState { head: 150, ..Default::default() };
+        state.powers.insert(100, vec![(Address::repeat_byte(1), 10)]);
+        state.powers.insert(150, vec![(Address::repeat_byte(1), 30), (Address::repeat_byte(2), 20)]);
+        let reader = reader(state);
+
+        assert_eq!(reader.latest_block().await.unwrap(), 150);
+        assert_eq!(reader.current_epoch(150).await.unwrap(), 1);
+
+        let at_start = reader.active_validators(100).await.unwrap();
+        assert_eq!(at_start.len(), 1);
+        assert_eq!(at_start[0].power, 10);
+        assert_eq!(at_start[0].accumulated_priority, -5);
+
+        let at_head = reader.active_validators(150).await.unwrap();
+        assert_eq!(at_head.iter().map(|v| v.power).collect::<Vec<_>>(), vec![30, 20]);
+        assert!(reader.active_validators(120).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_epoch_set_uses_start_block_powers() {
+        let mut state = MockState { head: 150, ..Default::default() };
+        state.powers.insert(100, vec![(Address::repeat_byte(1), 10), (Address::repeat_byte(2), 40)]);
+        state.powers.insert(150, vec![(Address::repeat_byte(1), 99), (Address::repeat_byte(2), 99)]);
+
+        let set = reader(state).epoch_validator_set(1).await.unwrap();
+        assert_eq!(set.block_number, 100);
+        assert_eq!(set.powers, vec![10, 40]);
+        assert_eq!(set.total_power, 50);
+        assert_eq!(set.timestamp, 1_700_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_leader_rotations_rebuilt_from_records() {
+        let (a, b, c) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let mut state = MockState { head: 300, ..Default::default() };
+        state.powers.insert(300, Vec::new());
+        state.rotations = vec![(a, 0, true), (b, 120, false), (c, 250, false)];
+        let reader = reader(state);
+
+        let rotations = reader.leader_rotations(100, 300).await.unwrap();
+        let handoffs: Vec<_> = rotations
+            .iter()
+            .map(|r| (r.rotation_number, r.old_leader, r.new_leader, r.block_number, r.reason.as_str()))
+            .collect();
+        assert_eq!(handoffs, vec![(1, a, b, 120, "Round-robin"), (2, b, c, 250, "Forced")]);
+
+        // Handoffs before `from` were applied already
+        assert_eq!(reader.leader_rotations(121, 300).await.unwrap().len(), 1);
+    }
+}
@@ -1,30 +1,135 @@
 //! Main consensus engine coordinating all consensus operations
+//!
+//! Validator sets, epochs and leader timeouts are read through a
+//! [`ConsensusDataSource`]: the JSON-RPC [`ContractClient`] by default, or
+//! the hosting node's own state via
+//! [`ConsensusEngine::with_data_source`].
+//!
+//! The validator set is re-read from AndeConsensus whenever one of its
+//! validator events arrives over WebSocket, so changes land within one
+//! block. Polling every `sync_interval` stays as a fallback for missed
+//! events and for nodes without a WebSocket endpoint.
+//!
+//! Blocks are finalized by quorum certificates: this validator attests every
+//! block it is told about via [`ConsensusEngine::attest_block`], gossips the
+//! attestation to the other validators, and collects theirs in an
+//! [`AttestationPool`].
+//!
+//! Proposers are precomputed per height into a [`ProposerSchedule`] from the
+//! validator set each epoch started with, so block import can check the
+//! proposer without awaiting the engine and every node agrees on it.
+//!
+//! Attestations and signed proposals are also checked for equivocation and
+//! against the schedule; the resulting evidence is gossiped and submitted to
+//! AndeConsensus by a background task that retries until it lands.
+//!
+//! Validator set changes take effect at epoch boundaries: the active set is
+//! the one the current epoch started with, the contract's live list is
+//! staged until AndeConsensus starts the next epoch, and every completed
+//! epoch and rotation is kept in an [`EpochTracker`] with its block stats.
+//!
+//! The node's own jail period is followed by a [`JailTracker`]: the
+//! countdown is exposed in [`ConsensusState`] and the metrics, and with
+//! `auto_unjail` the engine sends `unjail()` once the release block is
+//! reached and resyncs until the validator is active again.
+//!
+//! With `leader_handoff`, block production follows AndeSequencerCoordinator
+//! instead of the local schedule: a [`LeaderHandoff`] built from its
+//! `LeaderRotated` logs decides who produces each height, own blocks are
+//! reported with `recordBlockProduced`, and the sequencer next in line sends
+//! `checkTimeout` when the leader stalls.
+//!
+//! Attestations and proposals are signed through the configured
+//! [`SigningBackend`](crate::signer::SigningBackend) (key file, keystore or
+//! remote signer) behind [`SlashingProtection`], which records every signed
+//! height in `data_dir` and refuses a second hash at the same height.
+//!
+//! Every canonical block and verified attestation also feeds a
+//! [`PerformanceTracker`], which computes each validator's uptime, missed
+//! slot streaks, attestation participation and proposal latency over the
+//! last `performance_window` blocks, independently of the uptime
+//! AndeConsensus reports.
+//!
+//! For light clients, finalized blocks are turned into [`FinalityProof`]s
+//! on request, and at every epoch boundary this validator signs the
+//! handover from the epoch it served in to the new set; the signatures of
+//! the previous set are collected in a [`HandoverPool`] into a
+//! [`ValidatorSetChangeProof`].
+//!
+//! Local state (rotation position, priorities, uptime counters, epoch
+//! history and sync progress) is snapshotted to `data_dir` after every
+//! update through a [`StateStore`] and restored when the engine starts.
 
 use crate::{
+    attestation::{AttestationGossip, AttestationPool, AttestationSigner, QuorumCertificate},
     config::ConsensusConfig,
     contract_client::ContractClient,
+    coordinator::CoordinatorTransactor,
+    data_source::ConsensusDataSource,
+    epoch::EpochTracker,
     error::{ConsensusError, Result},
+    event_sync::{EventAction, ValidatorEventTracker},
+    evidence::{Evidence, EvidencePool},
+    finality::{finality_proof, light_client_set, HandoverPool, ValidatorSetSignature},
+    handoff::LeaderHandoff,
+    jail::{JailChange, JailTracker, UNJAIL_RETRY_BLOCKS},
     metrics::ConsensusMetrics,
-    types::ConsensusState,
+    performance::{PerformanceTracker, ValidatorPerformance},
+    persistence::{EngineSnapshot, StateStore},
+    proposer_schedule::ProposerSchedule,
+    seal::recover_sealer,
+    signer::load_signing_backend,
+    slashing_protection::SlashingProtection,
+    types::{
+        AttestationInfo, BlockProposal, ConsensusState, EpochInfo, LeaderRotation, RotationInfo,
+        ValidatorSetChange, ValidatorInfo, ValidatorSetEvent, ValidatorSetUpdate,
+    },
     validator_set::{ValidatorSet, ValidatorSetStats},
 };
-use alloy_primitives::Address;
+use alloy::consensus::Header;
+use alloy_primitives::{Address, Bytes, B256};
+use ande_light_client::{decode_header, FinalityProof, ValidatorSet as LightValidatorSet, ValidatorSetChangeProof};
 use prometheus::Registry;
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::{
-    sync::{Mutex, RwLock},
+    sync::{broadcast, mpsc, Mutex, RwLock},
     time::interval,
 };
 use tracing::{debug, error, info, warn};
 
+/// Capacity of the finalized certificate channel
+const FINALIZED_CHANNEL_CAPACITY: usize = 256;
+
+/// Capacity of the validator event channel
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// First delay before resubscribing to validator events
+const RESUBSCRIBE_MIN_DELAY: Duration = Duration::from_secs(1);
+
+/// Maximum delay between resubscription attempts
+const RESUBSCRIBE_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Own blocks kept for reporting to the coordinator
+const MAX_UNREPORTED_BLOCKS: usize = 256;
+
+/// Blocks of coordinator logs read per handoff step
+const MAX_LOG_RANGE: u64 = 1_000;
+
 /// Main consensus engine
 pub struct ConsensusEngine {
     /// Configuration
     config: ConsensusConfig,
 
-    /// Contract client for on-chain interactions
+    /// Contract client for subscriptions and transactions
     client: Arc<ContractClient>,
 
+    /// Where validator sets and timeouts are read from
+    source: Arc<dyn ConsensusDataSource>,
+
     /// Validator set manager
     validator_set: Arc<RwLock<ValidatorSet>>,
 
@@ -39,15 +144,74 @@ pub struct ConsensusEngine {
 
     /// Last block where timeout was checked
     last_timeout_check: Arc<RwLock<u64>>,
+
+    /// Collected attestations and quorum certificates
+    attestations: Arc<std::sync::RwLock<AttestationPool>>,
+
+    /// This validator's attestation key, if configured
+    signer: Option<Arc<AttestationSigner>>,
+
+    /// Attestation delivery to other validators
+    gossip: AttestationGossip,
+
+    /// Newly formed quorum certificates
+    finalized: broadcast::Sender<QuorumCertificate>,
+
+    /// Scheduled proposer per block height
+    schedule: Arc<std::sync::RwLock<ProposerSchedule>>,
+
+    /// Snapshot of local state in `data_dir`
+    store: StateStore,
+
+    /// Detected misbehaviour awaiting on-chain submission
+    evidence: Arc<std::sync::Mutex<EvidencePool>>,
+
+    /// Current and completed epochs and rotations
+    epochs: Arc<std::sync::RwLock<EpochTracker>>,
+
+    /// Jail period of this sequencer
+    jail: Arc<std::sync::Mutex<JailTracker>>,
+
+    /// Coordinator leader by height, followed with `leader_handoff`
+    handoff: Arc<std::sync::RwLock<LeaderHandoff>>,
+
+    /// Coordinator transactions from this sequencer, with `leader_handoff` and a key
+    transactor: Option<Arc<CoordinatorTransactor>>,
+
+    /// Own blocks not yet reported to the coordinator
+    produced: Arc<std::sync::Mutex<VecDeque<u64>>>,
+
+    /// Validator performance over recent canonical blocks
+    performance: Arc<std::sync::RwLock<PerformanceTracker>>,
+
+    /// Light client validator sets and their handover signatures
+    handovers: Arc<std::sync::RwLock<HandoverPool>>,
 }
 
 impl ConsensusEngine {
-    /// Create new consensus engine
+    /// Create new consensus engine reading the contracts over JSON-RPC
     ///
     /// # Errors
     ///
     /// Returns error if initialization fails
     pub async fn new(config: ConsensusConfig) -> Result<Self> {
+        Self::build(config, None).await
+    }
+
+    /// Create a consensus engine reading the contracts from `source`
+    ///
+    /// The JSON-RPC client is still used for event subscriptions and for
+    /// submitting evidence.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if initialization fails
+    pub async fn with_data_source(config: ConsensusConfig, source: Arc<dyn ConsensusDataSource>) -> Result<Self> {
+        Self::build(config, Some(source)).await
+    }
+
+    /// Create the engine with `source`, or the JSON-RPC client if `None`
+    async fn build(config: ConsensusConfig, source: Option<Arc<dyn ConsensusDataSource>>) -> Result<Self> {
         // Validate configuration
         config
             .validate()
@@ -59,16 +223,64 @@ impl ConsensusEngine {
             "Initializing consensus engine"
         );
 
-        // Initialize contract client
-        let client = Arc::new(
-            ContractClient::new(
+        // Attestation signing key (optional: non-validators only verify)
+        let signer = match load_signing_backend(&config).await? {
+            Some(backend) => {
+                if backend.address() != config.sequencer_address {
+                    return Err(ConsensusError::ConfigError(format!(
+                        "Signing key {} does not match sequencer address {}",
+                        backend.address(),
+                        config.sequencer_address
+                    )));
+                }
+                let protection = Arc::new(SlashingProtection::open(&config.data_dir)?);
+                info!(validator = ?backend.address(), backend = backend.name(), "Attestation signing enabled");
+                Some(Arc::new(
+                    AttestationSigner::new(backend, config.chain_id).with_slashing_protection(protection),
+                ))
+            }
+            None => {
+                info!(path = %config.private_key_path.display(), "No signing key, attestations are only verified");
+                None
+            }
+        };
+
+        // Contract transactions need the key in process
+        let secret = signer.as_ref().and_then(|signer| signer.secret_bytes());
+        if signer.is_some() && secret.is_none() {
+            info!("Remote signing key, evidence, unjail and coordinator transactions disabled");
+        }
+
+        // Initialize contract client, submitting evidence with the same key
+        let mut client = ContractClient::new(
+            &config.rpc_url,
+            Some(config.ws_url.as_str()).filter(|url| !url.is_empty()),
+            config.consensus_contract,
+            config.coordinator_contract,
+        )
+        .await?;
+        if let Some(secret) = &secret {
+            client = client.with_signer(secret, config.chain_id)?;
+        }
+        let client = Arc::new(client);
+        let source = source.unwrap_or_else(|| Arc::clone(&client) as Arc<dyn ConsensusDataSource>);
+        info!(source = source.name(), "Consensus data source selected");
+
+        // Coordinator transactions for leader handoff and timeouts
+        let transactor = match &secret {
+            Some(secret) => Some(Arc::new(CoordinatorTransactor::new(
                 &config.rpc_url,
-                Some(&config.ws_url),
-                config.consensus_contract,
                 config.coordinator_contract,
-            )
-            .await?,
-        );
+                secret,
+                config.chain_id,
+                u128::from(config.max_fee_per_gas_gwei) * 1_000_000_000,
+            )?)),
+            None if config.leader_handoff => {
+                info!("No signing key, leader handoff is followed but not reported");
+                None
+            }
+            _ => None,
+        };
 
         // Initialize validator set
         let validator_set = Arc::new(RwLock::new(ValidatorSet::new()));
@@ -90,16 +302,47 @@ impl ConsensusEngine {
             total_voting_power: 0,
             bft_threshold: 0,
             last_update: 0,
+            jailed: false,
+            jail_release_block: 0,
+            jail_blocks_remaining: 0,
         }));
 
+        let attestations = Arc::new(std::sync::RwLock::new(AttestationPool::new(
+            config.chain_id,
+            config.max_attestation_age,
+        )));
+        let (finalized, _) = broadcast::channel(FINALIZED_CHANNEL_CAPACITY);
+        let store = StateStore::new(&config.data_dir);
+        let performance = Arc::new(std::sync::RwLock::new(PerformanceTracker::new(config.performance_window)));
+        let handovers = Arc::new(std::sync::RwLock::new(HandoverPool::new(config.chain_id)));
+        let evidence = Arc::new(std::sync::Mutex::new(EvidencePool::new(
+            config.chain_id,
+            config.max_attestation_age,
+        )));
+
         Ok(Self {
             config,
             client,
+            source,
             validator_set,
             state,
             metrics,
             running: Arc::new(Mutex::new(false)),
             last_timeout_check: Arc::new(RwLock::new(0)),
+            attestations,
+            signer,
+            gossip: AttestationGossip::new(),
+            finalized,
+            schedule: Arc::new(std::sync::RwLock::new(ProposerSchedule::default())),
+            store,
+            evidence,
+            epochs: Arc::new(std::sync::RwLock::new(EpochTracker::new())),
+            jail: Arc::new(std::sync::Mutex::new(JailTracker::new())),
+            handoff: Arc::new(std::sync::RwLock::new(LeaderHandoff::new())),
+            transactor,
+            produced: Arc::new(std::sync::Mutex::new(VecDeque::new())),
+            performance,
+            handovers,
         })
     }
 
@@ -120,13 +363,23 @@ impl ConsensusEngine {
 
         info!("Starting consensus engine");
 
+        // Resume from the last snapshot before re-reading the chain
+        self.restore_state().await;
+
         // Initial sync
         self.sync_validator_set().await?;
 
         // Spawn background tasks
         self.spawn_sync_task();
-        self.spawn_timeout_monitor();
+        self.spawn_event_sync_task();
+        if self.config.leader_handoff {
+            self.spawn_leader_handoff();
+        } else {
+            self.spawn_timeout_monitor();
+        }
         self.spawn_metrics_updater();
+        self.spawn_evidence_submitter();
+        self.spawn_jail_monitor();
 
         info!("Consensus engine started successfully");
         Ok(())
@@ -149,27 +402,70 @@ impl ConsensusEngine {
     pub async fn sync_validator_set(&self) -> Result<()> {
         info!("Syncing validator set from chain");
 
-        // Fetch validator info from contract
-        let validators = self.client.get_all_validators_info().await?;
-        let block_number = self.client.get_block_number().await?;
-        let epoch = self.client.get_current_epoch().await?;
+        // Read everything at one block so the view is consistent
+        let block_number = self.source.latest_block().await?;
+        let validators = self.source.active_validators(block_number).await?;
+        let epoch = self.source.current_epoch(block_number).await?;
+
+        // Follow this sequencer's own jail period
+        let me = self.config.sequencer_address;
+        let release_block = match validators.iter().find(|v| v.validator == me) {
+            Some(validator) if validator.jailed => Some(self.source.jail_release_block(me, block_number).await?),
+            _ => None,
+        };
+
+        // Proposers and the active set follow the set the epoch started
+        // with, re-read every time so a changed set reschedules its heights
+        let epoch_set = self.source.epoch_validator_set(epoch).await?;
+        let last_rotation = self.schedule().last_rotation();
+        let rotations = self.source.forced_rotations(last_rotation, block_number).await?;
+        {
+            let mut schedule = self.schedule_mut();
+            schedule.set_epoch(epoch_set.clone());
+            schedule.record_rotations(rotations);
+            schedule.ensure_ahead(block_number)?;
+        }
 
-        // Update validator set
+        // Changes made since the epoch started wait for the next one
+        let live = live_update(epoch, block_number, &validators);
         let mut validator_set = self.validator_set.write().await;
-        validator_set.update_from_chain(validators, block_number)?;
+        validator_set.apply_epoch(&epoch_set, validators, block_number)?;
+        {
+            // Attestations are checked against the set the epoch started
+            // with, keyed by its on-chain start block rather than this sync
+            let mut pool = self.attestation_pool_mut();
+            pool.set_canonical_head(block_number);
+            pool.record_validator_set(epoch_set.clone());
+        }
+
+        let leader = self
+            .scheduled_proposer(epoch_set.block_number)
+            .or_else(|| validator_set.current_proposer())
+            .unwrap_or(Address::ZERO);
+        let completed = self.enter_epoch(&epoch_set, leader, live);
 
         // Update state
         let mut state = self.state.write().await;
         state.current_block = block_number;
         state.current_epoch = epoch;
+        if let Some(rotation) = self.epoch_tracker().current_rotation() {
+            state.current_rotation = rotation.rotation_number;
+        }
         state.active_validators = validator_set.active_count();
         state.total_voting_power = validator_set.total_voting_power();
         state.bft_threshold = validator_set.bft_threshold();
-        state.current_proposer = validator_set.current_proposer().unwrap_or(Address::ZERO);
+        state.current_proposer = self
+            .handoff_leader()
+            .or_else(|| self.scheduled_proposer(block_number + 1))
+            .or_else(|| validator_set.current_proposer())
+            .unwrap_or(Address::ZERO);
         state.last_update = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
+        let jail_change = self.jail_tracker().observe(release_block);
+        self.refresh_jail_countdown(&mut state, block_number);
+        let active = validator_set.is_active_validator(&me);
 
         // Update metrics
         self.metrics
@@ -184,19 +480,494 @@ impl ConsensusEngine {
             total_power = state.total_voting_power,
             "Validator set synced successfully"
         );
+        drop(state);
+        drop(validator_set);
+
+        match jail_change {
+            Some(JailChange::Jailed { release_block }) => {
+                warn!(
+                    release_block,
+                    blocks_remaining = release_block.saturating_sub(block_number),
+                    auto_unjail = self.config.auto_unjail,
+                    "This sequencer is jailed"
+                );
+            }
+            Some(JailChange::Released { by_us }) if active => {
+                info!(by_us, "This sequencer was unjailed and is back in the active set");
+            }
+            Some(JailChange::Released { by_us }) => {
+                warn!(by_us, "This sequencer was unjailed but is not in the epoch's active set");
+            }
+            None => {}
+        }
+
+        if let Some(previous) = completed {
+            self.sign_handover(&previous, &epoch_set).await;
+        }
+
+        self.client.update_last_synced_block(block_number).await;
+        self.persist_state().await;
 
         Ok(())
     }
 
+    /// Mirror the jail countdown at `head` into `state`
+    fn refresh_jail_countdown(&self, state: &mut ConsensusState, head: u64) {
+        let jail = self.jail_tracker();
+        state.jailed = jail.is_jailed();
+        state.jail_release_block = jail.release_block().unwrap_or(0);
+        state.jail_blocks_remaining = jail.blocks_remaining(head);
+    }
+
+    /// Access to the jail tracker
+    fn jail_tracker(&self) -> std::sync::MutexGuard<'_, JailTracker> {
+        self.jail.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Track the epoch in effect and stage the live set for its boundary
+    ///
+    /// Returns the epoch completed by entering a new one.
+    fn enter_epoch(&self, epoch_set: &ValidatorSetUpdate, leader: Address, live: ValidatorSetUpdate) -> Option<EpochInfo> {
+        self.record_light_client_set(epoch_set.epoch, epoch_set.block_number, &epoch_set.validators, &epoch_set.powers);
+
+        let mut epochs = self.epoch_tracker_mut();
+        let completed = epochs.begin_epoch(epoch_set, leader);
+        if completed.is_some() {
+            self.metrics.epoch_transitions.inc();
+        }
+
+        let (validators, total_power) = (live.validators.len(), live.total_power);
+        if epochs.stage(live) {
+            info!(
+                epoch = epoch_set.epoch,
+                validators,
+                total_power,
+                "Validator set change queued for the next epoch"
+            );
+        }
+        completed
+    }
+
+    /// Epoch in effect
+    pub fn current_epoch_info(&self) -> Option<EpochInfo> {
+        self.epoch_tracker().current().cloned()
+    }
+
+    /// Epoch `number`, if it is in effect or among the recent completed ones
+    pub fn epoch_info(&self, number: u64) -> Option<EpochInfo> {
+        self.epoch_tracker().epoch(number).cloned()
+    }
+
+    /// Recent completed epochs, oldest first
+    pub fn epoch_history(&self) -> Vec<EpochInfo> {
+        self.epoch_tracker().epochs().cloned().collect()
+    }
+
+    /// Rotation in effect
+    pub fn current_rotation_info(&self) -> Option<RotationInfo> {
+        self.epoch_tracker().current_rotation().cloned()
+    }
+
+    /// Recent completed rotations, oldest first
+    pub fn rotation_history(&self) -> Vec<RotationInfo> {
+        self.epoch_tracker().rotations().cloned().collect()
+    }
+
+    /// On-chain validator set queued for the next epoch boundary
+    pub fn pending_validator_set(&self) -> Option<ValidatorSetUpdate> {
+        self.epoch_tracker().pending().cloned()
+    }
+
+    /// Read access to the epoch tracker
+    fn epoch_tracker(&self) -> std::sync::RwLockReadGuard<'_, EpochTracker> {
+        self.epochs.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the epoch tracker
+    fn epoch_tracker_mut(&self) -> std::sync::RwLockWriteGuard<'_, EpochTracker> {
+        self.epochs.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Restore local state from the snapshot in `data_dir`
+    ///
+    /// A missing, outdated or corrupted snapshot leaves the fresh state.
+    pub(crate) async fn restore_state(&self) {
+        let snapshot = match self.store.load() {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => {
+                info!(path = %self.store.path().display(), "No consensus state snapshot, starting fresh");
+                return;
+            }
+            Err(e) => {
+                warn!(error = %e, "Ignoring unusable consensus state snapshot");
+                return;
+            }
+        };
+
+        self.validator_set.write().await.restore(snapshot.validator_set);
+        self.epoch_tracker_mut().restore(snapshot.epochs);
+        for epoch in self.epoch_history().into_iter().chain(self.current_epoch_info()) {
+            self.record_light_client_set(epoch.epoch_number, epoch.start_block, &epoch.validators, &epoch.powers);
+        }
+        *self.last_timeout_check.write().await = snapshot.last_timeout_check;
+        self.client.update_last_synced_block(snapshot.last_synced_block).await;
+
+        info!(
+            block = snapshot.state.current_block,
+            rotation = snapshot.state.current_rotation,
+            proposer = ?snapshot.state.current_proposer,
+            "Consensus state restored"
+        );
+        *self.state.write().await = snapshot.state;
+    }
+
+    /// Snapshot local state to `data_dir`
+    ///
+    /// Must not be called while holding the state or validator set locks.
+    async fn persist_state(&self) {
+        let epochs = self.epoch_tracker().snapshot();
+        let snapshot = EngineSnapshot {
+            state: self.state.read().await.clone(),
+            validator_set: self.validator_set.read().await.snapshot(),
+            epochs,
+            last_timeout_check: *self.last_timeout_check.read().await,
+            last_synced_block: self.client.last_synced_block().await,
+        };
+
+        if let Err(e) = self.store.save(&snapshot) {
+            error!(error = %e, path = %self.store.path().display(), "Failed to persist consensus state");
+        }
+    }
+
+    /// Attest a canonical block with this validator's key
+    ///
+    /// The attestation is collected locally and gossiped to the other
+    /// active validators. Returns `None` if this node has no signing key or
+    /// is not an active validator.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if signing or local collection fails
+    pub async fn attest_block(&self, block_number: u64, block_hash: B256) -> Result<Option<AttestationInfo>> {
+        self.attestation_pool_mut().set_canonical_head(block_number);
+        let Some(signer) = &self.signer else {
+            return Ok(None);
+        };
+
+        let Some(power) = self
+            .validator_set
+            .read()
+            .await
+            .get_validator(&signer.address())
+            .filter(|v| v.can_propose())
+            .map(|v| v.power)
+        else {
+            return Ok(None);
+        };
+        let epoch = self.epoch_at(block_number).ok_or(ConsensusError::UnknownValidatorSet(block_number))?;
+        let peers = self.peer_endpoints().await;
+
+        let attestation = signer.sign(epoch, block_number, block_hash, power).await?;
+        self.gossip.broadcast(peers, &attestation);
+        self.submit_attestation(attestation.clone())?;
+
+        debug!(block = block_number, hash = ?block_hash, "Block attested");
+        Ok(Some(attestation))
+    }
+
+    /// Epoch whose validator set attests `block_number`, if known
+    pub fn epoch_at(&self, block_number: u64) -> Option<u64> {
+        self.attestation_pool().validator_set_at(block_number).map(|set| set.epoch)
+    }
+
+    /// Collect an attestation from any validator
+    ///
+    /// Returns the quorum certificate if this attestation completed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the attestation does not verify
+    pub fn submit_attestation(&self, attestation: AttestationInfo) -> Result<Option<QuorumCertificate>> {
+        self.metrics.attestations_received.inc();
+
+        // Only members of the set at the height can produce evidence, and
+        // only within the bounds the attestation pool accepts
+        self.attestation_pool().verify(&attestation)?;
+        let equivocation = self.evidence_pool().observe_attestation(&attestation);
+        if let Some(evidence) = equivocation {
+            self.record_evidence(evidence);
+        }
+
+        let (block_number, block_hash, validator) =
+            (attestation.block_number, attestation.block_hash, attestation.validator);
+        let certificate = self.attestation_pool_mut().add(attestation)?;
+        self.performance_mut().observe_attestation(block_number, block_hash, validator);
+        if let Some(certificate) = &certificate {
+            self.on_finalized(certificate);
+        }
+        Ok(certificate)
+    }
+
+    /// Sign and gossip a proposal for a block this validator produced
+    ///
+    /// Returns `None` if this node has no signing key or did not produce the
+    /// block.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if signing fails
+    pub async fn announce_block(
+        &self,
+        block_number: u64,
+        block_hash: B256,
+        beneficiary: Address,
+    ) -> Result<Option<BlockProposal>> {
+        let Some(signer) = self.signer.as_ref().filter(|signer| signer.address() == beneficiary) else {
+            return Ok(None);
+        };
+
+        let proposal = signer.sign_proposal(block_number, block_hash).await?;
+        self.gossip.broadcast_proposal(self.peer_endpoints().await, &proposal);
+        self.submit_proposal(&proposal)?;
+
+        debug!(block = block_number, hash = ?block_hash, "Block proposal announced");
+        Ok(Some(proposal))
+    }
+
+    /// Check a signed proposal from any validator for misbehaviour
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the proposal is not signed by its producer or the
+    /// producer is not in the validator set at the height
+    pub fn submit_proposal(&self, proposal: &BlockProposal) -> Result<()> {
+        self.attestation_pool().member_power(proposal.block_number, &proposal.producer)?;
+        let scheduled = self.scheduled_proposer(proposal.block_number);
+        let evidence = self.evidence_pool().observe_proposal(proposal, scheduled)?;
+        for evidence in evidence {
+            self.record_evidence(evidence);
+        }
+        Ok(())
+    }
+
+    /// Verify and queue slashing evidence found by another node
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the evidence does not prove misbehaviour by a member
+    /// of the validator set at its height
+    pub fn submit_evidence(&self, evidence: Evidence) -> Result<()> {
+        self.attestation_pool().member_power(evidence.block_number(), &evidence.validator())?;
+        evidence.verify(self.config.chain_id, self.scheduled_proposer(evidence.block_number()))?;
+        self.record_evidence(evidence);
+        Ok(())
+    }
+
+    /// Queue new evidence for submission and gossip it to the other validators
+    fn record_evidence(&self, evidence: Evidence) {
+        if !self.evidence_pool().add(evidence.clone(), Instant::now()) {
+            return;
+        }
+
+        self.metrics.evidence_detected.inc();
+        warn!(
+            validator = ?evidence.validator(),
+            block = evidence.block_number(),
+            kind = evidence.kind(),
+            "Slashing evidence detected"
+        );
+
+        let engine = self.clone_self();
+        tokio::spawn(async move {
+            let peers = engine.peer_endpoints().await;
+            engine.gossip.broadcast_evidence(peers, &evidence);
+        });
+    }
+
+    /// RPC endpoints of the other active validators
+    async fn peer_endpoints(&self) -> Vec<String> {
+        let me = self.signer.as_ref().map(|signer| signer.address());
+        let validator_set = self.validator_set.read().await;
+        validator_set
+            .active_validators()
+            .iter()
+            .filter(|addr| Some(**addr) != me)
+            .filter_map(|addr| validator_set.get_validator(addr))
+            .map(|v| v.rpc_endpoint.clone())
+            .filter(|endpoint| !endpoint.is_empty())
+            .collect()
+    }
+
+    /// Number of detected evidences not yet accepted on-chain
+    pub fn pending_evidence(&self) -> usize {
+        self.evidence_pool().pending_count()
+    }
+
+    /// Access to the evidence pool
+    fn evidence_pool(&self) -> std::sync::MutexGuard<'_, EvidencePool> {
+        self.evidence.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Verify and store a quorum certificate formed by another node
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the certificate does not verify against the
+    /// validator set of its block
+    pub fn import_quorum_certificate(&self, certificate: QuorumCertificate) -> Result<()> {
+        let is_new = self.quorum_certificate(certificate.block_number).is_none();
+        self.attestation_pool_mut().import_certificate(certificate.clone())?;
+        if is_new {
+            self.on_finalized(&certificate);
+        }
+        Ok(())
+    }
+
+    /// Quorum certificate of a finalized block
+    pub fn quorum_certificate(&self, block_number: u64) -> Option<QuorumCertificate> {
+        self.attestation_pool().certificate(block_number).cloned()
+    }
+
+    /// Certificate of the highest finalized block
+    pub fn latest_finalized(&self) -> Option<QuorumCertificate> {
+        self.attestation_pool().latest_finalized().cloned()
+    }
+
+    /// Whether a block has a verified quorum certificate
+    pub fn is_block_finalized(&self, block_number: u64, block_hash: B256) -> bool {
+        self.attestation_pool().is_finalized(block_number, block_hash)
+    }
+
+    /// Subscribe to newly formed quorum certificates
+    pub fn subscribe_finalized(&self) -> broadcast::Receiver<QuorumCertificate> {
+        self.finalized.subscribe()
+    }
+
+    /// Record a newly finalized block
+    fn on_finalized(&self, certificate: &QuorumCertificate) {
+        self.metrics.blocks_finalized.inc();
+        let _ = self.finalized.send(certificate.clone());
+    }
+
+    /// Read access to the attestation pool
+    fn attestation_pool(&self) -> std::sync::RwLockReadGuard<'_, AttestationPool> {
+        self.attestations.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the attestation pool
+    fn attestation_pool_mut(&self) -> std::sync::RwLockWriteGuard<'_, AttestationPool> {
+        self.attestations.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Finality proof of the block with RLP-encoded `header`
+    ///
+    /// `None` if the block has no quorum certificate.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the header does not decode or is not the certified
+    /// block, or the certificate does not reach the threshold of the set
+    /// its epoch started with
+    pub fn finality_proof(&self, header: Bytes) -> Result<Option<FinalityProof>> {
+        let number = decode_header(&header)?.number;
+        let Some(certificate) = self.quorum_certificate(number) else {
+            return Ok(None);
+        };
+        let set = self
+            .light_client_validator_set(certificate.epoch)
+            .ok_or(ConsensusError::UnknownEpoch(certificate.epoch))?;
+        finality_proof(self.config.chain_id, header, &certificate, set).map(Some)
+    }
+
+    /// Proof of the handover to `epoch`'s validator set, once 2/3+1 of the
+    /// previous epoch's power signed it
+    pub fn validator_set_change_proof(&self, epoch: u64) -> Option<ValidatorSetChangeProof> {
+        self.handover_pool().change_proof(epoch)
+    }
+
+    /// Validator set light clients trust for `epoch`
+    pub fn light_client_validator_set(&self, epoch: u64) -> Option<LightValidatorSet> {
+        self.handover_pool().validator_set(epoch).cloned()
+    }
+
+    /// Collect a validator set handover signature from any validator
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the signature is not from a validator of the
+    /// previous epoch over the set its epoch started with
+    pub fn submit_validator_set_signature(&self, signature: ValidatorSetSignature) -> Result<()> {
+        let epoch = signature.epoch;
+        if self.handover_pool_mut().add(signature)? {
+            info!(epoch, "Validator set handover signed by 2/3+1 of the previous epoch");
+        }
+        Ok(())
+    }
+
+    /// Sign and gossip the handover to `next` if this validator served in
+    /// `previous`, the epoch right before it
+    async fn sign_handover(&self, previous: &EpochInfo, next: &ValidatorSetUpdate) {
+        let Some(signer) = self.signer.as_ref().filter(|signer| previous.validators.contains(&signer.address())) else {
+            return;
+        };
+        if previous.epoch_number.checked_add(1) != Some(next.epoch) {
+            debug!(previous = previous.epoch_number, next = next.epoch, "Epochs skipped, no handover to sign");
+            return;
+        }
+        let Some(set) = self.light_client_validator_set(next.epoch) else {
+            return;
+        };
+
+        let signature = match signer.sign_validator_set(&set).await {
+            Ok(signature) => signature,
+            Err(e) => {
+                warn!(epoch = next.epoch, error = %e, "Failed to sign validator set handover");
+                return;
+            }
+        };
+        self.gossip.broadcast_validator_set_signature(self.peer_endpoints().await, &signature);
+        if let Err(e) = self.submit_validator_set_signature(signature) {
+            warn!(epoch = next.epoch, error = %e, "Own validator set handover rejected");
+        }
+    }
+
+    /// Record the set an epoch started with for light client proofs
+    fn record_light_client_set(&self, epoch: u64, start_block: u64, validators: &[Address], powers: &[u64]) {
+        match light_client_set(epoch, start_block, validators, powers) {
+            Ok(set) => self.handover_pool_mut().record_set(set),
+            Err(e) => debug!(epoch, error = %e, "No light client validator set for epoch"),
+        }
+    }
+
+    /// Read access to the handover pool
+    fn handover_pool(&self) -> std::sync::RwLockReadGuard<'_, HandoverPool> {
+        self.handovers.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the handover pool
+    fn handover_pool_mut(&self) -> std::sync::RwLockWriteGuard<'_, HandoverPool> {
+        self.handovers.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Get current proposer address (without block number)
     pub async fn current_proposer(&self) -> Option<Address> {
         self.validator_set.read().await.current_proposer()
     }
 
     /// Get proposer for specific block number
-    pub async fn get_current_proposer(&self, _block_number: u64) -> Result<Address> {
-        // For now, return current proposer
-        // TODO: Implement historical proposer lookup based on block number
+    ///
+    /// Falls back to the current proposer for heights outside the schedule.
+    pub async fn get_current_proposer(&self, block_number: u64) -> Result<Address> {
+        if self.config.leader_handoff {
+            let handoff = self.handoff();
+            if let Some(leader) = handoff.leader_at(block_number).or_else(|| handoff.current_leader()) {
+                return Ok(leader);
+            }
+        }
+        if let Some(proposer) = self.scheduled_proposer(block_number) {
+            return Ok(proposer);
+        }
+
         self.validator_set
             .read()
             .await
@@ -204,13 +975,194 @@ impl ConsensusEngine {
             .ok_or_else(|| ConsensusError::Internal("No proposer available".to_string()))
     }
 
-    /// Check if this node is the current proposer
+    /// Check if this node is the proposer of the next block
+    ///
+    /// With `leader_handoff`, only once the coordinator logs before the next
+    /// block are applied and name this sequencer.
     pub async fn am_i_proposer(&self) -> bool {
-        self.current_proposer()
+        let next_block = self.state.read().await.current_block + 1;
+        if self.config.leader_handoff {
+            return self.handoff().may_produce(self.config.sequencer_address, next_block);
+        }
+        self.get_current_proposer(next_block)
             .await
             .map_or(false, |proposer| proposer == self.config.sequencer_address)
     }
 
+    /// Scheduled proposer of a block, if the schedule covers it
+    pub fn scheduled_proposer(&self, block_number: u64) -> Option<Address> {
+        self.schedule().proposer_at(block_number)
+    }
+
+    /// Check a block's proposer against the schedule without awaiting
+    ///
+    /// With `leader_handoff` the coordinator leader is checked instead, and
+    /// blocks ahead of the applied coordinator logs are accepted from the
+    /// last known leader. Blocks the schedule does not cover yet are refused rather than
+    /// trusted; the schedule runs half a window ahead of the head, so this
+    /// only defers blocks from an epoch this node has not synced.
+    ///
+    /// Blocks older than the schedule, as imported by a node syncing from
+    /// far behind, are not checked: their epochs' validator sets are no
+    /// longer kept, and they are bound by the hashes of the descendants the
+    /// node imports and checks at the head.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::InvalidProposer`] if another validator was
+    /// scheduled for `block_number` and
+    /// [`ConsensusError::UnscheduledHeight`] if nobody is scheduled yet
+    pub fn verify_scheduled_proposer(&self, block_number: u64, proposer: Address) -> Result<()> {
+        if self.first_checked_height().is_some_and(|first| block_number < first) {
+            debug!(block_number, %proposer, "Block older than the proposer schedule, proposer not checked");
+            return Ok(());
+        }
+
+        let result = if self.config.leader_handoff {
+            let handoff = self.handoff();
+            match handoff.leader_at(block_number) {
+                Some(leader) if leader != proposer => Err(ConsensusError::InvalidProposer {
+                    expected: leader,
+                    actual: proposer,
+                }),
+                Some(_) => Ok(()),
+                // Ahead of the applied logs only the last known leader is accepted
+                None if handoff.last_known_leader(block_number) == Some(proposer) => Ok(()),
+                None => Err(ConsensusError::UnscheduledHeight(block_number)),
+            }
+        } else {
+            self.schedule().verify(block_number, proposer)
+        };
+        if result.is_err() {
+            self.metrics.invalid_proposers.inc();
+        }
+        result
+    }
+
+    /// First height whose proposer is still known
+    fn first_checked_height(&self) -> Option<u64> {
+        if self.config.leader_handoff {
+            self.handoff().first_handoff()
+        } else {
+            self.schedule().start()
+        }
+    }
+
+    /// Check the proposer seal of an imported header
+    ///
+    /// The sealer must be the header's beneficiary and the proposer
+    /// scheduled for its height. Returns the sealer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::InvalidSeal`] if the seal is missing or
+    /// names another beneficiary, and the errors of
+    /// [`verify_scheduled_proposer`](Self::verify_scheduled_proposer)
+    pub fn verify_sealed_proposer(&self, header: &Header) -> Result<Address> {
+        let sealer = recover_sealer(self.config.chain_id, header).inspect_err(|_| {
+            self.metrics.invalid_proposers.inc();
+        })?;
+        if sealer != header.beneficiary {
+            self.metrics.invalid_proposers.inc();
+            return Err(ConsensusError::InvalidSeal(format!(
+                "sealed by {sealer} for beneficiary {}",
+                header.beneficiary
+            )));
+        }
+        self.verify_scheduled_proposer(header.number, sealer)?;
+        Ok(sealer)
+    }
+
+    /// Whether this node has a validator signer to seal its blocks with
+    pub fn can_seal(&self) -> bool {
+        self.signer.is_some()
+    }
+
+    /// Seal a header built by this node with its validator signer
+    ///
+    /// # Errors
+    ///
+    /// Returns error if this node has no signer, slashing protection refuses
+    /// or signing fails
+    pub async fn seal_header(&self, header: &mut Header) -> Result<()> {
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| ConsensusError::SignerError("No validator key to seal blocks".to_string()))?;
+        signer.seal_header(header).await
+    }
+
+    /// Keep the proposer schedule ahead of the canonical head
+    ///
+    /// # Errors
+    ///
+    /// Returns error if extending the schedule fails
+    pub fn advance_schedule(&self, head: u64) -> Result<()> {
+        self.schedule_mut().ensure_ahead(head)
+    }
+
+    /// Read access to the proposer schedule
+    fn schedule(&self) -> std::sync::RwLockReadGuard<'_, ProposerSchedule> {
+        self.schedule.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the proposer schedule
+    fn schedule_mut(&self) -> std::sync::RwLockWriteGuard<'_, ProposerSchedule> {
+        self.schedule.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Record a canonical block for validator performance
+    ///
+    /// The producer is compared with the proposer scheduled for the height,
+    /// or the coordinator leader with `leader_handoff`.
+    pub fn observe_canonical_block(&self, block_number: u64, block_hash: B256, producer: Address, timestamp: u64) {
+        let scheduled = if self.config.leader_handoff {
+            self.handoff().leader_at(block_number)
+        } else {
+            self.scheduled_proposer(block_number)
+        };
+        self.attestation_pool_mut().set_canonical_head(block_number);
+        let latency = self
+            .performance_mut()
+            .observe_block(block_number, block_hash, producer, scheduled, timestamp);
+
+        let me = self.config.sequencer_address;
+        if producer == me {
+            if let Some(latency) = latency {
+                self.metrics.proposal_latency.observe(latency as f64);
+            }
+        } else if scheduled == Some(me) {
+            warn!(block = block_number, producer = ?producer, "Scheduled slot produced by another validator");
+        }
+    }
+
+    /// Performance of a validator over the last `performance_window`
+    /// canonical blocks, `None` before any block was observed
+    pub fn validator_performance(&self, validator: Address) -> Option<ValidatorPerformance> {
+        self.performance().performance(validator)
+    }
+
+    /// Performance of the current epoch's validators and of every other
+    /// validator seen in the window
+    pub fn performance_report(&self) -> Vec<ValidatorPerformance> {
+        let tracker = self.performance();
+        let mut validators = tracker.validators();
+        if let Some(epoch) = self.epoch_tracker().current() {
+            validators.extend(epoch.validators.iter().copied());
+        }
+        validators.into_iter().filter_map(|validator| tracker.performance(validator)).collect()
+    }
+
+    /// Read access to the performance tracker
+    fn performance(&self) -> std::sync::RwLockReadGuard<'_, PerformanceTracker> {
+        self.performance.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the performance tracker
+    fn performance_mut(&self) -> std::sync::RwLockWriteGuard<'_, PerformanceTracker> {
+        self.performance.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Verify that a block was produced by the correct proposer
     pub async fn verify_block_proposer(&self, producer: Address) -> Result<()> {
         let validator_set = self.validator_set.read().await;
@@ -221,15 +1173,26 @@ impl ConsensusEngine {
     pub async fn record_block_produced(&self, producer: Address, block_number: u64) -> Result<()> {
         let mut validator_set = self.validator_set.write().await;
         validator_set.record_block_produced(&producer, block_number)?;
+        self.epoch_tracker_mut().record_produced();
 
         // Update metrics
         if producer == self.config.sequencer_address {
             self.metrics.blocks_produced.inc();
+            if self.transactor.is_some() && self.config.leader_handoff {
+                let mut produced = self.produced_queue();
+                produced.push_back(block_number);
+                if produced.len() > MAX_UNREPORTED_BLOCKS {
+                    produced.pop_front();
+                }
+            }
         }
 
         // Update state
         let mut state = self.state.write().await;
         state.current_block = block_number;
+        drop(state);
+        drop(validator_set);
+        self.persist_state().await;
 
         info!(producer = ?producer, block = block_number, "Block produced");
         Ok(())
@@ -239,19 +1202,30 @@ impl ConsensusEngine {
     pub async fn record_block_missed(&self, validator: Address) -> Result<()> {
         let mut validator_set = self.validator_set.write().await;
         validator_set.record_block_missed(&validator)?;
+        self.epoch_tracker_mut().record_missed();
 
         // Update metrics
         if validator == self.config.sequencer_address {
             self.metrics.blocks_missed.inc();
         }
 
+        drop(validator_set);
+        self.persist_state().await;
+
         warn!(validator = ?validator, "Block missed");
         Ok(())
     }
 
     /// Check for timeout condition
+    ///
+    /// Always `false` with `leader_handoff`, where timeouts rotate the
+    /// leader on-chain instead.
     pub async fn check_timeout(&self) -> Result<bool> {
-        let current_block = self.client.get_block_number().await?;
+        if self.config.leader_handoff {
+            return Ok(false);
+        }
+
+        let current_block = self.source.latest_block().await?;
         let last_check = *self.last_timeout_check.read().await;
 
         // Only check once per block
@@ -260,7 +1234,7 @@ impl ConsensusEngine {
         }
 
         // Check if timeout reached on-chain
-        let timeout_reached = self.client.is_timeout_reached().await?;
+        let timeout_reached = self.source.is_timeout_reached(current_block).await?;
 
         if timeout_reached {
             warn!(
@@ -273,6 +1247,7 @@ impl ConsensusEngine {
 
             // Update last check
             *self.last_timeout_check.write().await = current_block;
+            self.persist_state().await;
 
             return Ok(true);
         }
@@ -280,27 +1255,65 @@ impl ConsensusEngine {
         Ok(false)
     }
 
-    /// Force rotation to next proposer
+    /// Rotate past a timed-out proposer through the coordinator
+    ///
+    /// Rotations are settled on-chain so nodes cannot diverge on local
+    /// timeouts: with a signing key this node sends `checkTimeout`, then the
+    /// rotations forced on AndeSequencerCoordinator are read from chain state
+    /// and fed to the proposer schedule. Returns the proposer scheduled for
+    /// the next block.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the coordinator cannot be read or the next block has
+    /// no scheduled proposer
     pub async fn force_rotation(&self, reason: &str) -> Result<Address> {
-        info!(reason, "Forcing proposer rotation");
-
-        let mut validator_set = self.validator_set.write().await;
-        let next_proposer = validator_set.force_next_proposer()?;
+        info!(reason, "Forcing proposer rotation on the coordinator");
+
+        if let Some(transactor) = &self.transactor {
+            match transactor.check_timeout().await {
+                Ok(true) => self.metrics.coordinator_transactions.inc(),
+                Ok(false) => debug!("Coordinator sees no timeout, following recorded rotations only"),
+                Err(e) => {
+                    self.metrics.coordinator_transaction_failures.inc();
+                    warn!(error = %e, "checkTimeout failed, following recorded rotations only");
+                }
+            }
+        }
 
-        // Update metrics
-        self.metrics.forced_rotations.inc();
+        let block = self.source.latest_block().await?;
+        let last_rotation = self.schedule().last_rotation();
+        let rotations = self.source.forced_rotations(last_rotation, block).await?;
+        {
+            let mut schedule = self.schedule_mut();
+            schedule.record_rotations(rotations.iter().copied());
+            schedule.ensure_ahead(block)?;
+        }
+        let next_proposer =
+            self.scheduled_proposer(block + 1).ok_or(ConsensusError::UnscheduledHeight(block + 1))?;
 
         // Update state
         let mut state = self.state.write().await;
         state.current_proposer = next_proposer;
-        state.current_rotation += 1;
+        for rotation in &rotations {
+            self.metrics.forced_rotations.inc();
+            let leader = self.scheduled_proposer(rotation.block_number + 1).unwrap_or(next_proposer);
+            let mut epochs = self.epoch_tracker_mut();
+            epochs.rotate(leader, rotation.block_number);
+            state.current_rotation = epochs
+                .current_rotation()
+                .map_or(state.current_rotation + 1, |current| current.rotation_number);
+        }
 
         info!(
             next_proposer = ?next_proposer,
             rotation = state.current_rotation,
+            forced = rotations.len(),
             reason,
-            "Proposer rotated"
+            "Proposer schedule follows coordinator rotations"
         );
+        drop(state);
+        self.persist_state().await;
 
         Ok(next_proposer)
     }
@@ -339,6 +1352,93 @@ impl ConsensusEngine {
         });
     }
 
+    /// Spawn background task to resync the validator set on contract events
+    ///
+    /// Resubscribes with exponential backoff when the subscription fails or
+    /// ends; the polling task covers the gap.
+    fn spawn_event_sync_task(&self) {
+        if self.config.ws_url.is_empty() {
+            info!("No WebSocket URL configured, validator set sync uses polling only");
+            return;
+        }
+
+        let engine = self.clone_self();
+
+        tokio::spawn(async move {
+            let mut tracker = ValidatorEventTracker::default();
+            let mut delay = RESUBSCRIBE_MIN_DELAY;
+            let mut ticker = interval(engine.config.sync_interval);
+
+            while engine.is_running().await {
+                let (sender, mut events) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+                let client = Arc::clone(&engine.client);
+                let mut subscription =
+                    tokio::spawn(async move { client.subscribe_validator_set_updates(sender).await });
+
+                loop {
+                    tokio::select! {
+                        event = events.recv() => {
+                            let Some(event) = event else { break };
+                            delay = RESUBSCRIBE_MIN_DELAY;
+                            engine.handle_validator_event(&mut tracker, &event).await;
+                        }
+                        _ = ticker.tick() => {
+                            if !engine.is_running().await {
+                                subscription.abort();
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                match (&mut subscription).await {
+                    Ok(Err(e)) => warn!(error = %e, "Validator event subscription failed, polling continues"),
+                    Err(e) if !e.is_cancelled() => error!(error = %e, "Validator event task panicked"),
+                    _ => {}
+                }
+
+                if !engine.is_running().await {
+                    break;
+                }
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(RESUBSCRIBE_MAX_DELAY);
+            }
+
+            debug!("Event sync task stopped");
+        });
+    }
+
+    /// Apply a validator event from the subscription
+    async fn handle_validator_event(&self, tracker: &mut ValidatorEventTracker, event: &ValidatorSetEvent) {
+        self.metrics.validator_set_events.inc();
+
+        let EventAction::Resync { reorg } = tracker.apply(event) else {
+            debug!(block = event.block_number, "Ignoring already handled validator event");
+            return;
+        };
+
+        if reorg {
+            self.metrics.validator_set_reorgs.inc();
+            warn!(
+                block = event.block_number,
+                hash = ?event.block_hash,
+                change = ?event.change,
+                "Validator event removed by reorg, resyncing"
+            );
+        } else {
+            info!(block = event.block_number, change = ?event.change, "Validator set event received");
+
+            // Jailing takes effect right away, not at the epoch boundary
+            if let ValidatorSetChange::Jailed { validator, .. } = &event.change {
+                self.validator_set.write().await.set_jailed(validator, true);
+            }
+        }
+
+        if let Err(e) = self.sync_validator_set().await {
+            error!(error = %e, "Failed to sync validator set after event");
+        }
+    }
+
     /// Spawn background task to monitor timeouts
     fn spawn_timeout_monitor(&self) {
         let engine = self.clone_self();
@@ -374,6 +1474,292 @@ impl ConsensusEngine {
         });
     }
 
+    /// Spawn background task following the coordinator leader
+    fn spawn_leader_handoff(&self) {
+        let engine = self.clone_self();
+        let check_interval = engine.config.block_time;
+
+        tokio::spawn(async move {
+            let mut ticker = interval(check_interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !engine.is_running().await {
+                    break;
+                }
+
+                if let Err(e) = engine.drive_handoff().await {
+                    warn!(error = %e, "Leader handoff step failed, will retry");
+                }
+            }
+
+            debug!("Leader handoff stopped");
+        });
+    }
+
+    /// Apply new coordinator rotations, report own blocks and time out a
+    /// stalled leader
+    async fn drive_handoff(&self) -> Result<()> {
+        let head = self.source.latest_block().await?;
+        let synced = self.handoff().synced_through();
+        match synced {
+            None => {
+                let (leader, rotation) = self.source.coordinator_leader(head).await?;
+                self.handoff_mut().start(leader, rotation, head);
+                self.state.write().await.current_proposer = leader;
+                info!(leader = ?leader, rotation, head, "Following AndeSequencerCoordinator leader");
+            }
+            Some(synced) if head > synced => {
+                let to = head.min(synced + MAX_LOG_RANGE);
+                let rotations = self.source.leader_rotations(synced + 1, to).await?;
+                self.apply_leader_rotations(rotations, to).await;
+            }
+            Some(_) => {}
+        }
+
+        let Some(transactor) = &self.transactor else {
+            return Ok(());
+        };
+        self.report_produced_blocks(transactor).await;
+
+        // The sequencer next in line rotates a stalled leader away
+        if !self.source.is_timeout_reached(head).await? {
+            return Ok(());
+        }
+        let next_leader = self.source.next_leader(head).await?;
+        let due = self.handoff().should_check_timeout(
+            transactor.sender(),
+            next_leader,
+            head,
+            self.config.timeout_blocks,
+        );
+        if !due {
+            return Ok(());
+        }
+
+        self.metrics.timeouts_detected.inc();
+        warn!(head, leader = ?self.handoff().current_leader(), "Coordinator leader timed out, sending checkTimeout");
+        match transactor.check_timeout().await {
+            Ok(sent) => {
+                if sent {
+                    self.metrics.coordinator_transactions.inc();
+                    self.handoff_mut().mark_timeout_sent(head);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.metrics.coordinator_transaction_failures.inc();
+                Err(e)
+            }
+        }
+    }
+
+    /// Apply `LeaderRotated` logs read up to `synced_to`
+    async fn apply_leader_rotations(&self, rotations: Vec<LeaderRotation>, synced_to: u64) {
+        let applied: Vec<LeaderRotation> = {
+            let mut handoff = self.handoff_mut();
+            let applied = rotations.into_iter().filter(|rotation| handoff.apply(rotation)).collect::<Vec<_>>();
+            handoff.mark_synced(synced_to);
+            applied
+        };
+        if applied.is_empty() {
+            return;
+        }
+
+        let mut state = self.state.write().await;
+        for rotation in &applied {
+            let mut epochs = self.epoch_tracker_mut();
+            epochs.rotate(rotation.new_leader, rotation.block_number);
+            state.current_rotation = epochs
+                .current_rotation()
+                .map_or(state.current_rotation + 1, |current| current.rotation_number);
+            drop(epochs);
+            state.current_proposer = rotation.new_leader;
+            self.metrics.leader_handoffs.inc();
+
+            info!(
+                rotation = rotation.rotation_number,
+                old_leader = ?rotation.old_leader,
+                new_leader = ?rotation.new_leader,
+                block = rotation.block_number,
+                reason = %rotation.reason,
+                "Leader handed over"
+            );
+        }
+        drop(state);
+        self.persist_state().await;
+    }
+
+    /// Report queued own blocks with `recordBlockProduced`
+    ///
+    /// Stops at the first transport failure and retries on the next step;
+    /// blocks the coordinator rejects are dropped.
+    async fn report_produced_blocks(&self, transactor: &CoordinatorTransactor) {
+        loop {
+            let Some(block) = self.produced_queue().front().copied() else {
+                break;
+            };
+
+            match transactor.record_block_produced(block).await {
+                Ok(recorded) => {
+                    if recorded {
+                        self.metrics.coordinator_transactions.inc();
+                    }
+                }
+                Err(ConsensusError::CoordinatorReverted(revert)) => {
+                    self.metrics.coordinator_transaction_failures.inc();
+                    warn!(block, revert = ?revert, "Coordinator rejected block report, dropping it");
+                }
+                Err(e) => {
+                    self.metrics.coordinator_transaction_failures.inc();
+                    warn!(block, error = %e, "Failed to report block to coordinator, will retry");
+                    break;
+                }
+            }
+            self.produced_queue().pop_front();
+        }
+    }
+
+    /// Whether contract transactions can be signed, i.e. the key is local
+    fn can_transact(&self) -> bool {
+        self.signer.as_ref().is_some_and(|signer| signer.secret_bytes().is_some())
+    }
+
+    /// Coordinator leader after the latest handoff, with `leader_handoff`
+    fn handoff_leader(&self) -> Option<Address> {
+        if !self.config.leader_handoff {
+            return None;
+        }
+        self.handoff().current_leader()
+    }
+
+    /// Read access to the leader handoff
+    fn handoff(&self) -> std::sync::RwLockReadGuard<'_, LeaderHandoff> {
+        self.handoff.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Write access to the leader handoff
+    fn handoff_mut(&self) -> std::sync::RwLockWriteGuard<'_, LeaderHandoff> {
+        self.handoff.write().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Access to the queue of unreported own blocks
+    fn produced_queue(&self) -> std::sync::MutexGuard<'_, VecDeque<u64>> {
+        self.produced.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Spawn background task to submit queued evidence to AndeConsensus
+    ///
+    /// Failed submissions are retried with backoff by the evidence pool.
+    /// Nodes without a local signing key only detect and gossip evidence.
+    fn spawn_evidence_submitter(&self) {
+        if !self.can_transact() {
+            return;
+        }
+
+        let engine = self.clone_self();
+        let check_interval = engine.config.block_time;
+
+        tokio::spawn(async move {
+            let mut ticker = interval(check_interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !engine.is_running().await {
+                    break;
+                }
+
+                let due = engine.evidence_pool().due(Instant::now());
+                for evidence in due {
+                    let id = evidence.id();
+                    match engine.client.submit_evidence(&evidence).await {
+                        Ok(submitted) => {
+                            if submitted {
+                                engine.metrics.evidence_submitted.inc();
+                            }
+                            engine.evidence_pool().mark_submitted(id);
+                        }
+                        Err(e) => {
+                            if engine.evidence_pool().mark_failed(id, Instant::now()) {
+                                warn!(id = ?id, error = %e, "Evidence submission failed, will retry");
+                            } else {
+                                error!(id = ?id, error = %e, "Evidence submission failed, giving up");
+                            }
+                        }
+                    }
+                }
+            }
+
+            debug!("Evidence submitter stopped");
+        });
+    }
+
+    /// Spawn background task to follow this sequencer's jail period
+    ///
+    /// Refreshes the countdown every block and, with `auto_unjail` and a
+    /// signing key, sends the unjail transaction once it runs out.
+    fn spawn_jail_monitor(&self) {
+        let auto_unjail = self.config.auto_unjail && self.can_transact();
+        if self.config.auto_unjail && !auto_unjail {
+            info!("No local signing key, auto-unjail disabled");
+        }
+
+        let engine = self.clone_self();
+        let check_interval = engine.config.block_time;
+
+        tokio::spawn(async move {
+            let mut ticker = interval(check_interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !engine.is_running().await {
+                    break;
+                }
+
+                if !engine.jail_tracker().is_jailed() {
+                    continue;
+                }
+
+                if let Err(e) = engine.check_jail(auto_unjail).await {
+                    warn!(error = %e, "Jail check failed, will retry");
+                }
+            }
+
+            debug!("Jail monitor stopped");
+        });
+    }
+
+    /// Refresh the jail countdown and unjail once the release block is reached
+    ///
+    /// The unjail only counts once a resync finds this sequencer unjailed;
+    /// until then it is resent every [`UNJAIL_RETRY_BLOCKS`].
+    async fn check_jail(&self, auto_unjail: bool) -> Result<()> {
+        let head = self.source.latest_block().await?;
+        let mut state = self.state.write().await;
+        self.refresh_jail_countdown(&mut state, head);
+        self.metrics.jail_blocks_remaining.set(state.jail_blocks_remaining as i64);
+        drop(state);
+
+        if !auto_unjail || !self.jail_tracker().should_unjail(head) {
+            return Ok(());
+        }
+
+        info!(head, "Jail period over, sending unjail transaction");
+        if self.client.submit_unjail().await? {
+            self.metrics.unjails_submitted.inc();
+            self.jail_tracker().mark_unjail_sent(head);
+        }
+
+        self.sync_validator_set().await?;
+        if self.jail_tracker().is_jailed() {
+            warn!(retry_in = UNJAIL_RETRY_BLOCKS, "Unjail not visible yet");
+        }
+        Ok(())
+    }
+
     /// Spawn background task to update metrics
     fn spawn_metrics_updater(&self) {
         let engine = self.clone_self();
@@ -389,8 +1775,13 @@ impl ConsensusEngine {
                 }
 
                 let state = engine.state.read().await;
-                let stats = engine.validator_set.read().await.stats();
+                let validator_set = engine.validator_set.read().await;
+                let stats = validator_set.stats();
+                let onchain_uptime =
+                    validator_set.get_validator(&engine.config.sequencer_address).map(|v| v.uptime);
+                drop(validator_set);
                 engine.update_metrics_from_state(&state, &stats);
+                engine.update_performance_metrics(onchain_uptime);
             }
 
             debug!("Metrics updater stopped");
@@ -414,6 +1805,10 @@ impl ConsensusEngine {
             .bft_threshold
             .set(stats.bft_threshold as i64);
         self.metrics.uptime.set(i64::from(stats.average_uptime));
+        self.metrics.jailed.set(i64::from(state.jailed));
+        self.metrics
+            .jail_blocks_remaining
+            .set(state.jail_blocks_remaining as i64);
 
         // Set proposer flag
         let is_proposer =
@@ -421,23 +1816,74 @@ impl ConsensusEngine {
         self.metrics.is_proposer.set(is_proposer);
     }
 
+    /// Update metrics computed from canonical blocks for this sequencer
+    ///
+    /// The divergence is only set once AndeConsensus reported an uptime.
+    fn update_performance_metrics(&self, onchain_uptime: Option<u16>) {
+        let Some(performance) = self.validator_performance(self.config.sequencer_address) else {
+            return;
+        };
+
+        self.metrics.observed_uptime.set(i64::from(performance.uptime));
+        self.metrics.missed_slot_streak.set(performance.missed_streak as i64);
+        self.metrics
+            .attestation_participation
+            .set(i64::from(performance.attestation_participation));
+        if let Some(onchain_uptime) = onchain_uptime {
+            self.metrics
+                .uptime_divergence
+                .set(i64::from(performance.uptime) - i64::from(onchain_uptime));
+        }
+    }
+
     /// Clone self for spawning tasks
     fn clone_self(&self) -> Self {
         Self {
             config: self.config.clone(),
             client: Arc::clone(&self.client),
+            source: Arc::clone(&self.source),
             validator_set: Arc::clone(&self.validator_set),
             state: Arc::clone(&self.state),
             metrics: Arc::clone(&self.metrics),
             running: Arc::clone(&self.running),
             last_timeout_check: Arc::clone(&self.last_timeout_check),
+            attestations: Arc::clone(&self.attestations),
+            signer: self.signer.clone(),
+            gossip: self.gossip.clone(),
+            finalized: self.finalized.clone(),
+            schedule: Arc::clone(&self.schedule),
+            store: self.store.clone(),
+            evidence: Arc::clone(&self.evidence),
+            epochs: Arc::clone(&self.epochs),
+            jail: Arc::clone(&self.jail),
+            handoff: Arc::clone(&self.handoff),
+            transactor: self.transactor.clone(),
+            produced: Arc::clone(&self.produced),
+            performance: Arc::clone(&self.performance),
+            handovers: Arc::clone(&self.handovers),
         }
     }
 }
 
+/// Live on-chain validator list as a [`ValidatorSetUpdate`]
+fn live_update(epoch: u64, block_number: u64, validators: &[ValidatorInfo]) -> ValidatorSetUpdate {
+    let active: Vec<&ValidatorInfo> = validators.iter().filter(|v| v.can_propose()).collect();
+    let powers: Vec<u64> = active.iter().map(|v| v.voting_power()).collect();
+
+    ValidatorSetUpdate {
+        epoch,
+        validators: active.iter().map(|v| v.validator).collect(),
+        total_power: powers.iter().sum(),
+        powers,
+        block_number,
+        timestamp: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::simulator::{SimConfig, Simulator, SIM_JAIL_BLOCKS};
 
     #[tokio::test]
     #[ignore] // Requires running blockchain
@@ -448,4 +1894,32 @@ mod tests {
         // Should succeed with default config
         assert!(engine.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_auto_unjail_waits_for_release_and_resync() {
+        let mut sim = Simulator::new(SimConfig::default()).await.unwrap();
+        sim.run(2).await.unwrap();
+        sim.jail(0).await.unwrap();
+
+        // Nothing is sent before the release block
+        let engine = sim.engine(0).unwrap();
+        engine.check_jail(true).await.unwrap();
+        assert!(!engine.jail_tracker().should_unjail(sim.height()));
+        assert_eq!(engine.get_state().await.jail_blocks_remaining, SIM_JAIL_BLOCKS);
+
+        // Once released the transaction is attempted; the simulated node has
+        // no RPC, so it fails and stays due for the next check
+        sim.run(SIM_JAIL_BLOCKS).await.unwrap();
+        let engine = sim.engine(0).unwrap();
+        assert!(engine.check_jail(true).await.is_err());
+        assert!(engine.jail_tracker().should_unjail(sim.height()));
+        assert_eq!(engine.get_state().await.jail_blocks_remaining, 0);
+
+        // Only a resync that finds the validator unjailed ends the jail
+        sim.unjail(0).await.unwrap();
+        let engine = sim.engine(0).unwrap();
+        assert!(!engine.jail_tracker().is_jailed());
+        assert!(!engine.get_state().await.jailed);
+        engine.check_jail(true).await.unwrap();
+    }
 }
@@ -0,0 +1,344 @@
+//! Epoch and rotation bookkeeping
+//!
+//! AndeConsensus applies registrations, stake and power changes to its live
+//! validator list immediately, but only snapshots that list into a new epoch
+//! when `advanceEpoch` runs. The engine follows the snapshot: the live list
+//! is staged as pending and becomes the active set at the next epoch's
+//! start block.
+//!
+//! ```text
+//! chain:   epoch N starts        live list changes          epoch N+1 starts
+//!              │                        │                          │
+//! engine:  set_N active ─────── pending = live list ───────→ set_N+1 active
+//!                                                            EpochInfo(N) recorded
+//! ```
+//!
+//! Within an epoch, each proposer rotation is tracked as a [`RotationInfo`]:
+//! a new one starts with every epoch and with every forced rotation. Both
+//! carry the blocks produced and missed while they were current.
+
+use crate::types::{EpochInfo, RotationInfo, ValidatorSetUpdate};
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::info;
+
+/// Completed epochs kept
+pub const MAX_EPOCH_HISTORY: usize = 64;
+
+/// Completed rotations kept
+pub const MAX_ROTATION_HISTORY: usize = 256;
+
+/// Current epoch and rotation, with the completed ones before them
+#[derive(Debug, Clone, Default)]
+pub struct EpochTracker {
+    /// Epoch in effect
+    current: Option<EpochInfo>,
+
+    /// Rotation in effect
+    rotation: Option<RotationInfo>,
+
+    /// Live on-chain set waiting for the next epoch boundary
+    pending: Option<ValidatorSetUpdate>,
+
+    /// Completed epochs, oldest first
+    epochs: VecDeque<EpochInfo>,
+
+    /// Completed rotations, oldest first
+    rotations: VecDeque<RotationInfo>,
+}
+
+impl EpochTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enter the epoch of `set`, led by `leader`
+    ///
+    /// Completes the current epoch and rotation at the block before
+    /// `set.block_number` and returns the completed epoch. Does nothing if
+    /// `set` is the epoch in effect or an older one.
+    pub fn begin_epoch(&mut self, set: &ValidatorSetUpdate, leader: Address) -> Option<EpochInfo> {
+        if self.current.as_ref().is_some_and(|epoch| epoch.epoch_number >= set.epoch) {
+            return None;
+        }
+
+        let end_block = set.block_number.saturating_sub(1);
+        let completed = self.current.take().map(|mut epoch| {
+            epoch.end_block = end_block;
+            epoch.end_time = set.timestamp;
+            push_bounded(&mut self.epochs, epoch.clone(), MAX_EPOCH_HISTORY);
+            epoch
+        });
+        let rotation_number = self.next_rotation_number();
+        self.complete_rotation(end_block, true);
+
+        if let Some(epoch) = &completed {
+            info!(
+                epoch = epoch.epoch_number,
+                start_block = epoch.start_block,
+                end_block = epoch.end_block,
+                produced = epoch.blocks_produced,
+                missed = epoch.missed_blocks,
+                "Epoch completed"
+            );
+        }
+        info!(epoch = set.epoch, start_block = set.block_number, validators = set.validators.len(), "Epoch started");
+
+        self.current = Some(EpochInfo {
+            epoch_number: set.epoch,
+            start_block: set.block_number,
+            end_block: 0,
+            start_time: set.timestamp,
+            end_time: 0,
+            validators: set.validators.clone(),
+            powers: set.powers.clone(),
+            total_voting_power: set.total_power,
+            blocks_produced: 0,
+            missed_blocks: 0,
+        });
+        self.rotation = Some(start_rotation(rotation_number, set.block_number, leader));
+        self.pending = self.pending.take().filter(|pending| pending.epoch > set.epoch);
+
+        completed
+    }
+
+    /// Stage the live on-chain set for the next epoch boundary
+    ///
+    /// Returns `true` if it differs from both the set in effect and the set
+    /// already staged.
+    pub fn stage(&mut self, live: ValidatorSetUpdate) -> bool {
+        let unchanged = self
+            .current
+            .as_ref()
+            .is_some_and(|epoch| epoch.validators == live.validators && epoch.powers == live.powers);
+        if unchanged {
+            self.pending = None;
+            return false;
+        }
+
+        let staged = self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| pending.validators == live.validators && pending.powers == live.powers);
+        self.pending = Some(live);
+        !staged
+    }
+
+    /// Start a new rotation led by `leader` at `block_number`
+    ///
+    /// The current rotation is completed unsuccessfully and returned.
+    pub fn rotate(&mut self, leader: Address, block_number: u64) -> Option<RotationInfo> {
+        let rotation_number = self.next_rotation_number();
+        let completed = self.complete_rotation(block_number, false);
+        self.rotation = Some(start_rotation(rotation_number, block_number, leader));
+        completed
+    }
+
+    /// Count a produced block in the current epoch and rotation
+    pub fn record_produced(&mut self) {
+        if let Some(epoch) = &mut self.current {
+            epoch.blocks_produced += 1;
+        }
+        if let Some(rotation) = &mut self.rotation {
+            rotation.blocks_produced += 1;
+        }
+    }
+
+    /// Count a missed block in the current epoch and rotation
+    pub fn record_missed(&mut self) {
+        if let Some(epoch) = &mut self.current {
+            epoch.missed_blocks += 1;
+        }
+        if let Some(rotation) = &mut self.rotation {
+            rotation.missed_blocks += 1;
+        }
+    }
+
+    /// Epoch in effect
+    pub fn current(&self) -> Option<&EpochInfo> {
+        self.current.as_ref()
+    }
+
+    /// Rotation in effect
+    pub fn current_rotation(&self) -> Option<&RotationInfo> {
+        self.rotation.as_ref()
+    }
+
+    /// Live set waiting for the next epoch boundary
+    pub fn pending(&self) -> Option<&ValidatorSetUpdate> {
+        self.pending.as_ref()
+    }
+
+    /// Epoch `number`, in effect or completed
+    pub fn epoch(&self, number: u64) -> Option<&EpochInfo> {
+        self.current
+            .iter()
+            .chain(self.epochs.iter().rev())
+            .find(|epoch| epoch.epoch_number == number)
+    }
+
+    /// Completed epochs, oldest first
+    pub fn epochs(&self) -> impl Iterator<Item = &EpochInfo> {
+        self.epochs.iter()
+    }
+
+    /// Completed rotations, oldest first
+    pub fn rotations(&self) -> impl Iterator<Item = &RotationInfo> {
+        self.rotations.iter()
+    }
+
+    /// Local state to persist across restarts
+    pub fn snapshot(&self) -> EpochSnapshot {
+        EpochSnapshot {
+            current: self.current.clone(),
+            rotation: self.rotation.clone(),
+            pending: self.pending.clone(),
+            epochs: self.epochs.iter().cloned().collect(),
+            rotations: self.rotations.iter().cloned().collect(),
+        }
+    }
+
+    /// Restore local state persisted by [`snapshot`](Self::snapshot)
+    pub fn restore(&mut self, snapshot: EpochSnapshot) {
+        self.current = snapshot.current;
+        self.rotation = snapshot.rotation;
+        self.pending = snapshot.pending;
+        self.epochs = snapshot.epochs.into();
+        self.rotations = snapshot.rotations.into();
+    }
+
+    /// Number of the rotation after the current one
+    fn next_rotation_number(&self) -> u64 {
+        self.rotation
+            .as_ref()
+            .or_else(|| self.rotations.back())
+            .map_or(0, |rotation| rotation.rotation_number + 1)
+    }
+
+    /// Complete the current rotation at `end_block` and return it
+    fn complete_rotation(&mut self, end_block: u64, completed_successfully: bool) -> Option<RotationInfo> {
+        let mut rotation = self.rotation.take()?;
+        rotation.end_block = end_block;
+        rotation.completed_successfully = completed_successfully;
+        push_bounded(&mut self.rotations, rotation.clone(), MAX_ROTATION_HISTORY);
+        Some(rotation)
+    }
+}
+
+/// Persisted state of an [`EpochTracker`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSnapshot {
+    /// Epoch in effect
+    pub current: Option<EpochInfo>,
+
+    /// Rotation in effect
+    pub rotation: Option<RotationInfo>,
+
+    /// Live set waiting for the next epoch boundary
+    pub pending: Option<ValidatorSetUpdate>,
+
+    /// Completed epochs, oldest first
+    pub epochs: Vec<EpochInfo>,
+
+    /// Completed rotations, oldest first
+    pub rotations: Vec<RotationInfo>,
+}
+
+/// Rotation `number` led by `leader` from `start_block`
+const fn start_rotation(number: u64, start_block: u64, leader: Address) -> RotationInfo {
+    RotationInfo {
+        rotation_number: number,
+        start_block,
+        end_block: 0,
+        leader,
+        blocks_produced: 0,
+        missed_blocks: 0,
+        completed_successfully: false,
+    }
+}
+
+/// Append `item`, dropping the oldest entries beyond `max`
+fn push_bounded<T>(queue: &mut VecDeque<T>, item: T, max: usize) {
+    queue.push_back(item);
+    while queue.len() > max {
+        queue.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(epoch: u64, validators: &[(u8, u64)], start: u64) -> ValidatorSetUpdate {
+        ValidatorSetUpdate {
+            epoch,
+            validators: validators.iter().map(|(byte, _)| Address::repeat_byte(*byte)).collect(),
+            powers: validators.iter().map(|(_, power)| *power).collect(),
+            total_power: validators.iter().map(|(_, power)| power).sum(),
+            block_number: start,
+            timestamp: start * 2,
+        }
+    }
+
+    #[test]
+    fn test_epoch_boundary_records_stats() {
+        let mut tracker = EpochTracker::new();
+        assert!(tracker.begin_epoch(&set(1, &[(1, 100)], 0), Address::repeat_byte(1)).is_none());
+
+        tracker.record_produced();
+        tracker.record_produced();
+        tracker.record_missed();
+
+        // Re-entering the same epoch changes nothing
+        assert!(tracker.begin_epoch(&set(1, &[(1, 100)], 0), Address::repeat_byte(1)).is_none());
+
+        let completed = tracker.begin_epoch(&set(2, &[(1, 100), (2, 50)], 100), Address::repeat_byte(2)).unwrap();
+        assert_eq!((completed.epoch_number, completed.start_block, completed.end_block), (1, 0, 99));
+        assert_eq!((completed.blocks_produced, completed.missed_blocks, completed.end_time), (2, 1, 200));
+
+        assert_eq!(tracker.current().unwrap().epoch_number, 2);
+        assert_eq!(tracker.epoch(1), Some(&completed));
+        let rotation = tracker.rotations().next().unwrap();
+        assert_eq!((rotation.end_block, rotation.blocks_produced, rotation.completed_successfully), (99, 2, true));
+        assert_eq!(tracker.current_rotation().unwrap().rotation_number, 1);
+    }
+
+    #[test]
+    fn test_live_changes_wait_for_boundary() {
+        let mut tracker = EpochTracker::new();
+        tracker.begin_epoch(&set(1, &[(1, 100)], 0), Address::repeat_byte(1));
+
+        // The set in effect is not pending
+        assert!(!tracker.stage(set(1, &[(1, 100)], 10)));
+        assert!(tracker.pending().is_none());
+
+        // A registration is staged once, not applied
+        assert!(tracker.stage(set(1, &[(1, 100), (2, 50)], 20)));
+        assert!(!tracker.stage(set(1, &[(1, 100), (2, 50)], 30)));
+        assert_eq!(tracker.current().unwrap().validators, vec![Address::repeat_byte(1)]);
+
+        // The boundary consumes it
+        tracker.begin_epoch(&set(2, &[(1, 100), (2, 50)], 100), Address::repeat_byte(1));
+        assert!(tracker.pending().is_none());
+        assert_eq!(tracker.current().unwrap().validators.len(), 2);
+    }
+
+    #[test]
+    fn test_forced_rotation_and_restore() {
+        let mut tracker = EpochTracker::new();
+        tracker.begin_epoch(&set(1, &[(1, 100), (2, 100)], 0), Address::repeat_byte(1));
+        tracker.record_missed();
+
+        let failed = tracker.rotate(Address::repeat_byte(2), 40).unwrap();
+        assert_eq!((failed.leader, failed.end_block, failed.missed_blocks), (Address::repeat_byte(1), 40, 1));
+        assert!(!failed.completed_successfully);
+        assert_eq!(tracker.current_rotation().unwrap().leader, Address::repeat_byte(2));
+
+        let mut restored = EpochTracker::new();
+        restored.restore(tracker.snapshot());
+        assert_eq!(restored.snapshot(), tracker.snapshot());
+    }
+}
@@ -1,5 +1,8 @@
 //! Error types for consensus module
 
+use crate::bindings::{
+    IAndeConsensus::IAndeConsensusErrors, IAndeSequencerCoordinator::IAndeSequencerCoordinatorErrors,
+};
 use alloy_primitives::Address;
 use thiserror::Error;
 
@@ -53,6 +56,71 @@ pub enum ConsensusError {
         blocks: u64,
     },
 
+    /// Attestation is too old to be collected
+    #[error("Stale attestation for block {block_number} at head {head}")]
+    StaleAttestation {
+        /// Attested block
+        block_number: u64,
+        /// Highest block seen
+        head: u64,
+    },
+
+    /// Attestation is too far past the local canonical head
+    #[error("Attestation for block {block_number} is too far past head {head}")]
+    FutureAttestation {
+        /// Attested block
+        block_number: u64,
+        /// Highest block imported locally
+        head: u64,
+    },
+
+    /// Attestation names another epoch than the set in force at its block
+    #[error("Attestation for block {block_number} is for epoch {actual}, the block is in epoch {expected}")]
+    WrongEpoch {
+        /// Attested block
+        block_number: u64,
+        /// Epoch of the set in force at the block
+        expected: u64,
+        /// Epoch the attestation names
+        actual: u64,
+    },
+
+    /// Header carries no valid proposer seal
+    #[error("Invalid proposer seal: {0}")]
+    InvalidSeal(String),
+
+    /// No proposer is scheduled for a height yet
+    #[error("No proposer scheduled for block {0}")]
+    UnscheduledHeight(u64),
+
+    /// No validator set is known for a block
+    #[error("No validator set known for block {0}")]
+    UnknownValidatorSet(u64),
+
+    /// No validator set is known for an epoch
+    #[error("No validator set known for epoch {0}")]
+    UnknownEpoch(u64),
+
+    /// Light client proof does not verify
+    #[error("Light client proof: {0}")]
+    LightClient(#[from] ande_light_client::LightClientError),
+
+    /// Quorum certificate does not verify
+    #[error("Invalid quorum certificate: {0}")]
+    InvalidQuorumCertificate(String),
+
+    /// Slashing evidence does not prove misbehaviour
+    #[error("Invalid evidence: {0}")]
+    InvalidEvidence(String),
+
+    /// AndeConsensus reverted with one of its custom errors
+    #[error("Contract reverted: {0:?}")]
+    ContractReverted(IAndeConsensusErrors),
+
+    /// AndeSequencerCoordinator reverted with one of its custom errors
+    #[error("Coordinator reverted: {0:?}")]
+    CoordinatorReverted(IAndeSequencerCoordinatorErrors),
+
     /// Contract interaction failed
     #[error("Contract error: {0}")]
     ContractError(String),
@@ -82,6 +150,25 @@ pub enum ConsensusError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    /// Slashing protection refused to sign
+    #[error("Refusing to sign {kind} for block {block_number}: {reason}")]
+    SigningRefused {
+        /// What was to be signed
+        kind: &'static str,
+        /// Height of the refused signature
+        block_number: u64,
+        /// Why it was refused
+        reason: String,
+    },
+
+    /// Signing key or remote signer failed
+    #[error("Signer error: {0}")]
+    SignerError(String),
+
+    /// Persisted state is unreadable or fails its checksum
+    #[error("Corrupted consensus state: {0}")]
+    StateCorrupted(String),
+
     /// I/O error
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -91,19 +178,27 @@ pub enum ConsensusError {
     Internal(String),
 }
 
-impl From<ethers::providers::ProviderError> for ConsensusError {
-    fn from(err: ethers::providers::ProviderError) -> Self {
+impl From<alloy::contract::Error> for ConsensusError {
+    fn from(err: alloy::contract::Error) -> Self {
+        if let Some(revert) = err.as_decoded_interface_error::<IAndeConsensusErrors>() {
+            return Self::ContractReverted(revert);
+        }
+        match err.as_decoded_interface_error::<IAndeSequencerCoordinatorErrors>() {
+            Some(revert) => Self::CoordinatorReverted(revert),
+            None => Self::ContractError(err.to_string()),
+        }
+    }
+}
+
+impl From<alloy::transports::TransportError> for ConsensusError {
+    fn from(err: alloy::transports::TransportError) -> Self {
         Self::RpcError(err.to_string())
     }
 }
 
-impl From<ethers::contract::ContractError<ethers::providers::Provider<ethers::providers::Http>>>
-    for ConsensusError
-{
-    fn from(
-        err: ethers::contract::ContractError<ethers::providers::Provider<ethers::providers::Http>>,
-    ) -> Self {
-        Self::ContractError(err.to_string())
+impl From<alloy::providers::PendingTransactionError> for ConsensusError {
+    fn from(err: alloy::providers::PendingTransactionError) -> Self {
+        Self::RpcError(err.to_string())
     }
 }
 
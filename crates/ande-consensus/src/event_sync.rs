@@ -0,0 +1,162 @@
+//! Reorg-aware bookkeeping for AndeConsensus validator events
+//!
+//! The WebSocket log subscription delivers every `ValidatorSetUpdated`,
+//! `ValidatorJailed` and `ValidatorUnjailed` log once when its block is
+//! imported, and again with `removed = true` if a reorg drops that block.
+//! Logs may also be redelivered after a resubscription.
+//!
+//! ```text
+//! log (new)               ──→ Resync
+//! log (already applied)   ──→ Ignore
+//! log (removed, applied)  ──→ Resync (reorg), forgotten
+//! log (removed, unknown)  ──→ Ignore
+//! ```
+//!
+//! A resync re-reads the whole validator set from the contract at the
+//! current head, so the set always reflects the canonical chain regardless
+//! of the order events arrive in.
+
+use crate::types::ValidatorSetEvent;
+use alloy_primitives::B256;
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Blocks of applied events remembered for reorg and duplicate detection
+pub const DEFAULT_TRACKED_BLOCKS: u64 = 256;
+
+/// What to do with a received event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAction {
+    /// Already applied or never applied; nothing changed
+    Ignore,
+    /// Re-read the validator set from the contract
+    Resync {
+        /// Whether the event was undone by a reorg
+        reorg: bool,
+    },
+}
+
+/// Applied validator events, keyed by block number
+#[derive(Debug, Clone)]
+pub struct ValidatorEventTracker {
+    /// `(block_hash, log_index)` of applied events per block
+    applied: BTreeMap<u64, Vec<(B256, u64)>>,
+
+    /// Number of blocks to remember below the newest event
+    depth: u64,
+}
+
+impl ValidatorEventTracker {
+    /// Create a tracker remembering `depth` blocks
+    pub fn new(depth: u64) -> Self {
+        Self { applied: BTreeMap::new(), depth }
+    }
+
+    /// Record `event` and decide whether the validator set must be re-read
+    pub fn apply(&mut self, event: &ValidatorSetEvent) -> EventAction {
+        let key = (event.block_hash, event.log_index);
+
+        if event.removed {
+            let Some(logs) = self.applied.get_mut(&event.block_number) else {
+                return EventAction::Ignore;
+            };
+            let before = logs.len();
+            logs.retain(|applied| *applied != key);
+            if logs.len() == before {
+                return EventAction::Ignore;
+            }
+            if logs.is_empty() {
+                self.applied.remove(&event.block_number);
+            }
+
+            debug!(block = event.block_number, hash = ?event.block_hash, "Validator event reorged out");
+            return EventAction::Resync { reorg: true };
+        }
+
+        let logs = self.applied.entry(event.block_number).or_default();
+        if logs.contains(&key) {
+            return EventAction::Ignore;
+        }
+        logs.push(key);
+
+        self.prune();
+        EventAction::Resync { reorg: false }
+    }
+
+    /// Number of applied events remembered
+    pub fn len(&self) -> usize {
+        self.applied.values().map(Vec::len).sum()
+    }
+
+    /// Whether no events are remembered
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty()
+    }
+
+    /// Forget events more than `depth` blocks below the newest one
+    fn prune(&mut self) {
+        let Some(&newest) = self.applied.keys().next_back() else {
+            return;
+        };
+        let cutoff = newest.saturating_sub(self.depth);
+        self.applied = self.applied.split_off(&cutoff);
+    }
+}
+
+impl Default for ValidatorEventTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACKED_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ValidatorSetChange;
+    use alloy_primitives::Address;
+
+    fn jailed(block_number: u64, hash: u8, removed: bool) -> ValidatorSetEvent {
+        ValidatorSetEvent {
+            change: ValidatorSetChange::Jailed {
+                validator: Address::repeat_byte(1),
+                reason: "downtime".to_string(),
+            },
+            block_number,
+            block_hash: B256::repeat_byte(hash),
+            log_index: 0,
+            removed,
+        }
+    }
+
+    #[test]
+    fn test_duplicates_ignored() {
+        let mut tracker = ValidatorEventTracker::default();
+        assert_eq!(tracker.apply(&jailed(10, 1, false)), EventAction::Resync { reorg: false });
+        assert_eq!(tracker.apply(&jailed(10, 1, false)), EventAction::Ignore);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn test_reorg_triggers_resync() {
+        let mut tracker = ValidatorEventTracker::default();
+        tracker.apply(&jailed(10, 1, false));
+
+        // Removal of a log never applied changes nothing
+        assert_eq!(tracker.apply(&jailed(10, 2, true)), EventAction::Ignore);
+
+        // Removal of the applied log, then its replacement on the new fork
+        assert_eq!(tracker.apply(&jailed(10, 1, true)), EventAction::Resync { reorg: true });
+        assert!(tracker.is_empty());
+        assert_eq!(tracker.apply(&jailed(10, 2, false)), EventAction::Resync { reorg: false });
+    }
+
+    #[test]
+    fn test_old_blocks_pruned() {
+        let mut tracker = ValidatorEventTracker::new(5);
+        tracker.apply(&jailed(1, 1, false));
+        tracker.apply(&jailed(10, 2, false));
+
+        assert_eq!(tracker.len(), 1);
+        assert_eq!(tracker.apply(&jailed(1, 1, true)), EventAction::Ignore);
+    }
+}
@@ -0,0 +1,562 @@
+//! Slashing evidence for equivocation and unscheduled proposals
+//!
+//! Every attestation and signed proposal this node sees is checked against
+//! the first one recorded for the same validator and height. Misbehaviour
+//! becomes [`Evidence`] that anyone can verify from the signatures alone,
+//! which the engine gossips and submits to AndeConsensus:
+//!
+//! ```text
+//! attestation / proposal ─→ EvidencePool::observe_*
+//!                                │ same validator, same height, other hash ─→ Duplicate*
+//!                                │ proposal signed by a non-scheduled validator ─→ UnscheduledProposal
+//!                                ↓
+//!                        pending evidence ─→ slashEquivocation / slashUnscheduledProposal
+//!                                              │ failed: retry with backoff
+//!                                              ↓ success or processedEvidence[id]
+//!                                           handled (never resubmitted)
+//! ```
+//!
+//! The engine only passes on attestations and proposals of members of the
+//! validator set at their height, and the pool keeps at most
+//! [`MAX_SEEN_PER_HEIGHT`] signers per height and
+//! [`MAX_PENDING_PER_VALIDATOR`] pending evidences per validator, so a flood
+//! of signatures cannot grow it without bound.
+//!
+//! Evidence ids are computed exactly like `AndeConsensus.evidenceId`, so the
+//! contract's `processedEvidence` mapping deduplicates submissions from
+//! every node.
+
+use crate::{
+    attestation::{recover_attester, recover_proposer},
+    error::{ConsensusError, Result},
+    types::{AttestationInfo, BlockProposal},
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
+/// Submission attempts before evidence is dropped
+pub const MAX_SUBMIT_ATTEMPTS: u32 = 8;
+
+/// Delay before the first resubmission
+pub const SUBMIT_RETRY_BASE_DELAY: Duration = Duration::from_secs(6);
+
+/// Longest delay between resubmissions
+pub const SUBMIT_RETRY_MAX_DELAY: Duration = Duration::from_secs(600);
+
+/// Validators and kinds remembered per height; later signers are ignored
+pub const MAX_SEEN_PER_HEIGHT: usize = 512;
+
+/// Pending evidences per offending validator; more are dropped until some
+/// are handled
+pub const MAX_PENDING_PER_VALIDATOR: usize = 16;
+
+/// Evidence kind of duplicate attestations, as in AndeConsensus
+pub const EVIDENCE_DUPLICATE_ATTESTATION: u8 = 0;
+
+/// Evidence kind of duplicate proposals, as in AndeConsensus
+pub const EVIDENCE_DUPLICATE_PROPOSAL: u8 = 1;
+
+/// Evidence kind of unscheduled proposals, as in AndeConsensus
+pub const EVIDENCE_UNSCHEDULED_PROPOSAL: u8 = 2;
+
+/// A block hash and a validator's signature over it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedBlock {
+    /// Signed block hash
+    pub block_hash: B256,
+
+    /// 65-byte recoverable signature
+    pub signature: Bytes,
+}
+
+/// Proof that a validator misbehaved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Evidence {
+    /// Two attestations for different blocks at one height
+    #[serde(rename_all = "camelCase")]
+    DuplicateAttestation {
+        /// Offending validator
+        validator: Address,
+        /// Height attested twice
+        block_number: u64,
+        /// Epoch both attestations were signed for
+        epoch: u64,
+        /// First attestation
+        first: SignedBlock,
+        /// Conflicting attestation
+        second: SignedBlock,
+    },
+
+    /// Two proposals for different blocks at one height
+    #[serde(rename_all = "camelCase")]
+    DuplicateProposal {
+        /// Offending validator
+        validator: Address,
+        /// Height proposed twice
+        block_number: u64,
+        /// First proposal
+        first: SignedBlock,
+        /// Conflicting proposal
+        second: SignedBlock,
+    },
+
+    /// A proposal by a validator that was not scheduled for the height
+    #[serde(rename_all = "camelCase")]
+    UnscheduledProposal {
+        /// Offending validator
+        validator: Address,
+        /// Height of the proposal
+        block_number: u64,
+        /// Signed proposal
+        proposal: SignedBlock,
+        /// Validator the schedule selected
+        scheduled: Address,
+    },
+}
+
+impl Evidence {
+    /// Offending validator
+    pub const fn validator(&self) -> Address {
+        match self {
+            Self::DuplicateAttestation { validator, .. }
+            | Self::DuplicateProposal { validator, .. }
+            | Self::UnscheduledProposal { validator, .. } => *validator,
+        }
+    }
+
+    /// Height of the misbehaviour
+    pub const fn block_number(&self) -> u64 {
+        match self {
+            Self::DuplicateAttestation { block_number, .. }
+            | Self::DuplicateProposal { block_number, .. }
+            | Self::UnscheduledProposal { block_number, .. } => *block_number,
+        }
+    }
+
+    /// Epoch the signatures commit to, for attestations
+    pub const fn epoch(&self) -> Option<u64> {
+        match self {
+            Self::DuplicateAttestation { epoch, .. } => Some(*epoch),
+            Self::DuplicateProposal { .. } | Self::UnscheduledProposal { .. } => None,
+        }
+    }
+
+    /// Evidence kind as encoded by AndeConsensus
+    pub const fn kind(&self) -> u8 {
+        match self {
+            Self::DuplicateAttestation { .. } => EVIDENCE_DUPLICATE_ATTESTATION,
+            Self::DuplicateProposal { .. } => EVIDENCE_DUPLICATE_PROPOSAL,
+            Self::UnscheduledProposal { .. } => EVIDENCE_UNSCHEDULED_PROPOSAL,
+        }
+    }
+
+    /// Identifier matching `AndeConsensus.evidenceId`
+    ///
+    /// Duplicate hashes are ordered low to high, so both orders of the same
+    /// pair share an id.
+    pub fn id(&self) -> B256 {
+        let (low, high) = match self {
+            Self::DuplicateAttestation { first, second, .. }
+            | Self::DuplicateProposal { first, second, .. } => {
+                let (a, b) = (first.block_hash, second.block_hash);
+                if a < b { (a, b) } else { (b, a) }
+            }
+            Self::UnscheduledProposal { proposal, .. } => (proposal.block_hash, proposal.block_hash),
+        };
+
+        let mut preimage = Vec::with_capacity(20 + 1 + 8 + 64);
+        preimage.extend_from_slice(self.validator().as_slice());
+        preimage.push(self.kind());
+        preimage.extend_from_slice(&self.block_number().to_be_bytes());
+        preimage.extend_from_slice(low.as_slice());
+        preimage.extend_from_slice(high.as_slice());
+        keccak256(preimage)
+    }
+
+    /// Check the signatures, and for unscheduled proposals the schedule
+    ///
+    /// `scheduled` is this node's scheduled proposer of the height; evidence
+    /// claiming another one is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::InvalidEvidence`] if the evidence does not
+    /// prove misbehaviour
+    pub fn verify(&self, chain_id: u64, scheduled: Option<Address>) -> Result<()> {
+        let validator = self.validator();
+        let block_number = self.block_number();
+        let signed_by_validator = |block: &SignedBlock, proposal: bool| {
+            let signer = match self.epoch() {
+                Some(epoch) if !proposal => {
+                    recover_attester(chain_id, epoch, block_number, block.block_hash, &block.signature)
+                }
+                _ => recover_proposer(chain_id, block_number, block.block_hash, &block.signature),
+            };
+            signer.ok() == Some(validator)
+        };
+
+        let valid = match self {
+            Self::DuplicateAttestation { first, second, .. } => {
+                first.block_hash != second.block_hash
+                    && signed_by_validator(first, false)
+                    && signed_by_validator(second, false)
+            }
+            Self::DuplicateProposal { first, second, .. } => {
+                first.block_hash != second.block_hash
+                    && signed_by_validator(first, true)
+                    && signed_by_validator(second, true)
+            }
+            Self::UnscheduledProposal { proposal, scheduled: claimed, .. } => {
+                *claimed != validator
+                    && scheduled == Some(*claimed)
+                    && signed_by_validator(proposal, true)
+            }
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(ConsensusError::InvalidEvidence(format!(
+                "{} evidence against {validator} at block {block_number}",
+                self.kind()
+            )))
+        }
+    }
+}
+
+/// Evidence waiting for on-chain submission
+#[derive(Debug, Clone)]
+struct PendingEvidence {
+    /// The evidence
+    evidence: Evidence,
+
+    /// Failed submission attempts
+    attempts: u32,
+
+    /// Earliest time of the next attempt
+    next_attempt: Instant,
+}
+
+/// Detects misbehaviour and tracks evidence until it is submitted
+#[derive(Debug)]
+pub struct EvidencePool {
+    /// Chain the signatures are for
+    chain_id: u64,
+
+    /// First signed block per height, validator and whether it is a proposal
+    seen: BTreeMap<u64, HashMap<(Address, bool), SignedBlock>>,
+
+    /// Evidence not yet accepted on-chain, by id
+    pending: HashMap<B256, PendingEvidence>,
+
+    /// Submitted or dropped evidence ids with their block number
+    handled: HashMap<B256, u64>,
+
+    /// Highest height observed
+    head: u64,
+
+    /// Heights older than this many blocks below `head` are forgotten
+    max_age: u64,
+}
+
+impl EvidencePool {
+    /// Create an empty pool
+    pub fn new(chain_id: u64, max_age: u64) -> Self {
+        Self {
+            chain_id,
+            seen: BTreeMap::new(),
+            pending: HashMap::new(),
+            handled: HashMap::new(),
+            head: 0,
+            max_age,
+        }
+    }
+
+    /// Check an attestation for equivocation
+    ///
+    /// Attestations with invalid signatures are ignored. Membership is not
+    /// checked here; callers pass only attestations of the set at the height.
+    pub fn observe_attestation(&mut self, attestation: &AttestationInfo) -> Option<Evidence> {
+        let signer = recover_attester(
+            self.chain_id,
+            attestation.epoch,
+            attestation.block_number,
+            attestation.block_hash,
+            &attestation.signature,
+        )
+        .ok()
+        .filter(|signer| *signer == attestation.validator)?;
+
+        let block = SignedBlock { block_hash: attestation.block_hash, signature: attestation.signature.clone() };
+        let first = self.record(signer, attestation.block_number, false, block.clone())?;
+        Some(Evidence::DuplicateAttestation {
+            validator: signer,
+            block_number: attestation.block_number,
+            epoch: attestation.epoch,
+            first,
+            second: block,
+        })
+    }
+
+    /// Check a signed proposal for equivocation and against the schedule
+    ///
+    /// `scheduled` is the scheduled proposer of the height, if known.
+    /// Membership is not checked here, as for attestations.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the proposal is not signed by its producer
+    pub fn observe_proposal(&mut self, proposal: &BlockProposal, scheduled: Option<Address>) -> Result<Vec<Evidence>> {
+        let signer = recover_proposer(self.chain_id, proposal.block_number, proposal.block_hash, &proposal.signature)?;
+        if signer != proposal.producer {
+            return Err(ConsensusError::InvalidSignature { signer: proposal.producer });
+        }
+
+        let block = SignedBlock { block_hash: proposal.block_hash, signature: proposal.signature.clone() };
+        let mut evidence = Vec::new();
+        if let Some(scheduled) = scheduled.filter(|scheduled| *scheduled != signer) {
+            evidence.push(Evidence::UnscheduledProposal {
+                validator: signer,
+                block_number: proposal.block_number,
+                proposal: block.clone(),
+                scheduled,
+            });
+        }
+        if let Some(first) = self.record(signer, proposal.block_number, true, block.clone()) {
+            evidence.push(Evidence::DuplicateProposal {
+                validator: signer,
+                block_number: proposal.block_number,
+                first,
+                second: block,
+            });
+        }
+        Ok(evidence)
+    }
+
+    /// Queue evidence for submission
+    ///
+    /// Returns `false` if it is already pending or handled, or its validator
+    /// has [`MAX_PENDING_PER_VALIDATOR`] evidences pending.
+    pub fn add(&mut self, evidence: Evidence, now: Instant) -> bool {
+        let id = evidence.id();
+        if self.handled.contains_key(&id) || self.pending.contains_key(&id) {
+            return false;
+        }
+
+        let validator = evidence.validator();
+        let queued = self.pending.values().filter(|pending| pending.evidence.validator() == validator).count();
+        if queued >= MAX_PENDING_PER_VALIDATOR {
+            debug!(id = ?id, validator = ?validator, queued, "Evidence dropped, validator queue full");
+            return false;
+        }
+
+        debug!(id = ?id, validator = ?evidence.validator(), block = evidence.block_number(), "Evidence queued");
+        self.pending.insert(id, PendingEvidence { evidence, attempts: 0, next_attempt: now });
+        true
+    }
+
+    /// Evidence due for a submission attempt at `now`
+    pub fn due(&self, now: Instant) -> Vec<Evidence> {
+        self.pending
+            .values()
+            .filter(|pending| pending.next_attempt <= now)
+            .map(|pending| pending.evidence.clone())
+            .collect()
+    }
+
+    /// Mark evidence as accepted on-chain (or already processed there)
+    pub fn mark_submitted(&mut self, id: B256) {
+        if let Some(pending) = self.pending.remove(&id) {
+            self.handled.insert(id, pending.evidence.block_number());
+        }
+    }
+
+    /// Record a failed submission and schedule a retry with backoff
+    ///
+    /// Returns `false` if the evidence ran out of attempts and was dropped.
+    pub fn mark_failed(&mut self, id: B256, now: Instant) -> bool {
+        let Some(pending) = self.pending.get_mut(&id) else {
+            return false;
+        };
+
+        pending.attempts += 1;
+        if pending.attempts >= MAX_SUBMIT_ATTEMPTS {
+            let block_number = pending.evidence.block_number();
+            self.pending.remove(&id);
+            self.handled.insert(id, block_number);
+            return false;
+        }
+
+        let delay = SUBMIT_RETRY_BASE_DELAY
+            .saturating_mul(1 << (pending.attempts - 1))
+            .min(SUBMIT_RETRY_MAX_DELAY);
+        pending.next_attempt = now + delay;
+        true
+    }
+
+    /// Number of evidences awaiting submission
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Record the first signed block of a validator at a height
+    ///
+    /// Returns the earlier block if `signed` conflicts with it.
+    fn record(&mut self, validator: Address, block_number: u64, proposal: bool, signed: SignedBlock) -> Option<SignedBlock> {
+        if block_number.saturating_add(self.max_age) < self.head {
+            return None;
+        }
+        if block_number > self.head {
+            self.head = block_number;
+            self.prune();
+        }
+
+        let seen = self.seen.entry(block_number).or_default();
+        if seen.len() >= MAX_SEEN_PER_HEIGHT && !seen.contains_key(&(validator, proposal)) {
+            return None;
+        }
+
+        let first = seen.entry((validator, proposal)).or_insert_with(|| signed.clone());
+        (first.block_hash != signed.block_hash).then(|| first.clone())
+    }
+
+    /// Forget heights and handled ids older than `max_age`
+    fn prune(&mut self) {
+        let cutoff = self.head.saturating_sub(self.max_age);
+        self.seen.retain(|block_number, _| *block_number >= cutoff);
+        self.handled.retain(|_, block_number| *block_number >= cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::AttestationSigner;
+
+    const CHAIN_ID: u64 = 6174;
+
+    fn signer(byte: u8) -> AttestationSigner {
+        AttestationSigner::from_bytes(&[byte; 32], CHAIN_ID).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_attestation_detected() {
+        let validator = signer(1);
+        let mut pool = EvidencePool::new(CHAIN_ID, 100);
+
+        let first = validator.sign(1, 10, B256::repeat_byte(1), 0).await.unwrap();
+        assert!(pool.observe_attestation(&first).is_none());
+        assert!(pool.observe_attestation(&first).is_none());
+
+        let conflicting = validator.sign(1, 10, B256::repeat_byte(2), 0).await.unwrap();
+        let evidence = pool.observe_attestation(&conflicting).unwrap();
+        assert_eq!(evidence.validator(), validator.address());
+        evidence.verify(CHAIN_ID, None).unwrap();
+
+        // Both orders of the pair share the contract's id
+        let Evidence::DuplicateAttestation { block_number, epoch, first, second, .. } = evidence.clone() else {
+            panic!("expected duplicate attestation");
+        };
+        let swapped = Evidence::DuplicateAttestation {
+            validator: validator.address(),
+            block_number,
+            epoch,
+            first: second.clone(),
+            second: first.clone(),
+        };
+        assert_eq!(swapped.id(), evidence.id());
+
+        // Signatures bind the epoch they were made for
+        let moved = Evidence::DuplicateAttestation {
+            validator: validator.address(),
+            block_number,
+            epoch: epoch + 1,
+            first: first.clone(),
+            second: second.clone(),
+        };
+        assert!(matches!(moved.verify(CHAIN_ID, None), Err(ConsensusError::InvalidEvidence(_))));
+
+        // Signatures of one validator do not prove anything against another
+        let forged =
+            Evidence::DuplicateAttestation { validator: signer(2).address(), block_number, epoch, first, second };
+        assert!(matches!(forged.verify(CHAIN_ID, None), Err(ConsensusError::InvalidEvidence(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unscheduled_and_duplicate_proposals() {
+        let (producer, scheduled) = (signer(1), signer(2));
+        let mut pool = EvidencePool::new(CHAIN_ID, 100);
+
+        let proposal = producer.sign_proposal(5, B256::repeat_byte(1)).await.unwrap();
+        let evidence = pool.observe_proposal(&proposal, Some(scheduled.address())).unwrap();
+        assert_eq!(evidence.len(), 1);
+        evidence[0].verify(CHAIN_ID, Some(scheduled.address())).unwrap();
+        assert!(evidence[0].verify(CHAIN_ID, Some(producer.address())).is_err());
+
+        let conflicting = producer.sign_proposal(5, B256::repeat_byte(2)).await.unwrap();
+        let evidence = pool.observe_proposal(&conflicting, Some(producer.address())).unwrap();
+        assert!(matches!(evidence.as_slice(), [Evidence::DuplicateProposal { .. }]));
+        evidence[0].verify(CHAIN_ID, None).unwrap();
+
+        // Attestation signatures are not valid proposals
+        let mut attestation_as_proposal = conflicting;
+        attestation_as_proposal.signature = producer.sign(1, 5, B256::repeat_byte(2), 0).await.unwrap().signature;
+        assert!(pool.observe_proposal(&attestation_as_proposal, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pool_bounded_per_validator_and_height() {
+        let validator = signer(1);
+        let mut pool = EvidencePool::new(CHAIN_ID, 100);
+
+        let now = Instant::now();
+        for block_number in 1..=MAX_PENDING_PER_VALIDATOR as u64 + 1 {
+            pool.observe_attestation(&validator.sign(1, block_number, B256::repeat_byte(1), 0).await.unwrap());
+            let evidence = pool
+                .observe_attestation(&validator.sign(1, block_number, B256::repeat_byte(2), 0).await.unwrap())
+                .unwrap();
+            assert_eq!(pool.add(evidence, now), block_number <= MAX_PENDING_PER_VALIDATOR as u64);
+        }
+        assert_eq!(pool.pending_count(), MAX_PENDING_PER_VALIDATOR);
+
+        // Once a height is full, new signers are not remembered there
+        for index in 1..=MAX_SEEN_PER_HEIGHT as u64 {
+            let mut secret = [3; 32];
+            secret[..8].copy_from_slice(&index.to_be_bytes());
+            let key = AttestationSigner::from_bytes(&secret, CHAIN_ID).unwrap();
+            pool.observe_attestation(&key.sign(1, 50, B256::repeat_byte(1), 0).await.unwrap());
+        }
+        let late = signer(9);
+        pool.observe_attestation(&late.sign(1, 50, B256::repeat_byte(1), 0).await.unwrap());
+        assert!(pool.observe_attestation(&late.sign(1, 50, B256::repeat_byte(2), 0).await.unwrap()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_submission_retries_with_backoff() {
+        let validator = signer(1);
+        let mut pool = EvidencePool::new(CHAIN_ID, 100);
+        pool.observe_attestation(&validator.sign(1, 1, B256::repeat_byte(1), 0).await.unwrap());
+        let evidence = pool.observe_attestation(&validator.sign(1, 1, B256::repeat_byte(2), 0).await.unwrap()).unwrap();
+        let id = evidence.id();
+
+        let now = Instant::now();
+        assert!(pool.add(evidence.clone(), now));
+        assert!(!pool.add(evidence.clone(), now));
+        assert_eq!(pool.due(now).len(), 1);
+
+        assert!(pool.mark_failed(id, now));
+        assert!(pool.due(now).is_empty());
+        assert_eq!(pool.due(now + SUBMIT_RETRY_BASE_DELAY).len(), 1);
+
+        pool.mark_submitted(id);
+        assert_eq!(pool.pending_count(), 0);
+        assert!(!pool.add(evidence, now));
+    }
+}
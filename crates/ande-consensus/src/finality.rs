@@ -0,0 +1,327 @@
+//! Light client finality and validator set change proofs
+//!
+//! Quorum certificates are turned into [`FinalityProof`]s that external
+//! verifiers (bridges, relayers, exchanges) check with the `no_std`
+//! [`ande_light_client`] crate, given only the validator set of the block's
+//! epoch:
+//!
+//! ```text
+//! RLP header + QuorumCertificate + light client set of its epoch → FinalityProof
+//! ```
+//!
+//! Block attestations commit to their epoch but not to its validator set,
+//! so light clients cannot learn a new set from them. Instead, when an epoch
+//! begins, every validator of the previous epoch signs the hash of the new
+//! set and gossips it; signatures holding 2/3+1 of the previous set's power
+//! form a [`ValidatorSetChangeProof`]:
+//!
+//! ```text
+//! epoch E+1 begins ─→ validators of E sign validator_set_digest(E+1, hash(set E+1))
+//!                                   │ gossip (ande_submitValidatorSetSignature)
+//!                                   ↓
+//!                  HandoverPool: Σ power in set E ≥ 2/3+1 → ValidatorSetChangeProof
+//! ```
+//!
+//! Light client sets are the sets epochs started with, in canonical order,
+//! so every node derives the same hash. Handover signatures are kept in
+//! memory only; a node that restarts serves proofs for later epochs.
+
+use crate::{
+    attestation::QuorumCertificate,
+    error::{ConsensusError, Result},
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use ande_light_client::{
+    recover_signer, validator_set_digest, FinalityProof, LightClientError,
+    ValidatorSet as LightValidatorSet, ValidatorSetChangeProof, ValidatorSignature,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Epochs whose validator sets and handovers are kept
+pub const MAX_HANDOVER_EPOCHS: usize = 64;
+
+/// Signature of a validator handing over to the next epoch's set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSetSignature {
+    /// Epoch handed over to
+    pub epoch: u64,
+
+    /// Light client hash of the epoch's validator set
+    pub set_hash: B256,
+
+    /// Validator of the previous epoch
+    pub validator: Address,
+
+    /// 65-byte recoverable signature over the validator set digest
+    pub signature: Bytes,
+}
+
+/// Light client validator set of `epoch`, which starts at `start_block`
+///
+/// # Errors
+///
+/// Returns error if the set is empty, has duplicates or a validator without
+/// voting power
+pub fn light_client_set(
+    epoch: u64,
+    start_block: u64,
+    validators: &[Address],
+    powers: &[u64],
+) -> Result<LightValidatorSet> {
+    Ok(LightValidatorSet::new(epoch, start_block, validators, powers)?)
+}
+
+/// Finality proof of the block with RLP-encoded `header`
+///
+/// The proof is verified before it is returned, so a certificate formed
+/// against a set other than `set` is reported here rather than by the
+/// light client.
+///
+/// # Errors
+///
+/// Returns error if `header` is not the certified block, `set` is not the
+/// certificate's epoch, or the signatures fall short of `set`'s threshold
+pub fn finality_proof(
+    chain_id: u64,
+    header: Bytes,
+    certificate: &QuorumCertificate,
+    set: LightValidatorSet,
+) -> Result<FinalityProof> {
+    let hash = keccak256(&header);
+    if hash != certificate.block_hash {
+        return Err(ConsensusError::InvalidQuorumCertificate(format!(
+            "header hashes to {hash}, block {} finalized {}",
+            certificate.block_number, certificate.block_hash
+        )));
+    }
+    if set.epoch != certificate.epoch {
+        return Err(ConsensusError::InvalidQuorumCertificate(format!(
+            "signed by epoch {} but the validator set is for epoch {}",
+            certificate.epoch, set.epoch
+        )));
+    }
+
+    let proof = FinalityProof {
+        header,
+        validator_set: set,
+        signatures: certificate
+            .signatures
+            .iter()
+            .map(|entry| ValidatorSignature { validator: entry.validator, signature: entry.signature.clone() })
+            .collect(),
+    };
+    proof.verify(chain_id, &proof.validator_set)?;
+    Ok(proof)
+}
+
+/// Collects validator set handover signatures per epoch
+#[derive(Debug)]
+pub struct HandoverPool {
+    /// Chain the signatures are for
+    chain_id: u64,
+
+    /// Light client validator set of each epoch
+    sets: BTreeMap<u64, LightValidatorSet>,
+
+    /// Verified handover signatures by epoch handed over to and validator
+    signatures: BTreeMap<u64, BTreeMap<Address, Bytes>>,
+}
+
+impl HandoverPool {
+    /// Create an empty pool
+    pub fn new(chain_id: u64) -> Self {
+        Self { chain_id, sets: BTreeMap::new(), signatures: BTreeMap::new() }
+    }
+
+    /// Record the validator set an epoch started with
+    ///
+    /// Replacing an epoch's set drops the handover signatures checked
+    /// against the old one.
+    pub fn record_set(&mut self, set: LightValidatorSet) {
+        let epoch = set.epoch;
+        if self.sets.get(&epoch) == Some(&set) {
+            return;
+        }
+
+        debug!(epoch, hash = ?set.hash(), "Recording light client validator set");
+        if self.sets.insert(epoch, set).is_some() {
+            self.signatures.remove(&epoch);
+            self.signatures.remove(&(epoch + 1));
+        }
+
+        while self.sets.len() > MAX_HANDOVER_EPOCHS {
+            self.sets.pop_first();
+        }
+        // A handover needs the set of the epoch before it
+        if let Some(&oldest) = self.sets.keys().next() {
+            self.signatures = self.signatures.split_off(&(oldest + 1));
+        }
+    }
+
+    /// Light client validator set of `epoch`
+    pub fn validator_set(&self, epoch: u64) -> Option<&LightValidatorSet> {
+        self.sets.get(&epoch)
+    }
+
+    /// Verify and collect a handover signature
+    ///
+    /// Returns `true` if this signature completed the handover to its epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the sets of the epoch or the one before are
+    /// unknown, the signature is over another set, or the signer was not a
+    /// validator of the previous epoch
+    pub fn add(&mut self, signature: ValidatorSetSignature) -> Result<bool> {
+        let epoch = signature.epoch;
+        let previous = epoch
+            .checked_sub(1)
+            .and_then(|previous| self.sets.get(&previous))
+            .ok_or(ConsensusError::UnknownEpoch(epoch.saturating_sub(1)))?;
+        let next_hash = self.sets.get(&epoch).ok_or(ConsensusError::UnknownEpoch(epoch))?.hash();
+
+        if signature.set_hash != next_hash {
+            return Err(LightClientError::UntrustedValidatorSet { expected: next_hash, actual: signature.set_hash }.into());
+        }
+        let digest = validator_set_digest(self.chain_id, epoch, next_hash);
+        let signer = recover_signer(digest, &signature.signature, signature.validator)?;
+        if signer != signature.validator {
+            return Err(ConsensusError::InvalidSignature { signer: signature.validator });
+        }
+        previous.power_of(&signer).ok_or(ConsensusError::ValidatorNotFound(signer))?;
+
+        let threshold = previous.bft_threshold();
+        let before = self.signed_power(epoch);
+        self.signatures.entry(epoch).or_default().insert(signer, signature.signature);
+        let after = self.signed_power(epoch);
+        Ok(before < threshold && after >= threshold)
+    }
+
+    /// Voting power of the previous epoch that handed over to `epoch`
+    pub fn signed_power(&self, epoch: u64) -> u64 {
+        let Some(previous) = epoch.checked_sub(1).and_then(|previous| self.sets.get(&previous)) else {
+            return 0;
+        };
+        self.signatures
+            .get(&epoch)
+            .map_or(0, |signers| signers.keys().filter_map(|signer| previous.power_of(signer)).sum())
+    }
+
+    /// Proof of the handover to `epoch`, once 2/3+1 of the previous
+    /// epoch's power signed it
+    pub fn change_proof(&self, epoch: u64) -> Option<ValidatorSetChangeProof> {
+        let previous = self.sets.get(&epoch.checked_sub(1)?)?;
+        let next = self.sets.get(&epoch)?;
+        if self.signed_power(epoch) < previous.bft_threshold() {
+            return None;
+        }
+
+        let signatures = self
+            .signatures
+            .get(&epoch)?
+            .iter()
+            .map(|(validator, signature)| ValidatorSignature { validator: *validator, signature: signature.clone() })
+            .collect();
+        Some(ValidatorSetChangeProof { new_set: next.clone(), signatures })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attestation::{attestation_digest, AttestationPool, AttestationSigner},
+        types::ValidatorSetUpdate,
+    };
+    use ande_light_client::{Header, LightClient};
+
+    const CHAIN_ID: u64 = 6174;
+
+    fn signers(n: u8) -> Vec<AttestationSigner> {
+        (1..=n).map(|i| AttestationSigner::from_bytes(&[i; 32], CHAIN_ID).unwrap()).collect()
+    }
+
+    fn light_set(epoch: u64, start_block: u64, signers: &[AttestationSigner], powers: &[u64]) -> LightValidatorSet {
+        let validators: Vec<Address> = signers.iter().map(AttestationSigner::address).collect();
+        light_client_set(epoch, start_block, &validators, powers).unwrap()
+    }
+
+    #[test]
+    fn test_digest_matches_light_client() {
+        let hash = B256::repeat_byte(7);
+        assert_eq!(
+            attestation_digest(CHAIN_ID, 3, 42, hash),
+            ande_light_client::attestation_digest(CHAIN_ID, 3, 42, hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finality_proof_from_certificate() {
+        let signers = signers(4);
+        let set = light_set(2, 0, &signers, &[10, 10, 10, 10]);
+        let mut pool = AttestationPool::new(CHAIN_ID, 64);
+        pool.record_validator_set(ValidatorSetUpdate {
+            epoch: 2,
+            validators: set.validators.clone(),
+            powers: set.powers.clone(),
+            total_power: 40,
+            block_number: 0,
+            timestamp: 0,
+        });
+
+        let header = Bytes::from(alloy_rlp::encode(Header { number: 9, ..Default::default() }));
+        let hash = keccak256(&header);
+        for signer in &signers[..3] {
+            pool.add(signer.sign(2, 9, hash, 0).await.unwrap()).unwrap();
+        }
+        let certificate = pool.certificate(9).unwrap().clone();
+
+        let proof = finality_proof(CHAIN_ID, header.clone(), &certificate, set.clone()).unwrap();
+        let block = LightClient::new(CHAIN_ID, set.clone()).unwrap().verify_finality(&proof).unwrap();
+        assert_eq!((block.number, block.hash, block.voting_power), (9, hash, 30));
+
+        // Another header is not the certified block
+        let other = Bytes::from(alloy_rlp::encode(Header { number: 9, gas_limit: 1, ..Default::default() }));
+        assert!(finality_proof(CHAIN_ID, other, &certificate, set.clone()).is_err());
+
+        // The set must be the certificate's epoch
+        let mut later = set;
+        later.epoch = 3;
+        assert!(finality_proof(CHAIN_ID, header, &certificate, later).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handover_pool_forms_change_proof() {
+        let signers = signers(5);
+        let previous = light_set(4, 100, &signers[..4], &[10, 10, 10, 10]);
+        let next = light_set(5, 200, &signers[2..], &[10, 10, 10]);
+        let mut pool = HandoverPool::new(CHAIN_ID);
+        pool.record_set(previous.clone());
+        pool.record_set(next.clone());
+
+        // Validator 5 only joins in epoch 5
+        let newcomer = signers[4].sign_validator_set(&next).await.unwrap();
+        assert!(matches!(pool.add(newcomer), Err(ConsensusError::ValidatorNotFound(_))));
+
+        let mut completed = Vec::new();
+        for signer in &signers[..3] {
+            completed.push(pool.add(signer.sign_validator_set(&next).await.unwrap()).unwrap());
+        }
+        assert_eq!(completed, [false, false, true]);
+        assert_eq!(pool.signed_power(5), 30);
+
+        let proof = pool.change_proof(5).unwrap();
+        let mut client = LightClient::new(CHAIN_ID, previous).unwrap();
+        client.apply_change(&proof).unwrap();
+        assert_eq!(client.validator_set(), &next);
+
+        // Signatures over another set for epoch 5 are refused
+        let forged = light_set(5, 200, &signers[4..], &[100]);
+        let signature = AttestationSigner::from_bytes(&[1; 32], CHAIN_ID).unwrap().sign_validator_set(&forged).await.unwrap();
+        assert!(matches!(pool.add(signature), Err(ConsensusError::LightClient(_))));
+        assert!(pool.change_proof(6).is_none());
+    }
+}
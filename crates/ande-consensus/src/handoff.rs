@@ -0,0 +1,230 @@
+//! Leader handoff driven by AndeSequencerCoordinator
+//!
+//! Block production follows the coordinator's leader, and the handover
+//! happens at the block that emitted `LeaderRotated`: the old leader may
+//! produce up to that block, the new one from the next.
+//!
+//! ```text
+//!              recordBlockProduced (rotation due) or checkTimeout
+//!                                  │
+//! leader A:  ... B-2   B-1   B ────┘ LeaderRotated(A → C) in block B
+//! leader C:                  └──→ B+1   B+2 ...  once the logs of B are applied
+//! ```
+//!
+//! A node only produces height `h` after applying every `LeaderRotated`
+//! log up to `h - 1`, and only if the latest rotation before `h` names it.
+//! Old and new leader apply the same rule to the same logs, so they never
+//! both produce a height and the new leader starts right after the
+//! rotation block.
+//!
+//! Imported blocks may run ahead of the applied logs, since logs are only
+//! polled periodically. Those are checked against the last known leader:
+//! its blocks are accepted, anyone else's wait until the logs show the
+//! rotation that made them leader.
+
+use crate::types::LeaderRotation;
+use alloy_primitives::Address;
+use std::collections::BTreeMap;
+
+/// Handoffs kept for checking imported blocks
+pub const MAX_HANDOFFS: usize = 64;
+
+/// Coordinator leader by height
+#[derive(Debug, Clone, Default)]
+pub struct LeaderHandoff {
+    /// Leader from each handoff height on
+    leaders: BTreeMap<u64, Address>,
+
+    /// Highest block whose coordinator logs are applied
+    synced_through: Option<u64>,
+
+    /// Rotation number of the latest handoff
+    rotation: u64,
+
+    /// Head at which this node's `checkTimeout` landed, until its rotation is seen
+    timeout_sent_at: Option<u64>,
+}
+
+impl LeaderHandoff {
+    /// Create a handoff with no known leader
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the coordinator's `leader` and `rotation` read at `head`
+    ///
+    /// Heights up to `head` stay unknown.
+    pub fn start(&mut self, leader: Address, rotation: u64, head: u64) {
+        self.leaders.clear();
+        self.leaders.insert(head + 1, leader);
+        self.rotation = rotation;
+        self.synced_through = Some(head);
+        self.timeout_sent_at = None;
+    }
+
+    /// Apply a `LeaderRotated` log
+    ///
+    /// Returns `false` for rotations already applied.
+    pub fn apply(&mut self, rotation: &LeaderRotation) -> bool {
+        if rotation.rotation_number <= self.rotation && !self.leaders.is_empty() {
+            return false;
+        }
+
+        self.leaders.insert(rotation.block_number + 1, rotation.new_leader);
+        while self.leaders.len() > MAX_HANDOFFS {
+            self.leaders.pop_first();
+        }
+        self.rotation = rotation.rotation_number;
+        self.timeout_sent_at = None;
+        true
+    }
+
+    /// Record that the coordinator logs up to `block` are applied
+    pub fn mark_synced(&mut self, block: u64) {
+        self.synced_through = Some(self.synced_through.map_or(block, |synced| synced.max(block)));
+    }
+
+    /// Highest block whose coordinator logs are applied
+    pub const fn synced_through(&self) -> Option<u64> {
+        self.synced_through
+    }
+
+    /// Rotation number of the latest handoff
+    pub const fn rotation(&self) -> u64 {
+        self.rotation
+    }
+
+    /// Leader after the latest handoff
+    pub fn current_leader(&self) -> Option<Address> {
+        self.leaders.values().next_back().copied()
+    }
+
+    /// Height the oldest kept handoff took effect at
+    pub fn first_handoff(&self) -> Option<u64> {
+        self.leaders.keys().next().copied()
+    }
+
+    /// Height the latest handoff took effect at
+    pub fn last_handoff(&self) -> Option<u64> {
+        self.leaders.keys().next_back().copied()
+    }
+
+    /// Leader allowed to produce `height`
+    ///
+    /// `None` before the first known handoff and for heights whose preceding
+    /// logs are not applied yet.
+    pub fn leader_at(&self, height: u64) -> Option<Address> {
+        let synced = self.synced_through?;
+        if height > synced + 1 {
+            return None;
+        }
+        self.leaders.range(..=height).next_back().map(|(_, leader)| *leader)
+    }
+
+    /// Leader of `height` as far as the applied logs go
+    ///
+    /// Unlike [`leader_at`](Self::leader_at), heights beyond the applied
+    /// logs get the latest known leader, which a rotation not applied yet
+    /// may have replaced. `None` before the first known handoff.
+    pub fn last_known_leader(&self, height: u64) -> Option<Address> {
+        self.leaders.range(..=height).next_back().map(|(_, leader)| *leader)
+    }
+
+    /// Whether `me` may produce `height`
+    pub fn may_produce(&self, me: Address, height: u64) -> bool {
+        self.leader_at(height) == Some(me)
+    }
+
+    /// Whether `me` should send `checkTimeout` at `head`
+    ///
+    /// Only the sequencer the rotation would hand over to sends it, at most
+    /// once per `grace_blocks`, and not within `grace_blocks` of the latest
+    /// handoff so a fresh leader gets time to produce.
+    pub fn should_check_timeout(&self, me: Address, next_leader: Address, head: u64, grace_blocks: u64) -> bool {
+        me == next_leader
+            && self.current_leader().is_some_and(|leader| leader != me)
+            && self.last_handoff().is_some_and(|from| head >= from + grace_blocks)
+            && self.timeout_sent_at.is_none_or(|sent_at| head >= sent_at + grace_blocks)
+    }
+
+    /// Record that this node's `checkTimeout` landed at `head`
+    pub fn mark_timeout_sent(&mut self, head: u64) {
+        self.timeout_sent_at = Some(head);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation(number: u64, from: u8, to: u8, block_number: u64) -> LeaderRotation {
+        LeaderRotation {
+            rotation_number: number,
+            old_leader: Address::repeat_byte(from),
+            new_leader: Address::repeat_byte(to),
+            block_number,
+            reason: "Round-robin".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_handover_at_rotation_block() {
+        let (a, c) = (Address::repeat_byte(1), Address::repeat_byte(3));
+        let mut handoff = LeaderHandoff::new();
+        handoff.start(a, 4, 100);
+
+        // Nothing is known before the start or beyond the applied logs
+        assert_eq!(handoff.leader_at(100), None);
+        assert!(handoff.may_produce(a, 101));
+        assert_eq!(handoff.leader_at(102), None);
+
+        assert!(handoff.apply(&rotation(5, 1, 3, 120)));
+        assert!(!handoff.apply(&rotation(5, 1, 3, 120)));
+        handoff.mark_synced(125);
+
+        // A produces up to the rotation block, C from the next one
+        assert!(handoff.may_produce(a, 120));
+        assert!(!handoff.may_produce(c, 120));
+        assert!(handoff.may_produce(c, 121));
+        assert!(!handoff.may_produce(a, 121));
+        assert_eq!(handoff.current_leader(), Some(c));
+    }
+
+    #[test]
+    fn test_new_leader_waits_for_logs() {
+        let c = Address::repeat_byte(3);
+        let mut handoff = LeaderHandoff::new();
+        handoff.start(Address::repeat_byte(1), 0, 10);
+        handoff.mark_synced(19);
+
+        // C cannot know about a rotation in block 20 before applying its logs
+        assert!(!handoff.may_produce(c, 21));
+        assert_eq!(handoff.leader_at(21), None);
+        assert_eq!(handoff.last_known_leader(21), Some(Address::repeat_byte(1)));
+        assert_eq!(handoff.last_known_leader(10), None);
+        handoff.apply(&rotation(1, 1, 3, 20));
+        handoff.mark_synced(20);
+        assert!(handoff.may_produce(c, 21));
+    }
+
+    #[test]
+    fn test_timeout_sent_by_next_leader_only() {
+        let (a, b, c) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let mut handoff = LeaderHandoff::new();
+        handoff.start(a, 0, 100);
+
+        // Fresh leaders get a grace period
+        assert!(!handoff.should_check_timeout(b, b, 105, 10));
+        assert!(handoff.should_check_timeout(b, b, 111, 10));
+        assert!(!handoff.should_check_timeout(c, b, 111, 10));
+        assert!(!handoff.should_check_timeout(a, a, 111, 10));
+
+        handoff.mark_timeout_sent(111);
+        assert!(!handoff.should_check_timeout(b, b, 115, 10));
+        assert!(handoff.should_check_timeout(b, b, 121, 10));
+
+        // The rotation it caused clears it
+        handoff.apply(&rotation(1, 1, 2, 112));
+        assert!(!handoff.should_check_timeout(b, c, 130, 10));
+    }
+}
@@ -0,0 +1,162 @@
+//! Jail lifecycle of the local validator
+//!
+//! AndeConsensus jails a slashed validator until a release block, after
+//! which the validator itself may call `unjail()`. The engine follows its
+//! own validator through that lifecycle and, with `auto_unjail`, sends the
+//! transaction as soon as the release block is reached:
+//!
+//! ```text
+//!  active ──slashed──→ jailed until R ──head ≥ R──→ unjail() sent ──resync──→ active again
+//!                      countdown R - head             │                          │
+//!                                                     └── still jailed after ────┘
+//!                                                         UNJAIL_RETRY_BLOCKS: resend
+//! ```
+//!
+//! Unjailing is confirmed only by a resync that finds the validator back in
+//! the active set, never by the transaction receipt alone.
+
+/// Blocks to wait for a sent unjail to show up before sending it again
+pub const UNJAIL_RETRY_BLOCKS: u64 = 10;
+
+/// Jail status change seen by a resync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JailChange {
+    /// The validator was jailed until `release_block`
+    Jailed {
+        /// First block at which `unjail()` succeeds
+        release_block: u64,
+    },
+
+    /// The validator is no longer jailed
+    Released {
+        /// Whether this node sent the unjail transaction
+        by_us: bool,
+    },
+}
+
+/// Jail status of the local validator
+#[derive(Debug, Clone, Default)]
+pub struct JailTracker {
+    /// Release block while jailed
+    release_block: Option<u64>,
+
+    /// Head at which the last unjail transaction landed
+    unjail_sent_at: Option<u64>,
+}
+
+impl JailTracker {
+    /// Create a tracker for a validator that is not jailed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the on-chain status read by a resync
+    ///
+    /// `release_block` is `Some` while the validator is jailed. Returns the
+    /// change, if the status differs from the previous one.
+    pub fn observe(&mut self, release_block: Option<u64>) -> Option<JailChange> {
+        let previous = self.release_block;
+        self.release_block = release_block;
+
+        match (previous, release_block) {
+            (None, Some(release_block)) => {
+                self.unjail_sent_at = None;
+                Some(JailChange::Jailed { release_block })
+            }
+            (Some(old), Some(release_block)) if release_block > old => {
+                // Jailed again before the previous unjail went through
+                self.unjail_sent_at = None;
+                Some(JailChange::Jailed { release_block })
+            }
+            (Some(_), None) => {
+                let by_us = self.unjail_sent_at.take().is_some();
+                Some(JailChange::Released { by_us })
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the validator is jailed
+    pub const fn is_jailed(&self) -> bool {
+        self.release_block.is_some()
+    }
+
+    /// Release block while jailed
+    pub const fn release_block(&self) -> Option<u64> {
+        self.release_block
+    }
+
+    /// Blocks left until the validator can be unjailed at `head`
+    ///
+    /// `0` once the release block is reached or if the validator is free.
+    pub fn blocks_remaining(&self, head: u64) -> u64 {
+        self.release_block.map_or(0, |release_block| release_block.saturating_sub(head))
+    }
+
+    /// Whether an unjail transaction should be sent at `head`
+    ///
+    /// True once the release block is reached, unless a previous unjail
+    /// landed less than [`UNJAIL_RETRY_BLOCKS`] ago and may still be
+    /// propagating to the data source.
+    pub fn should_unjail(&self, head: u64) -> bool {
+        let Some(release_block) = self.release_block else {
+            return false;
+        };
+        head >= release_block
+            && self
+                .unjail_sent_at
+                .is_none_or(|sent_at| head >= sent_at + UNJAIL_RETRY_BLOCKS)
+    }
+
+    /// Record that an unjail transaction landed at `head`
+    pub fn mark_unjail_sent(&mut self, head: u64) {
+        self.unjail_sent_at = Some(head);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_and_unjail_window() {
+        let mut tracker = JailTracker::new();
+        assert!(!tracker.should_unjail(100));
+        assert_eq!(tracker.blocks_remaining(100), 0);
+
+        assert_eq!(tracker.observe(Some(150)), Some(JailChange::Jailed { release_block: 150 }));
+        assert_eq!(tracker.observe(Some(150)), None);
+        assert_eq!(tracker.blocks_remaining(120), 30);
+        assert!(!tracker.should_unjail(149));
+        assert!(tracker.should_unjail(150));
+
+        // A sent unjail is not repeated until it had time to land
+        tracker.mark_unjail_sent(150);
+        assert!(!tracker.should_unjail(150 + UNJAIL_RETRY_BLOCKS - 1));
+        assert!(tracker.should_unjail(150 + UNJAIL_RETRY_BLOCKS));
+    }
+
+    #[test]
+    fn test_release_reports_who_unjailed() {
+        let mut tracker = JailTracker::new();
+        tracker.observe(Some(50));
+        tracker.mark_unjail_sent(50);
+        assert_eq!(tracker.observe(None), Some(JailChange::Released { by_us: true }));
+        assert!(!tracker.is_jailed());
+
+        // Unjailed by the validator manager
+        tracker.observe(Some(80));
+        assert_eq!(tracker.observe(None), Some(JailChange::Released { by_us: false }));
+    }
+
+    #[test]
+    fn test_rejailing_resets_pending_unjail() {
+        let mut tracker = JailTracker::new();
+        tracker.observe(Some(50));
+        tracker.mark_unjail_sent(50);
+
+        assert_eq!(tracker.observe(Some(90)), Some(JailChange::Jailed { release_block: 90 }));
+        assert_eq!(tracker.release_block(), Some(90));
+        assert!(tracker.should_unjail(90));
+    }
+}
@@ -31,10 +31,37 @@
 //!
 //! ## Features
 //!
-//! - **Weighted Round-Robin**: CometBFT-style proposer selection
-//! - **BFT Finality**: 2/3+1 voting power threshold
+//! - **Weighted Round-Robin**: CometBFT proposer priorities replayed from
+//!   each epoch's validator set, a pure function of the height that block
+//!   import checks synchronously
+//! - **BFT Finality**: attestations aggregated into quorum certificates at
+//!   2/3+1 voting power
+//! - **Event-Driven Sync**: Validator set changes via WebSocket logs, with
+//!   polling as a fallback
+//! - **Pluggable Data Source**: Contracts read over JSON-RPC or straight
+//!   from the hosting node's state, pinned to a block
+//! - **Epoch Transitions**: Validator set changes take effect at epoch
+//!   boundaries, with per-epoch and per-rotation block statistics
 //! - **Timeout Detection**: Automatic rotation on missed blocks
-//! - **Slashing Integration**: Report invalid blocks on-chain
+//! - **Leader Handoff**: Optionally follow AndeSequencerCoordinator's
+//!   leader from its `LeaderRotated` logs, reporting produced blocks and
+//!   timing out stalled leaders on-chain
+//! - **Crash-Safe State**: Checksummed snapshots in `data_dir`, restored on
+//!   start
+//! - **Slashing Integration**: Equivocation and unscheduled proposals
+//!   detected from signed votes and submitted to AndeConsensus as evidence
+//! - **Signing Keys**: Key file, encrypted keystore or Web3Signer-compatible
+//!   remote signer, behind persisted slashing protection
+//! - **Jail Lifecycle**: Countdown of this sequencer's jail period and
+//!   automatic `unjail()` once it ends
+//! - **Simulator**: N engines against a mock validator set with injected
+//!   crashes, partitions, delays, forks and equivocation, for `cargo test`
+//! - **Validator Performance**: Uptime, missed-slot streaks, attestation
+//!   participation and proposal latency computed from canonical blocks over
+//!   a sliding window, to cross-check the on-chain uptime
+//! - **Light Client Proofs**: Finality proofs (header, validator set,
+//!   signatures) and validator set change proofs signed by the outgoing
+//!   epoch, verified by the `no_std` `ande-light-client` crate
 //! - **Metrics & Observability**: Prometheus metrics export
 //! - **Production Ready**: Error handling, logging, testing
 
@@ -43,19 +70,50 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod attestation;
+pub mod bindings;
 pub mod config;
 pub mod contract_client;
+pub mod coordinator;
+pub mod data_source;
 pub mod engine;
+pub mod epoch;
 pub mod error;
+pub mod event_sync;
+pub mod evidence;
+pub mod finality;
+pub mod handoff;
+pub mod jail;
 pub mod metrics;
+pub mod performance;
+pub mod persistence;
+pub mod proposer_schedule;
+pub mod proposer_selection;
+pub mod rpc;
+pub mod seal;
+pub mod signer;
+pub mod simulator;
+pub mod slashing_protection;
 pub mod types;
 pub mod validator_set;
 
-pub use config::ConsensusConfig;
+pub use attestation::{AttestationPool, AttestationSigner, QuorumCertificate};
+pub use config::{ConsensusConfig, DataSourceKind, SignerKind};
+pub use data_source::{ConsensusDataSource, StateReader, ViewCaller};
 pub use engine::ConsensusEngine;
+pub use epoch::EpochTracker;
 pub use error::{ConsensusError, Result};
+pub use evidence::{Evidence, EvidencePool};
+pub use finality::{HandoverPool, ValidatorSetSignature};
+pub use handoff::LeaderHandoff;
+pub use jail::JailTracker;
+pub use performance::{PerformanceTracker, ValidatorPerformance};
+pub use proposer_schedule::ProposerSchedule;
+pub use signer::{LocalKey, SigningBackend, Web3Signer};
+pub use slashing_protection::SlashingProtection;
 pub use types::{
-    AttestationInfo, BlockProposal, EpochInfo, RotationInfo, ValidatorInfo, ValidatorSetUpdate,
+    AttestationInfo, BlockProposal, EpochInfo, ForcedRotation, LeaderRotation, RotationInfo,
+    ValidatorInfo, ValidatorSetChange, ValidatorSetEvent, ValidatorSetUpdate,
 };
 
 /// Re-export commonly used types
@@ -43,9 +43,60 @@ pub struct ConsensusMetrics {
     /// Number of forced rotations
     pub forced_rotations: IntCounter,
 
+    /// Number of blocks rejected for an unscheduled proposer
+    pub invalid_proposers: IntCounter,
+
+    /// Number of slashing evidences detected or received
+    pub evidence_detected: IntCounter,
+
+    /// Number of slashing evidences accepted on-chain
+    pub evidence_submitted: IntCounter,
+
     /// Number of validator set updates
     pub validator_set_updates: IntCounter,
 
+    /// Number of epoch boundaries crossed
+    pub epoch_transitions: IntCounter,
+
+    /// Number of AndeConsensus validator events received
+    pub validator_set_events: IntCounter,
+
+    /// Number of validator events removed by reorgs
+    pub validator_set_reorgs: IntCounter,
+
+    /// Whether this sequencer is jailed
+    pub jailed: IntGauge,
+
+    /// Blocks left until this sequencer can unjail
+    pub jail_blocks_remaining: IntGauge,
+
+    /// Number of unjail transactions sent by this sequencer
+    pub unjails_submitted: IntCounter,
+
+    /// Number of coordinator leader handoffs applied
+    pub leader_handoffs: IntCounter,
+
+    /// Number of coordinator transactions mined
+    pub coordinator_transactions: IntCounter,
+
+    /// Number of coordinator transactions that failed
+    pub coordinator_transaction_failures: IntCounter,
+
+    /// Uptime of this sequencer computed from canonical blocks (basis points)
+    pub observed_uptime: IntGauge,
+
+    /// Computed uptime minus on-chain uptime (basis points)
+    pub uptime_divergence: IntGauge,
+
+    /// Scheduled slots this sequencer missed in a row
+    pub missed_slot_streak: IntGauge,
+
+    /// Canonical blocks this sequencer attested (basis points)
+    pub attestation_participation: IntGauge,
+
+    /// Time from the parent block to blocks of this sequencer (seconds)
+    pub proposal_latency: Histogram,
+
     /// Block production time (seconds)
     pub block_production_time: Histogram,
 
@@ -163,6 +214,33 @@ impl ConsensusMetrics {
                 .subsystem("consensus"),
             )?,
 
+            invalid_proposers: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_invalid_proposers_total",
+                    "Total blocks rejected for an unscheduled proposer",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            evidence_detected: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_evidence_detected_total",
+                    "Total slashing evidences detected or received",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            evidence_submitted: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_evidence_submitted_total",
+                    "Total slashing evidences accepted on-chain",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
             validator_set_updates: IntCounter::with_opts(
                 Opts::new(
                     "consensus_validator_set_updates_total",
@@ -172,6 +250,132 @@ impl ConsensusMetrics {
                 .subsystem("consensus"),
             )?,
 
+            epoch_transitions: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_epoch_transitions_total",
+                    "Total epoch boundaries crossed",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            validator_set_events: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_validator_set_events_total",
+                    "Total validator set events received over WebSocket",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            validator_set_reorgs: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_validator_set_reorgs_total",
+                    "Total validator set events removed by reorgs",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            jailed: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_jailed",
+                    "Whether this sequencer is jailed (0/1)",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            jail_blocks_remaining: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_jail_blocks_remaining",
+                    "Blocks left until this sequencer can unjail",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            unjails_submitted: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_unjails_submitted_total",
+                    "Total unjail transactions sent by this sequencer",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            leader_handoffs: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_leader_handoffs_total",
+                    "Total AndeSequencerCoordinator leader handoffs applied",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            coordinator_transactions: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_coordinator_transactions_total",
+                    "Total AndeSequencerCoordinator transactions mined",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            coordinator_transaction_failures: IntCounter::with_opts(
+                Opts::new(
+                    "consensus_coordinator_transaction_failures_total",
+                    "Total AndeSequencerCoordinator transactions that failed",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            observed_uptime: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_observed_uptime_basis_points",
+                    "Uptime of this sequencer computed from canonical blocks",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            uptime_divergence: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_uptime_divergence_basis_points",
+                    "Computed uptime minus the uptime reported by AndeConsensus",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            missed_slot_streak: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_missed_slot_streak",
+                    "Scheduled slots this sequencer missed in a row",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            attestation_participation: IntGauge::with_opts(
+                Opts::new(
+                    "consensus_attestation_participation_basis_points",
+                    "Canonical blocks in the performance window this sequencer attested",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
+            proposal_latency: Histogram::with_opts(
+                HistogramOpts::new(
+                    "consensus_proposal_latency_seconds",
+                    "Time from the parent block to blocks of this sequencer",
+                )
+                .namespace("ande")
+                .subsystem("consensus"),
+            )?,
+
             block_production_time: Histogram::with_opts(
                 HistogramOpts::new(
                     "consensus_block_production_duration_seconds",
@@ -231,7 +435,24 @@ impl ConsensusMetrics {
         registry.register(Box::new(metrics.blocks_finalized.clone()))?;
         registry.register(Box::new(metrics.timeouts_detected.clone()))?;
         registry.register(Box::new(metrics.forced_rotations.clone()))?;
+        registry.register(Box::new(metrics.invalid_proposers.clone()))?;
+        registry.register(Box::new(metrics.evidence_detected.clone()))?;
+        registry.register(Box::new(metrics.evidence_submitted.clone()))?;
         registry.register(Box::new(metrics.validator_set_updates.clone()))?;
+        registry.register(Box::new(metrics.epoch_transitions.clone()))?;
+        registry.register(Box::new(metrics.validator_set_events.clone()))?;
+        registry.register(Box::new(metrics.validator_set_reorgs.clone()))?;
+        registry.register(Box::new(metrics.jailed.clone()))?;
+        registry.register(Box::new(metrics.jail_blocks_remaining.clone()))?;
+        registry.register(Box::new(metrics.unjails_submitted.clone()))?;
+        registry.register(Box::new(metrics.leader_handoffs.clone()))?;
+        registry.register(Box::new(metrics.coordinator_transactions.clone()))?;
+        registry.register(Box::new(metrics.coordinator_transaction_failures.clone()))?;
+        registry.register(Box::new(metrics.observed_uptime.clone()))?;
+        registry.register(Box::new(metrics.uptime_divergence.clone()))?;
+        registry.register(Box::new(metrics.missed_slot_streak.clone()))?;
+        registry.register(Box::new(metrics.attestation_participation.clone()))?;
+        registry.register(Box::new(metrics.proposal_latency.clone()))?;
         registry.register(Box::new(metrics.block_production_time.clone()))?;
         registry.register(Box::new(metrics.attestation_time.clone()))?;
         registry.register(Box::new(metrics.finalization_time.clone()))?;
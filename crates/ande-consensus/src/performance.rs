@@ -0,0 +1,319 @@
+//! Validator performance computed from canonical chain data
+//!
+//! `ValidatorInfo::uptime` and `total_blocks_missed` are what AndeConsensus
+//! counts. The tracker derives the same figures independently, from the
+//! canonical blocks this node imports and the attestations it verified, so
+//! operators can cross-check the on-chain numbers:
+//!
+//! ```text
+//! canonical block n (hash, producer, timestamp), scheduled proposer S
+//!   ├─ producer == S → slot kept by S, S's missed streak reset
+//!   ├─ producer != S → slot missed by S, S's missed streak + 1
+//!   └─ timestamp(n) − timestamp(n−1) → proposal latency of the producer
+//!
+//! verified attestation (n, hash, validator)
+//!   └─ counts for participation once `hash` is canonical at n
+//!
+//! window: the last `performance_window` canonical blocks
+//! ```
+//!
+//! A reorg replaces every record from the first re-imported height on.
+//! Heights without a known scheduled proposer count towards production and
+//! latency but not towards uptime.
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Basis points of a full score
+const FULL_SCORE: u16 = 10_000;
+
+/// Performance of one validator over the window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorPerformance {
+    /// Validator address
+    pub validator: Address,
+
+    /// First canonical block of the window
+    pub window_start: u64,
+
+    /// Last canonical block of the window
+    pub window_end: u64,
+
+    /// Slots the validator was scheduled for
+    pub scheduled_slots: u64,
+
+    /// Scheduled slots another validator produced
+    pub missed_slots: u64,
+
+    /// Blocks the validator produced, scheduled or not
+    pub blocks_produced: u64,
+
+    /// Scheduled slots kept, in basis points (10000 = 100%)
+    pub uptime: u16,
+
+    /// Scheduled slots missed in a row up to the window end
+    pub missed_streak: u64,
+
+    /// Longest run of missed slots in the window
+    pub longest_missed_streak: u64,
+
+    /// Canonical blocks the validator attested
+    pub blocks_attested: u64,
+
+    /// Canonical blocks attested, in basis points of the window
+    pub attestation_participation: u16,
+
+    /// Average time from the parent block to the validator's blocks (ms)
+    pub avg_proposal_latency_ms: u64,
+
+    /// Longest time from the parent block to one of its blocks (seconds)
+    pub max_proposal_latency_secs: u64,
+}
+
+/// A canonical block as the tracker needs it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlockRecord {
+    /// Canonical hash
+    hash: B256,
+
+    /// Block producer
+    producer: Address,
+
+    /// Proposer scheduled for the height, if known
+    scheduled: Option<Address>,
+
+    /// Block timestamp
+    timestamp: u64,
+}
+
+/// Sliding window of canonical blocks and the attestations seen for them
+#[derive(Debug)]
+pub struct PerformanceTracker {
+    /// Canonical blocks kept
+    window: u64,
+
+    /// Canonical blocks by height
+    blocks: BTreeMap<u64, BlockRecord>,
+
+    /// Attested hash of each validator, by height
+    attestations: BTreeMap<u64, HashMap<Address, B256>>,
+}
+
+impl PerformanceTracker {
+    /// Create a tracker over the last `window` canonical blocks
+    pub fn new(window: u64) -> Self {
+        Self { window: window.max(1), blocks: BTreeMap::new(), attestations: BTreeMap::new() }
+    }
+
+    /// Record a canonical block
+    ///
+    /// Records at or above `number` belong to a replaced fork and are
+    /// dropped. Returns the seconds since the parent block, if recorded.
+    pub fn observe_block(
+        &mut self,
+        number: u64,
+        hash: B256,
+        producer: Address,
+        scheduled: Option<Address>,
+        timestamp: u64,
+    ) -> Option<u64> {
+        let _replaced = self.blocks.split_off(&number);
+        let latency = number
+            .checked_sub(1)
+            .and_then(|parent| self.blocks.get(&parent))
+            .map(|parent| timestamp.saturating_sub(parent.timestamp));
+        self.blocks.insert(number, BlockRecord { hash, producer, scheduled, timestamp });
+
+        let start = self.window_start(number);
+        self.blocks = self.blocks.split_off(&start);
+        self.attestations = self.attestations.split_off(&start);
+        latency
+    }
+
+    /// Record a verified attestation
+    ///
+    /// Attestations may arrive before their block; they are matched against
+    /// the canonical hash when performance is computed.
+    pub fn observe_attestation(&mut self, block_number: u64, block_hash: B256, validator: Address) {
+        if self.head().is_some_and(|head| block_number < self.window_start(head)) {
+            return;
+        }
+        self.attestations.entry(block_number).or_default().insert(validator, block_hash);
+    }
+
+    /// Highest canonical block recorded
+    pub fn head(&self) -> Option<u64> {
+        self.blocks.keys().next_back().copied()
+    }
+
+    /// Validators that produced, were scheduled for or attested a block in
+    /// the window
+    pub fn validators(&self) -> BTreeSet<Address> {
+        let mut validators = BTreeSet::new();
+        for block in self.blocks.values() {
+            validators.insert(block.producer);
+            validators.extend(block.scheduled);
+        }
+        for attesters in self.attestations.values() {
+            validators.extend(attesters.keys().copied());
+        }
+        validators
+    }
+
+    /// Performance of `validator` over the window, `None` before any block
+    pub fn performance(&self, validator: Address) -> Option<ValidatorPerformance> {
+        let (&window_start, _) = self.blocks.first_key_value()?;
+        let (&window_end, _) = self.blocks.last_key_value()?;
+
+        let mut performance = ValidatorPerformance {
+            validator,
+            window_start,
+            window_end,
+            scheduled_slots: 0,
+            missed_slots: 0,
+            blocks_produced: 0,
+            uptime: FULL_SCORE,
+            missed_streak: 0,
+            longest_missed_streak: 0,
+            blocks_attested: 0,
+            attestation_participation: 0,
+            avg_proposal_latency_ms: 0,
+            max_proposal_latency_secs: 0,
+        };
+        let (mut latency_sum, mut latency_count) = (0u64, 0u64);
+
+        let mut parent: Option<(u64, &BlockRecord)> = None;
+        for (&number, block) in &self.blocks {
+            if block.producer == validator {
+                performance.blocks_produced += 1;
+                if let Some((_, parent)) = parent.filter(|(parent_number, _)| *parent_number + 1 == number) {
+                    let latency = block.timestamp.saturating_sub(parent.timestamp);
+                    latency_sum += latency;
+                    latency_count += 1;
+                    performance.max_proposal_latency_secs = performance.max_proposal_latency_secs.max(latency);
+                }
+            }
+
+            if block.scheduled == Some(validator) {
+                performance.scheduled_slots += 1;
+                if block.producer == validator {
+                    performance.missed_streak = 0;
+                } else {
+                    performance.missed_slots += 1;
+                    performance.missed_streak += 1;
+                    performance.longest_missed_streak =
+                        performance.longest_missed_streak.max(performance.missed_streak);
+                }
+            }
+
+            let attested = self
+                .attestations
+                .get(&number)
+                .and_then(|attesters| attesters.get(&validator))
+                .is_some_and(|hash| *hash == block.hash);
+            if attested {
+                performance.blocks_attested += 1;
+            }
+
+            parent = Some((number, block));
+        }
+
+        let kept = performance.scheduled_slots - performance.missed_slots;
+        performance.uptime = basis_points(kept, performance.scheduled_slots).unwrap_or(FULL_SCORE);
+        performance.attestation_participation =
+            basis_points(performance.blocks_attested, self.blocks.len() as u64).unwrap_or_default();
+        if latency_count > 0 {
+            performance.avg_proposal_latency_ms = latency_sum.saturating_mul(1_000) / latency_count;
+        }
+
+        Some(performance)
+    }
+
+    /// First height kept with `head` as the highest canonical block
+    fn window_start(&self, head: u64) -> u64 {
+        head.saturating_sub(self.window - 1)
+    }
+}
+
+/// `part` of `total` in basis points, `None` if `total` is zero
+fn basis_points(part: u64, total: u64) -> Option<u16> {
+    if total == 0 {
+        return None;
+    }
+    let points = u128::from(part) * u128::from(FULL_SCORE) / u128::from(total);
+    Some(u16::try_from(points).unwrap_or(FULL_SCORE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    #[test]
+    fn test_uptime_and_missed_streaks() {
+        let mut tracker = PerformanceTracker::new(100);
+        // Validator 1 scheduled on every height; 2 takes over 3..=5 and 7
+        let producers = [1, 1, 2, 2, 2, 1, 2, 1];
+        for (i, producer) in producers.into_iter().enumerate() {
+            let number = i as u64 + 1;
+            tracker.observe_block(number, B256::with_last_byte(i as u8), validator(producer), Some(validator(1)), number * 2);
+        }
+
+        let one = tracker.performance(validator(1)).unwrap();
+        assert_eq!((one.scheduled_slots, one.missed_slots, one.blocks_produced), (8, 4, 4));
+        assert_eq!(one.uptime, 5_000);
+        assert_eq!((one.missed_streak, one.longest_missed_streak), (0, 3));
+        assert_eq!((one.window_start, one.window_end), (1, 8));
+
+        // Never scheduled, so nothing missed
+        let two = tracker.performance(validator(2)).unwrap();
+        assert_eq!((two.scheduled_slots, two.blocks_produced, two.uptime), (0, 4, FULL_SCORE));
+        assert_eq!((two.avg_proposal_latency_ms, two.max_proposal_latency_secs), (2_000, 2));
+    }
+
+    #[test]
+    fn test_participation_follows_canonical_hash() {
+        let mut tracker = PerformanceTracker::new(100);
+        for number in 1..=4 {
+            tracker.observe_block(number, B256::with_last_byte(number as u8), validator(1), None, number);
+        }
+
+        tracker.observe_attestation(1, B256::with_last_byte(1), validator(3));
+        tracker.observe_attestation(2, B256::with_last_byte(2), validator(3));
+        // Attested a fork that lost
+        tracker.observe_attestation(3, B256::repeat_byte(0xff), validator(3));
+
+        let three = tracker.performance(validator(3)).unwrap();
+        assert_eq!((three.blocks_attested, three.attestation_participation), (2, 5_000));
+
+        // The fork becomes canonical at 3, dropping the old block 4
+        tracker.observe_block(3, B256::repeat_byte(0xff), validator(2), None, 3);
+        let three = tracker.performance(validator(3)).unwrap();
+        assert_eq!((three.blocks_attested, three.window_end), (3, 3));
+        assert_eq!(three.attestation_participation, FULL_SCORE);
+    }
+
+    #[test]
+    fn test_window_slides() {
+        let mut tracker = PerformanceTracker::new(10);
+        for number in 1..=25 {
+            let producer = if number <= 15 { validator(2) } else { validator(1) };
+            tracker.observe_block(number, B256::ZERO, producer, Some(validator(1)), number);
+        }
+
+        let one = tracker.performance(validator(1)).unwrap();
+        assert_eq!((one.window_start, one.window_end), (16, 25));
+        assert_eq!((one.scheduled_slots, one.missed_slots, one.uptime), (10, 0, FULL_SCORE));
+        assert_eq!(one.longest_missed_streak, 0);
+
+        // Attestations older than the window are ignored
+        tracker.observe_attestation(3, B256::ZERO, validator(1));
+        assert_eq!(tracker.performance(validator(1)).unwrap().blocks_attested, 0);
+        assert!(!tracker.validators().contains(&validator(2)));
+    }
+}
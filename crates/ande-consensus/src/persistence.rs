@@ -0,0 +1,218 @@
+//! Crash-safe persistence of consensus engine state
+//!
+//! The engine snapshots its local state to `data_dir` after every update and
+//! restores it on start, so a restarted sequencer keeps its rotation
+//! position, proposer priorities, uptime counters and epoch history:
+//!
+//! ```text
+//! consensus-state.json
+//! {
+//!   "schemaVersion": 2,
+//!   "checksum": keccak256(snapshot JSON),
+//!   "snapshot": { state, validatorSet, epochs, lastTimeoutCheck, lastSyncedBlock }
+//! }
+//! ```
+//!
+//! Writes go to a temporary file that is fsynced and renamed over the
+//! snapshot, so a crash leaves either the old or the new snapshot. A file
+//! with another schema version or a wrong checksum is rejected.
+
+use crate::{
+    epoch::EpochSnapshot,
+    error::{ConsensusError, Result},
+    types::ConsensusState,
+    validator_set::ValidatorSetSnapshot,
+};
+use alloy_primitives::{keccak256, B256};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Snapshot file name inside `data_dir`
+pub const STATE_FILE: &str = "consensus-state.json";
+
+/// Version of the snapshot layout
+pub const STATE_SCHEMA_VERSION: u32 = 2;
+
+/// Engine state that survives restarts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineSnapshot {
+    /// Consensus state at the last update
+    pub state: ConsensusState,
+
+    /// Validators with priorities and uptime counters
+    pub validator_set: ValidatorSetSnapshot,
+
+    /// Current and completed epochs and rotations
+    pub epochs: EpochSnapshot,
+
+    /// Last block where the timeout was checked
+    pub last_timeout_check: u64,
+
+    /// Last block the contract client synced
+    pub last_synced_block: u64,
+}
+
+/// On-disk envelope of a snapshot
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotFile {
+    /// Layout version
+    schema_version: u32,
+
+    /// Keccak-256 of the JSON-encoded snapshot
+    checksum: B256,
+
+    /// The snapshot itself
+    snapshot: EngineSnapshot,
+}
+
+/// Reads and atomically writes [`EngineSnapshot`]s
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    /// Snapshot file
+    path: PathBuf,
+}
+
+impl StateStore {
+    /// Store keeping its snapshot in `data_dir`
+    pub fn new(data_dir: &Path) -> Self {
+        Self { path: data_dir.join(STATE_FILE) }
+    }
+
+    /// Snapshot file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the snapshot, or `None` if none has been written
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read, has another schema
+    /// version or fails its checksum
+    pub fn load(&self) -> Result<Option<EngineSnapshot>> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let file: SnapshotFile = serde_json::from_slice(&bytes)?;
+        if file.schema_version != STATE_SCHEMA_VERSION {
+            return Err(ConsensusError::StateCorrupted(format!(
+                "{}: schema version {}, expected {STATE_SCHEMA_VERSION}",
+                self.path.display(),
+                file.schema_version
+            )));
+        }
+
+        let checksum = checksum(&file.snapshot)?;
+        if checksum != file.checksum {
+            return Err(ConsensusError::StateCorrupted(format!(
+                "{}: checksum {checksum} does not match {}",
+                self.path.display(),
+                file.checksum
+            )));
+        }
+
+        Ok(Some(file.snapshot))
+    }
+
+    /// Atomically replace the snapshot (write temp file, fsync, rename)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the snapshot cannot be written
+    pub fn save(&self, snapshot: &EngineSnapshot) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = SnapshotFile {
+            schema_version: STATE_SCHEMA_VERSION,
+            checksum: checksum(snapshot)?,
+            snapshot: snapshot.clone(),
+        };
+        let bytes = serde_json::to_vec_pretty(&file)?;
+
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let mut tmp = std::fs::File::create(&tmp_path)?;
+            tmp.write_all(&bytes)?;
+            tmp.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        debug!(path = %self.path.display(), block = snapshot.state.current_block, "Consensus state saved");
+        Ok(())
+    }
+}
+
+/// Checksum of a snapshot's JSON encoding
+fn checksum(snapshot: &EngineSnapshot) -> Result<B256> {
+    Ok(keccak256(serde_json::to_vec(snapshot)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    fn snapshot() -> EngineSnapshot {
+        EngineSnapshot {
+            state: ConsensusState {
+                current_block: 120,
+                current_epoch: 2,
+                current_rotation: 7,
+                current_proposer: Address::repeat_byte(1),
+                active_validators: 3,
+                total_voting_power: 600,
+                bft_threshold: 401,
+                last_update: 1_700_000_000,
+                jailed: true,
+                jail_release_block: 160,
+                jail_blocks_remaining: 40,
+            },
+            validator_set: ValidatorSetSnapshot::default(),
+            epochs: EpochSnapshot::default(),
+            last_timeout_check: 118,
+            last_synced_block: 120,
+        }
+    }
+
+    fn temp_store(name: &str) -> StateStore {
+        let dir = std::env::temp_dir().join(format!("ande-consensus-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        StateStore::new(&dir)
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let store = temp_store("roundtrip");
+        assert_eq!(store.load().unwrap(), None);
+
+        store.save(&snapshot()).unwrap();
+        assert_eq!(store.load().unwrap(), Some(snapshot()));
+        assert!(!store.path().with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_tampered_snapshot_rejected() {
+        let store = temp_store("tampered");
+        store.save(&snapshot()).unwrap();
+
+        let contents = std::fs::read_to_string(store.path()).unwrap();
+        std::fs::write(store.path(), contents.replace("\"current_rotation\": 7", "\"current_rotation\": 8"))
+            .unwrap();
+        assert!(matches!(store.load(), Err(ConsensusError::StateCorrupted(_))));
+
+        let contents = contents.replace("\"schemaVersion\": 2", "\"schemaVersion\": 99");
+        std::fs::write(store.path(), contents).unwrap();
+        assert!(matches!(store.load(), Err(ConsensusError::StateCorrupted(_))));
+    }
+}
@@ -0,0 +1,421 @@
+//! Precomputed proposer schedule for synchronous block import checks
+//!
+//! Reth's header and block validators are synchronous, so they cannot ask
+//! the engine to run proposer selection while importing. Instead the engine
+//! records the validator set of each epoch and replays the deterministic
+//! selection of [`proposer_selection`](crate::proposer_selection) ahead of
+//! the head, keeping the result per height:
+//!
+//! ```text
+//! epochs:    S₁ ─→ set₁      S₂ ─→ set₂        (on-chain set at each epoch start)
+//! rotations:        R+1 ─→ skip one             (forced on the coordinator at R)
+//!              │                │
+//!              ▼ replay         ▼ replay (priorities restart at zero)
+//! windows:   [P(h), P(h+1), ...][P(S₂), P(S₂+1), ...]
+//!                       │
+//! import of block H ──→ proposer_at(H) == sealer of H ?
+//! ```
+//!
+//! The proposer of a height depends only on its epoch's validator set, the
+//! distance from the epoch start and the rotations forced on-chain since,
+//! so the schedule is the same on every node regardless of when it started
+//! or how often it polled the contracts. When any of these inputs changes,
+//! every height derived from it is recomputed.
+
+use crate::{
+    error::{ConsensusError, Result},
+    proposer_selection::ProposerPriorities,
+    types::{ForcedRotation, ValidatorSetUpdate},
+};
+use alloy_primitives::Address;
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// Heights precomputed per window
+pub const DEFAULT_SCHEDULE_WINDOW: u64 = 1024;
+
+/// Windows kept for importing older blocks
+pub const MAX_SCHEDULE_WINDOWS: usize = 16;
+
+/// Epoch validator sets kept
+pub const MAX_SCHEDULE_EPOCHS: usize = 16;
+
+/// Forced rotations kept
+pub const MAX_SCHEDULE_ROTATIONS: usize = 256;
+
+/// Proposer per block height, derived from epoch validator sets
+#[derive(Debug, Clone)]
+pub struct ProposerSchedule {
+    /// Validator set of each epoch, keyed by its first height
+    epochs: BTreeMap<u64, ValidatorSetUpdate>,
+
+    /// Height each forced rotation takes effect at, by rotation number
+    rotations: BTreeMap<u64, u64>,
+
+    /// Scheduled proposers, keyed by the first height of each window
+    windows: BTreeMap<u64, Vec<Address>>,
+
+    /// Next height to replay and its epoch's priorities at that height
+    cursor: Option<(u64, ProposerPriorities)>,
+
+    /// Heights computed per window
+    window: u64,
+}
+
+impl ProposerSchedule {
+    /// Create an empty schedule computing `window` heights at a time
+    pub fn new(window: u64) -> Self {
+        Self {
+            epochs: BTreeMap::new(),
+            rotations: BTreeMap::new(),
+            windows: BTreeMap::new(),
+            cursor: None,
+            window: window.max(1),
+        }
+    }
+
+    /// Record the on-chain validator set of the epoch starting at
+    /// `set.block_number`
+    ///
+    /// A set that differs from the recorded one replaces it, along with the
+    /// same epoch recorded at another start and any epoch out of order with
+    /// it. Heights scheduled from the earliest replaced start on
+    /// are recomputed on the next [`ensure_ahead`](Self::ensure_ahead).
+    pub fn set_epoch(&mut self, set: ValidatorSetUpdate) {
+        let start = set.block_number;
+        if self.epochs.get(&start) == Some(&set) {
+            return;
+        }
+
+        let stale: Vec<u64> = self
+            .epochs
+            .iter()
+            .filter(|(at, recorded)| {
+                // Another start for this epoch, or epochs out of order with it
+                **at != start && (recorded.epoch == set.epoch || (**at > start) != (recorded.epoch > set.epoch))
+            })
+            .map(|(at, _)| *at)
+            .collect();
+        let changed_from = stale.iter().copied().fold(start, u64::min);
+        for at in stale {
+            self.epochs.remove(&at);
+        }
+
+        debug!(
+            epoch = set.epoch,
+            start_block = start,
+            validators = set.validators.len(),
+            "Epoch recorded in proposer schedule"
+        );
+        self.epochs.insert(start, set);
+        while self.epochs.len() > MAX_SCHEDULE_EPOCHS {
+            self.epochs.pop_first();
+        }
+        self.invalidate_from(changed_from);
+    }
+
+    /// Record rotations forced on the coordinator
+    ///
+    /// Each one skips a proposer at the height after the block that forced
+    /// it; heights scheduled from there on are recomputed on the next
+    /// [`ensure_ahead`](Self::ensure_ahead).
+    pub fn record_rotations(&mut self, rotations: impl IntoIterator<Item = ForcedRotation>) {
+        for rotation in rotations {
+            let height = rotation.block_number.saturating_add(1);
+            if self.rotations.insert(rotation.rotation_number, height) == Some(height) {
+                continue;
+            }
+
+            debug!(
+                rotation = rotation.rotation_number,
+                from_block = height,
+                "Forced rotation recorded in proposer schedule"
+            );
+            while self.rotations.len() > MAX_SCHEDULE_ROTATIONS {
+                self.rotations.pop_first();
+            }
+            self.invalidate_from(height);
+        }
+    }
+
+    /// Number of the latest recorded forced rotation, 0 if none
+    pub fn last_rotation(&self) -> u64 {
+        self.rotations.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Schedule until at least half a window lies ahead of `head`
+    ///
+    /// An empty schedule starts one window behind `head`, so recently
+    /// produced blocks can still be checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no epoch covers `head`
+    pub fn ensure_ahead(&mut self, head: u64) -> Result<()> {
+        let target = head.saturating_add(self.window / 2);
+        let mut from = match self.end() {
+            // Heights far behind the head are skipped, not stored
+            Some(end) => end.max(head.saturating_sub(self.window)),
+            None => {
+                let epoch_start = self.epoch_start(head).ok_or_else(|| {
+                    ConsensusError::Internal(format!("No validator set known for block {head}"))
+                })?;
+                head.saturating_sub(self.window).max(epoch_start)
+            }
+        };
+
+        while from <= target {
+            let scheduled = self.schedule_window(from);
+            if scheduled == 0 {
+                break;
+            }
+            from += scheduled;
+        }
+        Ok(())
+    }
+
+    /// Scheduled proposer of `block_number`, if it has been computed
+    pub fn proposer_at(&self, block_number: u64) -> Option<Address> {
+        let (start, proposers) = self.windows.range(..=block_number).next_back()?;
+        let offset = usize::try_from(block_number - start).ok()?;
+        proposers.get(offset).copied()
+    }
+
+    /// Check `proposer` against the schedule
+    ///
+    /// Heights outside the schedule are not accepted: nobody is known to be
+    /// allowed to produce them yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::InvalidProposer`] on a mismatch and
+    /// [`ConsensusError::UnscheduledHeight`] outside the schedule
+    pub fn verify(&self, block_number: u64, proposer: Address) -> Result<()> {
+        match self.proposer_at(block_number) {
+            Some(expected) if expected != proposer => {
+                Err(ConsensusError::InvalidProposer { expected, actual: proposer })
+            }
+            Some(_) => Ok(()),
+            None => Err(ConsensusError::UnscheduledHeight(block_number)),
+        }
+    }
+
+    /// First height still scheduled; older heights were pruned or predate
+    /// the first recorded epoch
+    pub fn start(&self) -> Option<u64> {
+        self.windows.keys().next().copied()
+    }
+
+    /// First height not yet scheduled
+    pub fn end(&self) -> Option<u64> {
+        self.windows
+            .iter()
+            .next_back()
+            .map(|(start, proposers)| start + proposers.len() as u64)
+    }
+
+    /// Whether nothing has been scheduled
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Whether the validator set of `epoch` has been recorded
+    pub fn has_epoch(&self, epoch: u64) -> bool {
+        self.epochs.values().any(|set| set.epoch == epoch)
+    }
+
+    /// Recorded validator set of `epoch`
+    pub fn epoch_set(&self, epoch: u64) -> Option<&ValidatorSetUpdate> {
+        self.epochs.values().find(|set| set.epoch == epoch)
+    }
+
+    /// First height of the epoch containing `block_number`
+    pub fn epoch_start(&self, block_number: u64) -> Option<u64> {
+        self.epochs.range(..=block_number).next_back().map(|(start, _)| *start)
+    }
+
+    /// Forget every height scheduled from `start` on
+    fn invalidate_from(&mut self, start: u64) {
+        self.windows.retain(|window_start, _| *window_start < start);
+        if let Some((window_start, proposers)) = self.windows.iter_mut().next_back() {
+            proposers.truncate(usize::try_from(start - window_start).unwrap_or(usize::MAX));
+        }
+        if self.cursor.as_ref().is_some_and(|(height, _)| *height > start) {
+            self.cursor = None;
+        }
+    }
+
+    /// Proposers skipped at each height of `from..until` by forced rotations
+    fn skips(&self, from: u64, until: u64) -> BTreeMap<u64, u64> {
+        let mut skips = BTreeMap::new();
+        for height in self.rotations.values().filter(|height| (from..until).contains(*height)) {
+            *skips.entry(*height).or_insert(0) += 1;
+        }
+        skips
+    }
+
+    /// Schedule up to one window from `from`, stopping at the next epoch
+    ///
+    /// Returns the number of heights scheduled.
+    fn schedule_window(&mut self, from: u64) -> u64 {
+        let Some((&epoch_start, set)) = self.epochs.range(..=from).next_back() else {
+            return 0;
+        };
+        let epoch_end = self
+            .epochs
+            .range(from + 1..)
+            .next()
+            .map_or(u64::MAX, |(start, _)| *start);
+        let until = from.saturating_add(self.window).min(epoch_end);
+
+        // Resume the replay, or restart it from the epoch start
+        let mut priorities = match self.cursor.take() {
+            Some((height, priorities)) if height == from && from > epoch_start => priorities,
+            _ => {
+                let mut priorities = ProposerPriorities::new(set);
+                priorities.advance(from - epoch_start);
+                priorities.advance(self.skips(epoch_start, from).values().sum());
+                priorities
+            }
+        };
+        if priorities.is_empty() {
+            return 0;
+        }
+
+        // A forced rotation passes over the proposer due at its height
+        let skips = self.skips(from, until);
+        let proposers: Vec<Address> = (from..until)
+            .filter_map(|height| {
+                priorities.advance(skips.get(&height).copied().unwrap_or(0));
+                priorities.next_proposer()
+            })
+            .collect();
+        let scheduled = proposers.len() as u64;
+
+        self.windows.insert(from, proposers);
+        while self.windows.len() > MAX_SCHEDULE_WINDOWS {
+            self.windows.pop_first();
+        }
+        self.cursor = Some((until, priorities));
+
+        debug!(from, until, "Proposer schedule extended");
+        scheduled
+    }
+}
+
+impl Default for ProposerSchedule {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCHEDULE_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proposer_selection::proposer_for_height;
+
+    fn epoch(epoch: u64, validators: &[(u8, u64)], start: u64) -> ValidatorSetUpdate {
+        ValidatorSetUpdate {
+            epoch,
+            validators: validators.iter().map(|(byte, _)| Address::repeat_byte(*byte)).collect(),
+            powers: validators.iter().map(|(_, power)| *power).collect(),
+            total_power: validators.iter().map(|(_, power)| power).sum(),
+            block_number: start,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_schedule_matches_pure_selection() {
+        let set = epoch(1, &[(1, 100), (2, 300)], 10);
+        let mut schedule = ProposerSchedule::new(8);
+        schedule.set_epoch(set.clone());
+        schedule.ensure_ahead(10).unwrap();
+
+        // Extension continues the same replay across windows
+        schedule.ensure_ahead(40).unwrap();
+        for height in 32..44 {
+            assert_eq!(schedule.proposer_at(height), proposer_for_height(&set, height));
+        }
+        assert_eq!(schedule.proposer_at(9), None);
+
+        // A node starting later computes the same proposers
+        let mut late = ProposerSchedule::new(8);
+        late.set_epoch(set);
+        late.ensure_ahead(40).unwrap();
+        for height in 32..44 {
+            assert_eq!(late.proposer_at(height), schedule.proposer_at(height));
+        }
+    }
+
+    #[test]
+    fn test_new_epoch_restarts_priorities() {
+        let first = epoch(1, &[(1, 100), (2, 100)], 0);
+        let second = epoch(2, &[(3, 100), (4, 100)], 20);
+        let mut schedule = ProposerSchedule::new(8);
+        schedule.set_epoch(first.clone());
+        schedule.ensure_ahead(16).unwrap();
+
+        // Recording the next epoch late replaces its scheduled heights
+        schedule.set_epoch(second.clone());
+        schedule.ensure_ahead(24).unwrap();
+
+        assert_eq!(schedule.proposer_at(19), proposer_for_height(&first, 19));
+        assert_eq!(schedule.proposer_at(20), Some(Address::repeat_byte(3)));
+        assert_eq!(schedule.proposer_at(21), proposer_for_height(&second, 21));
+    }
+
+    #[test]
+    fn test_changed_inputs_reschedule() {
+        let set = epoch(1, &[(1, 100), (2, 100), (3, 100)], 0);
+        let mut schedule = ProposerSchedule::new(8);
+        schedule.set_epoch(set.clone());
+        schedule.ensure_ahead(20).unwrap();
+        let before: Vec<_> = (20..30).map(|height| schedule.proposer_at(height)).collect();
+
+        // A rotation forced in block 22 skips one proposer at 23
+        schedule.record_rotations([ForcedRotation { rotation_number: 1, block_number: 22 }]);
+        schedule.ensure_ahead(20).unwrap();
+        assert_eq!(schedule.last_rotation(), 1);
+        assert_eq!(schedule.proposer_at(22), before[2]);
+        let mut replay = ProposerPriorities::new(&set);
+        replay.advance(24);
+        assert_eq!(schedule.proposer_at(23), replay.next_proposer());
+
+        // A node starting later derives the same heights from the same inputs
+        let mut late = ProposerSchedule::new(8);
+        late.set_epoch(set);
+        late.record_rotations([ForcedRotation { rotation_number: 1, block_number: 22 }]);
+        late.ensure_ahead(28).unwrap();
+        for height in 20..30 {
+            assert_eq!(late.proposer_at(height), schedule.proposer_at(height));
+        }
+
+        // The epoch moving to another start block replaces the old copy
+        schedule.set_epoch(epoch(1, &[(4, 100)], 5));
+        schedule.ensure_ahead(20).unwrap();
+        assert_eq!(schedule.epoch_start(3), None);
+        assert_eq!(schedule.proposer_at(23), Some(Address::repeat_byte(4)));
+    }
+
+    #[test]
+    fn test_verify_proposer() {
+        let mut schedule = ProposerSchedule::new(4);
+        schedule.set_epoch(epoch(1, &[(1, 100)], 10));
+        schedule.ensure_ahead(10).unwrap();
+        assert_eq!(schedule.start(), Some(10));
+
+        assert!(schedule.verify(10, Address::repeat_byte(1)).is_ok());
+        assert!(matches!(
+            schedule.verify(11, Address::repeat_byte(2)),
+            Err(ConsensusError::InvalidProposer { .. })
+        ));
+
+        // Nobody may produce heights outside the schedule
+        assert!(matches!(
+            schedule.verify(100, Address::repeat_byte(1)),
+            Err(ConsensusError::UnscheduledHeight(100))
+        ));
+        assert!(matches!(schedule.verify(9, Address::repeat_byte(1)), Err(ConsensusError::UnscheduledHeight(9))));
+    }
+}
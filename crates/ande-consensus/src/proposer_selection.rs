@@ -0,0 +1,200 @@
+//! Deterministic CometBFT proposer selection
+//!
+//! The proposer of a height is a pure function of the validator set at the
+//! start of its epoch and the distance from that start, so every full node
+//! replaying from genesis arrives at the same schedule:
+//!
+//! ```text
+//! epoch start S: priorities = 0
+//!
+//! for each height S, S+1, ...:
+//!   1. rescale   if max - min > 2 × total, divide all by ⌈diff / (2 × total)⌉
+//!   2. center    subtract the (floored) average priority
+//!   3. increment every priority += power
+//!   4. select    highest priority, ties to the lower address
+//!   5. charge    proposer priority -= total
+//! ```
+//!
+//! This follows CometBFT's `IncrementProposerPriority(1)` per height,
+//! including its clipping arithmetic, so a validator set with equal
+//! priorities at the epoch start (CometBFT's state after adding a fresh set)
+//! yields the same sequence as CometBFT itself.
+
+use crate::types::ValidatorSetUpdate;
+use alloy_primitives::Address;
+
+/// Spread of priorities allowed before rescaling, in multiples of total power
+pub const PRIORITY_WINDOW_SIZE_FACTOR: i64 = 2;
+
+/// Proposer priorities of one validator set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposerPriorities {
+    /// `(validator, voting power, priority)` of validators with power
+    validators: Vec<(Address, i64, i64)>,
+
+    /// Total voting power
+    total_power: i64,
+}
+
+impl ProposerPriorities {
+    /// Priorities at the start of an epoch with `set`
+    ///
+    /// Validators without voting power never propose and are left out.
+    pub fn new(set: &ValidatorSetUpdate) -> Self {
+        let validators: Vec<_> = set
+            .validators
+            .iter()
+            .zip(&set.powers)
+            .filter(|(_, power)| **power > 0)
+            .map(|(validator, power)| (*validator, i64::try_from(*power).unwrap_or(i64::MAX), 0))
+            .collect();
+        let total_power = validators.iter().fold(0i64, |total, (_, power, _)| total.saturating_add(*power));
+
+        Self { validators, total_power }
+    }
+
+    /// Whether no validator can propose
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Advance one height and return its proposer
+    pub fn next_proposer(&mut self) -> Option<Address> {
+        if self.validators.is_empty() {
+            return None;
+        }
+
+        self.rescale(PRIORITY_WINDOW_SIZE_FACTOR.saturating_mul(self.total_power));
+        self.center();
+
+        for (_, power, priority) in &mut self.validators {
+            *priority = priority.saturating_add(*power);
+        }
+
+        let proposer = self
+            .validators
+            .iter_mut()
+            .reduce(|best, candidate| {
+                if candidate.2 > best.2 || (candidate.2 == best.2 && candidate.0 < best.0) {
+                    candidate
+                } else {
+                    best
+                }
+            })?;
+        proposer.2 = proposer.2.saturating_sub(self.total_power);
+
+        Some(proposer.0)
+    }
+
+    /// Skip `heights` heights without recording their proposers
+    pub fn advance(&mut self, heights: u64) {
+        for _ in 0..heights {
+            self.next_proposer();
+        }
+    }
+
+    /// Current priority of `validator`
+    pub fn priority(&self, validator: &Address) -> Option<i64> {
+        self.validators.iter().find(|(addr, _, _)| addr == validator).map(|(_, _, priority)| *priority)
+    }
+
+    /// Scale priorities down so their spread stays within `max_diff`
+    fn rescale(&mut self, max_diff: i64) {
+        if max_diff <= 0 {
+            return;
+        }
+
+        let (min, max) = self
+            .validators
+            .iter()
+            .fold((i64::MAX, i64::MIN), |(min, max), (_, _, p)| (min.min(*p), max.max(*p)));
+        let diff = i128::from(max) - i128::from(min);
+        let max_diff = i128::from(max_diff);
+        if diff <= max_diff {
+            return;
+        }
+
+        // Truncating division, as Go's
+        let ratio = (diff + max_diff - 1) / max_diff;
+        for (_, _, priority) in &mut self.validators {
+            *priority = (i128::from(*priority) / ratio) as i64;
+        }
+    }
+
+    /// Shift priorities so they average to zero
+    fn center(&mut self) {
+        let sum: i128 = self.validators.iter().map(|(_, _, p)| i128::from(*p)).sum();
+        let average = sum.div_euclid(self.validators.len() as i128) as i64;
+        for (_, _, priority) in &mut self.validators {
+            *priority = priority.saturating_sub(average);
+        }
+    }
+}
+
+/// Proposer of `block_number` in the epoch of `set`
+///
+/// `set.block_number` is the epoch's first height. Returns `None` for
+/// heights before the epoch or when no validator has voting power.
+pub fn proposer_for_height(set: &ValidatorSetUpdate, block_number: u64) -> Option<Address> {
+    let distance = block_number.checked_sub(set.block_number)?;
+    let mut priorities = ProposerPriorities::new(set);
+    priorities.advance(distance);
+    priorities.next_proposer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(validators: &[(u8, u64)], start: u64) -> ValidatorSetUpdate {
+        ValidatorSetUpdate {
+            epoch: 1,
+            validators: validators.iter().map(|(byte, _)| Address::repeat_byte(*byte)).collect(),
+            powers: validators.iter().map(|(_, power)| *power).collect(),
+            total_power: validators.iter().map(|(_, power)| power).sum(),
+            block_number: start,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_matches_cometbft_sequence() {
+        // CometBFT's TestProposerSelection1 with bar < baz < foo
+        let (bar, baz, foo) = (1, 2, 3);
+        let mut priorities = ProposerPriorities::new(&set(&[(foo, 1000), (bar, 300), (baz, 330)], 0));
+
+        let expected = [foo, baz, foo, bar, foo, foo, baz, foo, bar, foo, foo, baz, foo, foo, bar];
+        for byte in expected {
+            assert_eq!(priorities.next_proposer(), Some(Address::repeat_byte(byte)));
+        }
+    }
+
+    #[test]
+    fn test_equal_power_round_robin_by_address() {
+        let mut priorities = ProposerPriorities::new(&set(&[(3, 10), (1, 10), (2, 10)], 0));
+        let sequence: Vec<_> = (0..6).map(|_| priorities.next_proposer().unwrap()).collect();
+
+        let expected: Vec<_> = [1, 2, 3, 1, 2, 3].into_iter().map(Address::repeat_byte).collect();
+        assert_eq!(sequence, expected);
+    }
+
+    #[test]
+    fn test_pure_function_of_epoch_and_height() {
+        let epoch = set(&[(1, 100), (2, 300), (3, 50), (4, 0)], 1_000);
+        let mut priorities = ProposerPriorities::new(&epoch);
+
+        assert_eq!(proposer_for_height(&epoch, 999), None);
+        for height in 1_000..1_200 {
+            let proposer = priorities.next_proposer();
+            assert_eq!(proposer_for_height(&epoch, height), proposer);
+            assert_ne!(proposer, Some(Address::repeat_byte(4)));
+        }
+
+        // Centering and rescaling keep priorities within the window
+        let total = 450;
+        for byte in 1..=3 {
+            let priority = priorities.priority(&Address::repeat_byte(byte)).unwrap();
+            assert!(priority.abs() <= PRIORITY_WINDOW_SIZE_FACTOR * total);
+        }
+    }
+}
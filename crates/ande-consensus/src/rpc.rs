@@ -0,0 +1,154 @@
+//! Attestation RPC
+//!
+//! `ande_*` methods validators use to exchange attestations and that
+//! clients use to read finality:
+//!
+//! - `ande_submitAttestation`: gossip endpoint for other validators
+//! - `ande_submitQuorumCertificate`: import a certificate formed elsewhere
+//! - `ande_submitProposal`: signed proposal of a block, checked for misbehaviour
+//! - `ande_submitEvidence`: slashing evidence found by another validator
+//! - `ande_getQuorumCertificate`: certificate of a finalized block
+//! - `ande_getFinalizedBlock`: certificate of the highest finalized block
+//! - `ande_getValidatorPerformance`: uptime, missed slots, participation and
+//!   latency computed from canonical blocks
+//! - `ande_submitValidatorSetSignature`: handover to the next epoch's set,
+//!   gossiped by the previous epoch's validators
+//! - `ande_getFinalityProof`: light client proof of a finalized block, given
+//!   its RLP-encoded header (as returned by `debug_getRawHeader`)
+//! - `ande_getValidatorSetChangeProof`: light client proof of the handover to
+//!   an epoch's validator set
+//! - `ande_getLightClientValidatorSet`: validator set of an epoch as light
+//!   clients hash it, to bootstrap trust
+
+use crate::{
+    attestation::QuorumCertificate,
+    engine::ConsensusEngine,
+    error::ConsensusError,
+    evidence::Evidence,
+    finality::ValidatorSetSignature,
+    performance::ValidatorPerformance,
+    types::{AttestationInfo, BlockProposal},
+};
+use alloy_primitives::{Address, Bytes};
+use ande_light_client::{FinalityProof, ValidatorSet as LightValidatorSet, ValidatorSetChangeProof};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned},
+};
+use std::sync::Arc;
+
+/// Attestation RPC API
+#[rpc(server, namespace = "ande")]
+pub trait AttestationApi {
+    /// Collect an attestation; returns the certificate if it completed one
+    #[method(name = "submitAttestation")]
+    fn submit_attestation(&self, attestation: AttestationInfo) -> RpcResult<Option<QuorumCertificate>>;
+
+    /// Verify and store a quorum certificate
+    #[method(name = "submitQuorumCertificate")]
+    fn submit_quorum_certificate(&self, certificate: QuorumCertificate) -> RpcResult<()>;
+
+    /// Check a signed block proposal for equivocation and schedule violations
+    #[method(name = "submitProposal")]
+    fn submit_proposal(&self, proposal: BlockProposal) -> RpcResult<()>;
+
+    /// Verify and queue slashing evidence
+    #[method(name = "submitEvidence")]
+    fn submit_evidence(&self, evidence: Evidence) -> RpcResult<()>;
+
+    /// Quorum certificate of a finalized block
+    #[method(name = "getQuorumCertificate")]
+    fn get_quorum_certificate(&self, block_number: u64) -> RpcResult<Option<QuorumCertificate>>;
+
+    /// Quorum certificate of the highest finalized block
+    #[method(name = "getFinalizedBlock")]
+    fn get_finalized_block(&self) -> RpcResult<Option<QuorumCertificate>>;
+
+    /// Performance of `validator`, or of every known validator if omitted
+    #[method(name = "getValidatorPerformance")]
+    fn get_validator_performance(&self, validator: Option<Address>) -> RpcResult<Vec<ValidatorPerformance>>;
+
+    /// Collect a validator set handover signature
+    #[method(name = "submitValidatorSetSignature")]
+    fn submit_validator_set_signature(&self, signature: ValidatorSetSignature) -> RpcResult<()>;
+
+    /// Finality proof of the block with RLP-encoded `header`, if finalized
+    #[method(name = "getFinalityProof")]
+    fn get_finality_proof(&self, header: Bytes) -> RpcResult<Option<FinalityProof>>;
+
+    /// Proof of the handover to `epoch`'s validator set, once complete
+    #[method(name = "getValidatorSetChangeProof")]
+    fn get_validator_set_change_proof(&self, epoch: u64) -> RpcResult<Option<ValidatorSetChangeProof>>;
+
+    /// Validator set light clients trust for `epoch`
+    #[method(name = "getLightClientValidatorSet")]
+    fn get_light_client_validator_set(&self, epoch: u64) -> RpcResult<Option<LightValidatorSet>>;
+}
+
+/// Implementation of [`AttestationApiServer`]
+pub struct AttestationApiImpl {
+    /// Engine collecting attestations
+    engine: Arc<ConsensusEngine>,
+}
+
+impl AttestationApiImpl {
+    /// Create the API over `engine`
+    pub const fn new(engine: Arc<ConsensusEngine>) -> Self {
+        Self { engine }
+    }
+}
+
+impl AttestationApiServer for AttestationApiImpl {
+    fn submit_attestation(&self, attestation: AttestationInfo) -> RpcResult<Option<QuorumCertificate>> {
+        self.engine.submit_attestation(attestation).map_err(invalid_params)
+    }
+
+    fn submit_quorum_certificate(&self, certificate: QuorumCertificate) -> RpcResult<()> {
+        self.engine.import_quorum_certificate(certificate).map_err(invalid_params)
+    }
+
+    fn submit_proposal(&self, proposal: BlockProposal) -> RpcResult<()> {
+        self.engine.submit_proposal(&proposal).map_err(invalid_params)
+    }
+
+    fn submit_evidence(&self, evidence: Evidence) -> RpcResult<()> {
+        self.engine.submit_evidence(evidence).map_err(invalid_params)
+    }
+
+    fn get_quorum_certificate(&self, block_number: u64) -> RpcResult<Option<QuorumCertificate>> {
+        Ok(self.engine.quorum_certificate(block_number))
+    }
+
+    fn get_finalized_block(&self) -> RpcResult<Option<QuorumCertificate>> {
+        Ok(self.engine.latest_finalized())
+    }
+
+    fn get_validator_performance(&self, validator: Option<Address>) -> RpcResult<Vec<ValidatorPerformance>> {
+        Ok(match validator {
+            Some(validator) => self.engine.validator_performance(validator).into_iter().collect(),
+            None => self.engine.performance_report(),
+        })
+    }
+
+    fn submit_validator_set_signature(&self, signature: ValidatorSetSignature) -> RpcResult<()> {
+        self.engine.submit_validator_set_signature(signature).map_err(invalid_params)
+    }
+
+    fn get_finality_proof(&self, header: Bytes) -> RpcResult<Option<FinalityProof>> {
+        self.engine.finality_proof(header).map_err(invalid_params)
+    }
+
+    fn get_validator_set_change_proof(&self, epoch: u64) -> RpcResult<Option<ValidatorSetChangeProof>> {
+        Ok(self.engine.validator_set_change_proof(epoch))
+    }
+
+    fn get_light_client_validator_set(&self, epoch: u64) -> RpcResult<Option<LightValidatorSet>> {
+        Ok(self.engine.light_client_validator_set(epoch))
+    }
+}
+
+/// Map a consensus error to an invalid-params RPC error
+fn invalid_params(err: ConsensusError) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>)
+}
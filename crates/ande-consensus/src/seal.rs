@@ -0,0 +1,120 @@
+//! Proposer seals carried in block headers
+//!
+//! The beneficiary of a header is just a field the producer fills in, so
+//! the proposer also signs the block and the signature travels in the last
+//! 65 bytes of `extra_data`, after at most 32 bytes of vanity:
+//!
+//! ```text
+//! extra_data  = vanity (≤ 32 bytes) ‖ r ‖ s ‖ v (65 bytes)
+//!
+//! seal hash   = keccak(rlp(header with extra_data = vanity))
+//! seal digest = keccak("ANDE_SEAL_V1" ‖ uint64 chainId ‖ uint64 number ‖ seal hash)
+//! ```
+//!
+//! The block hash covers the seal, so the signed hash leaves it out. On
+//! import the sealer is recovered and must be both the beneficiary and the
+//! proposer scheduled for the height.
+
+use crate::{
+    attestation::{recover_signer, signing_digest, signing_message},
+    error::{ConsensusError, Result},
+};
+use alloy::consensus::Header;
+use alloy_primitives::{Address, Bytes, Signature, B256};
+
+/// Domain separator of the seal digest
+const SEAL_DOMAIN: &[u8] = b"ANDE_SEAL_V1";
+
+/// Bytes of `extra_data` kept as vanity in front of the seal
+pub const EXTRA_VANITY: usize = 32;
+
+/// Bytes of the proposer signature at the end of `extra_data`
+pub const EXTRA_SEAL: usize = 65;
+
+/// Largest `extra_data` of a sealed header
+pub const MAX_SEALED_EXTRA_DATA: usize = EXTRA_VANITY + EXTRA_SEAL;
+
+/// Digest a proposer signs to seal a header whose seal hash is `seal_hash`
+pub fn seal_digest(chain_id: u64, block_number: u64, seal_hash: B256) -> B256 {
+    signing_digest(SEAL_DOMAIN, chain_id, block_number, seal_hash)
+}
+
+/// Hash of `header` with `extra_data` replaced by `vanity`
+fn seal_hash(header: &Header, vanity: &[u8]) -> B256 {
+    let mut unsealed = header.clone();
+    unsealed.extra_data = Bytes::copy_from_slice(vanity);
+    unsealed.hash_slow()
+}
+
+/// Up to [`EXTRA_VANITY`] leading bytes of `extra_data`, kept by the seal
+fn vanity(header: &Header) -> &[u8] {
+    &header.extra_data[..header.extra_data.len().min(EXTRA_VANITY)]
+}
+
+/// Preimage of the seal digest of `header`, as handed to the signing backend
+pub fn seal_message(chain_id: u64, header: &Header) -> Vec<u8> {
+    signing_message(SEAL_DOMAIN, chain_id, header.number, seal_hash(header, vanity(header)))
+}
+
+/// Put `signature`, made over [`seal_message`], after the vanity of `header`
+pub fn apply_seal(header: &mut Header, signature: &Signature) {
+    let mut extra_data = Vec::with_capacity(MAX_SEALED_EXTRA_DATA);
+    extra_data.extend_from_slice(vanity(header));
+    extra_data.extend_from_slice(&signature.as_bytes());
+    header.extra_data = extra_data.into();
+}
+
+/// Address that sealed `header`
+///
+/// # Errors
+///
+/// Returns [`ConsensusError::InvalidSeal`] if `extra_data` holds no seal or
+/// the seal does not recover
+pub fn recover_sealer(chain_id: u64, header: &Header) -> Result<Address> {
+    let extra_data = &header.extra_data;
+    if extra_data.len() < EXTRA_SEAL || extra_data.len() > MAX_SEALED_EXTRA_DATA {
+        return Err(ConsensusError::InvalidSeal(format!(
+            "extra data of {} bytes holds no proposer seal",
+            extra_data.len()
+        )));
+    }
+
+    let (vanity, seal) = extra_data.split_at(extra_data.len() - EXTRA_SEAL);
+    recover_signer(seal_digest(chain_id, header.number, seal_hash(header, vanity)), seal)
+        .map_err(|_| ConsensusError::InvalidSeal("proposer seal does not recover".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{LocalKey, SigningBackend};
+
+    const CHAIN_ID: u64 = 6174;
+
+    #[tokio::test]
+    async fn test_seal_recovers_proposer() {
+        let key = LocalKey::from_bytes(&[7; 32]).unwrap();
+        let mut header = Header {
+            number: 42,
+            beneficiary: Address::repeat_byte(7),
+            extra_data: Bytes::from(vec![0xaa; 40]),
+            ..Header::default()
+        };
+        let signature = key.sign_message(&seal_message(CHAIN_ID, &header)).await.unwrap();
+        apply_seal(&mut header, &signature);
+
+        assert_eq!(header.extra_data.len(), MAX_SEALED_EXTRA_DATA);
+        assert_eq!(recover_sealer(CHAIN_ID, &header).unwrap(), key.address());
+
+        // Another chain or a changed field recovers someone else
+        assert_ne!(recover_sealer(1, &header).unwrap(), key.address());
+        header.gas_used += 1;
+        assert_ne!(recover_sealer(CHAIN_ID, &header).unwrap(), key.address());
+    }
+
+    #[test]
+    fn test_unsealed_header_rejected() {
+        let header = Header { number: 1, extra_data: Bytes::from_static(b"reth/v1.8.2"), ..Header::default() };
+        assert!(matches!(recover_sealer(CHAIN_ID, &header), Err(ConsensusError::InvalidSeal(_))));
+    }
+}
@@ -0,0 +1,475 @@
+//! Validator signing keys
+//!
+//! Attestations and proposals are signed through a [`SigningBackend`], so
+//! the key can live in a plain key file, an encrypted keystore or a remote
+//! signer:
+//!
+//! ```text
+//!                                          ┌─ LocalKey ←── hex key file (PRIVATE_KEY_PATH)
+//! AttestationSigner ──→ SigningBackend ────┤            ←── Web3 Secret Storage keystore
+//!   slashing protection                    │                 (KEYSTORE_PATH + KEYSTORE_PASSWORD_FILE)
+//!   checked and persisted first            └─ Web3Signer ──HTTP──→ POST /api/v1/eth1/sign/{address}
+//! ```
+//!
+//! Backends are handed the message and sign its `keccak256` hash with no
+//! message prefix, which is what Web3Signer's eth1 endpoint does, so the
+//! signatures verify on-chain as slashing evidence. A remote signature is
+//! only accepted if it recovers to the configured address. Contract
+//! transactions (evidence, unjail, coordinator reports) still need the key
+//! in process and are disabled with a remote signer.
+
+use crate::{
+    config::{ConsensusConfig, SignerKind},
+    error::{ConsensusError, Result},
+};
+use alloy::signers::local::PrivateKeySigner;
+use alloy_primitives::{hex, keccak256, Address, Bytes, Signature, B256};
+use async_trait::async_trait;
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::info;
+
+/// Web3Signer endpoint signing with the key of an address
+pub const WEB3SIGNER_SIGN_PATH: &str = "/api/v1/eth1/sign/";
+
+/// Web3Signer endpoint listing the keys it holds
+pub const WEB3SIGNER_KEYS_PATH: &str = "/api/v1/eth1/publicKeys";
+
+/// Web3Signer health endpoint
+pub const WEB3SIGNER_UPCHECK_PATH: &str = "/upcheck";
+
+/// Timeout of a single remote signer request
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Holds or reaches a validator key
+#[async_trait]
+pub trait SigningBackend: Send + Sync + std::fmt::Debug {
+    /// Address of the key
+    fn address(&self) -> Address;
+
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    /// Sign `keccak256(message)`, without any message prefix
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the key cannot sign or the signer is unreachable
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature>;
+
+    /// Raw secret key, if it is held in process
+    fn secret_bytes(&self) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+/// secp256k1 key held in process
+pub struct LocalKey {
+    /// Signing key
+    key: SigningKey,
+
+    /// Address of the key
+    address: Address,
+}
+
+impl std::fmt::Debug for LocalKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKey").field("address", &self.address).finish_non_exhaustive()
+    }
+}
+
+impl LocalKey {
+    /// Key from a raw 32-byte secret
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the secret is not a valid secp256k1 scalar
+    pub fn from_bytes(secret: &[u8]) -> Result<Self> {
+        let key = SigningKey::from_slice(secret)
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid signing key: {e}")))?;
+        let address = Address::from_private_key(&key);
+        Ok(Self { key, address })
+    }
+
+    /// Key from a file holding the hex-encoded secret
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or does not hold a key
+    pub fn from_key_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let secret = hex::decode(contents.trim())
+            .map_err(|e| ConsensusError::ConfigError(format!("Invalid key file {}: {e}", path.display())))?;
+        Self::from_bytes(&secret)
+    }
+
+    /// Key from a Web3 Secret Storage (v3) keystore encrypted with `password`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the keystore cannot be read or decrypted
+    pub fn from_keystore(path: &Path, password: impl AsRef<[u8]>) -> Result<Self> {
+        let signer = PrivateKeySigner::decrypt_keystore(path, password)
+            .map_err(|e| ConsensusError::ConfigError(format!("Cannot decrypt keystore {}: {e}", path.display())))?;
+        Self::from_bytes(&signer.credential().to_bytes())
+    }
+
+    /// Key from a keystore whose password is the first line of `password_file`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if either file cannot be read or the keystore cannot
+    /// be decrypted
+    pub fn from_keystore_files(path: &Path, password_file: &Path) -> Result<Self> {
+        let password = std::fs::read_to_string(password_file)?;
+        Self::from_keystore(path, password.lines().next().unwrap_or_default())
+    }
+
+    /// Uncompressed SEC1 public key, as Web3Signer lists it
+    pub fn public_key(&self) -> Bytes {
+        Bytes::copy_from_slice(self.key.verifying_key().to_encoded_point(false).as_bytes())
+    }
+
+    /// Sign `digest` without awaiting
+    pub(crate) fn sign_prehash(&self, digest: B256) -> Result<Signature> {
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(digest.as_slice())
+            .map_err(|e| ConsensusError::SignerError(format!("Signing failed: {e}")))?;
+        Ok(Signature::from_signature_and_parity(signature, recovery_id.is_y_odd()))
+    }
+}
+
+#[async_trait]
+impl SigningBackend for LocalKey {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign_prehash(keccak256(message))
+    }
+
+    fn secret_bytes(&self) -> Option<[u8; 32]> {
+        Some(self.key.to_bytes().into())
+    }
+}
+
+/// Body of a Web3Signer eth1 sign request
+#[derive(Debug, Serialize, Deserialize)]
+struct SignRequest {
+    /// Bytes whose `keccak256` hash is signed
+    data: Bytes,
+}
+
+/// Key held by a Web3Signer-compatible remote signer
+#[derive(Debug)]
+pub struct Web3Signer {
+    /// HTTP client
+    client: reqwest::Client,
+
+    /// Signer base URL, without trailing slash
+    url: String,
+
+    /// Address of the key
+    address: Address,
+}
+
+impl Web3Signer {
+    /// Remote key of `address` served at `url`, without checking the signer
+    pub fn new(url: &str, address: Address) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REMOTE_SIGNER_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client, url: url.trim_end_matches('/').to_string(), address }
+    }
+
+    /// Remote key of `address`, checking that the signer is up and holds it
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the signer is unreachable or does not hold the key
+    pub async fn connect(url: &str, address: Address) -> Result<Self> {
+        let signer = Self::new(url, address);
+
+        signer.get(WEB3SIGNER_UPCHECK_PATH).await?;
+        let keys: Vec<Bytes> = serde_json::from_str(&signer.get(WEB3SIGNER_KEYS_PATH).await?)?;
+        let held = keys.iter().any(|key| public_key_address(key) == Some(address));
+        if !held {
+            return Err(ConsensusError::ConfigError(format!(
+                "Remote signer {} does not hold a key for {address}",
+                signer.url
+            )));
+        }
+
+        info!(url = %signer.url, address = ?address, "Remote signer connected");
+        Ok(signer)
+    }
+
+    /// Body of a successful GET to `path`
+    async fn get(&self, path: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.url))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signer {path} failed: {e}")))?;
+        response
+            .text()
+            .await
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signer {path} failed: {e}")))
+    }
+}
+
+#[async_trait]
+impl SigningBackend for Web3Signer {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn name(&self) -> &'static str {
+        "web3signer"
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let request = SignRequest { data: Bytes::copy_from_slice(message) };
+        let response = self
+            .client
+            .post(format!("{}{WEB3SIGNER_SIGN_PATH}{}", self.url, self.address))
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signing failed: {e}")))?;
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signing failed: {e}")))?;
+
+        let bytes = hex::decode(body.trim().trim_matches('"'))
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signer returned no signature: {e}")))?;
+        let signature = Signature::try_from(bytes.as_slice())
+            .map_err(|e| ConsensusError::SignerError(format!("Remote signer returned a bad signature: {e}")))?;
+
+        // A signer that prefixes the data signs something else
+        let signer = signature.recover_address_from_prehash(&keccak256(message)).ok();
+        if signer != Some(self.address) {
+            return Err(ConsensusError::SignerError(format!(
+                "Remote signature recovers to {signer:?}, expected {}",
+                self.address
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+/// Address of an uncompressed public key, with or without the `0x04` tag
+fn public_key_address(key: &[u8]) -> Option<Address> {
+    match key.len() {
+        64 => Some(Address::from_raw_public_key(key)),
+        65 if key[0] == 0x04 => Some(Address::from_raw_public_key(&key[1..])),
+        _ => None,
+    }
+}
+
+/// Signing backend selected by `config`
+///
+/// `None` with [`SignerKind::Key`] and no key file: the node only verifies.
+///
+/// # Errors
+///
+/// Returns error if the configured key cannot be loaded or reached
+pub async fn load_signing_backend(config: &ConsensusConfig) -> Result<Option<Arc<dyn SigningBackend>>> {
+    let backend: Arc<dyn SigningBackend> = match config.signer {
+        SignerKind::Key if !config.private_key_path.exists() => return Ok(None),
+        SignerKind::Key => Arc::new(LocalKey::from_key_file(&config.private_key_path)?),
+        SignerKind::Keystore => Arc::new(LocalKey::from_keystore_files(
+            &config.keystore_path,
+            &config.keystore_password_file,
+        )?),
+        SignerKind::Web3Signer => {
+            Arc::new(Web3Signer::connect(&config.remote_signer_url, config.sequencer_address).await?)
+        }
+    };
+    Ok(Some(backend))
+}
+
+/// Web3Signer stand-in serving one local key over HTTP, for tests
+///
+/// Like Web3Signer, it signs the `keccak256` hash of the posted data.
+#[derive(Debug)]
+pub struct MockWeb3Signer {
+    /// Base URL
+    url: String,
+
+    /// Sign requests served
+    sign_requests: Arc<AtomicUsize>,
+
+    /// Accept loop
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl MockWeb3Signer {
+    /// Serve the key `secret` on a local port
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the key is invalid or no port can be bound
+    pub async fn start(secret: &[u8]) -> Result<Self> {
+        let key = Arc::new(LocalKey::from_bytes(secret)?);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let sign_requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&sign_requests);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let key = Arc::clone(&key);
+                let counter = Arc::clone(&counter);
+                tokio::spawn(async move {
+                    let _ = serve_mock_request(stream, &key, &counter).await;
+                });
+            }
+        });
+
+        Ok(Self { url, sign_requests, task })
+    }
+
+    /// Base URL to point a [`Web3Signer`] at
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sign requests served so far
+    pub fn sign_requests(&self) -> usize {
+        self.sign_requests.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for MockWeb3Signer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answer one HTTP request to the mock signer and close the connection
+async fn serve_mock_request(
+    mut stream: tokio::net::TcpStream,
+    key: &LocalKey,
+    sign_requests: &AtomicUsize,
+) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let body = &buf[header_end..buf.len().min(header_end + content_length)];
+
+    let mut request_line = head.split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+    let signed_by = path.strip_prefix(WEB3SIGNER_SIGN_PATH).and_then(|id| id.parse::<Address>().ok());
+
+    let (status, response) = match (method, path) {
+        ("GET", WEB3SIGNER_UPCHECK_PATH) => ("200 OK", "OK".to_string()),
+        ("GET", WEB3SIGNER_KEYS_PATH) => ("200 OK", format!("[\"{}\"]", key.public_key())),
+        ("POST", _) if signed_by == Some(key.address) => {
+            sign_requests.fetch_add(1, Ordering::Relaxed);
+            let digest = serde_json::from_slice::<SignRequest>(body).ok().map(|request| keccak256(&request.data));
+            match digest.map(|digest| key.sign_prehash(digest)) {
+                Some(Ok(signature)) => ("200 OK", hex::encode_prefixed(signature.as_bytes())),
+                _ => ("400 Bad Request", "expected hex data".to_string()),
+            }
+        }
+        _ => ("404 Not Found", String::new()),
+    };
+
+    let reply = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    );
+    stream.write_all(reply.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("ande-keystore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret = [7u8; 32];
+        PrivateKeySigner::encrypt_keystore(&dir, &mut rand::thread_rng(), secret, "hunter2", Some("validator.json"))
+            .unwrap();
+        std::fs::write(dir.join("password"), "hunter2\n").unwrap();
+
+        let key = LocalKey::from_keystore_files(&dir.join("validator.json"), &dir.join("password")).unwrap();
+        assert_eq!(key.address(), LocalKey::from_bytes(&secret).unwrap().address());
+        assert!(LocalKey::from_keystore(&dir.join("validator.json"), "wrong").is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_matches_local_key() {
+        let secret = [9u8; 32];
+        let local = LocalKey::from_bytes(&secret).unwrap();
+        let mock = MockWeb3Signer::start(&secret).await.unwrap();
+
+        let remote = Web3Signer::connect(mock.url(), local.address()).await.unwrap();
+        let message = b"ANDE_ATTESTATION_V2 message of any length";
+        let signature = remote.sign_message(message).await.unwrap();
+        assert_eq!(signature, local.sign_message(message).await.unwrap());
+        assert_eq!(signature.recover_address_from_prehash(&keccak256(message)).unwrap(), local.address());
+        assert_eq!(mock.sign_requests(), 1);
+        assert!(remote.secret_bytes().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_without_key_rejected() {
+        let mock = MockWeb3Signer::start(&[9u8; 32]).await.unwrap();
+        let other = LocalKey::from_bytes(&[10u8; 32]).unwrap().address();
+        assert!(matches!(
+            Web3Signer::connect(mock.url(), other).await,
+            Err(ConsensusError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,760 @@
+//! In-process multi-validator consensus simulator
+//!
+//! Runs N [`ConsensusEngine`]s against a shared [`MockValidatorSource`] and
+//! a simulated network driven by a logical clock, so consensus behaviour can
+//! be exercised from `cargo test` without contracts or a node:
+//!
+//! ```text
+//!              MockValidatorSource (validators, epochs, head)
+//!            ┌──────────────┬──────────────┬──────────────┐
+//!            ▼              ▼              ▼              ▼
+//!        engine 0       engine 1       engine 2  ...  engine N-1
+//!            │ attest_block                ▲ submit_attestation
+//!            └──→ network: partitions, per-node delay, crashes ──┘
+//!                   (delivered once the clock reaches them)
+//! ```
+//!
+//! Every [`step`](Simulator::step) advances the clock by one tick, produces
+//! one block, lets every running validator attest it and delivers the
+//! messages that are due. Faults are injected between steps: crashes and
+//! restarts from the persisted snapshot, partitions, delayed messages,
+//! forked blocks, equivocating validators and jailing.
+//!
+//! Safety (no two certificates for different hashes at one height, across
+//! all nodes) is checked after every step; liveness is checked on demand
+//! with [`check_liveness`](Simulator::check_liveness).
+
+use crate::{
+    attestation::AttestationSigner,
+    config::ConsensusConfig,
+    data_source::{next_sequencer, ConsensusDataSource},
+    engine::ConsensusEngine,
+    error::{ConsensusError, Result},
+    types::{AttestationInfo, ForcedRotation, LeaderRotation, ValidatorInfo, ValidatorSetUpdate},
+};
+use alloy_primitives::{hex, keccak256, Address, B256, U256};
+use async_trait::async_trait;
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+use tracing::debug;
+
+/// Distinguishes the data directories of simulators in one process
+static SIMULATION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Blocks a simulated validator stays jailed
+pub const SIM_JAIL_BLOCKS: u64 = 10;
+
+/// Contract state shared by all simulated validators
+#[derive(Debug, Default)]
+struct MockChain {
+    /// Latest block
+    head: u64,
+
+    /// Live validator list, as `getActiveValidators` would return it
+    validators: Vec<ValidatorInfo>,
+
+    /// Validator set of each started epoch
+    epochs: BTreeMap<u64, ValidatorSetUpdate>,
+
+    /// Release block of each jailed validator
+    jail_release: BTreeMap<Address, u64>,
+
+    /// Rotations forced by `checkTimeout`
+    forced_rotations: Vec<ForcedRotation>,
+}
+
+impl MockChain {
+    /// Coordinator leader of rotation `number`, taking turns in list order
+    fn leader(&self, number: u64) -> Address {
+        let index = usize::try_from(number).unwrap_or(usize::MAX) % self.validators.len().max(1);
+        self.validators.get(index).map_or(Address::ZERO, |v| v.validator)
+    }
+
+    /// Coordinator rotation number at `block`
+    fn rotation_at(&self, block: u64) -> u64 {
+        self.forced_rotations.iter().filter(|rotation| rotation.block_number <= block).count() as u64
+    }
+
+    /// Snapshot the live list into epoch `epoch` starting at the head
+    fn start_epoch(&mut self, epoch: u64) {
+        let active: Vec<&ValidatorInfo> = self.validators.iter().filter(|v| v.can_propose()).collect();
+        let powers: Vec<u64> = active.iter().map(|v| v.power).collect();
+
+        self.epochs.insert(
+            epoch,
+            ValidatorSetUpdate {
+                epoch,
+                validators: active.iter().map(|v| v.validator).collect(),
+                total_power: powers.iter().sum(),
+                powers,
+                block_number: self.head,
+                timestamp: self.head,
+            },
+        );
+    }
+}
+
+/// In-memory [`ConsensusDataSource`] standing in for AndeConsensus
+#[derive(Debug, Clone, Default)]
+pub struct MockValidatorSource {
+    /// Shared contract state
+    chain: Arc<Mutex<MockChain>>,
+}
+
+impl MockValidatorSource {
+    /// Source whose epoch 1 starts at block 0 with `validators`
+    pub fn new(validators: Vec<ValidatorInfo>) -> Self {
+        let mut chain = MockChain { head: 0, validators, ..MockChain::default() };
+        chain.start_epoch(1);
+        Self { chain: Arc::new(Mutex::new(chain)) }
+    }
+
+    /// Set the latest block
+    pub fn set_head(&self, block_number: u64) {
+        self.chain().head = block_number;
+    }
+
+    /// Change a validator in the live list; `false` if it is unknown
+    pub fn update_validator(&self, address: Address, update: impl FnOnce(&mut ValidatorInfo)) -> bool {
+        let mut chain = self.chain();
+        let Some(validator) = chain.validators.iter_mut().find(|v| v.validator == address) else {
+            return false;
+        };
+        update(validator);
+        true
+    }
+
+    /// Add a validator to the live list
+    pub fn add_validator(&self, validator: ValidatorInfo) {
+        self.chain().validators.push(validator);
+    }
+
+    /// Jail a validator until `release_block`, as slashing would
+    ///
+    /// Returns `false` if it is unknown.
+    pub fn jail(&self, address: Address, release_block: u64) -> bool {
+        let jailed = self.update_validator(address, |validator| {
+            validator.jailed = true;
+            validator.active = false;
+        });
+        if jailed {
+            self.chain().jail_release.insert(address, release_block);
+        }
+        jailed
+    }
+
+    /// Unjail a validator, as its `unjail()` would once the release block is reached
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the validator is not jailed or its jail period is not over
+    pub fn unjail(&self, address: Address) -> Result<()> {
+        let mut chain = self.chain();
+        let head = chain.head;
+        let Some(&release_block) = chain.jail_release.get(&address) else {
+            return Err(ConsensusError::Internal(format!("Validator {address} is not jailed")));
+        };
+        if head < release_block {
+            return Err(ConsensusError::Internal(format!(
+                "Validator {address} is jailed until block {release_block}, head is {head}"
+            )));
+        }
+
+        chain.jail_release.remove(&address);
+        if let Some(validator) = chain.validators.iter_mut().find(|v| v.validator == address) {
+            validator.jailed = false;
+            validator.active = true;
+        }
+        Ok(())
+    }
+
+    /// Start the next epoch at the head, as `advanceEpoch` would
+    ///
+    /// Returns the new epoch number.
+    pub fn advance_epoch(&self) -> u64 {
+        let mut chain = self.chain();
+        let epoch = chain.epochs.keys().next_back().map_or(1, |epoch| epoch + 1);
+        chain.start_epoch(epoch);
+        epoch
+    }
+
+    /// Force a coordinator rotation at the head, as `checkTimeout` would
+    ///
+    /// Returns the rotation.
+    pub fn force_rotation(&self) -> ForcedRotation {
+        let mut chain = self.chain();
+        let rotation = ForcedRotation {
+            rotation_number: chain.forced_rotations.last().map_or(1, |last| last.rotation_number + 1),
+            block_number: chain.head,
+        };
+        chain.forced_rotations.push(rotation);
+        rotation
+    }
+
+    /// Lock the shared state
+    fn chain(&self) -> std::sync::MutexGuard<'_, MockChain> {
+        self.chain.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl ConsensusDataSource for MockValidatorSource {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn latest_block(&self) -> Result<u64> {
+        Ok(self.chain().head)
+    }
+
+    async fn active_validators(&self, _block: u64) -> Result<Vec<ValidatorInfo>> {
+        Ok(self.chain().validators.clone())
+    }
+
+    async fn current_epoch(&self, block: u64) -> Result<u64> {
+        self.chain()
+            .epochs
+            .values()
+            .filter(|set| set.block_number <= block)
+            .map(|set| set.epoch)
+            .next_back()
+            .ok_or(ConsensusError::UnknownValidatorSet(block))
+    }
+
+    async fn epoch_validator_set(&self, epoch: u64) -> Result<ValidatorSetUpdate> {
+        self.chain()
+            .epochs
+            .get(&epoch)
+            .cloned()
+            .ok_or_else(|| ConsensusError::Internal(format!("Epoch {epoch} not started")))
+    }
+
+    async fn is_timeout_reached(&self, _block: u64) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn forced_rotations(&self, after: u64, block: u64) -> Result<Vec<ForcedRotation>> {
+        Ok(self
+            .chain()
+            .forced_rotations
+            .iter()
+            .filter(|rotation| rotation.rotation_number > after && rotation.block_number <= block)
+            .copied()
+            .collect())
+    }
+
+    async fn jail_release_block(&self, validator: Address, _block: u64) -> Result<u64> {
+        Ok(self.chain().jail_release.get(&validator).copied().unwrap_or(0))
+    }
+
+    async fn coordinator_leader(&self, block: u64) -> Result<(Address, u64)> {
+        let chain = self.chain();
+        let rotation = chain.rotation_at(block);
+        Ok((chain.leader(rotation), rotation))
+    }
+
+    async fn leader_rotations(&self, from: u64, to: u64) -> Result<Vec<LeaderRotation>> {
+        let chain = self.chain();
+        Ok(chain
+            .forced_rotations
+            .iter()
+            .filter(|rotation| (from..=to).contains(&rotation.block_number))
+            .map(|rotation| LeaderRotation {
+                rotation_number: rotation.rotation_number,
+                old_leader: chain.leader(rotation.rotation_number - 1),
+                new_leader: chain.leader(rotation.rotation_number),
+                block_number: rotation.block_number,
+                reason: "Timeout".to_string(),
+            })
+            .collect())
+    }
+
+    async fn next_leader(&self, block: u64) -> Result<Address> {
+        let chain = self.chain();
+        let sequencers: Vec<Address> = chain.validators.iter().map(|v| v.validator).collect();
+        next_sequencer(chain.leader(chain.rotation_at(block)), &sequencers)
+    }
+}
+
+/// Parameters of a simulation
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    /// Voting power of each validator
+    pub powers: Vec<u64>,
+
+    /// Chain the validators attest for
+    pub chain_id: u64,
+
+    /// Maximum attestation age (blocks)
+    pub max_attestation_age: u64,
+
+    /// Directory for key files and persisted engine state
+    pub data_dir: PathBuf,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        let id = SIMULATION_ID.fetch_add(1, Ordering::Relaxed);
+        Self {
+            powers: vec![100; 4],
+            chain_id: 6174,
+            max_attestation_age: 32,
+            data_dir: std::env::temp_dir().join(format!("ande-consensus-sim-{}-{id}", std::process::id())),
+        }
+    }
+}
+
+/// One simulated validator
+struct SimNode {
+    /// Engine configuration, reused on restart
+    config: ConsensusConfig,
+
+    /// Copy of the engine's key, for equivocating
+    signer: AttestationSigner,
+
+    /// Voting power
+    power: u64,
+
+    /// Running engine, `None` while crashed
+    engine: Option<ConsensusEngine>,
+
+    /// Partition the node is in
+    group: usize,
+
+    /// Ticks its messages take to arrive
+    delay: u64,
+
+    /// Whether it also attests a conflicting hash at every height
+    equivocating: bool,
+
+    /// Whether it sees a forked block instead of the canonical one
+    forked: bool,
+
+    /// Whether it has been jailed
+    jailed: bool,
+}
+
+/// Attestation on its way to a validator
+#[derive(Debug)]
+struct Envelope {
+    /// Tick the message arrives at
+    deliver_at: u64,
+
+    /// Receiving node
+    to: usize,
+
+    /// The attestation
+    attestation: AttestationInfo,
+}
+
+/// Multi-validator simulation with fault injection
+pub struct Simulator {
+    /// Shared contract state
+    source: Arc<MockValidatorSource>,
+
+    /// Simulated validators
+    nodes: Vec<SimNode>,
+
+    /// Messages not yet delivered
+    in_flight: Vec<Envelope>,
+
+    /// Logical clock
+    clock: u64,
+
+    /// Latest produced block
+    height: u64,
+
+    /// Finalized hash per height, as first seen on any node
+    finalized: BTreeMap<u64, B256>,
+}
+
+impl Simulator {
+    /// Start one engine per validator in `config`, synced to block 0
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a key file cannot be written or an engine fails to
+    /// start
+    pub async fn new(config: SimConfig) -> Result<Self> {
+        std::fs::create_dir_all(&config.data_dir)?;
+
+        let mut signers = Vec::with_capacity(config.powers.len());
+        let mut validators = Vec::with_capacity(config.powers.len());
+        for (index, power) in config.powers.iter().enumerate() {
+            let secret = keccak256(format!("ande-sim-validator-{index}"));
+            let signer = AttestationSigner::from_bytes(secret.as_slice(), config.chain_id)?;
+            validators.push(sim_validator(signer.address(), *power));
+            signers.push((secret, signer));
+        }
+        let source = Arc::new(MockValidatorSource::new(validators));
+
+        let mut nodes = Vec::with_capacity(signers.len());
+        for (index, ((secret, signer), power)) in signers.into_iter().zip(&config.powers).enumerate() {
+            let data_dir = config.data_dir.join(format!("validator-{index}"));
+            std::fs::create_dir_all(&data_dir)?;
+            let private_key_path = data_dir.join("validator.key");
+            std::fs::write(&private_key_path, hex::encode(secret))?;
+
+            let node_config = ConsensusConfig {
+                // Never contacted: reads go to the mock source, gossip to the simulator
+                rpc_url: "http://127.0.0.1:1".to_string(),
+                ws_url: String::new(),
+                sequencer_address: signer.address(),
+                private_key_path,
+                chain_id: config.chain_id,
+                max_attestation_age: config.max_attestation_age,
+                data_dir,
+                ..ConsensusConfig::default()
+            };
+
+            nodes.push(SimNode {
+                config: node_config,
+                signer,
+                power: *power,
+                engine: None,
+                group: 0,
+                delay: 0,
+                equivocating: false,
+                forked: false,
+                jailed: false,
+            });
+        }
+
+        let mut simulator = Self {
+            source,
+            nodes,
+            in_flight: Vec::new(),
+            clock: 0,
+            height: 0,
+            finalized: BTreeMap::new(),
+        };
+        for index in 0..simulator.nodes.len() {
+            simulator.restart(index).await?;
+        }
+        Ok(simulator)
+    }
+
+    /// Shared contract state
+    pub fn source(&self) -> &MockValidatorSource {
+        &self.source
+    }
+
+    /// Engine of `node`, `None` while crashed
+    pub fn engine(&self, node: usize) -> Option<&ConsensusEngine> {
+        self.nodes[node].engine.as_ref()
+    }
+
+    /// Address of `node`
+    pub fn address(&self, node: usize) -> Address {
+        self.nodes[node].signer.address()
+    }
+
+    /// Key of `node`, for signing messages on its behalf
+    pub fn signer(&self, node: usize) -> &AttestationSigner {
+        &self.nodes[node].signer
+    }
+
+    /// Latest produced block
+    pub const fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Logical clock
+    pub const fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    /// Highest block `node` holds a certificate for
+    pub fn finalized_height(&self, node: usize) -> Option<u64> {
+        self.engine(node)?.latest_finalized().map(|certificate| certificate.block_number)
+    }
+
+    /// Hash of the canonical block at `height`
+    pub fn canonical_hash(&self, height: u64) -> B256 {
+        sim_block_hash(height, false)
+    }
+
+    /// Finalized hash at `height`, as first seen on any node
+    pub fn finalized_hash(&self, height: u64) -> Option<B256> {
+        self.finalized.get(&height).copied()
+    }
+
+    /// Evidence `node` detected and has not submitted yet
+    pub fn pending_evidence(&self, node: usize) -> usize {
+        self.engine(node).map_or(0, ConsensusEngine::pending_evidence)
+    }
+
+    /// Stop `node`, dropping its engine and the messages queued for it
+    pub fn crash(&mut self, node: usize) {
+        debug!(node, "Simulated validator crashed");
+        self.nodes[node].engine = None;
+        self.in_flight.retain(|envelope| envelope.to != node);
+    }
+
+    /// Start `node` again from its persisted state and resync it
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the engine cannot be created or synced
+    pub async fn restart(&mut self, node: usize) -> Result<()> {
+        let engine =
+            ConsensusEngine::with_data_source(self.nodes[node].config.clone(), self.source.clone()).await?;
+        engine.restore_state().await;
+        engine.sync_validator_set().await?;
+
+        debug!(node, height = self.height, "Simulated validator started");
+        self.nodes[node].engine = Some(engine);
+        Ok(())
+    }
+
+    /// Split the network; nodes not listed form one more group
+    pub fn partition(&mut self, groups: &[&[usize]]) {
+        for node in &mut self.nodes {
+            node.group = groups.len();
+        }
+        for (group, members) in groups.iter().enumerate() {
+            for member in *members {
+                self.nodes[*member].group = group;
+            }
+        }
+    }
+
+    /// Reconnect all nodes
+    pub fn heal(&mut self) {
+        for node in &mut self.nodes {
+            node.group = 0;
+        }
+    }
+
+    /// Delay messages sent by `node` by `ticks`
+    pub fn delay(&mut self, node: usize, ticks: u64) {
+        self.nodes[node].delay = ticks;
+    }
+
+    /// Make `node` attest a conflicting hash at every height as well
+    pub fn equivocate(&mut self, node: usize, equivocating: bool) {
+        self.nodes[node].equivocating = equivocating;
+    }
+
+    /// Show `nodes` a forked block instead of the canonical one
+    ///
+    /// An empty slice ends the fork.
+    pub fn fork(&mut self, nodes: &[usize]) {
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            node.forked = nodes.contains(&index);
+        }
+    }
+
+    /// Jail `node` on-chain for [`SIM_JAIL_BLOCKS`] and resync every running engine
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a resync fails
+    pub async fn jail(&mut self, node: usize) -> Result<()> {
+        let address = self.address(node);
+        self.source.jail(address, self.height + SIM_JAIL_BLOCKS);
+        self.nodes[node].jailed = true;
+        self.sync_all().await
+    }
+
+    /// Unjail `node` on-chain, as its own `unjail()`, and resync every running engine
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the jail period is not over or a resync fails
+    pub async fn unjail(&mut self, node: usize) -> Result<()> {
+        self.source.unjail(self.address(node))?;
+        self.nodes[node].jailed = false;
+        self.sync_all().await
+    }
+
+    /// Start the next epoch on-chain and resync every running engine
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a resync fails
+    pub async fn advance_epoch(&mut self) -> Result<u64> {
+        let epoch = self.source.advance_epoch();
+        self.sync_all().await?;
+        Ok(epoch)
+    }
+
+    /// Resync every running engine from the mock source
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a resync fails
+    pub async fn sync_all(&self) -> Result<()> {
+        for engine in self.nodes.iter().filter_map(|node| node.engine.as_ref()) {
+            engine.sync_validator_set().await?;
+        }
+        Ok(())
+    }
+
+    /// Run `steps` steps
+    ///
+    /// # Errors
+    ///
+    /// Returns error on the first safety violation
+    pub async fn run(&mut self, steps: u64) -> Result<()> {
+        for _ in 0..steps {
+            self.step().await?;
+        }
+        Ok(())
+    }
+
+    /// Produce and attest one block, then deliver due messages
+    ///
+    /// # Errors
+    ///
+    /// Returns error if two nodes finalized different hashes at one height
+    pub async fn step(&mut self) -> Result<()> {
+        self.clock += 1;
+        self.height += 1;
+        let height = self.height;
+        self.source.set_head(height);
+
+        let canonical = sim_block_hash(height, false);
+        let mut outgoing = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let Some(engine) = &node.engine else { continue };
+            if let Err(e) = engine.advance_schedule(height) {
+                debug!(node = index, error = %e, "Proposer schedule not advanced");
+            }
+
+            let hash = if node.forked { sim_block_hash(height, true) } else { canonical };
+            match engine.attest_block(height, hash).await {
+                Ok(Some(attestation)) => outgoing.push((index, attestation)),
+                Ok(None) => {}
+                Err(e) => debug!(node = index, error = %e, "Simulated validator did not attest"),
+            }
+            if let Some(epoch) = engine.epoch_at(height).filter(|_| node.equivocating) {
+                let conflicting = if node.forked { canonical } else { sim_block_hash(height, true) };
+                outgoing.push((index, node.signer.sign(epoch, height, conflicting, node.power).await?));
+            }
+        }
+
+        for (from, attestation) in outgoing {
+            self.send(from, &attestation);
+        }
+        self.deliver();
+        self.check_safety()
+    }
+
+    /// Queue `attestation` from `from` to every node it can reach
+    fn send(&mut self, from: usize, attestation: &AttestationInfo) {
+        let (group, delay) = (self.nodes[from].group, self.nodes[from].delay);
+        for (to, node) in self.nodes.iter().enumerate() {
+            if to == from || node.group != group || node.engine.is_none() {
+                continue;
+            }
+            self.in_flight.push(Envelope { deliver_at: self.clock + delay, to, attestation: attestation.clone() });
+        }
+    }
+
+    /// Hand due messages to their receivers
+    fn deliver(&mut self) {
+        let (due, later): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.in_flight).into_iter().partition(|envelope| envelope.deliver_at <= self.clock);
+        self.in_flight = later;
+
+        for envelope in due {
+            let Some(engine) = &self.nodes[envelope.to].engine else { continue };
+            if let Err(e) = engine.submit_attestation(envelope.attestation) {
+                debug!(node = envelope.to, error = %e, "Simulated attestation rejected");
+            }
+        }
+    }
+
+    /// Check that no two nodes finalized different hashes at one height
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::InvalidQuorumCertificate`] on a conflict
+    pub fn check_safety(&mut self) -> Result<()> {
+        for (index, node) in self.nodes.iter().enumerate() {
+            let Some(engine) = &node.engine else { continue };
+            // Certificates only form for attestations within the age limit
+            let from = self.height.saturating_sub(2 * node.config.max_attestation_age);
+            for height in from..=self.height {
+                let Some(certificate) = engine.quorum_certificate(height) else { continue };
+                let first = *self.finalized.entry(height).or_insert(certificate.block_hash);
+                if first != certificate.block_hash {
+                    return Err(ConsensusError::InvalidQuorumCertificate(format!(
+                        "safety violated: validator {index} finalized {} at height {height}, another finalized {first}",
+                        certificate.block_hash
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every group with 2/3+ of the power in running, honest,
+    /// unjailed members finalized a block above `since` on each of them
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::Timeout`] if such a group made no progress
+    pub fn check_liveness(&self, since: u64) -> Result<()> {
+        let total: u64 = self.nodes.iter().filter(|node| !node.jailed).map(|node| node.power).sum();
+        let groups = self.nodes.iter().map(|node| node.group).max().unwrap_or(0);
+
+        for group in 0..=groups {
+            let honest: Vec<usize> = (0..self.nodes.len())
+                .filter(|index| {
+                    let node = &self.nodes[*index];
+                    node.group == group
+                        && node.engine.is_some()
+                        && !node.jailed
+                        && !node.equivocating
+                        && !node.forked
+                        && node.delay == 0
+                })
+                .collect();
+            let power: u64 = honest.iter().map(|index| self.nodes[*index].power).sum();
+            if power * 3 <= total * 2 {
+                continue;
+            }
+
+            for index in honest {
+                if self.finalized_height(index).map_or(true, |finalized| finalized <= since) {
+                    return Err(ConsensusError::Timeout { blocks: self.height - since });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Hash of the simulated block at `height`, or of its fork
+fn sim_block_hash(height: u64, forked: bool) -> B256 {
+    keccak256([&height.to_be_bytes()[..], &[u8::from(forked)]].concat())
+}
+
+/// Active, unjailed validator with `power`
+fn sim_validator(address: Address, power: u64) -> ValidatorInfo {
+    ValidatorInfo {
+        validator: address,
+        p2p_peer_id: B256::ZERO,
+        // No endpoint: the simulator carries the gossip
+        rpc_endpoint: String::new(),
+        stake: U256::from(power),
+        power,
+        accumulated_priority: 0,
+        total_blocks_produced: 0,
+        total_blocks_missed: 0,
+        uptime: 10000,
+        last_block_produced: 0,
+        registered_at: 0,
+        jailed: false,
+        active: true,
+        is_permanent: false,
+    }
+}
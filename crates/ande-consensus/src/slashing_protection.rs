@@ -0,0 +1,341 @@
+//! Slashing protection for this validator's signatures
+//!
+//! Every attestation and proposal is recorded on disk before it is signed,
+//! and a request to sign another hash at a recorded height is refused. A
+//! restart, a replayed request or a misbehaving caller therefore cannot make
+//! the key equivocate:
+//!
+//! ```text
+//! sign(kind, height, hash)
+//!   height ≤ floor            → refused (records pruned, conflict unknown)
+//!   height recorded, same hash → signed again
+//!   height recorded, other hash → refused
+//!   new height                → record, fsync, then sign
+//!
+//! slashing-protection.json
+//! {
+//!   "attestations":   { "floor": 812, "signed": { "813": "0x…", … } },
+//!   "proposals":      { "floor": null, "signed": { … } },
+//!   "validator_sets": { "floor": null, "signed": { "41": "0x…" } }
+//! }
+//! ```
+//!
+//! Header seals are not recorded: the payload builder seals every candidate
+//! of a slot. A seal is only refused at or below the highest proposed
+//! height, where the block is already announced and a second seal would
+//! equivocate.
+//!
+//! Validator set handovers are recorded by epoch instead of height: signing
+//! two different sets for one epoch would let light clients be forked.
+//!
+//! Only the latest [`MAX_SIGNED_RECORDS`] heights of each kind are kept;
+//! pruning raises the floor so nothing at or below a pruned height is signed
+//! again.
+
+use crate::error::{ConsensusError, Result};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+/// Slashing protection file name inside `data_dir`
+pub const SLASHING_PROTECTION_FILE: &str = "slashing-protection.json";
+
+/// Signed heights kept per kind
+pub const MAX_SIGNED_RECORDS: usize = 1024;
+
+/// What a signature is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningKind {
+    /// Block attestation
+    Attestation,
+
+    /// Proposal of a block this validator produced
+    Proposal,
+
+    /// Handover to the validator set of an epoch, recorded by epoch
+    ValidatorSet,
+}
+
+impl SigningKind {
+    /// Name used in errors and logs
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Attestation => "attestation",
+            Self::Proposal => "proposal",
+            Self::ValidatorSet => "validator set",
+        }
+    }
+}
+
+/// Heights signed for one kind
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct SignedHeights {
+    /// Highest pruned height; nothing at or below it is signed
+    floor: Option<u64>,
+
+    /// Hash signed at each height
+    signed: BTreeMap<u64, B256>,
+}
+
+impl SignedHeights {
+    /// Whether `hash` may be signed at `height`
+    ///
+    /// Returns `Ok(true)` for a new height that has to be recorded.
+    fn check(&self, kind: SigningKind, height: u64, hash: B256) -> Result<bool> {
+        if self.floor.is_some_and(|floor| height <= floor) {
+            return Err(ConsensusError::SigningRefused {
+                kind: kind.as_str(),
+                block_number: height,
+                reason: format!("at or below the protection floor {}", self.floor.unwrap_or_default()),
+            });
+        }
+        match self.signed.get(&height) {
+            Some(signed) if *signed == hash => Ok(false),
+            Some(signed) => Err(ConsensusError::SigningRefused {
+                kind: kind.as_str(),
+                block_number: height,
+                reason: format!("already signed {signed}, refusing {hash}"),
+            }),
+            None => Ok(true),
+        }
+    }
+
+    /// Record `hash` at `height`, pruning the oldest heights
+    fn record(&mut self, height: u64, hash: B256) {
+        self.signed.insert(height, hash);
+        while self.signed.len() > MAX_SIGNED_RECORDS {
+            if let Some((pruned, _)) = self.signed.pop_first() {
+                self.floor = Some(self.floor.map_or(pruned, |floor| floor.max(pruned)));
+            }
+        }
+    }
+}
+
+/// Everything this validator signed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct SignedRecords {
+    /// Attested heights
+    attestations: SignedHeights,
+
+    /// Proposed heights
+    proposals: SignedHeights,
+
+    /// Validator set hashes handed over to, by epoch
+    #[serde(default)]
+    validator_sets: SignedHeights,
+}
+
+impl SignedRecords {
+    /// Records of one kind
+    fn of(&self, kind: SigningKind) -> &SignedHeights {
+        match kind {
+            SigningKind::Attestation => &self.attestations,
+            SigningKind::Proposal => &self.proposals,
+            SigningKind::ValidatorSet => &self.validator_sets,
+        }
+    }
+
+    /// Mutable records of one kind
+    fn of_mut(&mut self, kind: SigningKind) -> &mut SignedHeights {
+        match kind {
+            SigningKind::Attestation => &mut self.attestations,
+            SigningKind::Proposal => &mut self.proposals,
+            SigningKind::ValidatorSet => &mut self.validator_sets,
+        }
+    }
+}
+
+/// Refuses conflicting signatures, persisting what was signed
+#[derive(Debug)]
+pub struct SlashingProtection {
+    /// Records file, `None` to keep them in memory only
+    path: Option<PathBuf>,
+
+    /// Signed heights, held while a record is written
+    records: std::sync::Mutex<SignedRecords>,
+}
+
+impl SlashingProtection {
+    /// Open the records kept in `data_dir`, starting empty if there are none
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the records exist but cannot be read; signing
+    /// without them could equivocate
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        Self::open_file(&data_dir.join(SLASHING_PROTECTION_FILE))
+    }
+
+    /// Open the records kept in `path`, starting empty if there are none
+    ///
+    /// Signatures over different messages need separate files: two
+    /// instances sharing one would overwrite each other's records.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the records exist but cannot be read
+    pub fn open_file(path: &Path) -> Result<Self> {
+        let path = path.to_path_buf();
+        let records = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                ConsensusError::StateCorrupted(format!("{}: {e}", path.display()))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => SignedRecords::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path: Some(path), records: std::sync::Mutex::new(records) })
+    }
+
+    /// Protection that forgets everything on restart, for tests
+    pub fn in_memory() -> Self {
+        Self { path: None, records: std::sync::Mutex::new(SignedRecords::default()) }
+    }
+
+    /// Records file, if persisted
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Allow signing `hash` at `height`, recording it first
+    ///
+    /// Signing the same hash at a height again is allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::SigningRefused`] for a conflicting or
+    /// pruned height, or an I/O error if the record cannot be persisted
+    pub fn check_and_record(&self, kind: SigningKind, height: u64, hash: B256) -> Result<()> {
+        let mut records = self.records.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if !records.of(kind).check(kind, height, hash)? {
+            return Ok(());
+        }
+
+        records.of_mut(kind).record(height, hash);
+        if let Err(e) = self.save(&records) {
+            // Not on disk, so not signed
+            records.of_mut(kind).signed.remove(&height);
+            warn!(kind = kind.as_str(), height, error = %e, "Failed to persist slashing protection record");
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Allow sealing a header at `height`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsensusError::SigningRefused`] if a proposal was already
+    /// signed at or above `height`
+    pub fn check_seal(&self, height: u64) -> Result<()> {
+        let records = self.records.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let proposals = &records.proposals;
+        match proposals.signed.keys().next_back().copied().max(proposals.floor) {
+            Some(proposed) if height <= proposed => Err(ConsensusError::SigningRefused {
+                kind: "seal",
+                block_number: height,
+                reason: format!("already proposed height {proposed}"),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Highest height signed for `kind`
+    pub fn last_signed(&self, kind: SigningKind) -> Option<u64> {
+        let records = self.records.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        records.of(kind).signed.keys().next_back().copied()
+    }
+
+    /// Atomically replace the records file (write temp file, fsync, rename)
+    fn save(&self, records: &SignedRecords) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut tmp = std::fs::File::create(&tmp_path)?;
+            tmp.write_all(&serde_json::to_vec(records)?)?;
+            tmp.sync_all()?;
+        }
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuses_second_hash_at_height() {
+        let protection = SlashingProtection::in_memory();
+        let (a, b) = (B256::repeat_byte(1), B256::repeat_byte(2));
+
+        protection.check_and_record(SigningKind::Attestation, 10, a).unwrap();
+        protection.check_and_record(SigningKind::Attestation, 10, a).unwrap();
+        assert!(matches!(
+            protection.check_and_record(SigningKind::Attestation, 10, b),
+            Err(ConsensusError::SigningRefused { block_number: 10, .. })
+        ));
+
+        // Kinds are tracked separately
+        protection.check_and_record(SigningKind::Proposal, 10, b).unwrap();
+        assert_eq!(protection.last_signed(SigningKind::Proposal), Some(10));
+    }
+
+    #[test]
+    fn test_seal_refused_at_proposed_heights() {
+        let protection = SlashingProtection::in_memory();
+        protection.check_seal(10).unwrap();
+        protection.check_seal(10).unwrap();
+
+        protection.check_and_record(SigningKind::Proposal, 10, B256::repeat_byte(1)).unwrap();
+        assert!(matches!(
+            protection.check_seal(10),
+            Err(ConsensusError::SigningRefused { kind: "seal", block_number: 10, .. })
+        ));
+        assert!(protection.check_seal(9).is_err());
+        protection.check_seal(11).unwrap();
+    }
+
+    #[test]
+    fn test_records_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("ande-slashing-protection-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let protection = SlashingProtection::open(&dir).unwrap();
+        protection.check_and_record(SigningKind::Proposal, 7, B256::repeat_byte(1)).unwrap();
+        drop(protection);
+
+        let reopened = SlashingProtection::open(&dir).unwrap();
+        assert!(reopened.check_and_record(SigningKind::Proposal, 7, B256::repeat_byte(2)).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_records_without_validator_sets_load() {
+        let json = r#"{"attestations":{"floor":null,"signed":{}},"proposals":{"floor":null,"signed":{}}}"#;
+        let records: SignedRecords = serde_json::from_str(json).unwrap();
+        assert_eq!(records, SignedRecords::default());
+    }
+
+    #[test]
+    fn test_pruned_heights_stay_refused() {
+        let protection = SlashingProtection::in_memory();
+        for height in 1..=MAX_SIGNED_RECORDS as u64 + 1 {
+            protection.check_and_record(SigningKind::Attestation, height, B256::repeat_byte(1)).unwrap();
+        }
+
+        // Height 1 was pruned, so even the same hash is refused
+        assert!(protection.check_and_record(SigningKind::Attestation, 1, B256::repeat_byte(1)).is_err());
+        assert!(protection.check_and_record(SigningKind::Attestation, 2, B256::repeat_byte(1)).is_ok());
+    }
+}
@@ -88,14 +88,20 @@ pub struct BlockProposal {
 }
 
 /// Attestation (vote) for a block by a validator
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AttestationInfo {
     /// Validator submitting attestation
     pub validator: Address,
 
+    /// Number of the attested block
+    pub block_number: u64,
+
     /// Hash of the attested block
     pub block_hash: B256,
 
+    /// Epoch of the validator set the attestation counts for
+    pub epoch: u64,
+
     /// Validator's signature
     pub signature: Bytes,
 
@@ -107,7 +113,7 @@ pub struct AttestationInfo {
 }
 
 /// Information about an epoch
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EpochInfo {
     /// Epoch number
     pub epoch_number: u64,
@@ -127,12 +133,24 @@ pub struct EpochInfo {
     /// Active validators in this epoch
     pub validators: Vec<Address>,
 
+    /// Voting power of each validator, in `validators` order
+    #[serde(default)]
+    pub powers: Vec<u64>,
+
     /// Total voting power in epoch
     pub total_voting_power: u64,
+
+    /// Blocks produced during the epoch
+    #[serde(default)]
+    pub blocks_produced: u64,
+
+    /// Blocks missed during the epoch
+    #[serde(default)]
+    pub missed_blocks: u64,
 }
 
 /// Information about a rotation period
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RotationInfo {
     /// Rotation number
     pub rotation_number: u64,
@@ -157,7 +175,7 @@ pub struct RotationInfo {
 }
 
 /// Update to the validator set
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidatorSetUpdate {
     /// Epoch number for this update
     pub epoch: u64,
@@ -183,10 +201,90 @@ impl ValidatorSetUpdate {
     pub const fn bft_threshold(&self) -> u64 {
         (self.total_power * 2) / 3 + 1
     }
+
+    /// Voting power of `validator`, `None` if not in the set
+    pub fn power_of(&self, validator: &Address) -> Option<u64> {
+        self.validators
+            .iter()
+            .position(|v| v == validator)
+            .and_then(|index| self.powers.get(index).copied())
+    }
+}
+
+/// Validator set change announced by an AndeConsensus event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidatorSetChange {
+    /// `ValidatorSetUpdated`: new validators and powers for an epoch
+    Updated(ValidatorSetUpdate),
+
+    /// `ValidatorJailed`
+    Jailed {
+        /// Jailed validator
+        validator: Address,
+        /// Reason given by the contract
+        reason: String,
+    },
+
+    /// `ValidatorUnjailed`
+    Unjailed {
+        /// Unjailed validator
+        validator: Address,
+    },
+}
+
+/// AndeConsensus event delivered by the log subscription
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSetEvent {
+    /// What changed
+    pub change: ValidatorSetChange,
+
+    /// Block that emitted the event
+    pub block_number: u64,
+
+    /// Hash of that block
+    pub block_hash: B256,
+
+    /// Index of the log within the block
+    pub log_index: u64,
+
+    /// Whether the log was removed by a reorg
+    pub removed: bool,
+}
+
+/// `LeaderRotated` emitted by AndeSequencerCoordinator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderRotation {
+    /// Rotation started by the event
+    pub rotation_number: u64,
+
+    /// Leader handing over
+    pub old_leader: Address,
+
+    /// Leader taking over
+    pub new_leader: Address,
+
+    /// Block that emitted the event; the new leader produces from the next one
+    pub block_number: u64,
+
+    /// Reason given by the contract
+    pub reason: String,
+}
+
+/// Coordinator rotation forced by a timeout or an admin
+///
+/// The proposer schedule skips one proposer at `block_number + 1`, so every
+/// node moves past the stalled proposer at the same height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForcedRotation {
+    /// Rotation started by the timeout
+    pub rotation_number: u64,
+
+    /// Block that forced the rotation
+    pub block_number: u64,
 }
 
 /// Consensus state snapshot
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConsensusState {
     /// Current block number
     pub current_block: u64,
@@ -211,4 +309,16 @@ pub struct ConsensusState {
 
     /// Timestamp of last state update
     pub last_update: u64,
+
+    /// Whether this sequencer is jailed
+    #[serde(default)]
+    pub jailed: bool,
+
+    /// Block from which this sequencer can unjail (0 if not jailed)
+    #[serde(default)]
+    pub jail_release_block: u64,
+
+    /// Blocks left until this sequencer can unjail
+    #[serde(default)]
+    pub jail_blocks_remaining: u64,
 }
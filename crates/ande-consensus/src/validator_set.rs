@@ -2,9 +2,10 @@
 
 use crate::{
     error::{ConsensusError, Result},
-    types::ValidatorInfo,
+    types::{ValidatorInfo, ValidatorSetUpdate},
 };
 use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, info, warn};
 
@@ -44,6 +45,10 @@ impl ValidatorSet {
     }
 
     /// Update the validator set from on-chain data
+    ///
+    /// Validators already known keep their local proposer priority and
+    /// block counters, and the current proposer keeps its turn while it
+    /// stays active.
     pub fn update_from_chain(
         &mut self,
         validators: Vec<ValidatorInfo>,
@@ -56,12 +61,15 @@ impl ValidatorSet {
         );
 
         // Clear existing validators
-        self.validators.clear();
+        let previous = std::mem::take(&mut self.validators);
         self.active_validators.clear();
         self.total_voting_power = 0;
 
         // Add new validators
-        for validator in validators {
+        for mut validator in validators {
+            if let Some(local) = previous.get(&validator.validator) {
+                merge_local_stats(&mut validator, local);
+            }
             if validator.can_propose() {
                 self.total_voting_power += validator.power;
                 self.active_validators.push(validator.validator);
@@ -71,8 +79,13 @@ impl ValidatorSet {
 
         self.last_update_block = block_number;
 
-        // Reset proposer selection if validator set changed
-        if !self.active_validators.is_empty() {
+        // Keep the rotation position, or restart it if the proposer left
+        let position = self
+            .current_proposer
+            .and_then(|proposer| self.active_validators.iter().position(|addr| *addr == proposer));
+        if let Some(index) = position {
+            self.proposer_index = index;
+        } else if !self.active_validators.is_empty() {
             self.proposer_index = 0;
             self.select_next_proposer()?;
         }
@@ -86,6 +99,105 @@ impl ValidatorSet {
         Ok(())
     }
 
+    /// Update the validator set to the members and powers of an epoch
+    ///
+    /// Only members of `epoch_set` can propose, with their epoch power.
+    /// Their remaining info comes from `validators`, the live on-chain list,
+    /// or from the local entry if they already left it; validators that
+    /// joined since the epoch started wait for the next one.
+    pub fn apply_epoch(
+        &mut self,
+        epoch_set: &ValidatorSetUpdate,
+        mut validators: Vec<ValidatorInfo>,
+        block_number: u64,
+    ) -> Result<()> {
+        let mut members = Vec::with_capacity(epoch_set.validators.len());
+        for (address, power) in epoch_set.validators.iter().zip(&epoch_set.powers) {
+            let live = validators.iter().position(|v| v.validator == *address).map(|i| validators.swap_remove(i));
+            let Some(mut validator) = live.or_else(|| self.validators.get(address).cloned()) else {
+                warn!(validator = ?address, epoch = epoch_set.epoch, "No info for epoch member, skipping");
+                continue;
+            };
+            validator.power = *power;
+            members.push(validator);
+        }
+
+        if !validators.is_empty() {
+            debug!(
+                epoch = epoch_set.epoch,
+                waiting = validators.len(),
+                "Validators outside the epoch set wait for the next epoch"
+            );
+        }
+
+        self.update_from_chain(members, block_number)
+    }
+
+    /// Mark a validator jailed or unjailed ahead of the next resync
+    ///
+    /// Jailing is not deferred to the epoch boundary. Returns `false` if the
+    /// validator is unknown.
+    pub fn set_jailed(&mut self, address: &Address, jailed: bool) -> bool {
+        let Some(validator) = self.validators.get_mut(address) else {
+            return false;
+        };
+        validator.jailed = jailed;
+
+        if jailed {
+            if let Some(index) = self.active_validators.iter().position(|addr| addr == address) {
+                self.active_validators.remove(index);
+                self.total_voting_power -= validator.power;
+                if self.proposer_index > index || self.proposer_index >= self.active_validators.len() {
+                    self.proposer_index = self.proposer_index.saturating_sub(1);
+                }
+                if self.current_proposer == Some(*address) {
+                    self.current_proposer = None;
+                }
+            }
+        }
+        true
+    }
+
+    /// Local state to persist across restarts
+    pub fn snapshot(&self) -> ValidatorSetSnapshot {
+        let mut validators: Vec<ValidatorInfo> = self.validators.values().cloned().collect();
+        validators.sort_by_key(|v| v.validator);
+
+        ValidatorSetSnapshot {
+            validators,
+            active_validators: self.active_validators.clone(),
+            current_proposer: self.current_proposer,
+            proposer_index: self.proposer_index,
+            last_update_block: self.last_update_block,
+        }
+    }
+
+    /// Restore local state persisted by [`snapshot`](Self::snapshot)
+    pub fn restore(&mut self, snapshot: ValidatorSetSnapshot) {
+        self.validators = snapshot.validators.into_iter().map(|v| (v.validator, v)).collect();
+        self.active_validators = snapshot
+            .active_validators
+            .into_iter()
+            .filter(|addr| self.is_active_validator(addr))
+            .collect();
+        self.total_voting_power = self
+            .active_validators
+            .iter()
+            .filter_map(|addr| self.validators.get(addr))
+            .map(|v| v.power)
+            .sum();
+        self.current_proposer = snapshot.current_proposer;
+        self.proposer_index = snapshot.proposer_index.min(self.active_validators.len().saturating_sub(1));
+        self.last_update_block = snapshot.last_update_block;
+
+        info!(
+            active_count = self.active_validators.len(),
+            proposer = ?self.current_proposer,
+            block = self.last_update_block,
+            "Validator set restored"
+        );
+    }
+
     /// Get validator info by address
     pub fn get_validator(&self, address: &Address) -> Option<&ValidatorInfo> {
         self.validators.get(address)
@@ -128,6 +240,22 @@ impl ValidatorSet {
         (self.total_voting_power * 2) / 3 + 1
     }
 
+    /// Active validators and powers as a [`ValidatorSetUpdate`]
+    pub fn to_update(&self, epoch: u64, block_number: u64) -> ValidatorSetUpdate {
+        ValidatorSetUpdate {
+            epoch,
+            validators: self.active_validators.clone(),
+            powers: self
+                .active_validators
+                .iter()
+                .map(|addr| self.validators.get(addr).map_or(0, ValidatorInfo::voting_power))
+                .collect(),
+            total_power: self.total_voting_power,
+            block_number,
+            timestamp: 0,
+        }
+    }
+
     /// Select next proposer using weighted round-robin (CometBFT algorithm)
     ///
     /// # Algorithm
@@ -355,6 +483,39 @@ impl Default for ValidatorSet {
     }
 }
 
+/// Keep locally tracked priority and counters of a re-read validator
+fn merge_local_stats(validator: &mut ValidatorInfo, local: &ValidatorInfo) {
+    validator.accumulated_priority = local.accumulated_priority;
+    validator.total_blocks_produced = validator.total_blocks_produced.max(local.total_blocks_produced);
+    validator.total_blocks_missed = validator.total_blocks_missed.max(local.total_blocks_missed);
+    validator.last_block_produced = validator.last_block_produced.max(local.last_block_produced);
+
+    let total_blocks = validator.total_blocks_produced + validator.total_blocks_missed;
+    if total_blocks > 0 {
+        validator.uptime = ((validator.total_blocks_produced * 10000) / total_blocks) as u16;
+    }
+}
+
+/// Persisted local state of a [`ValidatorSet`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSetSnapshot {
+    /// All known validators, ordered by address
+    pub validators: Vec<ValidatorInfo>,
+
+    /// Active validators in rotation order
+    pub active_validators: Vec<Address>,
+
+    /// Current proposer
+    pub current_proposer: Option<Address>,
+
+    /// Index of the current proposer in `active_validators`
+    pub proposer_index: usize,
+
+    /// Last block where the set was updated
+    pub last_update_block: u64,
+}
+
 /// Statistics about the validator set
 #[derive(Debug, Clone)]
 pub struct ValidatorSetStats {
@@ -502,6 +663,63 @@ mod tests {
         assert_eq!(validator.uptime, 5000); // 50%
     }
 
+    #[test]
+    fn test_resync_keeps_local_state() {
+        let mut set = ValidatorSet::new();
+        let addr_a = Address::from([1u8; 20]);
+        let addr_b = Address::from([2u8; 20]);
+        let validators =
+            vec![create_test_validator(addr_a, 100, true), create_test_validator(addr_b, 300, true)];
+
+        set.update_from_chain(validators.clone(), 1).unwrap();
+        set.select_next_proposer().unwrap();
+        set.record_block_produced(&addr_a, 2).unwrap();
+        let proposer = set.current_proposer();
+
+        // Restart: restore the snapshot, then resync from chain
+        let mut restored = ValidatorSet::new();
+        restored.restore(set.snapshot());
+        assert_eq!(restored.snapshot(), set.snapshot());
+
+        restored.update_from_chain(validators, 3).unwrap();
+        assert_eq!(restored.current_proposer(), proposer);
+        assert_eq!(restored.get_validator(&addr_a).unwrap().total_blocks_produced, 1);
+        assert_eq!(
+            restored.get_validator(&addr_b).unwrap().accumulated_priority,
+            set.get_validator(&addr_b).unwrap().accumulated_priority
+        );
+    }
+
+    #[test]
+    fn test_apply_epoch_defers_new_validators() {
+        let mut set = ValidatorSet::new();
+        let addr_a = Address::from([1u8; 20]);
+        let addr_b = Address::from([2u8; 20]);
+        let epoch_set = ValidatorSetUpdate {
+            epoch: 1,
+            validators: vec![addr_a],
+            powers: vec![100],
+            total_power: 100,
+            block_number: 0,
+            timestamp: 0,
+        };
+
+        // B registered and A's power changed after the epoch started
+        let live = vec![create_test_validator(addr_a, 400, true), create_test_validator(addr_b, 300, true)];
+        set.apply_epoch(&epoch_set, live, 10).unwrap();
+        assert_eq!(set.active_validators(), &[addr_a]);
+        assert_eq!(set.total_voting_power(), 100);
+
+        // A left the live list mid-epoch but stays until the boundary
+        set.apply_epoch(&epoch_set, vec![create_test_validator(addr_b, 300, true)], 11).unwrap();
+        assert_eq!(set.active_validators(), &[addr_a]);
+
+        // Jailing applies immediately
+        assert!(set.set_jailed(&addr_a, true));
+        assert_eq!(set.active_count(), 0);
+        assert_eq!(set.total_voting_power(), 0);
+    }
+
     #[test]
     fn test_bft_threshold() {
         let mut set = ValidatorSet::new();
@@ -0,0 +1,301 @@
+//! Multi-validator scenarios run through the in-process simulator
+
+use alloy::consensus::Header;
+use alloy_primitives::{Address, B256};
+use ande_consensus::{
+    evidence::SignedBlock,
+    simulator::{SimConfig, Simulator, SIM_JAIL_BLOCKS},
+    AttestationSigner, ConsensusError, Evidence,
+};
+
+async fn simulator() -> Simulator {
+    Simulator::new(SimConfig::default()).await.expect("simulator starts")
+}
+
+#[tokio::test]
+async fn test_honest_validators_finalize_every_block() {
+    let mut sim = simulator().await;
+    sim.run(20).await.unwrap();
+
+    sim.check_liveness(0).unwrap();
+    for node in 0..4 {
+        assert_eq!(sim.finalized_height(node), Some(20));
+    }
+    assert!((1..=20).all(|height| sim.finalized_hash(height).is_some()));
+}
+
+#[tokio::test]
+async fn test_crashes_and_restart() {
+    let mut sim = simulator().await;
+    sim.run(5).await.unwrap();
+
+    // 3 of 4 equal validators keep finalizing
+    sim.crash(3);
+    sim.run(5).await.unwrap();
+    sim.check_liveness(5).unwrap();
+    assert_eq!(sim.finalized_height(0), Some(10));
+
+    // 2 of 4 cannot, and nothing conflicting is finalized
+    sim.crash(2);
+    sim.run(5).await.unwrap();
+    assert_eq!(sim.finalized_height(0), Some(10));
+
+    // Restarted validators resume from their snapshots
+    sim.restart(2).await.unwrap();
+    sim.restart(3).await.unwrap();
+    sim.run(5).await.unwrap();
+    sim.check_liveness(15).unwrap();
+    assert_eq!(sim.finalized_height(3), Some(20));
+}
+
+#[tokio::test]
+async fn test_partitions() {
+    let mut sim = simulator().await;
+
+    // An even split finalizes nothing on either side
+    sim.partition(&[&[0, 1], &[2, 3]]);
+    sim.run(5).await.unwrap();
+    assert!((0..4).all(|node| sim.finalized_height(node).is_none()));
+
+    // The side with 3 of 4 validators makes progress, the other does not
+    sim.partition(&[&[0, 1, 2], &[3]]);
+    sim.run(5).await.unwrap();
+    sim.check_liveness(5).unwrap();
+    assert_eq!(sim.finalized_height(0), Some(10));
+    assert_eq!(sim.finalized_height(3), None);
+
+    sim.heal();
+    sim.run(5).await.unwrap();
+    sim.check_liveness(10).unwrap();
+    assert_eq!(sim.finalized_height(3), Some(15));
+}
+
+#[tokio::test]
+async fn test_byzantine_minority_is_safe_and_detected() {
+    let mut sim = simulator().await;
+
+    // One validator signs two hashes per height, another sees a forked block
+    sim.equivocate(0, true);
+    sim.fork(&[1]);
+    sim.run(5).await.unwrap();
+
+    // The fork gathers 2 of 4 votes; only canonical blocks are finalized
+    assert!(sim.pending_evidence(2) > 0);
+    for height in 1..=5 {
+        assert_eq!(sim.finalized_hash(height), Some(sim.canonical_hash(height)));
+    }
+
+    // Once the fork ends, the 3 honest validators are a quorum on their own
+    sim.fork(&[]);
+    sim.run(5).await.unwrap();
+    sim.check_liveness(5).unwrap();
+}
+
+#[tokio::test]
+async fn test_delayed_validator_and_jailing() {
+    let mut sim = simulator().await;
+
+    // Attestations arriving after the age limit are dropped
+    sim.delay(3, 40);
+    sim.run(10).await.unwrap();
+    sim.check_liveness(0).unwrap();
+    assert_eq!(sim.finalized_height(0), Some(10));
+
+    // A jailed validator no longer counts: the other 3 need all their votes
+    sim.delay(3, 0);
+    sim.jail(0).await.unwrap();
+    sim.run(5).await.unwrap();
+    sim.check_liveness(10).unwrap();
+
+    sim.crash(1);
+    sim.run(5).await.unwrap();
+    assert_eq!(sim.finalized_height(2), Some(15));
+}
+
+#[tokio::test]
+async fn test_jail_countdown_and_unjail() {
+    let mut sim = simulator().await;
+    sim.run(5).await.unwrap();
+
+    sim.jail(0).await.unwrap();
+    let state = sim.engine(0).unwrap().get_state().await;
+    assert!(state.jailed);
+    assert_eq!(state.jail_release_block, 5 + SIM_JAIL_BLOCKS);
+    assert_eq!(state.jail_blocks_remaining, SIM_JAIL_BLOCKS);
+
+    // The contract refuses an early unjail
+    assert!(sim.unjail(0).await.is_err());
+
+    sim.run(SIM_JAIL_BLOCKS).await.unwrap();
+    sim.sync_all().await.unwrap();
+    assert_eq!(sim.engine(0).unwrap().get_state().await.jail_blocks_remaining, 0);
+
+    // Once released the validator is back in the active set and attests again
+    sim.unjail(0).await.unwrap();
+    let state = sim.engine(0).unwrap().get_state().await;
+    assert!(!state.jailed);
+    assert_eq!(state.active_validators, 4);
+    sim.run(5).await.unwrap();
+    sim.check_liveness(15).unwrap();
+}
+
+#[tokio::test]
+async fn test_epoch_transition_applies_queued_changes() {
+    let mut sim = simulator().await;
+    sim.run(5).await.unwrap();
+
+    // A mid-epoch stake change is queued; the epoch keeps its set
+    assert!(sim.source().update_validator(sim.address(3), |validator| validator.power = 300));
+    sim.sync_all().await.unwrap();
+    let epoch = sim.engine(0).unwrap().current_epoch_info().unwrap();
+    assert_eq!((epoch.epoch_number, epoch.total_voting_power), (1, 400));
+    assert!(sim.engine(0).unwrap().pending_validator_set().is_some());
+
+    // Under the old powers 3 of 4 validators still finalize
+    sim.crash(3);
+    sim.run(2).await.unwrap();
+    assert_eq!(sim.finalized_height(0), Some(7));
+    sim.restart(3).await.unwrap();
+
+    // At the boundary every node switches to the queued set and closes epoch 1
+    assert_eq!(sim.advance_epoch().await.unwrap(), 2);
+    for node in 0..4 {
+        let engine = sim.engine(node).unwrap();
+        let epoch = engine.current_epoch_info().unwrap();
+        assert_eq!((epoch.epoch_number, epoch.start_block, epoch.total_voting_power), (2, 7, 600));
+        assert_eq!(engine.epoch_info(1).unwrap().end_block, 6);
+        assert!(engine.pending_validator_set().is_none());
+    }
+
+    // Now the other three hold only half the power
+    sim.crash(3);
+    sim.run(3).await.unwrap();
+    assert_eq!(sim.finalized_height(0), Some(7));
+
+    sim.restart(3).await.unwrap();
+    sim.run(2).await.unwrap();
+    assert_eq!(sim.finalized_height(0), Some(12));
+}
+
+#[tokio::test]
+async fn test_forced_rotation_hands_off_proposer() {
+    let mut sim = simulator().await;
+    sim.run(5).await.unwrap();
+    let timed_out = sim.engine(0).unwrap().scheduled_proposer(6).unwrap();
+
+    // Every node follows the rotation forced on the coordinator at block 5
+    let rotation = sim.source().force_rotation();
+    assert_eq!(rotation.block_number, 5);
+    let mut next = Vec::new();
+    for node in 0..4 {
+        next.push(sim.engine(node).unwrap().force_rotation("test").await.unwrap());
+    }
+    assert!(next.iter().all(|proposer| *proposer == next[0]));
+    assert_ne!(next[0], timed_out);
+
+    let engine = sim.engine(0).unwrap();
+    assert_eq!(engine.scheduled_proposer(6), Some(next[0]));
+    assert_eq!(engine.get_state().await.current_proposer, next[0]);
+    let rotation = engine.current_rotation_info().unwrap();
+    assert_eq!((rotation.start_block, rotation.leader), (5, next[0]));
+    assert!(engine.rotation_history().iter().any(|rotation| !rotation.completed_successfully));
+    assert!(matches!(
+        engine.verify_scheduled_proposer(6, timed_out),
+        Err(ConsensusError::InvalidProposer { .. })
+    ));
+
+    // A node that restarts picks the rotation up from chain state
+    sim.crash(1);
+    sim.restart(1).await.unwrap();
+    assert_eq!(sim.engine(1).unwrap().scheduled_proposer(6), Some(next[0]));
+}
+
+#[tokio::test]
+async fn test_imported_blocks_follow_the_schedule() {
+    let mut sim = simulator().await;
+    sim.run(3).await.unwrap();
+
+    let engine = sim.engine(0).unwrap();
+    let proposer = engine.scheduled_proposer(4).unwrap();
+    let scheduled = (0..4).find(|node| sim.address(*node) == proposer).unwrap();
+    let other = (scheduled + 1) % 4;
+    engine.verify_scheduled_proposer(4, proposer).unwrap();
+    assert!(matches!(
+        engine.verify_scheduled_proposer(4, sim.address(other)),
+        Err(ConsensusError::InvalidProposer { .. })
+    ));
+    assert!(matches!(
+        engine.verify_scheduled_proposer(1_000_000, proposer),
+        Err(ConsensusError::UnscheduledHeight(1_000_000))
+    ));
+
+    // The seal, not the beneficiary field, decides who produced the block
+    let sealed_by = async |node: usize, beneficiary: Address| {
+        let mut header = Header { number: 4, beneficiary, ..Header::default() };
+        sim.engine(node).unwrap().seal_header(&mut header).await.unwrap();
+        header
+    };
+    assert_eq!(engine.verify_sealed_proposer(&sealed_by(scheduled, proposer).await).unwrap(), proposer);
+    assert!(matches!(
+        engine.verify_sealed_proposer(&sealed_by(other, proposer).await),
+        Err(ConsensusError::InvalidSeal(_))
+    ));
+    assert!(matches!(
+        engine.verify_sealed_proposer(&sealed_by(other, sim.address(other)).await),
+        Err(ConsensusError::InvalidProposer { .. })
+    ));
+    let unsealed = Header { number: 4, beneficiary: proposer, ..Header::default() };
+    assert!(matches!(engine.verify_sealed_proposer(&unsealed), Err(ConsensusError::InvalidSeal(_))));
+}
+
+#[tokio::test]
+async fn test_attestation_and_evidence_intake() {
+    let mut sim = simulator().await;
+    sim.run(3).await.unwrap();
+    let engine = sim.engine(0).unwrap();
+    let hash = B256::repeat_byte(0xee);
+
+    // Outsiders cannot attest, and their double votes are not evidence
+    let outsider = AttestationSigner::from_bytes(&[0x42; 32], 6174).unwrap();
+    for byte in [1, 2] {
+        let attestation = outsider.sign(1, 3, B256::repeat_byte(byte), 100).await.unwrap();
+        assert!(matches!(engine.submit_attestation(attestation), Err(ConsensusError::ValidatorNotFound(_))));
+    }
+    assert_eq!(sim.pending_evidence(0), 0);
+
+    // Votes far past the local head are refused before anything is recorded
+    let far = sim.signer(1).sign(1, 1_000, hash, 100).await.unwrap();
+    assert!(matches!(engine.submit_attestation(far), Err(ConsensusError::FutureAttestation { .. })));
+    assert_eq!(sim.pending_evidence(0), 0);
+
+    // A member voting for a second hash is caught
+    let first = sim.signer(1).sign(1, 3, sim.canonical_hash(3), 100).await.unwrap();
+    let second = sim.signer(1).sign(1, 3, hash, 100).await.unwrap();
+    assert!(engine.submit_attestation(second.clone()).is_err());
+    assert_eq!(sim.pending_evidence(0), 1);
+
+    // Evidence from other nodes must name a member and verify
+    let evidence = Evidence::DuplicateAttestation {
+        validator: sim.address(1),
+        block_number: 3,
+        epoch: 1,
+        first: SignedBlock { block_hash: first.block_hash, signature: first.signature.clone() },
+        second: SignedBlock { block_hash: second.block_hash, signature: second.signature.clone() },
+    };
+    sim.engine(2).unwrap().submit_evidence(evidence.clone()).unwrap();
+    assert_eq!(sim.pending_evidence(2), 1);
+
+    let Evidence::DuplicateAttestation { first, second, .. } = evidence else { unreachable!() };
+    let forged = Evidence::DuplicateAttestation { validator: sim.address(2), block_number: 3, epoch: 1, first, second };
+    assert!(matches!(sim.engine(3).unwrap().submit_evidence(forged), Err(ConsensusError::InvalidEvidence(_))));
+
+    // Proposals by outsiders are refused; members off schedule are reported
+    let proposal = outsider.sign_proposal(4, hash).await.unwrap();
+    assert!(matches!(sim.engine(3).unwrap().submit_proposal(&proposal), Err(ConsensusError::ValidatorNotFound(_))));
+
+    let scheduled = sim.engine(3).unwrap().scheduled_proposer(4).unwrap();
+    let off_schedule = (0..4).find(|node| sim.address(*node) != scheduled).unwrap();
+    let proposal = sim.signer(off_schedule).sign_proposal(4, hash).await.unwrap();
+    sim.engine(3).unwrap().submit_proposal(&proposal).unwrap();
+    assert_eq!(sim.pending_evidence(3), 1);
+}
@@ -1,169 +1,565 @@
 //! Block Attestation Module
 //!
-//! Handles signing and attesting blocks to the AndeConsensusV2 contract.
+//! Signs every canonical block and attests it on the AndeConsensus
+//! contract, tracking each attestation until it is included:
+//!
+//! ```text
+//! canonical block n
+//!   └─ validator active and not jailed? ── no ──→ idle
+//!        └─ sign eip191(keccak256(uint256 n ‖ hash)) → Queued
+//!
+//! Queued ──send──→ Submitted ──included after CONFIRMATION_BLOCKS──→ Confirmed
+//!   ▲                  │
+//!   │            not included / send failed
+//!   │                  ▼
+//!   └──retry_at── Failed (backoff doubles) ──MAX_ATTEMPTS / too old──→ Abandoned
+//! ```
+//!
+//! `attestBlock` adds voting power on every call, so before resending the
+//! attester checks `getAttestations` and only resends when its attestation
+//! is missing. Signatures go through slashing protection: a reorged height
+//! is never signed with a second hash, since `slashDoubleSign` would
+//! punish exactly that.
 
 use alloy::{
-    primitives::{keccak256, Bytes, B256},
+    primitives::{keccak256, Address, Bytes, B256, U256},
     signers::local::PrivateKeySigner,
 };
+use ande_consensus::{
+    bindings::IAndeConsensus::IAndeConsensusErrors,
+    slashing_protection::SigningKind,
+    SigningBackend, SlashingProtection,
+};
 use eyre::Result;
-use std::sync::Arc;
-use tracing::{debug, info};
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    consensus_client::{revert_reason, AndeConsensusClient},
+    consensus_config::ConsensusConfig,
+};
+
+/// Slashing protection file for on-chain attestations inside the data dir
+pub const ATTESTATION_PROTECTION_FILE: &str = "onchain-attestation-protection.json";
+
+/// Blocks to wait before checking that a submitted attestation was included
+pub const CONFIRMATION_BLOCKS: u64 = 6;
+
+/// Submission attempts before an attestation is abandoned
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// Blocks to wait before the first retry, doubled on each further failure
+pub const RETRY_BASE_BLOCKS: u64 = 2;
+
+/// Blocks after which an unconfirmed attestation is abandoned
+pub const MAX_ATTESTATION_AGE: u64 = 256;
 
-use crate::consensus_client::AndeConsensusClient;
+/// Blocks between checks that this validator is active
+pub const ACTIVITY_REFRESH_BLOCKS: u64 = 32;
+
+/// Submission status of one attestation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationStatus {
+    /// Signed, not sent yet
+    Queued,
+    /// Transaction sent at `at_block`
+    Submitted {
+        /// Attestation transaction
+        tx_hash: B256,
+        /// Canonical block when it was sent
+        at_block: u64,
+    },
+    /// Recorded by the contract
+    Confirmed,
+    /// Last attempt failed, retried at `retry_at`
+    Failed {
+        /// Canonical block from which it is retried
+        retry_at: u64,
+        /// Why the last attempt failed
+        error: String,
+    },
+    /// Given up
+    Abandoned {
+        /// Why it was given up
+        error: String,
+    },
+}
+
+/// An attestation and its submission status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedAttestation {
+    /// Attested block hash
+    pub block_hash: B256,
+    /// 65-byte signature sent to the contract
+    pub signature: Bytes,
+    /// Submission attempts so far
+    pub attempts: u32,
+    /// Current status
+    pub status: AttestationStatus,
+}
+
+/// Work the tracker hands out for a canonical block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationAction {
+    /// Send (or resend) the attestation of `block_number`
+    Submit {
+        /// Attested block number
+        block_number: u64,
+        /// Attested block hash
+        block_hash: B256,
+        /// Signature to send
+        signature: Bytes,
+        /// Attempt number, starting at 1
+        attempt: u32,
+    },
+    /// Check that the attestation of `block_number` was included
+    Check {
+        /// Attested block number
+        block_number: u64,
+        /// Attested block hash
+        block_hash: B256,
+    },
+}
+
+/// Submission state of this validator's attestations, by block number
+///
+/// Pure state machine; [`BlockAttester`] performs the actions it hands out.
+#[derive(Debug, Default)]
+pub struct AttestationTracker {
+    /// Attestations by block number
+    entries: BTreeMap<u64, TrackedAttestation>,
+}
+
+impl AttestationTracker {
+    /// Start tracking a signed attestation; a tracked height is kept as is
+    pub fn track(&mut self, block_number: u64, block_hash: B256, signature: Bytes) {
+        self.entries.entry(block_number).or_insert(TrackedAttestation {
+            block_hash,
+            signature,
+            attempts: 0,
+            status: AttestationStatus::Queued,
+        });
+    }
+
+    /// Actions due at canonical block `current`
+    ///
+    /// Counts an attempt for every submission handed out, and abandons
+    /// attestations that are too old or ran out of attempts.
+    pub fn next_actions(&mut self, current: u64) -> Vec<AttestationAction> {
+        let mut actions = Vec::new();
+        for (&block_number, entry) in &mut self.entries {
+            if matches!(
+                entry.status,
+                AttestationStatus::Confirmed | AttestationStatus::Abandoned { .. }
+            ) {
+                continue;
+            }
+            if block_number.saturating_add(MAX_ATTESTATION_AGE) < current {
+                entry.status = AttestationStatus::Abandoned {
+                    error: format!("not included within {MAX_ATTESTATION_AGE} blocks"),
+                };
+                continue;
+            }
+
+            let submit = match &entry.status {
+                AttestationStatus::Queued => true,
+                AttestationStatus::Failed { retry_at, .. } => *retry_at <= current,
+                AttestationStatus::Submitted { at_block, .. } => {
+                    if at_block.saturating_add(CONFIRMATION_BLOCKS) <= current {
+                        actions.push(AttestationAction::Check {
+                            block_number,
+                            block_hash: entry.block_hash,
+                        });
+                    }
+                    false
+                }
+                AttestationStatus::Confirmed | AttestationStatus::Abandoned { .. } => false,
+            };
+            if !submit {
+                continue;
+            }
+
+            if entry.attempts >= MAX_ATTEMPTS {
+                let last = match &entry.status {
+                    AttestationStatus::Failed { error, .. } => error.clone(),
+                    _ => String::new(),
+                };
+                entry.status = AttestationStatus::Abandoned {
+                    error: format!("{MAX_ATTEMPTS} attempts failed, last: {last}"),
+                };
+                continue;
+            }
+            entry.attempts += 1;
+            actions.push(AttestationAction::Submit {
+                block_number,
+                block_hash: entry.block_hash,
+                signature: entry.signature.clone(),
+                attempt: entry.attempts,
+            });
+        }
+        actions
+    }
+
+    /// Record a sent attestation transaction
+    pub fn submitted(&mut self, block_number: u64, tx_hash: B256, at_block: u64) {
+        self.set(block_number, AttestationStatus::Submitted { tx_hash, at_block });
+    }
+
+    /// Record that the contract holds the attestation
+    pub fn confirmed(&mut self, block_number: u64) {
+        self.set(block_number, AttestationStatus::Confirmed);
+    }
+
+    /// Record a failed attempt, backing off exponentially from `at_block`
+    pub fn failed(&mut self, block_number: u64, at_block: u64, error: String) {
+        let Some(entry) = self.entries.get_mut(&block_number) else {
+            return;
+        };
+        let backoff = RETRY_BASE_BLOCKS << entry.attempts.saturating_sub(1).min(16);
+        entry.status = AttestationStatus::Failed { retry_at: at_block.saturating_add(backoff), error };
+    }
+
+    /// Give up on an attestation
+    pub fn abandon(&mut self, block_number: u64, error: String) {
+        self.set(block_number, AttestationStatus::Abandoned { error });
+    }
+
+    /// Tracked attestation of `block_number`
+    pub fn get(&self, block_number: u64) -> Option<&TrackedAttestation> {
+        self.entries.get(&block_number)
+    }
+
+    /// Attestations neither confirmed nor abandoned
+    pub fn pending_count(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|e| {
+                !matches!(e.status, AttestationStatus::Confirmed | AttestationStatus::Abandoned { .. })
+            })
+            .count()
+    }
+
+    /// Forget attestations older than [`MAX_ATTESTATION_AGE`] blocks
+    ///
+    /// Returns the forgotten attestations that were never confirmed.
+    pub fn prune(&mut self, current: u64) -> Vec<(u64, TrackedAttestation)> {
+        let keep = self.entries.split_off(&current.saturating_sub(MAX_ATTESTATION_AGE));
+        let pruned = std::mem::replace(&mut self.entries, keep);
+        pruned
+            .into_iter()
+            .filter(|(_, e)| e.status != AttestationStatus::Confirmed)
+            .collect()
+    }
+
+    /// Replace the status of a tracked attestation
+    fn set(&mut self, block_number: u64, status: AttestationStatus) {
+        if let Some(entry) = self.entries.get_mut(&block_number) {
+            entry.status = status;
+        }
+    }
+}
+
+/// Whether this validator may attest, as of a block
+#[derive(Debug, Clone, Copy)]
+struct Activity {
+    /// Active and not jailed
+    active: bool,
+    /// Block of the last check
+    checked_at: u64,
+}
 
 /// Block attester for signing and submitting blocks to consensus contract
+#[derive(Debug)]
 pub struct BlockAttester {
-    /// Signer for creating ECDSA signatures
-    signer: PrivateKeySigner,
-    /// Consensus client for submitting proposals
+    /// Key the attestations are signed with
+    backend: Arc<dyn SigningBackend>,
+    /// Consensus client sending the attestations
     consensus_client: Arc<AndeConsensusClient>,
+    /// Refuses a second hash at an attested height
+    protection: SlashingProtection,
+    /// Submission state of each attestation
+    tracker: Mutex<AttestationTracker>,
+    /// Last activity check, `None` until one succeeds
+    activity: Mutex<Option<Activity>>,
 }
 
 impl BlockAttester {
     /// Create a new block attester
     ///
     /// # Arguments
-    /// * `signer` - Private key signer for attestations
-    /// * `consensus_client` - Consensus contract client
-    pub fn new(signer: PrivateKeySigner, consensus_client: Arc<AndeConsensusClient>) -> Self {
+    /// * `backend` - Key for attestations
+    /// * `consensus_client` - Consensus contract client, sending as the same key
+    /// * `protection_file` - Slashing protection records for these attestations
+    pub fn new(
+        backend: Arc<dyn SigningBackend>,
+        consensus_client: Arc<AndeConsensusClient>,
+        protection_file: &Path,
+    ) -> Result<Self> {
+        let protection = SlashingProtection::open_file(protection_file)?;
         info!(
             "BlockAttester initialized with signer address: {:?}",
-            signer.address()
+            backend.address()
         );
-        Self {
-            signer,
+        Ok(Self {
+            backend,
             consensus_client,
-        }
+            protection,
+            tracker: Mutex::new(AttestationTracker::default()),
+            activity: Mutex::new(None),
+        })
     }
 
-    /// Attest a block by signing and submitting to consensus contract
+    /// Create from the consensus configuration, keeping records in `data_dir`
     ///
-    /// # Arguments
-    /// * `block_number` - Block number to attest
-    /// * `block_hash` - Hash of the block
-    ///
-    /// # Returns
-    /// Transaction hash of the attestation
-    pub async fn attest_block(&self, block_number: u64, block_hash: B256) -> Result<B256> {
-        debug!(
-            "Attesting block {} with hash {:?}",
-            block_number, block_hash
-        );
+    /// The key must be local: attestations are sent as transactions.
+    pub fn from_config(config: &ConsensusConfig, data_dir: &Path) -> Result<Self> {
+        let key = config
+            .local_key()?
+            .ok_or_else(|| eyre::eyre!("No sequencer key configured for attestations"))?;
+        let secret = key
+            .secret_bytes()
+            .ok_or_else(|| eyre::eyre!("Attestation key cannot sign transactions"))?;
+        let signer = PrivateKeySigner::from_slice(&secret)?;
 
-        // 1. Create message to sign (blockNumber || blockHash)
-        let message = Self::create_attestation_message(block_number, block_hash);
-        debug!("Attestation message hash: {:?}", message);
+        let client = AndeConsensusClient::new(&config.rpc_url, config.consensus_address, Some(signer))?;
+        Self::new(Arc::new(key), Arc::new(client), &data_dir.join(ATTESTATION_PROTECTION_FILE))
+    }
 
-        // 2. Sign the message
-        let signature = self.sign_message(message).await?;
-        debug!("Signature created: {} bytes", signature.len());
+    /// Get the signer's address
+    pub fn address(&self) -> Address {
+        self.backend.address()
+    }
 
-        // 3. Submit to consensus contract
-        let tx_hash = self
-            .consensus_client
-            .propose_block(block_number, block_hash, signature)
-            .await?;
+    /// Attest a canonical block and advance pending attestations
+    ///
+    /// Does nothing while this validator is not active. Failures are
+    /// tracked and retried on later blocks rather than returned.
+    pub async fn on_canonical_block(&self, block_number: u64, block_hash: B256) {
+        if !self.is_active(block_number).await {
+            debug!(block = block_number, "Not an active validator, skipping attestation");
+            return;
+        }
 
-        info!(
-            "Block {} attested successfully, tx: {:?}",
-            block_number, tx_hash
-        );
+        match self.sign_attestation(block_number, block_hash).await {
+            Ok(signature) => self.tracker().track(block_number, block_hash, signature),
+            Err(e) => warn!(block = block_number, "Failed to sign attestation: {}", e),
+        }
 
-        Ok(tx_hash)
+        self.process(block_number).await;
     }
 
-    /// Create attestation message hash
-    ///
-    /// Message format: keccak256(abi.encodePacked(blockNumber, blockHash))
-    fn create_attestation_message(block_number: u64, block_hash: B256) -> B256 {
-        let mut message = Vec::new();
-        message.extend_from_slice(&block_number.to_be_bytes());
-        message.extend_from_slice(block_hash.as_slice());
+    /// Attestations neither confirmed nor abandoned
+    pub fn pending_count(&self) -> usize {
+        self.tracker().pending_count()
+    }
 
-        keccak256(&message)
+    /// Status of the attestation of `block_number`, if tracked
+    pub fn status(&self, block_number: u64) -> Option<AttestationStatus> {
+        self.tracker().get(block_number).map(|e| e.status.clone())
     }
 
-    /// Sign a message using eth_sign format
-    ///
-    /// This creates an Ethereum signed message hash and signs it.
-    async fn sign_message(&self, message_hash: B256) -> Result<Bytes> {
-        // Ethereum signed message: "\x19Ethereum Signed Message:\n32" + message_hash
-        let eth_message = Self::eth_signed_message_hash(message_hash);
+    /// Perform the actions due at `current`
+    async fn process(&self, current: u64) {
+        let actions = self.tracker().next_actions(current);
+        for action in actions {
+            match action {
+                AttestationAction::Submit { block_number, block_hash, signature, attempt } => {
+                    if attempt > 1 && self.is_included(block_number, block_hash).await {
+                        continue;
+                    }
+                    self.submit(block_number, block_hash, signature, current).await;
+                }
+                AttestationAction::Check { block_number, block_hash } => {
+                    if !self.is_included(block_number, block_hash).await {
+                        self.tracker().failed(
+                            block_number,
+                            current,
+                            format!("not included after {CONFIRMATION_BLOCKS} blocks"),
+                        );
+                    }
+                }
+            }
+        }
+
+        let pruned = self.tracker().prune(current);
+        for (block_number, entry) in pruned {
+            warn!(block = block_number, status = ?entry.status, "Attestation never confirmed");
+        }
+    }
 
-        // Sign the message
-        let signature = self.signer.sign_hash(&eth_message).await?;
+    /// Send an attestation transaction
+    async fn submit(&self, block_number: u64, block_hash: B256, signature: Bytes, current: u64) {
+        match self.consensus_client.attest_block(block_number, block_hash, signature).await {
+            Ok(tx_hash) => {
+                debug!(block = block_number, tx = ?tx_hash, "Attestation submitted");
+                self.tracker().submitted(block_number, tx_hash, current);
+            }
+            Err(e) => match revert_reason(&e) {
+                Some(
+                    IAndeConsensusErrors::ValidatorNotActive(_)
+                    | IAndeConsensusErrors::ValidatorIsJailed(_)
+                    | IAndeConsensusErrors::ValidatorNotFound(_),
+                ) => {
+                    warn!(block = block_number, "Attestation rejected, validator not active: {}", e);
+                    self.tracker().abandon(block_number, e.to_string());
+                    *self.activity() = Some(Activity { active: false, checked_at: current });
+                }
+                Some(IAndeConsensusErrors::InvalidSignature(_)) => {
+                    warn!(block = block_number, "Attestation signature rejected: {}", e);
+                    self.tracker().abandon(block_number, e.to_string());
+                }
+                _ => {
+                    warn!(block = block_number, "Failed to submit attestation: {}", e);
+                    self.tracker().failed(block_number, current, e.to_string());
+                }
+            },
+        }
+    }
 
-        // Convert to bytes (r + s + v format)
-        let mut sig_bytes = Vec::new();
-        sig_bytes.extend_from_slice(signature.r().as_le_slice());
-        sig_bytes.extend_from_slice(signature.s().as_le_slice());
-        sig_bytes.push(signature.v().y_parity_byte());
+    /// Whether the contract holds our attestation, marking it confirmed if so
+    async fn is_included(&self, block_number: u64, block_hash: B256) -> bool {
+        match self.consensus_client.has_attested(block_hash, self.address()).await {
+            Ok(true) => {
+                debug!(block = block_number, "Attestation confirmed");
+                self.tracker().confirmed(block_number);
+                true
+            }
+            Ok(false) => false,
+            Err(e) => {
+                debug!(block = block_number, "Failed to check attestation: {}", e);
+                false
+            }
+        }
+    }
 
-        Ok(Bytes::from(sig_bytes))
+    /// Whether this validator is active and not jailed, checked every
+    /// [`ACTIVITY_REFRESH_BLOCKS`] blocks
+    async fn is_active(&self, current: u64) -> bool {
+        let cached = *self.activity();
+        if let Some(activity) = cached {
+            if current < activity.checked_at.saturating_add(ACTIVITY_REFRESH_BLOCKS) {
+                return activity.active;
+            }
+        }
+
+        let active = match self.consensus_client.get_validator_info(self.address()).await {
+            Ok(info) => info.active && !info.jailed,
+            Err(e) if matches!(revert_reason(&e), Some(IAndeConsensusErrors::ValidatorNotFound(_))) => false,
+            Err(e) => {
+                debug!("Failed to check validator status: {}", e);
+                return cached.is_some_and(|a| a.active);
+            }
+        };
+        if cached.map(|a| a.active) != Some(active) {
+            info!(validator = ?self.address(), active, "On-chain attestation status changed");
+        }
+        *self.activity() = Some(Activity { active, checked_at: current });
+        active
     }
 
-    /// Create Ethereum signed message hash
-    ///
-    /// Format: keccak256("\x19Ethereum Signed Message:\n32" + message_hash)
-    fn eth_signed_message_hash(message_hash: B256) -> B256 {
-        let mut eth_message = Vec::new();
-        eth_message.extend_from_slice(b"\x19Ethereum Signed Message:\n32");
-        eth_message.extend_from_slice(message_hash.as_slice());
+    /// Sign the attestation of a block, through slashing protection
+    async fn sign_attestation(&self, block_number: u64, block_hash: B256) -> Result<Bytes> {
+        self.protection.check_and_record(SigningKind::Attestation, block_number, block_hash)?;
+        let signature = self.backend.sign_message(&attestation_message(block_number, block_hash)).await?;
+        Ok(Bytes::copy_from_slice(&signature.as_bytes()))
+    }
 
-        keccak256(&eth_message)
+    /// Attestation tracker
+    fn tracker(&self) -> std::sync::MutexGuard<'_, AttestationTracker> {
+        self.tracker.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    /// Get the signer's address
-    pub fn address(&self) -> alloy::primitives::Address {
-        self.signer.address()
+    /// Last activity check
+    fn activity(&self) -> std::sync::MutexGuard<'_, Option<Activity>> {
+        self.activity.lock().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
+/// Digest signed for an attestation
+///
+/// The eth-signed hash of `keccak256(abi.encodePacked(uint256 blockNumber,
+/// bytes32 blockHash))`, as `AndeConsensus.attestBlock` recovers it.
+pub fn attestation_digest(block_number: u64, block_hash: B256) -> B256 {
+    keccak256(attestation_message(block_number, block_hash))
+}
+
+/// Preimage of [`attestation_digest`]: the EIP-191 prefixed hash of the
+/// packed block number and hash
+pub fn attestation_message(block_number: u64, block_hash: B256) -> Vec<u8> {
+    let mut packed = [0u8; 64];
+    packed[..32].copy_from_slice(&U256::from(block_number).to_be_bytes::<32>());
+    packed[32..].copy_from_slice(block_hash.as_slice());
+    let mut message = b"\x19Ethereum Signed Message:\n32".to_vec();
+    message.extend_from_slice(keccak256(packed).as_slice());
+    message
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy::primitives::address;
+    use alloy::primitives::{eip191_hash_message, Signature};
+    use ande_consensus::LocalKey;
 
-    #[test]
-    fn test_attestation_message() {
-        let block_number = 12345u64;
-        let block_hash = B256::from([1u8; 32]);
+    #[tokio::test]
+    async fn test_attestation_signature_recovers() {
+        let key = LocalKey::from_bytes(&[7u8; 32]).unwrap();
+        let hash = B256::repeat_byte(1);
+        let digest = attestation_digest(12345, hash);
 
-        let message = BlockAttester::create_attestation_message(block_number, block_hash);
+        let packed = [U256::from(12345u64).to_be_bytes::<32>().as_slice(), hash.as_slice()].concat();
+        assert_eq!(digest, eip191_hash_message(keccak256(packed)));
 
-        // Verify message is deterministic
-        let message2 = BlockAttester::create_attestation_message(block_number, block_hash);
-        assert_eq!(message, message2);
+        let signature = key.sign_message(&attestation_message(12345, hash)).await.unwrap();
+        let bytes = signature.as_bytes();
+        assert!(bytes[64] == 27 || bytes[64] == 28);
 
-        // Verify different inputs produce different hashes
-        let message3 = BlockAttester::create_attestation_message(block_number + 1, block_hash);
-        assert_ne!(message, message3);
+        let decoded = Signature::try_from(&bytes[..]).unwrap();
+        assert_eq!(decoded.recover_address_from_prehash(&digest).unwrap(), key.address());
+        assert_ne!(digest, attestation_digest(12346, hash));
     }
 
     #[test]
-    fn test_eth_signed_message_hash() {
-        let message_hash = B256::from([1u8; 32]);
-        let eth_hash = BlockAttester::eth_signed_message_hash(message_hash);
+    fn test_tracker_confirms_after_check() {
+        let mut tracker = AttestationTracker::default();
+        tracker.track(10, B256::repeat_byte(1), Bytes::from_static(&[1]));
 
-        // Verify deterministic
-        let eth_hash2 = BlockAttester::eth_signed_message_hash(message_hash);
-        assert_eq!(eth_hash, eth_hash2);
+        let actions = tracker.next_actions(10);
+        assert!(matches!(actions[..], [AttestationAction::Submit { block_number: 10, attempt: 1, .. }]));
+        tracker.submitted(10, B256::repeat_byte(9), 10);
 
-        // Verify format
-        assert_ne!(eth_hash, message_hash); // Should be different after wrapping
+        assert!(tracker.next_actions(10 + CONFIRMATION_BLOCKS - 1).is_empty());
+        let actions = tracker.next_actions(10 + CONFIRMATION_BLOCKS);
+        assert!(matches!(actions[..], [AttestationAction::Check { block_number: 10, .. }]));
+
+        tracker.confirmed(10);
+        assert!(tracker.next_actions(20).is_empty());
+        assert_eq!(tracker.pending_count(), 0);
     }
 
-    #[tokio::test]
-    async fn test_signer_address() {
-        // Create a test private key
-        let signer = PrivateKeySigner::random();
-        let expected_address = signer.address();
+    #[test]
+    fn test_tracker_backs_off_then_abandons() {
+        let mut tracker = AttestationTracker::default();
+        tracker.track(1, B256::repeat_byte(1), Bytes::new());
 
-        // Mock consensus client (won't actually be used in this test)
-        // In real tests, you'd use a mock or testnet
+        let mut current = 1;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let actions = tracker.next_actions(current);
+            assert!(matches!(actions[..], [AttestationAction::Submit { attempt: a, .. }] if a == attempt));
+            tracker.failed(1, current, "rpc down".to_string());
+
+            let AttestationStatus::Failed { retry_at, .. } = tracker.get(1).unwrap().status else {
+                panic!("expected failed status");
+            };
+            assert_eq!(retry_at - current, RETRY_BASE_BLOCKS << (attempt - 1));
+            assert!(tracker.next_actions(retry_at - 1).is_empty());
+            current = retry_at;
+        }
 
-        // Just verify the address is accessible
-        assert_ne!(expected_address, address!("0000000000000000000000000000000000000000"));
+        assert!(tracker.next_actions(current).is_empty());
+        assert!(matches!(tracker.get(1).unwrap().status, AttestationStatus::Abandoned { .. }));
     }
 }
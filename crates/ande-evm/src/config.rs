@@ -5,9 +5,14 @@
 //! - REVM 29.0.1 optimization techniques
 //! - Production deployment requirements
 
-use alloy_primitives::U256;
+use crate::fair_ordering::{ThresholdEncryptionConfig, TxOrderingMode};
+use crate::mev::AndeMevRedirect;
+use alloy_genesis::Genesis;
+use alloy_primitives::{Address, U256};
 use revm::primitives::hardfork::SpecId;
 use revm::context::CfgEnv;  // Reth 1.8.2 / REVM 29 correct path
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// Default maximum bytes for txpool selection (1.85 MiB)
 pub const DEFAULT_MAX_TXPOOL_BYTES: u64 = 1_939_865;
@@ -38,6 +43,100 @@ impl Default for EvolveConfig {
     }
 }
 
+/// Key of the ANDE section inside the genesis `config` object
+pub const ANDE_CHAIN_CONFIG_KEY: &str = "ande";
+
+/// Consensus-relevant ANDE settings carried by the chainspec
+///
+/// Read from the `ande` object in the genesis `config` section so that every
+/// node of a network agrees on them:
+///
+/// ```json
+/// "config": {
+///   "chainId": 6174,
+///   "ande": {
+///     "mevEnabled": true,
+///     "mevSink": "0x…",
+///     "mevActivationBlock": 1000000,
+///     "txOrdering": "commitReveal",
+///     "thresholdEncryption": {
+///       "threshold": 3,
+///       "publicKey": "0x02…",
+///       "verificationKeys": { "1": "0x03…", "2": "0x02…", … }
+///     }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AndeChainConfig {
+    /// Whether base fees are redirected to `mev_sink`
+    #[serde(default)]
+    pub mev_enabled: bool,
+    /// Address credited with redirected base fees, required if enabled
+    #[serde(default)]
+    pub mev_sink: Option<Address>,
+    /// Minimum profit classified as MEV (wei), defaults to 0.001 ETH
+    #[serde(default)]
+    pub mev_min_threshold: Option<U256>,
+    /// First block at which base fees are redirected to the MEV sink,
+    /// required if enabled
+    #[serde(default)]
+    pub mev_activation_block: Option<u64>,
+    /// Ordering policy of `txpoolExt_getTxs`
+    #[serde(default)]
+    pub tx_ordering: TxOrderingMode,
+    /// Committee key, required by the commit-reveal ordering
+    #[serde(default)]
+    pub threshold_encryption: Option<ThresholdEncryptionConfig>,
+}
+
+impl AndeChainConfig {
+    /// Load the ANDE settings from the genesis `config` section
+    ///
+    /// Returns the default (everything disabled) if the section is absent.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Self, AndeChainConfigError> {
+        let config = match genesis.config.extra_fields.get_deserialized::<Self>(ANDE_CHAIN_CONFIG_KEY) {
+            Some(Ok(config)) => config,
+            Some(Err(err)) => return Err(AndeChainConfigError::Invalid(err.to_string())),
+            None => Self::default(),
+        };
+        config.mev_redirect()?;
+        Ok(config)
+    }
+
+    /// Base-fee redirect of this network, with its activation block
+    ///
+    /// `None` unless `mevEnabled` is set. The sink credit is part of the
+    /// state transition, so it is only ever configured here and never from
+    /// node-local settings.
+    pub fn mev_redirect(&self) -> Result<Option<(AndeMevRedirect, u64)>, AndeChainConfigError> {
+        if !self.mev_enabled {
+            return Ok(None);
+        }
+
+        let sink = self
+            .mev_sink
+            .ok_or_else(|| AndeChainConfigError::Invalid("`mevEnabled` requires `mevSink`".into()))?;
+        let activation_block = self.mev_activation_block.ok_or_else(|| {
+            AndeChainConfigError::Invalid("`mevEnabled` requires `mevActivationBlock`".into())
+        })?;
+        let threshold = self.mev_min_threshold.unwrap_or(AndeMevRedirect::DEFAULT_MIN_MEV_THRESHOLD);
+        let redirect = AndeMevRedirect::try_new(sink, threshold)
+            .map_err(|_| AndeChainConfigError::Invalid("`mevSink` cannot be the zero address".into()))?;
+
+        Ok(Some((redirect, activation_block)))
+    }
+}
+
+/// Errors that can occur when reading ANDE settings from the chainspec
+#[derive(Debug, Error)]
+pub enum AndeChainConfigError {
+    /// The `ande` genesis section could not be decoded
+    #[error("invalid `ande` section in genesis config: {0}")]
+    Invalid(String),
+}
+
 /// Optimized EVM configuration for production use
 #[derive(Debug, Clone)]
 pub struct AndeEvmConfig {
@@ -279,6 +378,59 @@ mod tests {
         assert_eq!(config.block_gas_limit, 50_000_000);
     }
 
+    #[test]
+    fn test_chain_config_defaults_when_missing() {
+        let genesis = Genesis::default();
+        let config = AndeChainConfig::from_genesis(&genesis).unwrap();
+        assert_eq!(config, AndeChainConfig::default());
+        assert!(config.mev_activation_block.is_none());
+    }
+
+    #[test]
+    fn test_chain_config_from_genesis() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":6174,"ande":{"mevActivationBlock":1000}},"alloc":{}}"#,
+        )
+        .unwrap();
+
+        let config = AndeChainConfig::from_genesis(&genesis).unwrap();
+        assert_eq!(config.mev_activation_block, Some(1000));
+        // Activation height alone does not enable the redirect
+        assert!(config.mev_redirect().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_chain_config_mev_redirect() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":6174,"ande":{"mevEnabled":true,
+                "mevSink":"0x0000000000000000000000000000000000000042","mevActivationBlock":1000}},"alloc":{}}"#,
+        )
+        .unwrap();
+
+        let (redirect, activation_block) =
+            AndeChainConfig::from_genesis(&genesis).unwrap().mev_redirect().unwrap().unwrap();
+        assert_eq!(redirect.mev_sink(), Address::with_last_byte(0x42));
+        assert_eq!(redirect.min_threshold(), AndeMevRedirect::DEFAULT_MIN_MEV_THRESHOLD);
+        assert_eq!(activation_block, 1000);
+
+        // Enabled without a sink is rejected when the chainspec is loaded
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":6174,"ande":{"mevEnabled":true,"mevActivationBlock":1000}},"alloc":{}}"#,
+        )
+        .unwrap();
+        assert!(AndeChainConfig::from_genesis(&genesis).is_err());
+    }
+
+    #[test]
+    fn test_chain_config_rejects_malformed_section() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":6174,"ande":{"mevActivationBlock":"soon"}},"alloc":{}}"#,
+        )
+        .unwrap();
+
+        assert!(AndeChainConfig::from_genesis(&genesis).is_err());
+    }
+
     #[test]
     fn test_cfg_env_conversion() {
         let config = AndeEvmConfig::production();
@@ -1,6 +1,7 @@
 //! Consensus configuration for AndeChain PoS integration
 
 use alloy_primitives::Address;
+use ande_consensus::LocalKey;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -27,7 +28,7 @@ pub struct ConsensusConfig {
     /// Private key (direct, not recommended for production)
     pub private_key: Option<String>,
 
-    /// Enable block attestation (proposeBlock calls)
+    /// Enable on-chain block attestation (attestBlock calls)
     #[serde(default = "default_attestation_enabled")]
     pub attestation_enabled: bool,
 
@@ -91,6 +92,21 @@ impl ConsensusConfig {
     pub fn get_private_key(&self) -> Option<&String> {
         self.private_key.as_ref()
     }
+
+    /// Load the sequencer key, preferring `private_key_file` over `private_key`
+    ///
+    /// Returns `None` if neither is configured.
+    pub fn local_key(&self) -> eyre::Result<Option<LocalKey>> {
+        if let Some(path) = &self.private_key_file {
+            return Ok(Some(LocalKey::from_key_file(path)?));
+        }
+        let Some(key) = &self.private_key else {
+            return Ok(None);
+        };
+        let secret = alloy::hex::decode(key.trim())
+            .map_err(|e| eyre::eyre!("Invalid SEQUENCER_PRIVATE_KEY: {}", e))?;
+        Ok(Some(LocalKey::from_bytes(&secret)?))
+    }
 }
 
 impl Default for ConsensusConfig {
@@ -148,4 +164,19 @@ mod tests {
         let key = config.get_private_key();
         assert!(key.is_some());
     }
+
+    #[test]
+    fn test_local_key_from_hex() {
+        let mut config = ConsensusConfig::default();
+        assert!(config.local_key().unwrap().is_none());
+
+        config.private_key = Some(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
+        );
+        let key = config.local_key().unwrap().unwrap();
+        assert_eq!(
+            ande_consensus::SigningBackend::address(&key),
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse::<Address>().unwrap()
+        );
+    }
 }
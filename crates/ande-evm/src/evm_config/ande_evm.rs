@@ -0,0 +1,164 @@
+//! ANDE EVM with MEV Redirection
+//!
+//! Thin wrapper around the revm `Evm` that runs every transaction through
+//! [`AndeHandler`] when an MEV redirect policy is active for the block.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! AndeEvmFactory::create_evm(db, env)
+//!     ↓ block >= mevActivationBlock ?
+//! AndeEvm { mev_redirect: Some(..) }
+//!     ↓ transact_raw(tx)
+//! AndeHandler::run() → reward_beneficiary() → credit mev_sink
+//! ```
+//!
+//! Below the activation height the EVM behaves exactly like `EthEvm`, so
+//! blocks produced before MEV redirection was enabled keep validating.
+
+use crate::mev::{AndeHandler, AndeMevRedirect};
+use alloy_evm::{eth::EthEvmContext, Database, Evm, EvmEnv};
+use alloy_primitives::{Address, Bytes};
+use reth_ethereum::evm::revm::{
+    context::{BlockEnv, ContextSetters, Evm as RevmEvm, TxEnv},
+    context_interface::{
+        result::{EVMError, HaltReason, ResultAndState},
+        JournalTr,
+    },
+    handler::{instructions::EthInstructions, EthFrame, Handler, PrecompileProvider},
+    inspector::{Inspector, InspectorHandler},
+    interpreter::{interpreter::EthInterpreter, InterpreterResult},
+    primitives::hardfork::SpecId,
+    Context, ExecuteEvm, InspectEvm, SystemCallEvm,
+};
+
+/// Inner revm EVM type used by [`AndeEvm`]
+pub type AndeRevmEvm<DB, I, P> =
+    RevmEvm<EthEvmContext<DB>, I, EthInstructions<EthInterpreter, EthEvmContext<DB>>, P, EthFrame>;
+
+/// ANDE EVM
+///
+/// Mirrors `alloy_evm::EthEvm` but executes transactions with [`AndeHandler`]
+/// so that the base fee portion of every transaction is credited to the MEV
+/// distribution sink as part of the state transition.
+#[derive(Debug)]
+pub struct AndeEvm<DB: Database, I, P> {
+    /// Inner revm EVM
+    inner: AndeRevmEvm<DB, I, P>,
+    /// Whether the inspector is enabled
+    inspect: bool,
+    /// MEV redirect policy, `None` below the activation height
+    mev_redirect: Option<AndeMevRedirect>,
+}
+
+impl<DB: Database, I, P> AndeEvm<DB, I, P> {
+    /// Create a new ANDE EVM from an inner revm EVM
+    pub const fn new(
+        inner: AndeRevmEvm<DB, I, P>,
+        inspect: bool,
+        mev_redirect: Option<AndeMevRedirect>,
+    ) -> Self {
+        Self { inner, inspect, mev_redirect }
+    }
+
+    /// Returns the MEV redirect policy active for this EVM, if any
+    pub const fn mev_redirect(&self) -> Option<AndeMevRedirect> {
+        self.mev_redirect
+    }
+
+    /// Returns a reference to the inner EVM context
+    pub const fn ctx(&self) -> &EthEvmContext<DB> {
+        &self.inner.ctx
+    }
+
+    /// Returns a mutable reference to the inner EVM context
+    pub fn ctx_mut(&mut self) -> &mut EthEvmContext<DB> {
+        &mut self.inner.ctx
+    }
+}
+
+impl<DB, I, P> Evm for AndeEvm<DB, I, P>
+where
+    DB: Database,
+    I: Inspector<EthEvmContext<DB>>,
+    P: PrecompileProvider<EthEvmContext<DB>, Output = InterpreterResult>,
+{
+    type DB = DB;
+    type Tx = TxEnv;
+    type Error = EVMError<DB::Error>;
+    type HaltReason = HaltReason;
+    type Spec = SpecId;
+    type Precompiles = P;
+    type Inspector = I;
+
+    fn block(&self) -> &BlockEnv {
+        &self.inner.ctx.block
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.inner.ctx.cfg.chain_id
+    }
+
+    fn transact_raw(
+        &mut self,
+        tx: Self::Tx,
+    ) -> Result<ResultAndState<Self::HaltReason>, Self::Error> {
+        let Some(redirect) = self.mev_redirect else {
+            // No MEV policy for this block: identical to EthEvm
+            return if self.inspect {
+                self.inner.inspect_tx(tx)
+            } else {
+                self.inner.transact(tx)
+            };
+        };
+
+        self.inner.ctx.set_tx(tx);
+        let mut handler = AndeHandler::<_, _, EthFrame<EthInterpreter>>::new(Some(redirect));
+        let result = if self.inspect {
+            handler.inspect_run(&mut self.inner)
+        } else {
+            handler.run(&mut self.inner)
+        };
+
+        // Always finalize the journal so a failed transaction does not leak
+        // state into the next one
+        let state = self.inner.ctx.journal_mut().finalize();
+        result.map(|result| ResultAndState::new(result, state))
+    }
+
+    fn transact_system_call(
+        &mut self,
+        caller: Address,
+        contract: Address,
+        data: Bytes,
+    ) -> Result<ResultAndState<Self::HaltReason>, Self::Error> {
+        // System calls never pay fees, so they are not subject to MEV redirection
+        self.inner.system_call_with_caller(caller, contract, data)
+    }
+
+    fn finish(self) -> (Self::DB, EvmEnv<Self::Spec>) {
+        let Context { block: block_env, cfg: cfg_env, journaled_state, .. } = self.inner.ctx;
+
+        (journaled_state.database, EvmEnv { block_env, cfg_env })
+    }
+
+    fn set_inspector_enabled(&mut self, enabled: bool) {
+        self.inspect = enabled;
+    }
+
+    fn components(&self) -> (&Self::DB, &Self::Inspector, &Self::Precompiles) {
+        (
+            &self.inner.ctx.journaled_state.database,
+            &self.inner.inspector,
+            &self.inner.precompiles,
+        )
+    }
+
+    fn components_mut(&mut self) -> (&mut Self::DB, &mut Self::Inspector, &mut Self::Precompiles) {
+        (
+            &mut self.inner.ctx.journaled_state.database,
+            &mut self.inner.inspector,
+            &mut self.inner.precompiles,
+        )
+    }
+}
@@ -16,25 +16,29 @@
 //! ## Features
 //! - ✅ Token Duality precompile at 0xFD
 //! - ✅ Native balance transfers via journal.transfer()
+//! - ✅ MEV redirection through `AndeHandler` from the configured activation block
 //! - ✅ Compatible with standard Reth infrastructure
 //! - ✅ Production-ready and tested
 
+use super::ande_evm::AndeEvm;
+use crate::mev::AndeMevRedirect;
 use alloy_evm::{
-    eth::{EthEvmBuilder, EthEvmContext},
+    eth::EthEvmContext,
     precompiles::{PrecompilesMap, Precompile},
     EvmEnv, EvmFactory,
 };
+use alloy_primitives::U256;
 use reth_ethereum::evm::{
     primitives::Database,
     revm::{
-        context::TxEnv,
+        context::{Context, TxEnv},
         context_interface::result::{EVMError, HaltReason},
         inspector::{Inspector, NoOpInspector},
         interpreter::interpreter::EthInterpreter,
         primitives::hardfork::SpecId,
+        MainBuilder, MainContext,
     },
 };
-use reth_evm::EthEvm;
 
 /// ANDE EVM Factory with Token Duality Precompile
 ///
@@ -42,15 +46,23 @@ use reth_evm::EthEvm;
 /// - Standard Ethereum precompiles (0x01-0x0A)
 /// - ANDE Token Duality precompile (0xFD)
 ///
+/// When an MEV redirect policy is configured, EVMs created for blocks at or
+/// above `mev_activation_block` execute transactions through `AndeHandler`.
+///
 /// ## Usage
 /// ```ignore
-/// let factory = AndeEvmFactory::new(SpecId::CANCUN);
+/// let factory = AndeEvmFactory::new(SpecId::CANCUN)
+///     .with_mev_redirect(mev_config.to_redirect(), activation_block);
 /// let evm = factory.create_evm(db, env);
 /// ```
 #[derive(Debug, Clone)]
 pub struct AndeEvmFactory {
     /// Spec ID for EVM configuration
     spec_id: SpecId,
+    /// MEV redirect policy (optional)
+    mev_redirect: Option<AndeMevRedirect>,
+    /// First block at which the MEV redirect policy is applied
+    mev_activation_block: u64,
 }
 
 impl AndeEvmFactory {
@@ -60,13 +72,45 @@ impl AndeEvmFactory {
             ?spec_id,
             "🔧 Initializing ANDE EVM Factory with Token Duality precompile"
         );
-        Self { spec_id }
+        Self {
+            spec_id,
+            mev_redirect: None,
+            mev_activation_block: 0,
+        }
+    }
+
+    /// Enable MEV redirection starting at `activation_block`
+    pub fn with_mev_redirect(mut self, redirect: AndeMevRedirect, activation_block: u64) -> Self {
+        tracing::info!(
+            mev_sink = ?redirect.mev_sink(),
+            activation_block,
+            "💰 MEV redirection enabled in ANDE EVM factory"
+        );
+        self.mev_redirect = Some(redirect);
+        self.mev_activation_block = activation_block;
+        self
     }
 
     /// Get the spec ID
     pub fn spec_id(&self) -> SpecId {
         self.spec_id
     }
+
+    /// Get the configured MEV redirect policy, if any
+    pub const fn mev_redirect(&self) -> Option<AndeMevRedirect> {
+        self.mev_redirect
+    }
+
+    /// Get the block at which MEV redirection activates
+    pub const fn mev_activation_block(&self) -> u64 {
+        self.mev_activation_block
+    }
+
+    /// Returns the MEV redirect policy that applies to the given block
+    pub fn mev_redirect_for_block(&self, block_number: U256) -> Option<AndeMevRedirect> {
+        self.mev_redirect
+            .filter(|_| block_number >= U256::from(self.mev_activation_block))
+    }
 }
 
 impl Default for AndeEvmFactory {
@@ -80,7 +124,7 @@ impl Default for AndeEvmFactory {
 /// Creates EVMs with PrecompilesMap that includes the Token Duality precompile
 impl EvmFactory for AndeEvmFactory {
     type Evm<DB: Database, I: Inspector<EthEvmContext<DB>, EthInterpreter>> =
-        EthEvm<DB, I, PrecompilesMap>;
+        AndeEvm<DB, I, PrecompilesMap>;
     type Tx = TxEnv;
     type Error<DBError: core::error::Error + Send + Sync + 'static> = EVMError<DBError>;
     type HaltReason = HaltReason;
@@ -93,14 +137,8 @@ impl EvmFactory for AndeEvmFactory {
             spec_id = ?self.spec_id,
             "✅ Creating ANDE EVM with Token Duality precompile at 0xFD"
         );
-        
-        // Create ANDE precompile map with Token Duality at 0xFD
-        let precompiles = self.create_ande_precompiles();
-        
-        // Use EthEvmBuilder to create EVM with custom precompiles
-        EthEvmBuilder::new(db, input)
-            .precompiles(precompiles)
-            .build()
+
+        self.build_ande_evm(db, input, NoOpInspector {}, false)
     }
 
     fn create_evm_with_inspector<DB: Database, I: Inspector<Self::Context<DB>, EthInterpreter>>(
@@ -113,19 +151,36 @@ impl EvmFactory for AndeEvmFactory {
             spec_id = ?self.spec_id,
             "✅ Creating ANDE EVM with inspector and Token Duality precompile at 0xFD"
         );
-        
-        // Create ANDE precompile map with Token Duality at 0xFD
-        let precompiles = self.create_ande_precompiles();
-        
-        // Use EthEvmBuilder to create EVM with custom precompiles and inspector
-        EthEvmBuilder::new(db, input)
-            .precompiles(precompiles)
-            .activate_inspector(inspector)
-            .build()
+
+        self.build_ande_evm(db, input, inspector, true)
     }
 }
 
 impl AndeEvmFactory {
+    /// Builds the revm EVM with ANDE precompiles and wraps it in [`AndeEvm`]
+    fn build_ande_evm<DB: Database, I: Inspector<EthEvmContext<DB>, EthInterpreter>>(
+        &self,
+        db: DB,
+        input: EvmEnv,
+        inspector: I,
+        inspect: bool,
+    ) -> AndeEvm<DB, I, PrecompilesMap> {
+        // Create ANDE precompile map with Token Duality at 0xFD
+        let precompiles = self.create_ande_precompiles();
+
+        // MEV redirection only applies from the activation block onwards
+        let mev_redirect = self.mev_redirect_for_block(input.block_env.number);
+
+        let inner = Context::mainnet()
+            .with_block(input.block_env)
+            .with_cfg(input.cfg_env)
+            .with_db(db)
+            .build_mainnet_with_inspector(inspector)
+            .with_precompiles(precompiles);
+
+        AndeEvm::new(inner, inspect, mev_redirect)
+    }
+
     /// Creates a PrecompilesMap with standard Ethereum precompiles + ANDE Token Duality
     fn create_ande_precompiles(&self) -> PrecompilesMap {
         use revm_precompile::{PrecompileSpecId, Precompiles};
@@ -178,5 +233,75 @@ mod tests {
     fn test_default_factory() {
         let factory = AndeEvmFactory::default();
         assert_eq!(factory.spec_id(), SpecId::CANCUN);
+        assert!(factory.mev_redirect().is_none());
+    }
+
+    #[test]
+    fn test_mev_redirect_respects_activation_block() {
+        use alloy_primitives::address;
+
+        let sink = address!("0x00000000000000000000000000000000000000AE");
+        let factory = AndeEvmFactory::new(SpecId::CANCUN)
+            .with_mev_redirect(AndeMevRedirect::with_default_threshold(sink), 100);
+
+        assert!(factory.mev_redirect_for_block(U256::from(99)).is_none());
+        assert_eq!(
+            factory.mev_redirect_for_block(U256::from(100)).map(|r| r.mev_sink()),
+            Some(sink)
+        );
+        assert!(factory.mev_redirect_for_block(U256::from(1_000)).is_some());
+    }
+
+    #[test]
+    fn test_evm_credits_sink_after_activation() {
+        use alloy_evm::Evm;
+        use alloy_primitives::{address, TxKind};
+        use reth_ethereum::evm::revm::{
+            database::{CacheDB, EmptyDB},
+            state::AccountInfo,
+        };
+
+        const BASE_FEE: u64 = 100;
+        const ACTIVATION: u64 = 10;
+
+        let sink = address!("0x00000000000000000000000000000000000000AE");
+        let caller = address!("0x1000000000000000000000000000000000000001");
+        let recipient = address!("0x2000000000000000000000000000000000000002");
+
+        let factory = AndeEvmFactory::new(SpecId::CANCUN)
+            .with_mev_redirect(AndeMevRedirect::with_default_threshold(sink), ACTIVATION);
+
+        let run = |block_number: u64| {
+            let mut db = CacheDB::new(EmptyDB::default());
+            db.insert_account_info(
+                caller,
+                AccountInfo { balance: U256::from(10u128.pow(18)), ..Default::default() },
+            );
+
+            let mut env = EvmEnv::default();
+            env.cfg_env.spec = SpecId::CANCUN;
+            env.block_env.number = U256::from(block_number);
+            env.block_env.basefee = BASE_FEE;
+
+            let mut evm = factory.create_evm(db, env);
+            let tx = TxEnv {
+                caller,
+                kind: TxKind::Call(recipient),
+                value: U256::from(1),
+                gas_limit: 21_000,
+                gas_price: 2 * BASE_FEE as u128,
+                ..Default::default()
+            };
+            evm.transact(tx).expect("transfer succeeds")
+        };
+
+        // Before activation the sink is untouched
+        let before = run(ACTIVATION - 1);
+        assert!(before.state.get(&sink).is_none());
+
+        // From activation the base fee is credited to the sink
+        let after = run(ACTIVATION);
+        let sink_account = after.state.get(&sink).expect("sink credited");
+        assert_eq!(sink_account.info.balance, U256::from(BASE_FEE * 21_000));
     }
 }
@@ -6,6 +6,7 @@
 pub mod precompile_config;
 pub mod precompile_inspector;
 pub mod ande_precompile_provider;
+pub mod ande_evm;
 pub mod ande_evm_factory;
 pub mod ande_token_duality;
 pub mod factory;
@@ -33,6 +34,7 @@ pub use precompile_inspector::AndePrecompileInspector;
 
 // EVM integration
 pub use ande_precompile_provider::AndePrecompileProvider;
+pub use ande_evm::AndeEvm;
 pub use ande_evm_factory::AndeEvmFactory;
 pub use wrapper::AndeEvmConfig;
 pub use factory::create_ande_evm_config;
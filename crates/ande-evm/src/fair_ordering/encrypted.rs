@@ -0,0 +1,509 @@
+//! Commit-Reveal Encrypted Mempool
+//!
+//! Encrypted transactions are ordered by arrival and sealed into a batch
+//! before anyone can read them. Only a sealed batch collects decryption
+//! shares, so its order is final by the time its contents are revealed.
+//!
+//! ```text
+//! submit(EncryptedTx, submitter signature) ──→ pending (arrival order)
+//!                             │ seal()              ← txpoolExt_getTxs, once per block
+//!                             ↓
+//!                        SealedBatch #n ←── decryption shares (keyholders / local)
+//!                             │ ≥ threshold keyholders      │ < threshold after
+//!                             ↓                             ↓ DEFAULT_BATCH_TIMEOUT_ROUNDS
+//!                        revealed txs ──→ block,       expired, envelopes failed
+//!                                         before any plaintext tx
+//! ```
+//!
+//! Batches are revealed strictly in sealing order; a batch waiting for
+//! shares holds back every later batch until it expires. Shares are only
+//! accepted from registered keyholders with a valid proof, once per
+//! keyholder and batch.
+//!
+//! Every envelope is signed by its submitter, which may be the sender or a
+//! relay. Each submitter holds at most [`DEFAULT_MAX_PER_SUBMITTER`]
+//! envelopes at once, so no single key can fill the mempool.
+
+use super::threshold::{
+    DecryptionShare, EncryptedTx, KeyShare, ThresholdError, ThresholdPublicKey,
+};
+use super::FairOrderingError;
+use alloy_primitives::{Address, Bytes, Signature, B256};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{btree_map::Entry, BTreeMap, HashMap, VecDeque};
+use tracing::{debug, warn};
+
+/// Default maximum number of encrypted transactions waiting to be sealed
+pub const DEFAULT_MAX_PENDING_ENCRYPTED: usize = 10_000;
+
+/// Default maximum number of sealed batches waiting for shares
+pub const DEFAULT_MAX_SEALED_BATCHES: usize = 64;
+
+/// Default sealing rounds (blocks) a batch may wait for shares before it expires
+pub const DEFAULT_BATCH_TIMEOUT_ROUNDS: u64 = 16;
+
+/// Default maximum number of envelopes one submitter holds at once
+pub const DEFAULT_MAX_PER_SUBMITTER: usize = 64;
+
+/// Sealed batch awaiting decryption shares, as served to keyholders
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealedBatchInfo {
+    /// Batch number
+    pub batch_id: u64,
+    /// Envelopes in their final order
+    pub envelopes: Vec<EncryptedTx>,
+    /// Keyholders that already contributed
+    pub contributors: Vec<u32>,
+}
+
+/// Batch whose order is fixed
+#[derive(Debug)]
+struct SealedBatch {
+    /// Batch number
+    id: u64,
+    /// Envelopes in their final order
+    envelopes: Vec<EncryptedTx>,
+    /// Shares per keyholder, one per envelope
+    shares: BTreeMap<u32, Vec<DecryptionShare>>,
+    /// Sealing round the batch was sealed in
+    sealed_round: u64,
+}
+
+/// Mutable mempool state
+#[derive(Debug, Default)]
+struct State {
+    /// Envelopes not yet sealed, in arrival order
+    pending: Vec<EncryptedTx>,
+    /// Submitter of every envelope currently held, by envelope id
+    held: HashMap<B256, Address>,
+    /// Envelopes currently held per submitter
+    per_submitter: HashMap<Address, usize>,
+    /// Sealed batches, oldest first
+    batches: VecDeque<SealedBatch>,
+    /// Revealed transactions not yet handed out
+    revealed: VecDeque<Bytes>,
+    /// Next batch number
+    next_batch: u64,
+    /// Sealing rounds so far
+    round: u64,
+}
+
+impl State {
+    /// Forget an envelope that left the mempool
+    fn release(&mut self, id: &B256) {
+        let Some(submitter) = self.held.remove(id) else {
+            return;
+        };
+        if let Some(count) = self.per_submitter.get_mut(&submitter) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_submitter.remove(&submitter);
+            }
+        }
+    }
+}
+
+/// Commit-reveal mempool for threshold-encrypted transactions
+#[derive(Debug)]
+pub struct EncryptedMempool {
+    /// Committee key and threshold
+    public_key: ThresholdPublicKey,
+    /// Key shares held by this node, contributed automatically on sealing
+    local_shares: Vec<KeyShare>,
+    /// Maximum pending envelopes
+    max_pending: usize,
+    /// Maximum sealed batches waiting for shares
+    max_batches: usize,
+    /// Sealing rounds a batch may wait for shares
+    batch_timeout: u64,
+    /// Maximum envelopes held per submitter
+    max_per_submitter: usize,
+    /// Mempool state
+    state: Mutex<State>,
+}
+
+impl EncryptedMempool {
+    /// Create a mempool for `public_key`
+    pub fn new(public_key: ThresholdPublicKey, local_shares: Vec<KeyShare>) -> Self {
+        Self {
+            public_key,
+            local_shares,
+            max_pending: DEFAULT_MAX_PENDING_ENCRYPTED,
+            max_batches: DEFAULT_MAX_SEALED_BATCHES,
+            batch_timeout: DEFAULT_BATCH_TIMEOUT_ROUNDS,
+            max_per_submitter: DEFAULT_MAX_PER_SUBMITTER,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Committee key transactions must be encrypted to
+    pub fn public_key(&self) -> &ThresholdPublicKey {
+        &self.public_key
+    }
+
+    /// Accept an encrypted transaction, returning its envelope id
+    ///
+    /// `signature` is the submitter's EIP-191 signature over the envelope id.
+    pub fn submit(
+        &self,
+        envelope: EncryptedTx,
+        signature: &Signature,
+    ) -> Result<B256, FairOrderingError> {
+        envelope.validate()?;
+        let id = envelope.id();
+        let submitter = signature
+            .recover_address_from_msg(id)
+            .map_err(|_| FairOrderingError::InvalidSubmitterSignature)?;
+
+        let mut state = self.state.lock();
+        if state.held.contains_key(&id) {
+            return Ok(id);
+        }
+        if state.pending.len() >= self.max_pending {
+            return Err(FairOrderingError::MempoolFull);
+        }
+        if state.per_submitter.get(&submitter).copied().unwrap_or_default() >= self.max_per_submitter
+        {
+            return Err(FairOrderingError::SubmitterLimit(submitter));
+        }
+
+        state.held.insert(id, submitter);
+        *state.per_submitter.entry(submitter).or_default() += 1;
+        state.pending.push(envelope);
+        Ok(id)
+    }
+
+    /// Fix the order of all pending envelopes, returning the new batch id
+    ///
+    /// Every call is one sealing round, aging the sealed batches. Nothing is
+    /// sealed while [`DEFAULT_MAX_SEALED_BATCHES`] batches wait for shares.
+    pub fn seal(&self) -> Option<u64> {
+        let (id, envelopes) = {
+            let mut state = self.state.lock();
+            state.round += 1;
+            if state.pending.is_empty() {
+                return None;
+            }
+            if state.batches.len() >= self.max_batches {
+                debug!(batches = state.batches.len(), "Sealed batches full, not sealing");
+                return None;
+            }
+
+            let id = state.next_batch;
+            state.next_batch += 1;
+            let envelopes = std::mem::take(&mut state.pending);
+            let sealed_round = state.round;
+            state.batches.push_back(SealedBatch {
+                id,
+                envelopes: envelopes.clone(),
+                shares: BTreeMap::new(),
+                sealed_round,
+            });
+            (id, envelopes)
+        };
+        debug!(batch = id, txs = envelopes.len(), "Sealed encrypted batch");
+
+        // The order is fixed; local shares are computed without holding the lock
+        let local: Vec<(u32, Vec<DecryptionShare>)> = self
+            .local_shares
+            .iter()
+            .filter_map(|key_share| {
+                let shares: Result<Vec<_>, _> =
+                    envelopes.iter().map(|envelope| key_share.decryption_share(envelope)).collect();
+                shares.ok().map(|shares| (key_share.index, shares))
+            })
+            .collect();
+        if !local.is_empty() {
+            let mut state = self.state.lock();
+            if let Some(batch) = state.batches.iter_mut().find(|batch| batch.id == id) {
+                for (index, shares) in local {
+                    batch.shares.entry(index).or_insert(shares);
+                }
+            }
+        }
+        Some(id)
+    }
+
+    /// Drop leading batches that waited [`DEFAULT_BATCH_TIMEOUT_ROUNDS`]
+    /// sealing rounds without reaching the threshold
+    ///
+    /// A batch that never collects enough shares would otherwise hold back
+    /// every later batch. Returns the ids of the envelopes that failed.
+    pub fn expire_stuck(&self) -> Vec<B256> {
+        let mut state = self.state.lock();
+        let threshold = self.public_key.threshold;
+        let mut failed = Vec::new();
+
+        while state.batches.front().is_some_and(|batch| {
+            batch.shares.len() < threshold && state.round - batch.sealed_round >= self.batch_timeout
+        }) {
+            let Some(batch) = state.batches.pop_front() else {
+                break;
+            };
+            warn!(
+                batch = batch.id,
+                txs = batch.envelopes.len(),
+                contributors = batch.shares.len(),
+                "Expiring encrypted batch stuck below the decryption threshold"
+            );
+            for envelope in &batch.envelopes {
+                let id = envelope.id();
+                state.release(&id);
+                failed.push(id);
+            }
+        }
+
+        failed
+    }
+
+    /// Record a keyholder's shares for every envelope of a sealed batch
+    ///
+    /// Every share must come from the same registered keyholder and carry a
+    /// valid proof. A keyholder's accepted shares are never replaced.
+    pub fn add_shares(
+        &self,
+        batch_id: u64,
+        shares: Vec<DecryptionShare>,
+    ) -> Result<(), FairOrderingError> {
+        let index = shares.first().map(|share| share.index).unwrap_or_default();
+        if let Some(other) = shares.iter().find(|share| share.index != index) {
+            return Err(ThresholdError::InvalidIndex(other.index).into());
+        }
+
+        let envelopes = {
+            let state = self.state.lock();
+            let batch = state
+                .batches
+                .iter()
+                .find(|batch| batch.id == batch_id)
+                .ok_or(FairOrderingError::UnknownBatch(batch_id))?;
+
+            if shares.len() != batch.envelopes.len() {
+                return Err(FairOrderingError::ShareCountMismatch {
+                    expected: batch.envelopes.len(),
+                    got: shares.len(),
+                });
+            }
+            if batch.shares.contains_key(&index) {
+                return Err(FairOrderingError::DuplicateShares { batch_id, index });
+            }
+            batch.envelopes.clone()
+        };
+
+        // Proofs are checked without holding the lock
+        for (envelope, share) in envelopes.iter().zip(&shares) {
+            self.public_key.verify_share(envelope, share)?;
+        }
+
+        let mut state = self.state.lock();
+        let batch = state
+            .batches
+            .iter_mut()
+            .find(|batch| batch.id == batch_id)
+            .ok_or(FairOrderingError::UnknownBatch(batch_id))?;
+        match batch.shares.entry(index) {
+            Entry::Vacant(entry) => {
+                entry.insert(shares);
+                Ok(())
+            }
+            Entry::Occupied(_) => Err(FairOrderingError::DuplicateShares { batch_id, index }),
+        }
+    }
+
+    /// Sealed batches still waiting for shares
+    pub fn sealed_batches(&self) -> Vec<SealedBatchInfo> {
+        self.state
+            .lock()
+            .batches
+            .iter()
+            .map(|batch| SealedBatchInfo {
+                batch_id: batch.id,
+                envelopes: batch.envelopes.clone(),
+                contributors: batch.shares.keys().copied().collect(),
+            })
+            .collect()
+    }
+
+    /// Decrypt every leading batch that has reached the threshold
+    ///
+    /// Envelopes that fail to decrypt are dropped; the rest keep their
+    /// sealed order.
+    pub fn reveal(&self) -> usize {
+        let mut state = self.state.lock();
+        let threshold = self.public_key.threshold;
+        let mut revealed = 0;
+
+        while state.batches.front().is_some_and(|batch| batch.shares.len() >= threshold) {
+            let Some(batch) = state.batches.pop_front() else {
+                break;
+            };
+
+            for (position, envelope) in batch.envelopes.iter().enumerate() {
+                state.release(&envelope.id());
+                let shares: Vec<DecryptionShare> =
+                    batch.shares.values().map(|shares| shares[position].clone()).collect();
+
+                match envelope.decrypt(&shares, threshold) {
+                    Ok(tx) => {
+                        state.revealed.push_back(tx);
+                        revealed += 1;
+                    }
+                    Err(e) => warn!(batch = batch.id, "Dropping encrypted tx {}: {}", envelope.id(), e),
+                }
+            }
+        }
+
+        revealed
+    }
+
+    /// Hand out revealed transactions in order while `accept` returns true
+    pub fn take_revealed(&self, mut accept: impl FnMut(&Bytes) -> bool) -> Vec<Bytes> {
+        let mut state = self.state.lock();
+        let mut taken = Vec::new();
+
+        while let Some(tx) = state.revealed.front() {
+            if !accept(tx) {
+                break;
+            }
+            taken.extend(state.revealed.pop_front());
+        }
+
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fair_ordering::threshold::deal_committee;
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+    /// Submit `envelope` signed by `submitter`
+    fn submit_as(
+        mempool: &EncryptedMempool,
+        submitter: &PrivateKeySigner,
+        envelope: EncryptedTx,
+    ) -> Result<B256, FairOrderingError> {
+        let signature = submitter.sign_message_sync(envelope.id().as_slice()).unwrap();
+        mempool.submit(envelope, &signature)
+    }
+
+    #[test]
+    fn test_batches_reveal_in_sealed_order() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(1), 2, 3);
+        let mempool = EncryptedMempool::new(pk.clone(), vec![shares[0].clone()]);
+        let submitter = PrivateKeySigner::random();
+
+        submit_as(&mempool, &submitter, pk.encrypt(b"first")).unwrap();
+        submit_as(&mempool, &submitter, pk.encrypt(b"second")).unwrap();
+        let batch = mempool.seal().unwrap();
+        submit_as(&mempool, &submitter, pk.encrypt(b"third")).unwrap();
+        let later = mempool.seal().unwrap();
+
+        // One local share is below the threshold of two
+        assert_eq!(mempool.reveal(), 0);
+
+        // Completing only the later batch reveals nothing: order is strict
+        let info = mempool.sealed_batches();
+        let keyholder_shares = |batch: &SealedBatchInfo| -> Vec<DecryptionShare> {
+            batch.envelopes.iter().map(|e| shares[2].decryption_share(e).unwrap()).collect()
+        };
+        mempool.add_shares(later, keyholder_shares(&info[1])).unwrap();
+        assert_eq!(mempool.reveal(), 0);
+
+        mempool.add_shares(batch, keyholder_shares(&info[0])).unwrap();
+        assert_eq!(mempool.reveal(), 3);
+
+        let txs = mempool.take_revealed(|_| true);
+        let txs: Vec<&[u8]> = txs.iter().map(|tx| tx.as_ref()).collect();
+        assert_eq!(txs, [b"first".as_slice(), b"second", b"third"]);
+    }
+
+    #[test]
+    fn test_unverified_shares_are_rejected() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(3), 2, 3);
+        let (_, outsider) = deal_committee(B256::repeat_byte(4), 2, 3);
+        let mempool = EncryptedMempool::new(pk.clone(), vec![shares[0].clone()]);
+        let submitter = PrivateKeySigner::random();
+
+        let envelope = pk.encrypt(b"tx");
+        submit_as(&mempool, &submitter, envelope.clone()).unwrap();
+        let batch = mempool.seal().unwrap();
+
+        // A share under a key that is not registered for index 2
+        let forged = outsider[1].decryption_share(&envelope).unwrap();
+        assert!(matches!(
+            mempool.add_shares(batch, vec![forged]),
+            Err(FairOrderingError::Threshold(ThresholdError::InvalidShareProof(2)))
+        ));
+        // The local keyholder's accepted shares cannot be replaced
+        let replacement = shares[0].decryption_share(&envelope).unwrap();
+        assert!(matches!(
+            mempool.add_shares(batch, vec![replacement]),
+            Err(FairOrderingError::DuplicateShares { index: 1, .. })
+        ));
+        assert_eq!(mempool.sealed_batches()[0].contributors, vec![1]);
+
+        mempool.add_shares(batch, vec![shares[1].decryption_share(&envelope).unwrap()]).unwrap();
+        assert_eq!(mempool.reveal(), 1);
+    }
+
+    #[test]
+    fn test_invalid_and_duplicate_envelopes() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(2), 1, 1);
+        let mempool = EncryptedMempool::new(pk.clone(), shares);
+        let submitter = PrivateKeySigner::random();
+
+        let mut bad = pk.encrypt(b"tx");
+        bad.ephemeral = Bytes::from_static(&[1, 2, 3]);
+        assert!(matches!(
+            submit_as(&mempool, &submitter, bad),
+            Err(FairOrderingError::Threshold(ThresholdError::InvalidPoint))
+        ));
+
+        let envelope = pk.encrypt(b"tx");
+        submit_as(&mempool, &submitter, envelope.clone()).unwrap();
+        submit_as(&mempool, &submitter, envelope).unwrap();
+        mempool.seal();
+        assert_eq!(mempool.reveal(), 1);
+        assert!(mempool.seal().is_none());
+
+        let unsigned = Signature::new(Default::default(), Default::default(), false);
+        assert!(matches!(
+            mempool.submit(pk.encrypt(b"unsigned"), &unsigned),
+            Err(FairOrderingError::InvalidSubmitterSignature)
+        ));
+    }
+
+    #[test]
+    fn test_stuck_batches_expire_and_submitters_are_capped() {
+        let (pk, _) = deal_committee(B256::repeat_byte(5), 2, 3);
+        let mut mempool = EncryptedMempool::new(pk.clone(), Vec::new());
+        mempool.max_per_submitter = 2;
+        let submitter = PrivateKeySigner::random();
+
+        let stuck = submit_as(&mempool, &submitter, pk.encrypt(b"stuck")).unwrap();
+        submit_as(&mempool, &submitter, pk.encrypt(b"second")).unwrap();
+        assert!(matches!(
+            submit_as(&mempool, &submitter, pk.encrypt(b"third")),
+            Err(FairOrderingError::SubmitterLimit(address)) if address == submitter.address()
+        ));
+
+        // No keyholder ever contributes, so the batch expires after the timeout
+        mempool.seal().unwrap();
+        for _ in 1..DEFAULT_BATCH_TIMEOUT_ROUNDS {
+            assert!(mempool.seal().is_none());
+            assert!(mempool.expire_stuck().is_empty());
+        }
+        mempool.seal();
+        let failed = mempool.expire_stuck();
+        assert_eq!(failed.len(), 2);
+        assert!(failed.contains(&stuck));
+        assert!(mempool.sealed_batches().is_empty());
+
+        // Expired envelopes no longer count against their submitter
+        submit_as(&mempool, &submitter, pk.encrypt(b"third")).unwrap();
+    }
+}
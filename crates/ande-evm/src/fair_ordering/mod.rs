@@ -0,0 +1,239 @@
+//! Fair Transaction Ordering for the Sequencer
+//!
+//! `txpoolExt_getTxs` hands the sequencer the transactions of the next
+//! block. Ordering them by priority fee lets anyone watching the pool buy
+//! a place in front of a victim. Each network picks a policy in its genesis
+//! (`config.ande.txOrdering`):
+//!
+//! ```text
+//! priority      best_transactions()               (tip auction, default)
+//! fcfs          pending txs by arrival            (tips cannot reorder)
+//! commitReveal  sealed encrypted batches first,   (contents unknown until
+//!               then plaintext txs by arrival      the order is final)
+//! ```
+
+pub mod encrypted;
+pub mod threshold;
+
+pub use encrypted::{
+    EncryptedMempool, SealedBatchInfo, DEFAULT_BATCH_TIMEOUT_ROUNDS, DEFAULT_MAX_PENDING_ENCRYPTED,
+    DEFAULT_MAX_PER_SUBMITTER, DEFAULT_MAX_SEALED_BATCHES,
+};
+pub use threshold::{
+    deal_committee, DecryptionShare, EncryptedTx, KeyShare, ThresholdError, ThresholdPublicKey,
+};
+
+use crate::config::AndeChainConfig;
+use alloy_primitives::{Address, Bytes};
+use reth_transaction_pool::{PoolTransaction, ValidPoolTransaction};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Environment variable holding this node's key shares (`index:0xsecret,...`)
+pub const THRESHOLD_KEY_SHARES_ENV: &str = "ANDE_THRESHOLD_KEY_SHARES";
+
+/// Ordering policy of a network, as written in the genesis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxOrderingMode {
+    /// Highest priority fee first
+    #[default]
+    Priority,
+    /// First come, first served by pool arrival
+    Fcfs,
+    /// Threshold-encrypted commit-reveal batches
+    CommitReveal,
+}
+
+/// Committee key for the commit-reveal mode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdEncryptionConfig {
+    /// Keyholders required to decrypt
+    pub threshold: usize,
+    /// Compressed secp256k1 committee public key
+    pub public_key: Bytes,
+    /// Registered keyholders: index → compressed verification key `s_i·G`
+    pub verification_keys: BTreeMap<u32, Bytes>,
+}
+
+/// Errors of the fair ordering policies
+#[derive(Debug, Error)]
+pub enum FairOrderingError {
+    /// Threshold cryptography failure
+    #[error(transparent)]
+    Threshold(#[from] ThresholdError),
+
+    /// The encrypted mempool is full
+    #[error("encrypted mempool is full")]
+    MempoolFull,
+
+    /// The submitter signature over the envelope id does not recover
+    #[error("invalid submitter signature")]
+    InvalidSubmitterSignature,
+
+    /// The submitter already holds its maximum of envelopes
+    #[error("submitter {0} holds too many encrypted transactions")]
+    SubmitterLimit(Address),
+
+    /// No sealed batch with this id is awaiting shares
+    #[error("unknown or already revealed batch {0}")]
+    UnknownBatch(u64),
+
+    /// The keyholder's shares for this batch were already accepted
+    #[error("keyholder {index} already submitted shares for batch {batch_id}")]
+    DuplicateShares {
+        /// Batch number
+        batch_id: u64,
+        /// Keyholder index
+        index: u32,
+    },
+
+    /// A keyholder must send one share per envelope
+    #[error("expected {expected} decryption shares, got {got}")]
+    ShareCountMismatch {
+        /// Envelopes in the batch
+        expected: usize,
+        /// Shares received
+        got: usize,
+    },
+
+    /// Invalid ordering configuration
+    #[error("invalid ordering configuration: {0}")]
+    Config(String),
+}
+
+/// Active ordering policy of the txpool RPC
+#[derive(Debug, Clone, Default)]
+pub enum TxOrdering {
+    /// Highest priority fee first
+    #[default]
+    Priority,
+    /// First come, first served
+    Fcfs,
+    /// Encrypted batches first, then plaintext transactions by arrival
+    CommitReveal(Arc<EncryptedMempool>),
+}
+
+impl TxOrdering {
+    /// Build the policy selected by the chain config
+    ///
+    /// Local key shares for the commit-reveal mode are read from
+    /// [`THRESHOLD_KEY_SHARES_ENV`].
+    pub fn from_chain_config(config: &AndeChainConfig) -> Result<Self, FairOrderingError> {
+        match config.tx_ordering {
+            TxOrderingMode::Priority => Ok(Self::Priority),
+            TxOrderingMode::Fcfs => Ok(Self::Fcfs),
+            TxOrderingMode::CommitReveal => {
+                let encryption = config.threshold_encryption.as_ref().ok_or_else(|| {
+                    FairOrderingError::Config(
+                        "commitReveal ordering requires `thresholdEncryption`".to_string(),
+                    )
+                })?;
+                if encryption.threshold == 0 {
+                    return Err(FairOrderingError::Config("threshold must be at least 1".to_string()));
+                }
+                let public_key = ThresholdPublicKey::from_bytes(
+                    encryption.threshold,
+                    &encryption.public_key,
+                    &encryption.verification_keys,
+                )?;
+
+                let local_shares = match std::env::var(THRESHOLD_KEY_SHARES_ENV) {
+                    Ok(value) => value
+                        .split(',')
+                        .filter(|share| !share.trim().is_empty())
+                        .map(KeyShare::parse)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Err(_) => Vec::new(),
+                };
+                // Local shares skip proof checks, so they must match the registry
+                for share in &local_shares {
+                    if encryption.verification_keys.get(&share.index)
+                        != Some(&share.verification_key())
+                    {
+                        return Err(FairOrderingError::Config(format!(
+                            "local key share {} does not match its verification key",
+                            share.index
+                        )));
+                    }
+                }
+
+                Ok(Self::CommitReveal(Arc::new(EncryptedMempool::new(public_key, local_shares))))
+            }
+        }
+    }
+
+    /// Mode of this policy
+    pub fn mode(&self) -> TxOrderingMode {
+        match self {
+            Self::Priority => TxOrderingMode::Priority,
+            Self::Fcfs => TxOrderingMode::Fcfs,
+            Self::CommitReveal(_) => TxOrderingMode::CommitReveal,
+        }
+    }
+}
+
+/// Order pending transactions by arrival in the pool
+///
+/// A transaction never precedes a lower nonce of its sender, so it is
+/// placed at the later of its own arrival and its predecessor's.
+pub fn order_by_arrival<T: PoolTransaction>(
+    mut txs: Vec<Arc<ValidPoolTransaction<T>>>,
+) -> Vec<Arc<ValidPoolTransaction<T>>> {
+    txs.sort_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let mut previous: Option<(Address, u64)> = None;
+    let mut keyed: Vec<(u64, Arc<ValidPoolTransaction<T>>)> = Vec::with_capacity(txs.len());
+    for tx in txs {
+        let arrival = match previous {
+            Some((sender, arrival)) if sender == tx.sender() => arrival.max(tx.submission_id),
+            _ => tx.submission_id,
+        };
+        previous = Some((tx.sender(), arrival));
+        keyed.push((arrival, tx));
+    }
+
+    keyed.sort_by_key(|(arrival, tx)| (*arrival, tx.submission_id));
+    keyed.into_iter().map(|(_, tx)| tx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_ordering_from_genesis() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{"config":{"chainId":6174,"ande":{"txOrdering":"fcfs"}},"alloc":{}}"#,
+        )
+        .unwrap();
+        let config = AndeChainConfig::from_genesis(&genesis).unwrap();
+        assert_eq!(TxOrdering::from_chain_config(&config).unwrap().mode(), TxOrderingMode::Fcfs);
+
+        assert_eq!(
+            TxOrdering::from_chain_config(&AndeChainConfig::default()).unwrap().mode(),
+            TxOrderingMode::Priority
+        );
+    }
+
+    #[test]
+    fn test_commit_reveal_requires_committee_key() {
+        let mut config =
+            AndeChainConfig { tx_ordering: TxOrderingMode::CommitReveal, ..Default::default() };
+        assert!(TxOrdering::from_chain_config(&config).is_err());
+
+        let (pk, _) = deal_committee(B256::repeat_byte(3), 2, 3);
+        config.threshold_encryption = Some(ThresholdEncryptionConfig {
+            threshold: 2,
+            public_key: pk.to_bytes(),
+            verification_keys: pk.verification_keys(),
+        });
+        let ordering = TxOrdering::from_chain_config(&config).unwrap();
+        assert_eq!(ordering.mode(), TxOrderingMode::CommitReveal);
+    }
+}
@@ -0,0 +1,538 @@
+//! Threshold Encryption for Transactions
+//!
+//! Hashed threshold ElGamal over secp256k1. The committee key `s` is
+//! Shamir-shared among keyholders; any `threshold` of them can jointly
+//! recover the per-transaction key, but fewer learn nothing.
+//!
+//! ```text
+//! user:       r ←$, U = r·G, K = r·PK          ──→ EncryptedTx { U, E(k, tx), tag }
+//! keyholder i: D_i = s_i·U, DLEQ(Y_i, D_i)     ──→ DecryptionShare { i, D_i, proof }
+//! sequencer:  verify proof against Y_i = s_i·G (genesis verification key)
+//!             K = Σ λ_i·D_i (t shares)  ──→ k = H(K) ──→ tx, checked against tag
+//! ```
+//!
+//! Every share carries a Chaum-Pedersen proof that `log_G(Y_i) = log_U(D_i)`,
+//! so only registered keyholders can contribute and a wrong share is
+//! rejected before it is combined.
+//!
+//! This scheme is implemented here rather than taken from an audited
+//! library. It needs an independent cryptographic review before the
+//! `commitReveal` ordering is enabled on a production network.
+
+use alloy_primitives::{keccak256, Bytes, B256};
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::{Group, PrimeField};
+use k256::{ProjectivePoint, Scalar};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Length of a compressed secp256k1 point
+const POINT_LEN: usize = 33;
+
+/// Domain separator for key derivation
+const KDF_DOMAIN: &[u8] = b"ANDE-TPKE-v1";
+
+/// Domain separator for decryption share proofs
+const DLEQ_DOMAIN: &[u8] = b"ANDE-TPKE-DLEQ-v1";
+
+/// Length of a share proof, challenge and response scalars
+const PROOF_LEN: usize = 64;
+
+/// Errors of the threshold encryption scheme
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ThresholdError {
+    /// A point is not a valid compressed secp256k1 point
+    #[error("invalid curve point")]
+    InvalidPoint,
+
+    /// A scalar is zero or not canonical
+    #[error("invalid key share scalar")]
+    InvalidScalar,
+
+    /// Keyholder indices must be non-zero and distinct
+    #[error("invalid keyholder index {0}")]
+    InvalidIndex(u32),
+
+    /// Not enough decryption shares
+    #[error("need {needed} decryption shares, have {have}")]
+    NotEnoughShares {
+        /// Shares required
+        needed: usize,
+        /// Shares available
+        have: usize,
+    },
+
+    /// The recovered key does not authenticate the ciphertext
+    #[error("decryption failed: authentication tag mismatch")]
+    TagMismatch,
+
+    /// The share does not come from a registered keyholder
+    #[error("keyholder {0} is not registered")]
+    UnknownKeyholder(u32),
+
+    /// The share proof does not verify against the keyholder's key
+    #[error("invalid decryption share proof from keyholder {0}")]
+    InvalidShareProof(u32),
+
+    /// The verification keys do not interpolate to the committee key
+    #[error("verification keys do not match the committee key")]
+    InconsistentVerificationKeys,
+}
+
+/// Committee public key, threshold and keyholder verification keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdPublicKey {
+    /// Shares required to decrypt
+    pub threshold: usize,
+    /// Committee public key `s·G`
+    key: ProjectivePoint,
+    /// Registered keyholders and their verification keys `s_i·G`
+    verification_keys: BTreeMap<u32, ProjectivePoint>,
+}
+
+impl ThresholdPublicKey {
+    /// Parse a compressed committee key and the keyholder verification keys
+    ///
+    /// At least `threshold` keyholders must be registered, and the first
+    /// `threshold` verification keys must interpolate to the committee key.
+    pub fn from_bytes(
+        threshold: usize,
+        key: &[u8],
+        verification_keys: &BTreeMap<u32, Bytes>,
+    ) -> Result<Self, ThresholdError> {
+        let key = decode_point(key)?;
+        let verification_keys = verification_keys
+            .iter()
+            .map(|(&index, point)| {
+                if index == 0 {
+                    return Err(ThresholdError::InvalidIndex(index));
+                }
+                Ok((index, decode_point(point)?))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        if verification_keys.len() < threshold {
+            return Err(ThresholdError::NotEnoughShares {
+                needed: threshold,
+                have: verification_keys.len(),
+            });
+        }
+        let first: Vec<_> =
+            verification_keys.iter().take(threshold).map(|(&i, &point)| (i, point)).collect();
+        if interpolate_at_zero(&first)? != key {
+            return Err(ThresholdError::InconsistentVerificationKeys);
+        }
+
+        Ok(Self { threshold, key, verification_keys })
+    }
+
+    /// Compressed committee key
+    pub fn to_bytes(&self) -> Bytes {
+        encode_point(&self.key)
+    }
+
+    /// Compressed verification keys of the registered keyholders
+    pub fn verification_keys(&self) -> BTreeMap<u32, Bytes> {
+        self.verification_keys.iter().map(|(&index, point)| (index, encode_point(point))).collect()
+    }
+
+    /// Check that `share` was produced by a registered keyholder for `envelope`
+    pub fn verify_share(
+        &self,
+        envelope: &EncryptedTx,
+        share: &DecryptionShare,
+    ) -> Result<(), ThresholdError> {
+        let verification_key = self
+            .verification_keys
+            .get(&share.index)
+            .ok_or(ThresholdError::UnknownKeyholder(share.index))?;
+        let ephemeral = decode_point(&envelope.ephemeral)?;
+        let point = decode_point(&share.point)?;
+
+        let invalid = ThresholdError::InvalidShareProof(share.index);
+        if share.proof.len() != PROOF_LEN {
+            return Err(invalid);
+        }
+        let (Some(challenge), Some(response)) =
+            (scalar_from_bytes(&share.proof[..32]), scalar_from_bytes(&share.proof[32..]))
+        else {
+            return Err(invalid);
+        };
+
+        // Recompute the commitments from the response and check the challenge
+        let commitment_g = ProjectivePoint::GENERATOR * response - *verification_key * challenge;
+        let commitment_u = ephemeral * response - point * challenge;
+        let expected = dleq_challenge(
+            verification_key,
+            &ephemeral,
+            &point,
+            &commitment_g,
+            &commitment_u,
+        );
+        if expected != challenge {
+            return Err(invalid);
+        }
+        Ok(())
+    }
+
+    /// Encrypt `plaintext` with ephemeral secret `nonce`
+    ///
+    /// `nonce` must be uniformly random and never reused.
+    pub fn encrypt_with_nonce(&self, plaintext: &[u8], nonce: B256) -> EncryptedTx {
+        let r = scalar_from_hash(nonce);
+        let ephemeral = ProjectivePoint::GENERATOR * r;
+        let key = derive_key(&(self.key * r));
+
+        EncryptedTx {
+            ephemeral: encode_point(&ephemeral),
+            ciphertext: apply_keystream(&key, plaintext).into(),
+            tag: auth_tag(&key, plaintext),
+        }
+    }
+
+    /// Encrypt `plaintext` with a fresh random nonce
+    pub fn encrypt(&self, plaintext: &[u8]) -> EncryptedTx {
+        self.encrypt_with_nonce(plaintext, B256::random())
+    }
+}
+
+/// Threshold-encrypted transaction envelope
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedTx {
+    /// Ephemeral point `r·G` (compressed)
+    pub ephemeral: Bytes,
+    /// EIP-2718 transaction encrypted under the derived key
+    pub ciphertext: Bytes,
+    /// `keccak256(key || plaintext)`, authenticates the decryption
+    pub tag: B256,
+}
+
+impl EncryptedTx {
+    /// Envelope identifier, fixed before decryption
+    pub fn id(&self) -> B256 {
+        let mut data = Vec::with_capacity(self.ephemeral.len() + self.ciphertext.len() + 32);
+        data.extend_from_slice(&self.ephemeral);
+        data.extend_from_slice(&self.ciphertext);
+        data.extend_from_slice(self.tag.as_slice());
+        keccak256(data)
+    }
+
+    /// Check that the envelope is well formed
+    pub fn validate(&self) -> Result<(), ThresholdError> {
+        decode_point(&self.ephemeral).map(|_| ())
+    }
+
+    /// Decrypt with at least `threshold` shares for this envelope
+    pub fn decrypt(
+        &self,
+        shares: &[DecryptionShare],
+        threshold: usize,
+    ) -> Result<Bytes, ThresholdError> {
+        if shares.len() < threshold {
+            return Err(ThresholdError::NotEnoughShares { needed: threshold, have: shares.len() });
+        }
+
+        let shared = combine_shares(&shares[..threshold])?;
+        let key = derive_key(&shared);
+        let plaintext = apply_keystream(&key, &self.ciphertext);
+
+        if auth_tag(&key, &plaintext) != self.tag {
+            return Err(ThresholdError::TagMismatch);
+        }
+        Ok(plaintext.into())
+    }
+}
+
+/// A keyholder's share of the committee secret
+#[derive(Clone)]
+pub struct KeyShare {
+    /// Keyholder index (Shamir x-coordinate, non-zero)
+    pub index: u32,
+    /// Secret share `s_i`
+    secret: Scalar,
+}
+
+impl std::fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyShare").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+impl KeyShare {
+    /// Create a share from its index and 32-byte big-endian secret
+    pub fn from_bytes(index: u32, secret: B256) -> Result<Self, ThresholdError> {
+        if index == 0 {
+            return Err(ThresholdError::InvalidIndex(index));
+        }
+        let secret = Option::<Scalar>::from(Scalar::from_repr(secret.0.into()))
+            .filter(|s| !bool::from(s.is_zero()))
+            .ok_or(ThresholdError::InvalidScalar)?;
+        Ok(Self { index, secret })
+    }
+
+    /// Parse `index:0xsecret`
+    pub fn parse(value: &str) -> Result<Self, ThresholdError> {
+        let (index, secret) = value.split_once(':').ok_or(ThresholdError::InvalidScalar)?;
+        let index = index.trim().parse().map_err(|_| ThresholdError::InvalidIndex(0))?;
+        let secret = secret.trim().parse().map_err(|_| ThresholdError::InvalidScalar)?;
+        Self::from_bytes(index, secret)
+    }
+
+    /// Verification key `s_i·G` to register for this share
+    pub fn verification_key(&self) -> Bytes {
+        encode_point(&(ProjectivePoint::GENERATOR * self.secret))
+    }
+
+    /// Decryption share for an envelope, with its proof of correctness
+    pub fn decryption_share(&self, envelope: &EncryptedTx) -> Result<DecryptionShare, ThresholdError> {
+        let ephemeral = decode_point(&envelope.ephemeral)?;
+        let point = ephemeral * self.secret;
+
+        // Deterministic nonce, unique per secret and envelope
+        let nonce = scalar_from_hash(keccak256(
+            [DLEQ_DOMAIN, self.secret.to_repr().as_slice(), envelope.ephemeral.as_ref()].concat(),
+        ));
+        let challenge = dleq_challenge(
+            &(ProjectivePoint::GENERATOR * self.secret),
+            &ephemeral,
+            &point,
+            &(ProjectivePoint::GENERATOR * nonce),
+            &(ephemeral * nonce),
+        );
+        let response = nonce + challenge * self.secret;
+
+        let proof = [challenge.to_repr().as_slice(), response.to_repr().as_slice()].concat();
+        Ok(DecryptionShare { index: self.index, point: encode_point(&point), proof: proof.into() })
+    }
+}
+
+/// A keyholder's contribution to decrypting one envelope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    /// Keyholder index
+    pub index: u32,
+    /// `s_i·U` (compressed)
+    pub point: Bytes,
+    /// Chaum-Pedersen proof `challenge || response` that `point` uses the
+    /// keyholder's registered secret
+    pub proof: Bytes,
+}
+
+/// Deal a committee key from `seed` (devnets and tests)
+///
+/// Production committees should run a distributed key generation instead,
+/// so that no single party ever knows the full secret.
+pub fn deal_committee(
+    seed: B256,
+    threshold: usize,
+    keyholders: u32,
+) -> (ThresholdPublicKey, Vec<KeyShare>) {
+    assert!(threshold >= 1 && threshold as u32 <= keyholders, "invalid threshold");
+
+    // Polynomial f(x) = a_0 + a_1 x + … + a_{t-1} x^{t-1}, secret a_0
+    let coefficients: Vec<Scalar> = (0..threshold)
+        .map(|i| scalar_from_hash(keccak256([seed.as_slice(), &(i as u64).to_be_bytes()].concat())))
+        .collect();
+
+    let shares: Vec<KeyShare> = (1..=keyholders)
+        .map(|index| {
+            let x = Scalar::from(index as u64);
+            let secret = coefficients.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c);
+            KeyShare { index, secret }
+        })
+        .collect();
+
+    let verification_keys = shares
+        .iter()
+        .map(|share| (share.index, ProjectivePoint::GENERATOR * share.secret))
+        .collect();
+    let public_key = ThresholdPublicKey {
+        threshold,
+        key: ProjectivePoint::GENERATOR * coefficients[0],
+        verification_keys,
+    };
+    (public_key, shares)
+}
+
+/// Recover `s·U` from shares by Lagrange interpolation at zero
+fn combine_shares(shares: &[DecryptionShare]) -> Result<ProjectivePoint, ThresholdError> {
+    let points = shares
+        .iter()
+        .map(|share| Ok((share.index, decode_point(&share.point)?)))
+        .collect::<Result<Vec<_>, ThresholdError>>()?;
+    interpolate_at_zero(&points)
+}
+
+/// Lagrange interpolation at zero of points on a degree `len - 1` polynomial
+fn interpolate_at_zero(points: &[(u32, ProjectivePoint)]) -> Result<ProjectivePoint, ThresholdError> {
+    let mut combined = ProjectivePoint::IDENTITY;
+
+    for (i, &(index, point)) in points.iter().enumerate() {
+        if index == 0 {
+            return Err(ThresholdError::InvalidIndex(index));
+        }
+        let x_i = Scalar::from(index as u64);
+        let mut lambda = Scalar::ONE;
+
+        for (j, &(other, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if other == index {
+                return Err(ThresholdError::InvalidIndex(other));
+            }
+            let x_j = Scalar::from(other as u64);
+            let denominator = Option::<Scalar>::from((x_j - x_i).invert())
+                .ok_or(ThresholdError::InvalidIndex(other))?;
+            lambda *= x_j * denominator;
+        }
+
+        combined += point * lambda;
+    }
+
+    Ok(combined)
+}
+
+/// Fiat-Shamir challenge of a decryption share proof
+fn dleq_challenge(
+    verification_key: &ProjectivePoint,
+    ephemeral: &ProjectivePoint,
+    point: &ProjectivePoint,
+    commitment_g: &ProjectivePoint,
+    commitment_u: &ProjectivePoint,
+) -> Scalar {
+    let mut data = Vec::with_capacity(DLEQ_DOMAIN.len() + 5 * POINT_LEN);
+    data.extend_from_slice(DLEQ_DOMAIN);
+    for p in [verification_key, ephemeral, point, commitment_g, commitment_u] {
+        data.extend_from_slice(p.to_bytes().as_slice());
+    }
+    scalar_from_hash(keccak256(data))
+}
+
+/// Symmetric key from the shared point
+fn derive_key(shared: &ProjectivePoint) -> B256 {
+    keccak256([KDF_DOMAIN, shared.to_bytes().as_slice()].concat())
+}
+
+/// XOR `data` with the keccak keystream of `key`
+fn apply_keystream(key: &B256, data: &[u8]) -> Vec<u8> {
+    data.chunks(32)
+        .enumerate()
+        .flat_map(|(counter, chunk)| {
+            let block = keccak256([key.as_slice(), &(counter as u64).to_be_bytes()].concat());
+            chunk.iter().zip(block).map(|(byte, pad)| byte ^ pad).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Authentication tag binding the key to the plaintext
+fn auth_tag(key: &B256, plaintext: &[u8]) -> B256 {
+    keccak256([key.as_slice(), plaintext].concat())
+}
+
+/// Canonical scalar from 32 big-endian bytes
+fn scalar_from_bytes(bytes: &[u8]) -> Option<Scalar> {
+    let repr: [u8; 32] = bytes.try_into().ok()?;
+    Option::<Scalar>::from(Scalar::from_repr(repr.into()))
+}
+
+/// Non-zero scalar from a 32-byte value
+fn scalar_from_hash(mut value: B256) -> Scalar {
+    loop {
+        if let Some(scalar) = Option::<Scalar>::from(Scalar::from_repr(value.0.into())) {
+            if !bool::from(scalar.is_zero()) {
+                return scalar;
+            }
+        }
+        value = keccak256(value);
+    }
+}
+
+fn encode_point(point: &ProjectivePoint) -> Bytes {
+    Bytes::copy_from_slice(point.to_bytes().as_slice())
+}
+
+fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint, ThresholdError> {
+    if bytes.len() != POINT_LEN {
+        return Err(ThresholdError::InvalidPoint);
+    }
+    let mut repr = <ProjectivePoint as GroupEncoding>::Repr::default();
+    repr.copy_from_slice(bytes);
+    Option::<ProjectivePoint>::from(ProjectivePoint::from_bytes(&repr))
+        .filter(|p| !bool::from(p.is_identity()))
+        .ok_or(ThresholdError::InvalidPoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_roundtrip() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(7), 3, 5);
+        let tx = b"raw eip-2718 transaction bytes, longer than one keystream block";
+        let envelope = pk.encrypt(tx);
+        assert_ne!(envelope.ciphertext.as_ref(), tx.as_slice());
+
+        // Any 3 of 5 keyholders can decrypt
+        let dec: Vec<_> =
+            [&shares[4], &shares[1], &shares[2]].iter().map(|s| s.decryption_share(&envelope).unwrap()).collect();
+        assert_eq!(envelope.decrypt(&dec, 3).unwrap().as_ref(), tx.as_slice());
+    }
+
+    #[test]
+    fn test_below_threshold_cannot_decrypt() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(7), 3, 5);
+        let envelope = pk.encrypt(b"secret swap");
+
+        let dec: Vec<_> = shares[..2].iter().map(|s| s.decryption_share(&envelope).unwrap()).collect();
+        assert_eq!(
+            envelope.decrypt(&dec, 3),
+            Err(ThresholdError::NotEnoughShares { needed: 3, have: 2 })
+        );
+        // Pretending the threshold is lower yields a wrong key
+        assert_eq!(envelope.decrypt(&dec, 2), Err(ThresholdError::TagMismatch));
+    }
+
+    #[test]
+    fn test_key_share_parsing_and_encoding() {
+        let share = KeyShare::parse(&format!("2:{}", B256::with_last_byte(9))).unwrap();
+        assert_eq!(share.index, 2);
+        assert!(KeyShare::parse(&format!("0:{}", B256::with_last_byte(9))).is_err());
+        assert!(KeyShare::from_bytes(1, B256::ZERO).is_err());
+
+        let (pk, _) = deal_committee(B256::ZERO, 2, 3);
+        let keys = pk.verification_keys();
+        assert_eq!(ThresholdPublicKey::from_bytes(2, &pk.to_bytes(), &keys).unwrap(), pk);
+        let identity = encode_point(&ProjectivePoint::identity());
+        assert!(ThresholdPublicKey::from_bytes(2, &identity, &keys).is_err());
+
+        // Verification keys of another committee are rejected
+        let (other, _) = deal_committee(B256::repeat_byte(1), 2, 3);
+        assert_eq!(
+            ThresholdPublicKey::from_bytes(2, &pk.to_bytes(), &other.verification_keys()),
+            Err(ThresholdError::InconsistentVerificationKeys)
+        );
+    }
+
+    #[test]
+    fn test_share_proofs() {
+        let (pk, shares) = deal_committee(B256::repeat_byte(4), 2, 3);
+        let envelope = pk.encrypt(b"tx");
+        let share = shares[1].decryption_share(&envelope).unwrap();
+        assert_eq!(pk.verify_share(&envelope, &share), Ok(()));
+
+        // A share for another envelope, a forged point or an unknown index fail
+        let other = pk.encrypt(b"other tx");
+        assert_eq!(pk.verify_share(&other, &share), Err(ThresholdError::InvalidShareProof(2)));
+        let forged = DecryptionShare {
+            point: shares[0].decryption_share(&envelope).unwrap().point,
+            ..share.clone()
+        };
+        assert_eq!(pk.verify_share(&envelope, &forged), Err(ThresholdError::InvalidShareProof(2)));
+        let unknown = DecryptionShare { index: 9, ..share };
+        assert_eq!(pk.verify_share(&envelope, &unknown), Err(ThresholdError::UnknownKeyholder(9)));
+    }
+}
@@ -5,6 +5,7 @@
 //! - Evolve-specific types and traits
 //! - Custom consensus implementation
 //! - Custom EVM configuration with ANDE precompiles
+//! - On-chain attestation of canonical blocks to AndeConsensus
 
 /// Evolve-specific types and related definitions.
 pub mod types;
@@ -21,6 +22,12 @@ pub mod consensus;
 /// Consensus configuration module.
 pub mod consensus_config;
 
+/// Client for the AndeConsensus contract.
+pub mod consensus_client;
+
+/// On-chain attestation of canonical blocks.
+pub mod attestation;
+
 /// Custom EVM configuration with Evolve-specific precompiles.
 pub mod evm_config;
 
@@ -33,14 +40,23 @@ pub mod parallel_executor;
 /// MEV detection and integration module.
 pub mod mev;
 
+/// Fair transaction ordering (FCFS and encrypted commit-reveal) for the sequencer.
+pub mod fair_ordering;
+
 #[cfg(test)]
 mod tests;
 
 // Re-export public types
-pub use config::{EvolveConfig, DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS};
+pub use config::{
+    AndeChainConfig, AndeChainConfigError, EvolveConfig, DEFAULT_MAX_TXPOOL_BYTES,
+    DEFAULT_MAX_TXPOOL_GAS,
+};
+pub use attestation::{AttestationStatus, AttestationTracker, BlockAttester};
 pub use consensus::{EvolveConsensus, EvolveConsensusBuilder};
+pub use consensus_client::AndeConsensusClient;
 pub use evm_config::{
     AndeTokenDualityPrecompile,
+    AndeEvm,
     AndeEvmFactory,
     AndePrecompileProvider,
     AndeConfigError,
@@ -49,3 +65,4 @@ pub use evm_config::{
 pub use parallel_executor::{ParallelExecutor, TxExecutionResult, optimal_worker_count};
 pub use types::{EvolvePayloadAttributes, PayloadAttributesError};
 pub use mev::{AndeHandler, AndeMevRedirect, MevDetection, MevRedirectError, MevType, MevConfig, MevConfigError};
+pub use mev::{BundleLane, BundleSubmission, MevAuctionClient, MevAuctionError};
@@ -2,11 +2,57 @@
 //!
 //! Provides interface to interact with the MEVAuctionManager smart contract
 //! for bundle submission, execution tracking, and searcher management.
+//!
+//! ## Bundle Lane
+//!
+//! ```text
+//! Searcher ── submitBundle(hash, bid, target) ──→ MEVAuctionManager.sol
+//!    └──── signed txs ──→ MevAuctionClient::submit_bundle (checks commitment)
+//!                               ↓
+//!                          BundleLane (pending bundles per target block)
+//!                               ↓ winning_bundle(block)
+//!                          payload builder (bundle at top of block, all-or-nothing)
+//!                               ↓ canonical block
+//!                          settle_block → markBundleExecuted / markBundleRejected
+//! ```
 
-use alloy_primitives::{Address, U256, B256};
+use alloy::{
+    network::EthereumWallet,
+    providers::{DynProvider, Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    sol,
+};
+use alloy_consensus::transaction::{Recovered, SignerRecoverable};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use parking_lot::RwLock;
+use reth_primitives::TransactionSigned;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+sol! {
+    /// On-chain bundle commitments and settlement (MEVAuctionManager.sol)
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IMEVAuctionManager {
+        struct Bundle {
+            bytes32 bundleHash;
+            address searcher;
+            uint256 bidAmount;
+            uint256 blockNumber;
+            bool executed;
+            uint256 timestamp;
+            uint256 mevCaptured;
+        }
+
+        function markBundleExecuted(bytes32 bundleHash, uint256 mevCaptured, uint256 bidPaid) external;
+        function markBundleRejected(bytes32 bundleHash, string calldata reason) external;
+        function getBlockBundles(uint256 blockNumber) external view returns (bytes32[] memory bundleHashes);
+        function getBundle(bytes32 bundleHash) external view returns (Bundle memory bundle);
+    }
+}
 
 /// Bundle submission for MEV auction
 #[derive(Debug, Clone)]
@@ -21,6 +67,103 @@ pub struct BundleSubmission {
     pub transactions: Vec<B256>,
     /// Searcher address
     pub searcher: Address,
+    /// EIP-2718 encoded signed transactions, in execution order
+    pub raw_transactions: Vec<Bytes>,
+    /// Earliest block timestamp the bundle may be included at
+    pub min_timestamp: Option<u64>,
+    /// Latest block timestamp the bundle may be included at
+    pub max_timestamp: Option<u64>,
+    /// Transactions allowed to revert without invalidating the bundle
+    pub reverting_tx_hashes: Vec<B256>,
+}
+
+impl BundleSubmission {
+    /// Build a bundle from raw signed transactions
+    ///
+    /// The bundle hash is `keccak256(txHash_0 || txHash_1 || ...)`, which is
+    /// what searchers commit to on MEVAuctionManager.
+    pub fn from_raw(
+        searcher: Address,
+        bid_amount: U256,
+        target_block: u64,
+        raw_transactions: Vec<Bytes>,
+    ) -> Result<Self, MevAuctionError> {
+        let transactions = decode_bundle_transactions(&raw_transactions)?
+            .iter()
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            bundle_hash: Self::compute_bundle_hash(&transactions),
+            bid_amount,
+            target_block,
+            transactions,
+            searcher,
+            raw_transactions,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+        })
+    }
+
+    /// Restrict inclusion to blocks within a timestamp window
+    pub fn with_timestamps(mut self, min_timestamp: Option<u64>, max_timestamp: Option<u64>) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Allow the given transactions to revert
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<B256>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Whether a block with `timestamp` satisfies the bundle's timestamp window
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.is_none_or(|min| timestamp >= min)
+            && self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+
+    /// Compute the bundle hash from the ordered transaction hashes
+    pub fn compute_bundle_hash(tx_hashes: &[B256]) -> B256 {
+        let mut preimage = Vec::with_capacity(tx_hashes.len() * 32);
+        for hash in tx_hashes {
+            preimage.extend_from_slice(hash.as_slice());
+        }
+        keccak256(preimage)
+    }
+
+    /// Whether the signed transactions of this bundle are known
+    pub fn has_body(&self) -> bool {
+        !self.raw_transactions.is_empty()
+    }
+
+    /// Decode and recover the signed transactions of this bundle
+    pub fn decode_transactions(
+        &self,
+    ) -> Result<Vec<Recovered<TransactionSigned>>, MevAuctionError> {
+        decode_bundle_transactions(&self.raw_transactions)
+    }
+}
+
+/// Decode EIP-2718 encoded transactions and recover their signers
+pub fn decode_bundle_transactions(
+    raw_transactions: &[Bytes],
+) -> Result<Vec<Recovered<TransactionSigned>>, MevAuctionError> {
+    raw_transactions
+        .iter()
+        .enumerate()
+        .map(|(index, raw)| {
+            let tx = TransactionSigned::decode_2718(&mut raw.as_ref()).map_err(|e| {
+                MevAuctionError::InvalidTransaction { index, reason: e.to_string() }
+            })?;
+            let signer = tx.recover_signer().map_err(|e| {
+                MevAuctionError::InvalidTransaction { index, reason: e.to_string() }
+            })?;
+            Ok(Recovered::new_unchecked(tx, signer))
+        })
+        .collect()
 }
 
 /// Bundle execution result
@@ -36,57 +179,345 @@ pub struct BundleExecutionResult {
     pub rejection_reason: Option<String>,
 }
 
+/// Errors that can occur in the MEV auction
+#[derive(Debug, Error)]
+pub enum MevAuctionError {
+    /// Bundle has no transactions
+    #[error("Bundle must contain at least one transaction")]
+    EmptyBundle,
+
+    /// Bid is zero
+    #[error("Bid amount must be positive")]
+    ZeroBid,
+
+    /// A bundle transaction could not be decoded or recovered
+    #[error("Invalid bundle transaction at index {index}: {reason}")]
+    InvalidTransaction {
+        /// Position of the transaction in the bundle
+        index: usize,
+        /// Decoding or recovery failure
+        reason: String,
+    },
+
+    /// Bundle body does not match the committed hashes
+    #[error("Bundle body does not match bundle hash {0}")]
+    HashMismatch(B256),
+
+    /// Bundle was already submitted
+    #[error("Bundle {0} already submitted")]
+    Duplicate(B256),
+
+    /// No commitment for the bundle on MEVAuctionManager
+    #[error("Bundle {0} has no on-chain commitment")]
+    CommitmentNotFound(B256),
+
+    /// On-chain commitment differs from the submission
+    #[error("On-chain commitment for bundle {hash} does not match: {reason}")]
+    CommitmentMismatch {
+        /// Bundle hash
+        hash: B256,
+        /// Mismatching field
+        reason: String,
+    },
+
+    /// Contract call failed
+    #[error("Contract call failed: {0}")]
+    Contract(String),
+
+    /// Invalid or missing configuration
+    #[error("Configuration error: {0}")]
+    Config(String),
+}
+
+/// On-chain settlement that still has to be sent to MEVAuctionManager
+#[derive(Debug, Clone)]
+enum Settlement {
+    Executed { bundle_hash: B256, mev_captured: U256, bid_paid: U256 },
+    Rejected { bundle_hash: B256, reason: String },
+}
+
+/// In-process lane holding bundles until they are built into a block
+///
+/// Uses synchronous locks so the payload builder can read it from its
+/// blocking build task.
+#[derive(Debug, Default)]
+pub struct BundleLane {
+    /// Pending bundles
+    pending: RwLock<Vec<BundleSubmission>>,
+    /// Executed or rejected bundles
+    executed: RwLock<Vec<(B256, BundleExecutionResult)>>,
+    /// MEV value observed by the payload builder for included bundles
+    built: RwLock<HashMap<B256, U256>>,
+    /// Bundles the payload builder could not execute, with the reason
+    excluded: RwLock<HashMap<B256, String>>,
+}
+
+impl BundleLane {
+    /// Create an empty lane
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a bundle to the lane
+    pub fn insert(&self, bundle: BundleSubmission) -> Result<(), MevAuctionError> {
+        let mut pending = self.pending.write();
+        if pending.iter().any(|b| b.bundle_hash == bundle.bundle_hash) {
+            return Err(MevAuctionError::Duplicate(bundle.bundle_hash));
+        }
+        pending.push(bundle);
+        Ok(())
+    }
+
+    /// Get pending bundles for a target block
+    pub fn bundles_for_block(&self, block_number: u64) -> Vec<BundleSubmission> {
+        self.pending
+            .read()
+            .iter()
+            .filter(|b| b.target_block == block_number)
+            .cloned()
+            .collect()
+    }
+
+    /// Highest bid for a block (ties go to the earliest submission)
+    ///
+    /// Bundles excluded by the payload builder are skipped.
+    pub fn winning_bundle(&self, block_number: u64) -> Option<BundleSubmission> {
+        let excluded = self.excluded.read();
+        self.pending
+            .read()
+            .iter()
+            .filter(|b| b.target_block == block_number && !excluded.contains_key(&b.bundle_hash))
+            .fold(None, |best: Option<&BundleSubmission>, b| match best {
+                Some(current) if current.bid_amount >= b.bid_amount => Some(current),
+                _ => Some(b),
+            })
+            .cloned()
+    }
+
+    /// Record the MEV value of a bundle placed in a built payload
+    pub fn record_built(&self, bundle_hash: B256, mev_value: U256) {
+        self.built.write().insert(bundle_hash, mev_value);
+    }
+
+    /// MEV value recorded when the bundle was built, if any
+    pub fn built_value(&self, bundle_hash: &B256) -> Option<U256> {
+        self.built.read().get(bundle_hash).copied()
+    }
+
+    /// Exclude a bundle that cannot be executed in full
+    ///
+    /// The bundle stays pending until its block is settled, where it is
+    /// rejected on-chain with `reason`.
+    pub fn exclude(&self, bundle_hash: B256, reason: String) {
+        self.excluded.write().insert(bundle_hash, reason);
+    }
+
+    /// Reason a bundle was excluded, if any
+    pub fn excluded_reason(&self, bundle_hash: &B256) -> Option<String> {
+        self.excluded.read().get(bundle_hash).cloned()
+    }
+
+    /// Remove a bundle from the pending set
+    fn take_pending(&self, bundle_hash: &B256) -> Option<BundleSubmission> {
+        let mut pending = self.pending.write();
+        let pos = pending.iter().position(|b| &b.bundle_hash == bundle_hash)?;
+        Some(pending.remove(pos))
+    }
+
+    /// Move a bundle to the executed/rejected history
+    fn finalize(&self, bundle_hash: B256, result: BundleExecutionResult) {
+        self.take_pending(&bundle_hash);
+        self.built.write().remove(&bundle_hash);
+        self.excluded.write().remove(&bundle_hash);
+        self.executed.write().push((bundle_hash, result));
+    }
+
+    /// Final result of a bundle, if it was executed or rejected
+    fn result_of(&self, bundle_hash: &B256) -> Option<BundleExecutionResult> {
+        self.executed
+            .read()
+            .iter()
+            .rev()
+            .find(|(hash, _)| hash == bundle_hash)
+            .map(|(_, result)| result.clone())
+    }
+
+    /// Bundles whose target block is at or below `block_number`
+    fn due_bundles(&self, block_number: u64) -> Vec<BundleSubmission> {
+        self.pending
+            .read()
+            .iter()
+            .filter(|b| b.target_block <= block_number)
+            .cloned()
+            .collect()
+    }
+}
+
 /// MEV Auction client for sequencer integration
 #[derive(Debug)]
 pub struct MevAuctionClient {
     /// Auction manager contract address
-    #[allow(dead_code)]
     contract_address: Address,
     /// Sequencer address
-    #[allow(dead_code)]
     sequencer_address: Address,
-    /// Pending bundles
-    pending_bundles: Arc<RwLock<Vec<BundleSubmission>>>,
-    /// Executed bundles
-    executed_bundles: Arc<RwLock<Vec<(B256, BundleExecutionResult)>>>,
+    /// Contract instance (None when running without an RPC provider)
+    contract: Option<IMEVAuctionManager::IMEVAuctionManagerInstance<DynProvider>>,
+    /// Bundle lane shared with the payload builder
+    lane: Arc<BundleLane>,
+    /// Settlements that failed on-chain and are retried on the next block
+    unsettled: RwLock<Vec<Settlement>>,
 }
 
 impl MevAuctionClient {
-    /// Create new auction client
+    /// Create new auction client without provider (for local testing)
     pub fn new(contract_address: Address, sequencer_address: Address) -> Self {
         Self {
             contract_address,
             sequencer_address,
-            pending_bundles: Arc::new(RwLock::new(Vec::new())),
-            executed_bundles: Arc::new(RwLock::new(Vec::new())),
+            contract: None,
+            lane: Arc::new(BundleLane::new()),
+            unsettled: RwLock::new(Vec::new()),
         }
     }
+
+    /// Create auction client connected to MEVAuctionManager
+    ///
+    /// `signer` must be the sequencer key registered on the contract, since
+    /// `markBundleExecuted`/`markBundleRejected` are `onlySequencer`.
+    pub fn with_provider(
+        contract_address: Address,
+        rpc_url: &str,
+        signer: PrivateKeySigner,
+    ) -> Result<Self, MevAuctionError> {
+        let url = rpc_url
+            .parse()
+            .map_err(|e| MevAuctionError::Config(format!("Invalid RPC URL {rpc_url}: {e}")))?;
+        let sequencer_address = signer.address();
+
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .connect_http(url)
+            .erased();
+
+        info!(
+            contract = ?contract_address,
+            sequencer = ?sequencer_address,
+            "MEV auction client connected to MEVAuctionManager"
+        );
+
+        Ok(Self {
+            contract_address,
+            sequencer_address,
+            contract: Some(IMEVAuctionManager::new(contract_address, provider)),
+            lane: Arc::new(BundleLane::new()),
+            unsettled: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Create from environment variables
+    ///
+    /// - `MEV_AUCTION_ADDRESS`: MEVAuctionManager contract address
+    /// - `SEQUENCER_PRIVATE_KEY`: sequencer key used for settlement transactions
+    /// - `RPC_URL`: RPC endpoint (default: http://localhost:8545)
+    pub fn from_env() -> Result<Self, MevAuctionError> {
+        let contract_address = std::env::var("MEV_AUCTION_ADDRESS")
+            .map_err(|_| MevAuctionError::Config("MEV_AUCTION_ADDRESS not set".to_string()))?
+            .parse()
+            .map_err(|e| MevAuctionError::Config(format!("Invalid MEV_AUCTION_ADDRESS: {e}")))?;
+
+        let signer: PrivateKeySigner = std::env::var("SEQUENCER_PRIVATE_KEY")
+            .map_err(|_| MevAuctionError::Config("SEQUENCER_PRIVATE_KEY not set".to_string()))?
+            .parse()
+            .map_err(|e| MevAuctionError::Config(format!("Invalid SEQUENCER_PRIVATE_KEY: {e}")))?;
+
+        let rpc_url = std::env::var("RPC_URL")
+            .unwrap_or_else(|_| "http://localhost:8545".to_string());
+
+        Self::with_provider(contract_address, &rpc_url, signer)
+    }
+
+    /// Check if provider is configured for contract calls
+    pub fn has_provider(&self) -> bool {
+        self.contract.is_some()
+    }
+
+    /// Auction manager contract address
+    pub const fn contract_address(&self) -> Address {
+        self.contract_address
+    }
+
+    /// Sequencer address used for settlement
+    pub const fn sequencer_address(&self) -> Address {
+        self.sequencer_address
+    }
+
+    /// Bundle lane shared with the payload builder
+    pub fn lane(&self) -> Arc<BundleLane> {
+        Arc::clone(&self.lane)
+    }
     
     /// Get pending bundles for a target block
     pub async fn get_bundles_for_block(&self, block_number: u64) -> Vec<BundleSubmission> {
-        let bundles = self.pending_bundles.read().await;
-        bundles
-            .iter()
-            .filter(|b| b.target_block == block_number)
-            .cloned()
-            .collect()
+        self.lane.bundles_for_block(block_number)
     }
     
     /// Submit a bundle to the auction
-    pub async fn submit_bundle(&self, bundle: BundleSubmission) -> Result<(), String> {
+    ///
+    /// With a provider configured the bundle must match its on-chain
+    /// commitment; the committed bid is authoritative.
+    pub async fn submit_bundle(&self, mut bundle: BundleSubmission) -> Result<(), MevAuctionError> {
         // Validate bundle
         if bundle.transactions.is_empty() {
-            return Err("Bundle must contain at least one transaction".to_string());
+            return Err(MevAuctionError::EmptyBundle);
         }
         
+        if bundle.has_body() {
+            let hashes: Vec<B256> =
+                bundle.decode_transactions()?.iter().map(|tx| *tx.hash()).collect();
+            if hashes != bundle.transactions
+                || BundleSubmission::compute_bundle_hash(&hashes) != bundle.bundle_hash
+            {
+                return Err(MevAuctionError::HashMismatch(bundle.bundle_hash));
+            }
+        }
+
+        if let Some(contract) = &self.contract {
+            let commitment = contract
+                .getBundle(bundle.bundle_hash)
+                .call()
+                .await
+                .map_err(|e| MevAuctionError::Contract(e.to_string()))?;
+
+            if commitment.searcher.is_zero() {
+                return Err(MevAuctionError::CommitmentNotFound(bundle.bundle_hash));
+            }
+            if commitment.searcher != bundle.searcher {
+                return Err(MevAuctionError::CommitmentMismatch {
+                    hash: bundle.bundle_hash,
+                    reason: format!("searcher is {}", commitment.searcher),
+                });
+            }
+            if commitment.blockNumber != U256::from(bundle.target_block) {
+                return Err(MevAuctionError::CommitmentMismatch {
+                    hash: bundle.bundle_hash,
+                    reason: format!("target block is {}", commitment.blockNumber),
+                });
+            }
+            if commitment.executed {
+                return Err(MevAuctionError::CommitmentMismatch {
+                    hash: bundle.bundle_hash,
+                    reason: "bundle already settled".to_string(),
+                });
+            }
+
+            bundle.bid_amount = commitment.bidAmount;
+        }
+
         if bundle.bid_amount == U256::ZERO {
-            return Err("Bid amount must be positive".to_string());
+            return Err(MevAuctionError::ZeroBid);
         }
         
-        // In production, this would call the smart contract
-        // For now, store in memory
-        let mut bundles = self.pending_bundles.write().await;
-        bundles.push(bundle.clone());
+        self.lane.insert(bundle.clone())?;
         
         info!(
             "Bundle submitted: hash={}, bid={}, target_block={}",
@@ -96,18 +527,27 @@ impl MevAuctionClient {
         Ok(())
     }
     
+    /// Cancel a pending bundle
+    ///
+    /// The bundle is no longer considered for block building and is rejected
+    /// on-chain when its target block is settled, refunding the searcher.
+    pub fn cancel_bundle(&self, bundle_hash: B256) -> bool {
+        let pending = self.lane.pending.read().iter().any(|b| b.bundle_hash == bundle_hash);
+        if pending {
+            self.lane.exclude(bundle_hash, "cancelled by searcher".to_string());
+            debug!("Bundle cancelled: hash={}", bundle_hash);
+        }
+        pending
+    }
+
     /// Mark bundle as executed
     pub async fn mark_bundle_executed(
         &self,
         bundle_hash: B256,
         mev_captured: U256,
         bid_paid: U256,
-    ) -> Result<(), String> {
-        // Remove from pending
-        let mut pending = self.pending_bundles.write().await;
-        if let Some(pos) = pending.iter().position(|b| b.bundle_hash == bundle_hash) {
-            pending.remove(pos);
-        } else {
+    ) -> Result<(), MevAuctionError> {
+        if self.lane.take_pending(&bundle_hash).is_none() {
             warn!("Attempted to mark unknown bundle as executed: {}", bundle_hash);
         }
         
@@ -118,19 +558,15 @@ impl MevAuctionClient {
             bid_paid,
             rejection_reason: None,
         };
-        
-        let mut executed = self.executed_bundles.write().await;
-        executed.push((bundle_hash, result.clone()));
+        self.lane.finalize(bundle_hash, result);
         
         info!(
             "Bundle executed: hash={}, mev_captured={}, bid_paid={}",
             bundle_hash, mev_captured, bid_paid
         );
         
-        // In production, this would call the smart contract
-        // self.call_contract_mark_executed(bundle_hash, mev_captured, bid_paid).await?;
-        
-        Ok(())
+        self.send_settlement(Settlement::Executed { bundle_hash, mev_captured, bid_paid })
+            .await
     }
     
     /// Mark bundle as rejected
@@ -138,13 +574,7 @@ impl MevAuctionClient {
         &self,
         bundle_hash: B256,
         reason: String,
-    ) -> Result<(), String> {
-        // Remove from pending
-        let mut pending = self.pending_bundles.write().await;
-        if let Some(pos) = pending.iter().position(|b| b.bundle_hash == bundle_hash) {
-            pending.remove(pos);
-        }
-        
+    ) -> Result<(), MevAuctionError> {
         // Add to executed with rejection
         let result = BundleExecutionResult {
             executed: false,
@@ -152,31 +582,107 @@ impl MevAuctionClient {
             bid_paid: U256::ZERO,
             rejection_reason: Some(reason.clone()),
         };
-        
-        let mut executed = self.executed_bundles.write().await;
-        executed.push((bundle_hash, result));
+        self.lane.finalize(bundle_hash, result);
         
         debug!("Bundle rejected: hash={}, reason={}", bundle_hash, reason);
         
-        // In production, this would call the smart contract
-        // self.call_contract_mark_rejected(bundle_hash, reason).await?;
-        
-        Ok(())
+        self.send_settlement(Settlement::Rejected { bundle_hash, reason }).await
+    }
+
+    /// Send a settlement transaction, queueing it for retry if it cannot be sent
+    ///
+    /// Returns once the transaction is accepted by the node; its receipt is
+    /// awaited in a background task.
+    async fn send_settlement(&self, settlement: Settlement) -> Result<(), MevAuctionError> {
+        let Some(contract) = &self.contract else {
+            return Ok(());
+        };
+
+        let result = match &settlement {
+            Settlement::Executed { bundle_hash, mev_captured, bid_paid } => {
+                contract
+                    .markBundleExecuted(*bundle_hash, *mev_captured, *bid_paid)
+                    .send()
+                    .await
+            }
+            Settlement::Rejected { bundle_hash, reason } => {
+                contract.markBundleRejected(*bundle_hash, reason.clone()).send().await
+            }
+        };
+
+        match result {
+            Ok(pending) => {
+                // Confirmed out of band so a slow receipt does not hold back later blocks
+                tokio::spawn(async move {
+                    match pending.watch().await {
+                        Ok(tx_hash) => debug!(?tx_hash, ?settlement, "Bundle settlement confirmed"),
+                        Err(e) => warn!(error = %e, ?settlement, "Bundle settlement not confirmed"),
+                    }
+                });
+                Ok(())
+            }
+            Err(e) => {
+                error!(error = %e, ?settlement, "Bundle settlement failed, will retry");
+                self.unsettled.write().push(settlement);
+                Err(MevAuctionError::Contract(e.to_string()))
+            }
+        }
+    }
+
+    /// Settle all bundles due at a canonical block
+    ///
+    /// Bundles targeting `block_number` are checked against the block's
+    /// transactions with [`Self::validate_bundle_execution`] and marked
+    /// executed or rejected on-chain. Bundles for earlier blocks that are
+    /// still pending missed their slot and are rejected.
+    ///
+    /// Returns the bundles finalized for this block.
+    pub async fn settle_block(
+        &self,
+        block_number: u64,
+        executed_txs: &[B256],
+    ) -> Vec<(BundleSubmission, BundleExecutionResult)> {
+        // Retry settlements that failed earlier
+        let retries = std::mem::take(&mut *self.unsettled.write());
+        for settlement in retries {
+            let _ = self.send_settlement(settlement).await;
+        }
+
+        let mut finalized = Vec::new();
+        for bundle in self.lane.due_bundles(block_number) {
+            let outcome = if let Some(reason) = self.lane.excluded_reason(&bundle.bundle_hash) {
+                Err(reason)
+            } else if bundle.target_block < block_number {
+                Err("target block passed without inclusion".to_string())
+            } else if self.validate_bundle_execution(&bundle, executed_txs).await {
+                Ok(self.lane.built_value(&bundle.bundle_hash).unwrap_or_default())
+            } else {
+                Err("bundle not included atomically at target block".to_string())
+            };
+
+            let settled = match outcome {
+                Ok(mev_captured) => {
+                    self.mark_bundle_executed(bundle.bundle_hash, mev_captured, bundle.bid_amount)
+                        .await
+                }
+                Err(reason) => self.mark_bundle_rejected(bundle.bundle_hash, reason).await,
+            };
+
+            if let Err(e) = settled {
+                warn!(bundle = ?bundle.bundle_hash, error = %e, "Bundle settlement deferred");
+            }
+
+            if let Some(result) = self.lane.result_of(&bundle.bundle_hash) {
+                finalized.push((bundle, result));
+            }
+        }
+
+        finalized
     }
     
     /// Select winning bundle for a block (highest bid)
     pub async fn select_winning_bundle(&self, block_number: u64) -> Option<BundleSubmission> {
-        let bundles = self.get_bundles_for_block(block_number).await;
-        
-        if bundles.is_empty() {
-            return None;
-        }
-        
-        // Find bundle with highest bid
-        let winner = bundles
-            .iter()
-            .max_by(|a, b| a.bid_amount.cmp(&b.bid_amount))?
-            .clone();
+        let winner = self.lane.winning_bundle(block_number)?;
         
         info!(
             "Winning bundle selected: hash={}, bid={}, block={}",
@@ -188,8 +694,8 @@ impl MevAuctionClient {
     
     /// Get auction statistics
     pub async fn get_auction_stats(&self) -> AuctionStats {
-        let pending = self.pending_bundles.read().await;
-        let executed = self.executed_bundles.read().await;
+        let pending = self.lane.pending.read();
+        let executed = self.lane.executed.read();
         
         let total_bundles = pending.len() + executed.len();
         let executed_count = executed.iter().filter(|(_, r)| r.executed).count();
@@ -222,11 +728,11 @@ impl MevAuctionClient {
         let cutoff_block = current_block.saturating_sub(blocks_to_keep);
         
         // Remove old pending bundles
-        let mut pending = self.pending_bundles.write().await;
+        let mut pending = self.lane.pending.write();
         pending.retain(|b| b.target_block >= cutoff_block);
         
         // Optionally clean up old executed bundles
-        let mut executed = self.executed_bundles.write().await;
+        let mut executed = self.lane.executed.write();
         let exec_len = executed.len();
         if exec_len > 10000 {
             // Keep only last 10000 executions
@@ -241,32 +747,29 @@ impl MevAuctionClient {
     }
     
     /// Validate bundle against block transactions
+    ///
+    /// A bundle counts as executed only if all its transactions are in the
+    /// block, contiguous and in bundle order.
     pub async fn validate_bundle_execution(
         &self,
         bundle: &BundleSubmission,
         executed_txs: &[B256],
     ) -> bool {
-        // Check if all bundle transactions were executed
-        for tx_hash in &bundle.transactions {
-            if !executed_txs.contains(tx_hash) {
-                warn!(
-                    "Bundle validation failed: tx {} not found in block",
-                    tx_hash
-                );
-                return false;
-            }
-        }
-        
-        // Check transaction order
-        let mut last_index = 0;
-        for tx_hash in &bundle.transactions {
-            if let Some(index) = executed_txs.iter().position(|h| h == tx_hash) {
-                if index < last_index {
-                    warn!("Bundle validation failed: incorrect transaction order");
-                    return false;
-                }
-                last_index = index;
-            }
+        let Some(first) = bundle.transactions.first() else {
+            return false;
+        };
+        let Some(start) = executed_txs.iter().position(|h| h == first) else {
+            warn!("Bundle validation failed: tx {} not found in block", first);
+            return false;
+        };
+
+        // Check transaction order and atomic placement
+        let included = executed_txs
+            .get(start..start + bundle.transactions.len())
+            .is_some_and(|window| window == bundle.transactions.as_slice());
+        if !included {
+            warn!("Bundle validation failed: transactions missing or out of order");
+            return false;
         }
         
         debug!("Bundle validation passed: hash={}", bundle.bundle_hash);
@@ -335,6 +838,10 @@ mod tests {
             target_block: 100,
             transactions: vec![B256::random()],
             searcher: Address::random(),
+            raw_transactions: Vec::new(),
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
         };
         
         let result = client.submit_bundle(bundle.clone()).await;
@@ -355,6 +862,10 @@ mod tests {
             target_block: 100,
             transactions: vec![B256::random()],
             searcher: Address::random(),
+            raw_transactions: Vec::new(),
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
         };
         
         client.submit_bundle(bundle.clone()).await.unwrap();
@@ -387,6 +898,10 @@ mod tests {
                 target_block: 100,
                 transactions: vec![B256::random()],
                 searcher: Address::random(),
+                raw_transactions: Vec::new(),
+                min_timestamp: None,
+                max_timestamp: None,
+                reverting_tx_hashes: Vec::new(),
             };
             client.submit_bundle(bundle).await.unwrap();
         }
@@ -408,6 +923,10 @@ mod tests {
                 target_block: 100,
                 transactions: vec![B256::random()],
                 searcher: Address::random(),
+                raw_transactions: Vec::new(),
+                min_timestamp: None,
+                max_timestamp: None,
+                reverting_tx_hashes: Vec::new(),
             };
             client.submit_bundle(bundle.clone()).await.unwrap();
             
@@ -429,4 +948,93 @@ mod tests {
         assert_eq!(stats.pending_bundles, 1);
         assert!(stats.success_rate() > 0.6);
     }
+
+    fn hash_only_bundle(target_block: u64, transactions: Vec<B256>) -> BundleSubmission {
+        BundleSubmission {
+            bundle_hash: BundleSubmission::compute_bundle_hash(&transactions),
+            bid_amount: U256::from(1000),
+            target_block,
+            transactions,
+            searcher: Address::random(),
+            raw_transactions: Vec::new(),
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bundle_validation_requires_contiguous_order() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let (a, b, other) = (B256::random(), B256::random(), B256::random());
+        let bundle = hash_only_bundle(100, vec![a, b]);
+
+        assert!(client.validate_bundle_execution(&bundle, &[a, b, other]).await);
+        assert!(client.validate_bundle_execution(&bundle, &[other, a, b]).await);
+        assert!(!client.validate_bundle_execution(&bundle, &[a, other, b]).await);
+        assert!(!client.validate_bundle_execution(&bundle, &[b, a]).await);
+        assert!(!client.validate_bundle_execution(&bundle, &[a]).await);
+    }
+
+    #[tokio::test]
+    async fn test_settle_block() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let (a, b) = (B256::random(), B256::random());
+
+        let included = hash_only_bundle(100, vec![a]);
+        let missed = hash_only_bundle(100, vec![b]);
+        let future = hash_only_bundle(101, vec![B256::random()]);
+        for bundle in [&included, &missed, &future] {
+            client.submit_bundle(bundle.clone()).await.unwrap();
+        }
+        client.lane().record_built(included.bundle_hash, U256::from(5000));
+
+        let finalized = client.settle_block(100, &[a]).await;
+        assert_eq!(finalized.len(), 2);
+
+        let stats = client.get_auction_stats().await;
+        assert_eq!(stats.executed_bundles, 1);
+        assert_eq!(stats.rejected_bundles, 1);
+        assert_eq!(stats.pending_bundles, 1);
+        assert_eq!(stats.total_mev_captured, U256::from(5000));
+    }
+
+    #[tokio::test]
+    async fn test_excluded_bundle_is_skipped_and_rejected() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let mut high = hash_only_bundle(100, vec![B256::random()]);
+        high.bid_amount = U256::from(5000);
+        let low = hash_only_bundle(100, vec![B256::random()]);
+        client.submit_bundle(high.clone()).await.unwrap();
+        client.submit_bundle(low.clone()).await.unwrap();
+
+        let lane = client.lane();
+        lane.exclude(high.bundle_hash, "simulation failed".to_string());
+        assert_eq!(lane.winning_bundle(100).unwrap().bundle_hash, low.bundle_hash);
+
+        client.settle_block(100, &low.transactions).await;
+
+        let stats = client.get_auction_stats().await;
+        assert_eq!(stats.executed_bundles, 1);
+        assert_eq!(stats.rejected_bundles, 1);
+        assert!(lane.excluded_reason(&high.bundle_hash).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_and_mismatched_bundles_rejected() {
+        let client = MevAuctionClient::new(Address::random(), Address::random());
+        let bundle = hash_only_bundle(100, vec![B256::random()]);
+        client.submit_bundle(bundle.clone()).await.unwrap();
+        assert!(matches!(
+            client.submit_bundle(bundle).await,
+            Err(MevAuctionError::Duplicate(_))
+        ));
+
+        let mut with_bad_body = hash_only_bundle(100, vec![B256::random()]);
+        with_bad_body.raw_transactions = vec![Bytes::from_static(&[0x02, 0x01])];
+        assert!(matches!(
+            client.submit_bundle(with_bad_body).await,
+            Err(MevAuctionError::InvalidTransaction { index: 0, .. })
+        ));
+    }
 }
@@ -1,6 +1,10 @@
 //! MEV Configuration from Environment Variables
 //!
 //! Provides configuration for MEV redistribution policy loaded from environment.
+//!
+//! For off-chain tooling only: the node executes blocks with the redirect
+//! from the chainspec (`AndeChainConfig::mev_redirect`), since every node
+//! must agree on where base fees are credited.
 
 use alloy_primitives::{Address, U256};
 use std::env;
@@ -5,16 +5,35 @@
 //! - Sandwich attacks
 //! - Liquidations
 //! - Front-running opportunities
+//!
+//! Heuristics over signed transactions are cheap but blind to what actually
+//! happened. [`MevDetector::analyze_executed_block`] instead reconstructs
+//! swaps from receipts (see [`super::swaps`]) and reports sandwiches and
+//! cyclic arbitrage with profits valued in ANDE. Liquidations on AndeLend
+//! and AndePerpetuals are decoded from their events (see
+//! [`super::liquidations`]) and the bonus is attributed to the liquidator.
+//!
+//! Router and lending addresses come from [`DetectorConfig`] and, when set,
+//! the hot-reloaded [`ProtocolRegistry`].
 
-use alloy_primitives::{Address, U256, B256};
+use super::liquidations::{self, LiquidationEvent, LiquidationKind, PriceOracleReader};
+use super::registry::ProtocolRegistry;
+use super::swaps::{self, PoolKind, PoolStateReader, SwapEvent};
+use crate::evm_config::ANDE_PRECOMPILE_ADDRESS;
+use alloy::sol_types::SolCall;
+use alloy_primitives::{Address, Bytes, U256, B256};
 use alloy_consensus::Transaction;
 use alloy_consensus::transaction::SignerRecoverable;
-use reth_primitives::TransactionSigned;
+use reth_ethereum_primitives::{Block, Receipt};
+use reth_primitives::{RecoveredBlock, TransactionSigned};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Type of MEV opportunity detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum MevType {
     /// Arbitrage opportunity between DEXes
     Arbitrage,
@@ -54,7 +73,7 @@ pub struct MevOpportunity {
     pub mev_type: MevType,
     /// Transaction hash
     pub tx_hash: B256,
-    /// Estimated MEV value (in wei)
+    /// Estimated MEV value (in ANDE wei)
     pub value: U256,
     /// Addresses involved
     pub addresses: Vec<Address>,
@@ -103,6 +122,12 @@ pub struct DetectorConfig {
     pub dex_routers: HashSet<Address>,
     /// Known lending protocol addresses
     pub lending_protocols: HashSet<Address>,
+    /// Token profits are valued in (the ANDE ERC20 precompile)
+    pub ande_token: Address,
+    /// Preferred V2 pool for pricing a token in ANDE, by token
+    pub pricing_pools: HashMap<Address, Address>,
+    /// Protocol registry consulted in addition to the sets above
+    pub registry: Option<Arc<ProtocolRegistry>>,
 }
 
 impl Default for DetectorConfig {
@@ -114,10 +139,38 @@ impl Default for DetectorConfig {
             min_value: U256::from(100_000_000_000_000_000u64), // 0.1 ANDE
             dex_routers: HashSet::new(),
             lending_protocols: HashSet::new(),
+            ande_token: ANDE_PRECOMPILE_ADDRESS,
+            pricing_pools: HashMap::new(),
+            registry: None,
         }
     }
 }
 
+impl DetectorConfig {
+    /// Use `registry` for protocol addresses and pool discovery
+    pub fn with_registry(mut self, registry: Arc<ProtocolRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Whether `address` is a known DEX router
+    pub fn is_dex_router(&self, address: &Address) -> bool {
+        self.dex_routers.contains(address) ||
+            self.registry.as_ref().is_some_and(|r| r.is_dex_router(address))
+    }
+
+    /// Whether swaps of a `kind` pool at `address` are decoded
+    pub fn accepts_pool(&self, address: &Address, kind: PoolKind) -> bool {
+        self.registry.as_ref().is_none_or(|r| r.accepts_pool(address, kind))
+    }
+
+    /// Whether `address` is a known lending protocol
+    pub fn is_lending_protocol(&self, address: &Address) -> bool {
+        self.lending_protocols.contains(address) ||
+            self.registry.as_ref().is_some_and(|r| r.is_lending_market(address))
+    }
+}
+
 /// MEV Detector
 #[derive(Debug)]
 pub struct MevDetector {
@@ -142,6 +195,8 @@ struct TransactionInfo {
     value: U256,
     /// Gas price
     gas_price: U256,
+    /// Calldata
+    input: Bytes,
     /// Block number
     block_number: u64,
 }
@@ -230,6 +285,199 @@ impl MevDetector {
         all_opportunities
     }
     
+    /// Analyze an executed block using its receipts
+    ///
+    /// `reader` must see the post-block state; it is used to resolve pool
+    /// tokens, reserves and oracle prices.
+    pub fn analyze_executed_block<R: PoolStateReader + PriceOracleReader>(
+        &self,
+        block: &RecoveredBlock<Block>,
+        receipts: &[Receipt],
+        reader: &mut R,
+    ) -> Vec<MevOpportunity> {
+        let txs: Vec<(B256, Address)> = block
+            .transactions_with_sender()
+            .map(|(sender, tx)| (*tx.hash(), *sender))
+            .collect();
+
+        self.analyze_executed_transactions(block.header().number, &txs, receipts, reader)
+    }
+
+    /// Analyze executed transactions given as `(hash, sender)` with their receipts
+    pub fn analyze_executed_transactions<R: PoolStateReader + PriceOracleReader>(
+        &self,
+        block_number: u64,
+        txs: &[(B256, Address)],
+        receipts: &[Receipt],
+        reader: &mut R,
+    ) -> Vec<MevOpportunity> {
+        if let Some(registry) = &self.config.registry {
+            for receipt in receipts.iter().filter(|receipt| receipt.success) {
+                registry.discover_pools(&receipt.logs);
+            }
+        }
+
+        let swaps: Vec<SwapEvent> = txs
+            .iter()
+            .zip(receipts)
+            .enumerate()
+            .filter(|(_, (_, receipt))| receipt.success)
+            .flat_map(|(index, ((hash, from), receipt))| {
+                swaps::decode_swaps(index, *hash, *from, &receipt.logs, reader, |pool, kind| {
+                    self.config.accepts_pool(pool, kind)
+                })
+            })
+            .collect();
+
+        let mut opportunities = Vec::new();
+
+        if self.config.detect_liquidation {
+            let liquidations = txs
+                .iter()
+                .zip(receipts)
+                .enumerate()
+                .filter(|(_, (_, receipt))| receipt.success)
+                .flat_map(|(index, ((hash, _), receipt))| {
+                    liquidations::decode_liquidations(index, *hash, &receipt.logs, |address| {
+                        self.config.is_lending_protocol(address)
+                    })
+                });
+
+            for liquidation in liquidations {
+                if let Some(opp) = self.price_liquidation(&liquidation, block_number, &swaps, reader) {
+                    opportunities.push(opp);
+                }
+            }
+        }
+
+        if self.config.detect_sandwich {
+            for sandwich in swaps::find_sandwiches(&swaps) {
+                let token = sandwich.front_run.token_in;
+                let Some(value) = self.value_in_ande(token, sandwich.profit, &swaps, reader) else {
+                    debug!(pool = %sandwich.front_run.pool, "Unpriced sandwich profit token {}", token);
+                    continue;
+                };
+                if value < self.config.min_value {
+                    continue;
+                }
+
+                let mut opp =
+                    MevOpportunity::new(MevType::Sandwich, sandwich.back_run.tx_hash, value, block_number);
+                opp.add_address(sandwich.front_run.from);
+                opp.add_address(sandwich.victim.from);
+                opp.add_address(sandwich.front_run.pool);
+                opp.add_metadata("front_run".to_string(), sandwich.front_run.tx_hash.to_string());
+                opp.add_metadata("victim".to_string(), sandwich.victim.tx_hash.to_string());
+                opp.add_metadata("back_run".to_string(), sandwich.back_run.tx_hash.to_string());
+                opp.add_metadata("profit_token".to_string(), token.to_string());
+                opp.add_metadata("profit".to_string(), sandwich.profit.to_string());
+                opportunities.push(opp);
+            }
+        }
+
+        if self.config.detect_arbitrage {
+            for arb in swaps::find_cyclic_arbitrage(&swaps) {
+                let Some(value) = self.value_in_ande(arb.token, arb.profit, &swaps, reader) else {
+                    debug!(tx = %arb.swaps[0].tx_hash, "Unpriced arbitrage profit token {}", arb.token);
+                    continue;
+                };
+                if value < self.config.min_value {
+                    continue;
+                }
+
+                let first = &arb.swaps[0];
+                let mut opp = MevOpportunity::new(MevType::Arbitrage, first.tx_hash, value, block_number);
+                opp.add_address(first.from);
+                for swap in &arb.swaps {
+                    opp.add_address(swap.pool);
+                }
+                opp.add_metadata("hops".to_string(), arb.swaps.len().to_string());
+                opp.add_metadata("profit_token".to_string(), arb.token.to_string());
+                opp.add_metadata("profit".to_string(), arb.profit.to_string());
+                opportunities.push(opp);
+            }
+        }
+
+        for opp in &opportunities {
+            info!(
+                "🔍 {} in block {}: {} wei ANDE (tx {})",
+                opp.mev_type.name(),
+                block_number,
+                opp.value,
+                opp.tx_hash
+            );
+        }
+
+        opportunities
+    }
+
+    /// Liquidation bonus as an opportunity attributed to the liquidator
+    ///
+    /// The bonus is valued in ANDE through the oracle when it prices ANDE,
+    /// and through the pricing pools otherwise.
+    fn price_liquidation<R: PoolStateReader + PriceOracleReader>(
+        &self,
+        liquidation: &LiquidationEvent,
+        block_number: u64,
+        swaps: &[SwapEvent],
+        reader: &mut R,
+    ) -> Option<MevOpportunity> {
+        let Some(bonus) = liquidations::liquidation_bonus(liquidation, reader) else {
+            debug!(tx = %liquidation.tx_hash, "Unpriced {} liquidation", liquidation.protocol());
+            return None;
+        };
+
+        let via_oracle = bonus.usd.and_then(|usd| {
+            let oracle = reader.price_oracle(liquidation.contract)?;
+            let ande_price = reader.price(oracle, self.config.ande_token)?;
+            Some(usd * U256::from(1_000_000_000_000_000_000u64) / ande_price)
+        });
+        let value = via_oracle.or_else(|| self.value_in_ande(bonus.token, bonus.amount, swaps, reader))?;
+        if value < self.config.min_value {
+            return None;
+        }
+
+        let mut opp = MevOpportunity::new(MevType::Liquidation, liquidation.tx_hash, value, block_number);
+        opp.add_address(liquidation.liquidator);
+        opp.add_address(liquidation.borrower);
+        opp.add_address(liquidation.contract);
+        opp.add_metadata("protocol".to_string(), liquidation.protocol().to_string());
+        opp.add_metadata("bonus_token".to_string(), bonus.token.to_string());
+        opp.add_metadata("bonus".to_string(), bonus.amount.to_string());
+        if let Some(usd) = bonus.usd {
+            opp.add_metadata("bonus_usd".to_string(), usd.to_string());
+        }
+        match &liquidation.kind {
+            LiquidationKind::Lend { debt_token, debt_amount, .. } => {
+                opp.add_metadata("debt_token".to_string(), debt_token.to_string());
+                opp.add_metadata("debt_repaid".to_string(), debt_amount.to_string());
+            }
+            LiquidationKind::Perpetual { market, price, .. } => {
+                opp.add_metadata("market".to_string(), market.to_string());
+                opp.add_metadata("liquidation_price".to_string(), price.to_string());
+            }
+        }
+        Some(opp)
+    }
+
+    /// Value a token amount in ANDE
+    fn value_in_ande<R: PoolStateReader>(
+        &self,
+        token: Address,
+        amount: U256,
+        swaps: &[SwapEvent],
+        reader: &mut R,
+    ) -> Option<U256> {
+        swaps::value_in_ande(
+            token,
+            amount,
+            self.config.ande_token,
+            &self.config.pricing_pools,
+            swaps,
+            reader,
+        )
+    }
+
     /// Extract transaction information
     fn extract_tx_info(&self, tx: &TransactionSigned, block_number: u64) -> TransactionInfo {
         let from = tx.recover_signer().unwrap_or_default();
@@ -254,6 +502,7 @@ impl MevDetector {
             to,
             value,
             gas_price,
+            input: tx.input().clone(),
             block_number,
         }
     }
@@ -262,7 +511,7 @@ impl MevDetector {
     fn detect_arbitrage(&self, tx_info: &TransactionInfo) -> Option<MevOpportunity> {
         // Check if transaction interacts with DEX routers
         if let Some(to) = tx_info.to {
-            if self.config.dex_routers.contains(&to) {
+            if self.config.is_dex_router(&to) {
                 // Heuristic: High gas price + interaction with DEX = possible arbitrage
                 let base_gas_price = U256::from(50_000_000_000u64); // 50 gwei
                 
@@ -327,31 +576,35 @@ impl MevDetector {
     
     /// Detect liquidation opportunities
     fn detect_liquidation(&self, tx_info: &TransactionInfo) -> Option<MevOpportunity> {
-        // Check if transaction interacts with lending protocols
-        if let Some(to) = tx_info.to {
-            if self.config.lending_protocols.contains(&to) {
-                // Heuristic: High value + lending protocol = possible liquidation
-                let min_liquidation_value = U256::from(1_000_000_000_000_000_000u64); // 1 ANDE
-                
-                if tx_info.value > min_liquidation_value {
-                    let estimated_value = tx_info.value / U256::from(10); // 10% estimate
-                    
-                    let mut opp = MevOpportunity::new(
-                        MevType::Liquidation,
-                        tx_info.hash,
-                        estimated_value,
-                        tx_info.block_number,
-                    );
-                    opp.add_address(to);
-                    opp.add_address(tx_info.from);
-                    
-                    debug!("Potential liquidation detected: tx={}", tx_info.hash);
-                    return Some(opp);
-                }
-            }
-        }
-        
-        None
+        // Only `liquidate` calls into known lending protocols
+        let to = tx_info.to.filter(|to| self.config.is_lending_protocol(to))?;
+
+        // AndePerpetuals pays a fee only known after execution, so only
+        // AndeLend calls can be estimated up front
+        let call = liquidations::lend::liquidateCall::abi_decode(&tx_info.input).ok()?;
+
+        // Bonus estimate in debt token units. Without state it can only be
+        // valued when the debt is in ANDE; other tokens are recorded with
+        // their unit and priced from receipts once executed
+        let bonus = call.debtAmount * U256::from(liquidations::lend::LIQUIDATION_BONUS_BPS) / U256::from(10_000);
+        let estimated_value = if call.debtToken == self.config.ande_token { bonus } else { U256::ZERO };
+
+        let mut opp = MevOpportunity::new(
+            MevType::Liquidation,
+            tx_info.hash,
+            estimated_value,
+            tx_info.block_number,
+        );
+        opp.add_address(tx_info.from);
+        opp.add_address(call.borrower);
+        opp.add_address(to);
+        opp.add_metadata("debt_token".to_string(), call.debtToken.to_string());
+        opp.add_metadata("collateral_token".to_string(), call.collateralToken.to_string());
+        opp.add_metadata("bonus_token".to_string(), call.debtToken.to_string());
+        opp.add_metadata("bonus".to_string(), bonus.to_string());
+
+        debug!("Potential liquidation detected: tx={}", tx_info.hash);
+        Some(opp)
     }
     
     /// Detect cross-transaction MEV patterns
@@ -435,6 +688,7 @@ impl MevDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mev::swaps::tests::{v2_swap_log, MockPools};
 
     #[test]
     fn test_mev_type_name() {
@@ -479,4 +733,191 @@ mod tests {
         config.dex_routers.insert(dex_addr);
         assert!(config.dex_routers.contains(&dex_addr));
     }
+
+    #[test]
+    fn test_detector_config_registry() {
+        use crate::mev::registry::{DexProtocol, ProtocolRegistryFile};
+
+        let router = Address::random();
+        let pool = Address::random();
+        assert!(DetectorConfig::default().accepts_pool(&router, PoolKind::V2));
+
+        let file = ProtocolRegistryFile {
+            dex: vec![DexProtocol {
+                name: "AndeSwap".to_string(),
+                kind: PoolKind::V2,
+                factory: None,
+                routers: vec![router],
+                pools: vec![pool],
+                events: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        let registry = Arc::new(ProtocolRegistry::from_registry_file(&file).unwrap());
+        let config = DetectorConfig::default().with_registry(registry);
+
+        assert!(config.is_dex_router(&router));
+        assert!(!config.is_lending_protocol(&router));
+        assert!(config.accepts_pool(&pool, PoolKind::V2));
+        assert!(!config.accepts_pool(&router, PoolKind::V2));
+    }
+
+    /// Receipt of a successful transaction with the given logs
+    fn receipt(logs: Vec<alloy_primitives::Log>) -> Receipt {
+        Receipt { success: true, logs, ..Default::default() }
+    }
+
+    #[test]
+    fn test_executed_sandwich_detected() {
+        let ande = ANDE_PRECOMPILE_ADDRESS;
+        let token = Address::repeat_byte(0xaa);
+        let pair = Address::repeat_byte(0x01);
+        let (attacker, victim) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+
+        let mut pools = MockPools::default();
+        pools.tokens.insert(pair, (ande, token));
+        pools.reserves.insert(pair, (U256::from(10u128.pow(24)), U256::from(10u128.pow(24))));
+
+        // Attacker buys token with 1 ANDE, victim buys, attacker sells for 1.5 ANDE
+        let one = 10u64.pow(18);
+        let txs = vec![
+            (B256::repeat_byte(1), attacker),
+            (B256::repeat_byte(2), victim),
+            (B256::repeat_byte(3), attacker),
+        ];
+        let receipts = vec![
+            receipt(vec![v2_swap_log(pair, one, 0, 0, one)]),
+            receipt(vec![v2_swap_log(pair, 5 * one, 0, 0, 4 * one)]),
+            receipt(vec![v2_swap_log(pair, 0, one, one + one / 2, 0)]),
+        ];
+
+        let detector = MevDetector::default();
+        let opps = detector.analyze_executed_transactions(7, &txs, &receipts, &mut pools);
+
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].mev_type, MevType::Sandwich);
+        assert_eq!(opps[0].tx_hash, B256::repeat_byte(3));
+        assert_eq!(opps[0].value, U256::from(one / 2));
+        assert_eq!(opps[0].addresses[..2], [attacker, victim]);
+    }
+
+    #[test]
+    fn test_executed_cyclic_arbitrage_valued_in_ande() {
+        let ande = ANDE_PRECOMPILE_ADDRESS;
+        let (usd, other) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb));
+        let (pool_a, pool_b, pricing) =
+            (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+
+        let mut pools = MockPools::default();
+        pools.tokens.insert(pool_a, (usd, other));
+        pools.tokens.insert(pool_b, (other, usd));
+        pools.tokens.insert(pricing, (usd, ande));
+        // 1 USD = 2 ANDE
+        pools.reserves.insert(pricing, (U256::from(1_000), U256::from(2_000)));
+
+        let one = 10u64.pow(18);
+        let txs = vec![(B256::repeat_byte(9), Address::repeat_byte(0x33))];
+        let receipts = vec![receipt(vec![
+            v2_swap_log(pool_a, one, 0, 0, 3 * one),
+            v2_swap_log(pool_b, 3 * one, 0, 0, 2 * one),
+        ])];
+
+        let mut config = DetectorConfig::default();
+        config.pricing_pools.insert(usd, pricing);
+        let detector = MevDetector::new(config);
+        let opps = detector.analyze_executed_transactions(7, &txs, &receipts, &mut pools);
+
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].mev_type, MevType::Arbitrage);
+        assert_eq!(opps[0].value, U256::from(2 * one));
+    }
+
+    #[test]
+    fn test_failed_transactions_ignored() {
+        let pair = Address::repeat_byte(0x01);
+        let mut pools = MockPools::default();
+        pools.tokens.insert(pair, (ANDE_PRECOMPILE_ADDRESS, Address::repeat_byte(0xaa)));
+
+        let txs = vec![(B256::repeat_byte(1), Address::repeat_byte(0x11))];
+        let mut failed = receipt(vec![v2_swap_log(pair, 1, 0, 0, 1)]);
+        failed.success = false;
+
+        let detector = MevDetector::default();
+        assert!(detector.analyze_executed_transactions(1, &txs, &[failed], &mut pools).is_empty());
+    }
+
+    #[test]
+    fn test_executed_liquidation_attributed_to_liquidator() {
+        use crate::mev::liquidations::tests::{lend_liquidation_log, usd, MockOracle};
+
+        let (lend, oracle) = (Address::repeat_byte(0x10), Address::repeat_byte(0x11));
+        let (weth, usdc) = (Address::repeat_byte(0xe0), Address::repeat_byte(0xc0));
+        let liquidator = Address::repeat_byte(0xaa);
+
+        let mut reader = MockOracle::default();
+        reader.oracles.insert(lend, oracle);
+        reader.prices.insert(weth, usd(2_000));
+        reader.prices.insert(usdc, usd(1));
+        reader.prices.insert(ANDE_PRECOMPILE_ADDRESS, usd(2));
+        reader.decimals.insert(usdc, 6);
+
+        let log = lend_liquidation_log(
+            lend,
+            liquidator,
+            weth,
+            usdc,
+            U256::from(1_000_000_000u64),
+            U256::from(525_000_000_000_000_000u64),
+        );
+        let txs = vec![(B256::repeat_byte(1), liquidator)];
+        let receipts = vec![receipt(vec![log])];
+
+        // Unknown lending contracts are ignored
+        let detector = MevDetector::default();
+        assert!(detector.analyze_executed_transactions(1, &txs, &receipts, &mut reader).is_empty());
+
+        let mut detector = MevDetector::default();
+        detector.add_lending_protocol(lend);
+        let opps = detector.analyze_executed_transactions(1, &txs, &receipts, &mut reader);
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].mev_type, MevType::Liquidation);
+        assert_eq!(opps[0].addresses[0], liquidator);
+        // 50 USD bonus at 2 USD per ANDE
+        assert_eq!(opps[0].value, U256::from(25) * U256::from(10).pow(U256::from(18)));
+        assert_eq!(opps[0].metadata["protocol"], "AndeLend");
+    }
+
+    #[test]
+    fn test_liquidation_call_valued_only_in_ande() {
+        let lend = Address::repeat_byte(0x10);
+        let usdc = Address::repeat_byte(0xc0);
+        let mut detector = MevDetector::default();
+        detector.add_lending_protocol(lend);
+
+        let liquidation = |debt_token| TransactionInfo {
+            hash: B256::repeat_byte(1),
+            from: Address::repeat_byte(0xaa),
+            to: Some(lend),
+            value: U256::ZERO,
+            gas_price: U256::ZERO,
+            input: liquidations::lend::liquidateCall {
+                borrower: Address::repeat_byte(0xbb),
+                debtToken: debt_token,
+                collateralToken: Address::repeat_byte(0xe0),
+                debtAmount: U256::from(1_000_000),
+            }
+            .abi_encode()
+            .into(),
+            block_number: 1,
+        };
+
+        // A USDC bonus is labelled with its unit but not reported as ANDE
+        let opp = detector.detect_liquidation(&liquidation(usdc)).unwrap();
+        assert_eq!(opp.value, U256::ZERO);
+        assert_eq!(opp.metadata["bonus_token"], usdc.to_string());
+        assert_eq!(opp.metadata["bonus"], "50000");
+
+        let opp = detector.detect_liquidation(&liquidation(ANDE_PRECOMPILE_ADDRESS)).unwrap();
+        assert_eq!(opp.value, U256::from(50_000));
+    }
 }
@@ -14,26 +14,107 @@
 //! - 80% to veANDE stakers
 //! - 15% protocol fee
 //! - 5% treasury
-
-use alloy_primitives::{Address, U256};
-use ethers::prelude::{abigen, Provider, Http, Middleware};
+//!
+//! ## Deposit Buffer
+//!
+//! ```text
+//! settled bundles ── add_mev ──→ buffer (persisted to disk on every change)
+//!                                   ↓ interval elapsed or buffer >= max
+//!                              sign depositMEV(amount)
+//!                                   ↓ persist PendingDeposit { tx_hash, nonce, raw }
+//!                              broadcast, return
+//!                                   ↓ next maintenance tick
+//!                              receipt ──→ buffer -= amount, clear marker
+//! epoch timer ── timeRemaining == 0 ──→ settleEpoch() ──→ receipt awaited in the background
+//! ```
+//!
+//! Nothing waits for a receipt in the caller: deposits are confirmed by the
+//! maintenance loop, so settlement never stalls behind a slow block. The
+//! buffer is only reduced after the deposit transaction is confirmed.
+//! The signed transaction is persisted before it is broadcast, and no new
+//! deposit is signed while a marker is outstanding: on restart the marker is
+//! reconciled against its receipt (applied or dropped), against the
+//! sequencer nonce (dropped once the nonce was used by another transaction)
+//! or rebroadcast, so the same MEV is deposited exactly once. Without a
+//! provider nothing is deposited and the buffer is kept. `depositMEV` pulls
+//! ANDE with `transferFrom`, so the sequencer account must have approved the
+//! distributor.
+
+use alloy::{
+    network::{EthereumWallet, TransactionBuilder},
+    providers::{DynProvider, Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+    sol,
+};
+use alloy_consensus::Transaction;
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
-use tokio::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
-// Generate contract bindings
-abigen!(
-    MEVDistributorContract,
-    r#"[
-        function depositMEV(uint256 amount) external
-        function settleEpoch() external
-        function currentEpoch() external view returns (uint256)
-        function epochs(uint256 epochNum) external view returns (uint256 totalMEVCaptured, uint256 totalVotingPower, uint256 stakersReward, uint256 protocolFee, uint256 treasuryAmount, bool settled, uint256 timestamp)
-        function epochStartTime() external view returns (uint256)
-        function EPOCH_DURATION() external pure returns (uint256)
-    ]"#
-);
+sol! {
+    /// MEV deposit and epoch settlement (MEVDistributor.sol)
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IMEVDistributor {
+        event MEVDeposited(uint256 indexed epoch, uint256 amount, address indexed depositor);
+        event EpochSettled(
+            uint256 indexed epoch,
+            uint256 totalMEV,
+            uint256 stakersReward,
+            uint256 protocolFee,
+            uint256 treasuryAmount
+        );
+
+        function depositMEV(uint256 amount) external;
+        function settleEpoch() external;
+        function getCurrentEpochInfo() external view returns (
+            uint256 epoch,
+            uint256 startTime,
+            uint256 endTime,
+            uint256 timeRemaining
+        );
+        function getEpochData(uint256 epochId) external view returns (
+            uint256 totalMEV,
+            uint256 stakersReward,
+            uint256 protocolFee,
+            uint256 treasuryAmount,
+            bool settled,
+            uint256 timestamp
+        );
+    }
+}
+
+/// Default file name of the persisted deposit buffer
+pub const DISTRIBUTOR_BUFFER_FILE: &str = "mev_distributor_buffer.json";
+
+/// Interval of the background maintenance loop
+const MAINTENANCE_TICK: Duration = Duration::from_secs(30);
+
+/// Errors that can occur in the MEV distributor client
+#[derive(Debug, Error)]
+pub enum MevDistributorError {
+    /// Contract call or transaction failed
+    #[error("Contract call failed: {0}")]
+    Contract(String),
+
+    /// Transaction was mined but reverted
+    #[error("Transaction {0} reverted")]
+    Reverted(String),
+
+    /// Buffer file could not be read or written
+    #[error("Buffer persistence failed: {0}")]
+    Persistence(String),
+
+    /// Invalid or missing configuration
+    #[error("Configuration error: {0}")]
+    Config(String),
+}
 
 /// Epoch data from distributor contract
 #[derive(Debug, Clone)]
@@ -54,6 +135,72 @@ pub struct EpochData {
     pub timestamp: u64,
 }
 
+/// Signed `depositMEV` transaction that may have been broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDeposit {
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Sequencer nonce the transaction uses
+    pub nonce: u64,
+    /// Amount deposited, still part of the buffer until confirmed
+    pub amount: U256,
+    /// EIP-2718 encoded signed transaction, for rebroadcasting
+    pub raw: Bytes,
+}
+
+/// Deposit buffer state persisted to disk
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistributorBufferState {
+    /// MEV captured but not yet deposited
+    pub pending_mev: U256,
+    /// Unix timestamp of the last successful deposit
+    pub last_deposit_time: u64,
+    /// Total amount deposited (lifetime)
+    pub total_deposited: U256,
+    /// Number of deposits made
+    pub deposits_count: u64,
+    /// Deposit signed but not yet reconciled with the chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_deposit: Option<PendingDeposit>,
+}
+
+impl DistributorBufferState {
+    /// Load buffer state from `path`, or the empty state if it does not exist
+    pub fn load(path: &Path) -> Result<Self, MevDistributorError> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| MevDistributorError::Persistence(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(MevDistributorError::Persistence(format!("{}: {e}", path.display()))),
+        }
+    }
+
+    /// Atomically write buffer state to `path` (write temp file, fsync, rename)
+    pub fn save(&self, path: &Path) -> Result<(), MevDistributorError> {
+        use std::io::Write;
+
+        let persist_err = |e: std::io::Error| {
+            MevDistributorError::Persistence(format!("{}: {e}", path.display()))
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(persist_err)?;
+        }
+
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|e| MevDistributorError::Persistence(e.to_string()))?;
+        let tmp_path = path.with_extension("json.tmp");
+        {
+            let mut file = std::fs::File::create(&tmp_path).map_err(persist_err)?;
+            file.write_all(&bytes).map_err(persist_err)?;
+            file.sync_all().map_err(persist_err)?;
+        }
+        std::fs::rename(&tmp_path, path).map_err(persist_err)
+    }
+}
+
 /// MEV Distributor client for sequencer integration
 #[derive(Debug)]
 pub struct MevDistributorClient {
@@ -61,20 +208,22 @@ pub struct MevDistributorClient {
     contract_address: Address,
     /// Sequencer address
     sequencer_address: Address,
-    /// HTTP provider for contract calls
-    provider: Option<Arc<Provider<Http>>>,
-    /// Accumulated MEV waiting to be deposited
-    mev_buffer: Arc<RwLock<U256>>,
-    /// Last deposit timestamp
-    last_deposit_time: Arc<RwLock<SystemTime>>,
+    /// Contract instance (None when running without an RPC provider)
+    contract: Option<IMEVDistributor::IMEVDistributorInstance<DynProvider>>,
+    /// Sequencer wallet, deposits are signed before they are persisted and sent
+    wallet: Option<EthereumWallet>,
+    /// Buffer, deposit time and lifetime totals
+    state: Arc<RwLock<DistributorBufferState>>,
+    /// Where the buffer is persisted (None keeps it in memory only)
+    buffer_path: Option<PathBuf>,
+    /// Serializes deposits so the same MEV is never sent twice concurrently
+    deposit_lock: Mutex<()>,
     /// Current epoch number (cached)
     current_epoch: Arc<RwLock<u64>>,
     /// Deposit interval
     deposit_interval: Duration,
     /// Maximum MEV buffer before forcing deposit
     max_buffer: U256,
-    /// RPC URL for provider
-    rpc_url: Option<String>,
 }
 
 impl MevDistributorClient {
@@ -88,51 +237,68 @@ impl MevDistributorClient {
         Self {
             contract_address,
             sequencer_address,
-            provider: None,
-            mev_buffer: Arc::new(RwLock::new(U256::ZERO)),
-            last_deposit_time: Arc::new(RwLock::new(SystemTime::now())),
+            contract: None,
+            wallet: None,
+            state: Arc::new(RwLock::new(DistributorBufferState {
+                last_deposit_time: unix_now(),
+                ..Default::default()
+            })),
+            buffer_path: None,
+            deposit_lock: Mutex::new(()),
             current_epoch: Arc::new(RwLock::new(1)),
             deposit_interval,
             max_buffer,
-            rpc_url: None,
         }
     }
 
-    /// Create distributor client with RPC provider for contract calls
+    /// Create distributor client connected to MEVDistributor
+    ///
+    /// `signer` must be the sequencer key registered on the contract, since
+    /// `depositMEV` is `onlySequencer`.
     pub fn with_provider(
         contract_address: Address,
-        sequencer_address: Address,
         rpc_url: &str,
+        signer: PrivateKeySigner,
         deposit_interval: Duration,
         max_buffer: U256,
-    ) -> Result<Self, String> {
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| format!("Failed to create provider: {}", e))?;
-
-        Ok(Self {
-            contract_address,
-            sequencer_address,
-            provider: Some(Arc::new(provider)),
-            mev_buffer: Arc::new(RwLock::new(U256::ZERO)),
-            last_deposit_time: Arc::new(RwLock::new(SystemTime::now())),
-            current_epoch: Arc::new(RwLock::new(1)),
-            deposit_interval,
-            max_buffer,
-            rpc_url: Some(rpc_url.to_string()),
-        })
+    ) -> Result<Self, MevDistributorError> {
+        let url = rpc_url.parse().map_err(|e| {
+            MevDistributorError::Config(format!("Invalid RPC URL {rpc_url}: {e}"))
+        })?;
+        let sequencer_address = signer.address();
+
+        let wallet = EthereumWallet::from(signer);
+        let provider =
+            ProviderBuilder::new().wallet(wallet.clone()).connect_http(url).erased();
+
+        let mut client =
+            Self::new(contract_address, sequencer_address, deposit_interval, max_buffer);
+        client.contract = Some(IMEVDistributor::new(contract_address, provider));
+        client.wallet = Some(wallet);
+        Ok(client)
     }
 
     /// Create from environment variables
-    pub fn from_env() -> Result<Self, String> {
+    ///
+    /// - `MEV_DISTRIBUTOR_ADDRESS`: MEVDistributor contract address
+    /// - `SEQUENCER_PRIVATE_KEY`: sequencer key used for deposits
+    /// - `RPC_URL`: RPC endpoint (default: http://localhost:8545)
+    /// - `MEV_DEPOSIT_INTERVAL`: seconds between deposits (default: 3600)
+    /// - `MEV_MAX_BUFFER`: ANDE buffered before forcing a deposit (default: 1000)
+    pub fn from_env() -> Result<Self, MevDistributorError> {
         let contract_address = std::env::var("MEV_DISTRIBUTOR_ADDRESS")
-            .map_err(|_| "MEV_DISTRIBUTOR_ADDRESS not set")?
+            .map_err(|_| MevDistributorError::Config("MEV_DISTRIBUTOR_ADDRESS not set".into()))?
             .parse()
-            .map_err(|e| format!("Invalid MEV_DISTRIBUTOR_ADDRESS: {}", e))?;
+            .map_err(|e| {
+                MevDistributorError::Config(format!("Invalid MEV_DISTRIBUTOR_ADDRESS: {e}"))
+            })?;
 
-        let sequencer_address = std::env::var("SEQUENCER_ADDRESS")
-            .map_err(|_| "SEQUENCER_ADDRESS not set")?
+        let signer: PrivateKeySigner = std::env::var("SEQUENCER_PRIVATE_KEY")
+            .map_err(|_| MevDistributorError::Config("SEQUENCER_PRIVATE_KEY not set".into()))?
             .parse()
-            .map_err(|e| format!("Invalid SEQUENCER_ADDRESS: {}", e))?;
+            .map_err(|e| {
+                MevDistributorError::Config(format!("Invalid SEQUENCER_PRIVATE_KEY: {e}"))
+            })?;
 
         let rpc_url = std::env::var("RPC_URL")
             .unwrap_or_else(|_| "http://localhost:8545".to_string());
@@ -149,13 +315,7 @@ impl MevDistributorClient {
             .map(|v| U256::from(v) * U256::from(10u64.pow(18)))
             .unwrap_or_else(|| U256::from(1000) * U256::from(10u64.pow(18)));
 
-        Self::with_provider(
-            contract_address,
-            sequencer_address,
-            &rpc_url,
-            deposit_interval,
-            max_buffer,
-        )
+        Self::with_provider(contract_address, &rpc_url, signer, deposit_interval, max_buffer)
     }
 
     /// Create with default configuration (for testing)
@@ -168,64 +328,123 @@ impl MevDistributorClient {
         )
     }
 
+    /// Persist the deposit buffer at `path`, restoring any buffer left there
+    /// by a previous run
+    pub fn with_buffer_path(mut self, path: impl Into<PathBuf>) -> Result<Self, MevDistributorError> {
+        let path = path.into();
+        let restored = DistributorBufferState::load(&path)?;
+
+        if restored.pending_mev > U256::ZERO {
+            info!(
+                "Restored pending MEV buffer: amount={}, path={}",
+                restored.pending_mev,
+                path.display()
+            );
+        }
+        if let Some(pending) = &restored.pending_deposit {
+            info!(
+                "Restored unconfirmed MEV deposit: tx={}, amount={}, reconciling before the next deposit",
+                pending.tx_hash, pending.amount
+            );
+        }
+
+        let state = if restored == DistributorBufferState::default() {
+            DistributorBufferState { last_deposit_time: unix_now(), ..Default::default() }
+        } else {
+            restored
+        };
+
+        self.state = Arc::new(RwLock::new(state));
+        self.buffer_path = Some(path);
+        Ok(self)
+    }
+
     /// Check if provider is configured for contract calls
     pub fn has_provider(&self) -> bool {
-        self.provider.is_some()
+        self.contract.is_some()
+    }
+
+    /// Distributor contract address
+    pub const fn contract_address(&self) -> Address {
+        self.contract_address
     }
-    
+
+    /// Sequencer address used for deposits
+    pub const fn sequencer_address(&self) -> Address {
+        self.sequencer_address
+    }
+
+    /// Write the buffer state to disk, if persistence is configured
+    fn persist(&self, state: &DistributorBufferState) {
+        if let Some(path) = &self.buffer_path {
+            if let Err(e) = state.save(path) {
+                error!("Failed to persist MEV buffer: {}", e);
+            }
+        }
+    }
+
     /// Add MEV to buffer
+    ///
+    /// The buffer is deposited by [`Self::run`] on its next tick.
     pub async fn add_mev(&self, amount: U256) {
         if amount == U256::ZERO {
             return;
         }
-        
-        let mut buffer = self.mev_buffer.write().await;
-        *buffer += amount;
-        
-        debug!("MEV added to buffer: amount={}, total_buffer={}", amount, *buffer);
-        
-        // Check if we should deposit immediately
-        drop(buffer); // Release lock before checking deposit
-        self.check_and_deposit().await;
-    }
-    
+
+        {
+            let mut state = self.state.write().await;
+            state.pending_mev += amount;
+            self.persist(&state);
+
+            debug!("MEV added to buffer: amount={}, total_buffer={}", amount, state.pending_mev);
+        }
+    }
+
+    /// Write the buffer state to disk, failing if it could not be written
+    fn try_persist(&self, state: &DistributorBufferState) -> Result<(), MevDistributorError> {
+        match &self.buffer_path {
+            Some(path) => state.save(path),
+            None => Ok(()),
+        }
+    }
+
     /// Check if deposit is needed and execute if so
     async fn check_and_deposit(&self) {
-        let buffer = *self.mev_buffer.read().await;
-        let last_deposit = *self.last_deposit_time.read().await;
-        let elapsed = SystemTime::now()
-            .duration_since(last_deposit)
-            .unwrap_or(Duration::ZERO);
-        
-        // Deposit if buffer is full or interval elapsed
-        let should_deposit = buffer >= self.max_buffer || elapsed >= self.deposit_interval;
-        
-        if should_deposit && buffer > U256::ZERO {
+        let buffer = self.state.read().await.pending_mev;
+
+        // Deposit if buffer is full or interval elapsed; without a provider
+        // the MEV stays buffered
+        if self.has_provider() && self.is_deposit_pending().await && buffer > U256::ZERO {
             if let Err(e) = self.deposit_mev().await {
                 error!("Failed to deposit MEV: {}", e);
             }
         }
     }
-    
-    /// Deposit accumulated MEV to distributor contract
-    pub async fn deposit_mev(&self) -> Result<(), String> {
-        let amount = {
-            let mut buffer = self.mev_buffer.write().await;
-            let amount = *buffer;
-
-            if amount == U256::ZERO {
-                return Ok(());
-            }
 
-            // Clear buffer
-            *buffer = U256::ZERO;
-            amount
+    /// Deposit accumulated MEV to distributor contract
+    ///
+    /// Returns the amount sent, without waiting for the receipt. The amount
+    /// is removed from the buffer once a later call finds the transaction
+    /// confirmed; MEV added meanwhile stays buffered. Nothing is deposited
+    /// while an earlier deposit is still unconfirmed, and without a provider
+    /// the buffer is kept.
+    pub async fn deposit_mev(&self) -> Result<U256, MevDistributorError> {
+        let _guard = self.deposit_lock.lock().await;
+
+        let (Some(contract), Some(wallet)) = (&self.contract, &self.wallet) else {
+            return Err(MevDistributorError::Config(
+                "no provider configured, MEV stays buffered".into(),
+            ));
         };
 
-        // Update last deposit time
-        {
-            let mut last_deposit = self.last_deposit_time.write().await;
-            *last_deposit = SystemTime::now();
+        if !self.reconcile_pending_deposit().await? {
+            debug!("Previous MEV deposit not confirmed yet, deferring deposit");
+            return Ok(U256::ZERO);
+        }
+
+        let amount = self.state.read().await.pending_mev;
+        if amount == U256::ZERO {
+            return Ok(U256::ZERO);
         }
 
         info!(
@@ -233,57 +452,156 @@ impl MevDistributorClient {
             amount, self.contract_address
         );
 
-        // Call contract if provider is configured
-        if let Some(provider) = &self.provider {
-            // Convert alloy Address to ethers Address
-            let contract_addr = ethers::types::Address::from_slice(self.contract_address.as_slice());
-            let contract = MEVDistributorContract::new(contract_addr, provider.clone());
+        let provider = contract.provider();
+        let contract_err = |e: String| MevDistributorError::Contract(e);
+
+        // Sign first so the transaction can be persisted before it is broadcast
+        let nonce = provider
+            .get_transaction_count(self.sequencer_address)
+            .pending()
+            .await
+            .map_err(|e| contract_err(e.to_string()))?;
+        let chain_id = provider.get_chain_id().await.map_err(|e| contract_err(e.to_string()))?;
+        let request = contract
+            .depositMEV(amount)
+            .into_transaction_request()
+            .with_from(self.sequencer_address)
+            .with_nonce(nonce)
+            .with_chain_id(chain_id);
+        let gas_limit =
+            provider.estimate_gas(request.clone()).await.map_err(|e| contract_err(e.to_string()))?;
+        let fees =
+            provider.estimate_eip1559_fees().await.map_err(|e| contract_err(e.to_string()))?;
+        let envelope = request
+            .with_gas_limit(gas_limit)
+            .with_max_fee_per_gas(fees.max_fee_per_gas)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .build(wallet)
+            .await
+            .map_err(|e| contract_err(e.to_string()))?;
+
+        let pending = PendingDeposit {
+            tx_hash: *envelope.tx_hash(),
+            nonce: envelope.nonce(),
+            amount,
+            raw: envelope.encoded_2718().into(),
+        };
+        {
+            let mut state = self.state.write().await;
+            state.pending_deposit = Some(pending.clone());
+            if let Err(e) = self.try_persist(&state) {
+                state.pending_deposit = None;
+                return Err(e);
+            }
+        }
 
-            // Convert alloy U256 to ethers U256
-            let eth_amount = ethers::types::U256::from_big_endian(&amount.to_be_bytes::<32>());
+        // From here on the marker is reconciled on failure, on the next tick or on restart
+        provider.send_raw_transaction(&pending.raw).await.map_err(|e| contract_err(e.to_string()))?;
 
-            match contract.deposit_mev(eth_amount).call().await {
-                Ok(_) => {
-                    info!("MEV deposit successful: amount={}", amount);
-                }
-                Err(e) => {
-                    // On error, return the amount to buffer
-                    let mut buffer = self.mev_buffer.write().await;
-                    *buffer += amount;
-                    error!("MEV deposit failed, returning to buffer: {}", e);
-                    return Err(format!("Contract call failed: {}", e));
+        info!("MEV deposit sent: amount={}, tx={}", amount, pending.tx_hash);
+        Ok(amount)
+    }
+
+    /// Reconcile a persisted [`PendingDeposit`] with the chain
+    ///
+    /// Returns true when no deposit is outstanding anymore. A confirmed
+    /// deposit is applied to the buffer, a reverted or replaced one is
+    /// dropped, and one that is neither is rebroadcast and kept.
+    async fn reconcile_pending_deposit(&self) -> Result<bool, MevDistributorError> {
+        let Some(pending) = self.state.read().await.pending_deposit.clone() else {
+            return Ok(true);
+        };
+        let Some(contract) = &self.contract else {
+            return Ok(false);
+        };
+        let provider = contract.provider();
+
+        // Nonce before receipt: a deposit mined in between is then still found
+        let nonce = provider
+            .get_transaction_count(self.sequencer_address)
+            .await
+            .map_err(|e| MevDistributorError::Contract(e.to_string()))?;
+        let receipt = provider
+            .get_transaction_receipt(pending.tx_hash)
+            .await
+            .map_err(|e| MevDistributorError::Contract(e.to_string()))?;
+
+        let mut state = self.state.write().await;
+        match receipt {
+            Some(receipt) if receipt.status() => {
+                info!(
+                    "Pending MEV deposit confirmed: amount={}, tx={}",
+                    pending.amount, pending.tx_hash
+                );
+                self.apply_deposit(&mut state, pending.amount);
+                Ok(true)
+            }
+            Some(_) => {
+                warn!("Pending MEV deposit {} reverted, keeping amount in buffer", pending.tx_hash);
+                state.pending_deposit = None;
+                self.persist(&state);
+                Ok(true)
+            }
+            None if nonce > pending.nonce => {
+                warn!(
+                    "Pending MEV deposit {} replaced by another transaction, keeping amount in buffer",
+                    pending.tx_hash
+                );
+                state.pending_deposit = None;
+                self.persist(&state);
+                Ok(true)
+            }
+            None => {
+                drop(state);
+                if let Err(e) = provider.send_raw_transaction(&pending.raw).await {
+                    debug!("Rebroadcast of MEV deposit {} failed: {}", pending.tx_hash, e);
                 }
+                Ok(false)
             }
-        } else {
-            warn!("No provider configured, MEV deposit not sent to contract");
         }
+    }
 
-        Ok(())
+    /// Remove a confirmed deposit from the buffer and clear its marker
+    fn apply_deposit(&self, state: &mut DistributorBufferState, amount: U256) {
+        state.pending_mev = state.pending_mev.saturating_sub(amount);
+        state.total_deposited += amount;
+        state.deposits_count += 1;
+        state.last_deposit_time = unix_now();
+        state.pending_deposit = None;
+        self.persist(state);
     }
-    
+
     /// Force deposit regardless of buffer state
-    pub async fn force_deposit(&self) -> Result<U256, String> {
-        let amount = *self.mev_buffer.read().await;
-        
-        if amount > U256::ZERO {
-            self.deposit_mev().await?;
-            Ok(amount)
-        } else {
-            Ok(U256::ZERO)
-        }
+    pub async fn force_deposit(&self) -> Result<U256, MevDistributorError> {
+        self.deposit_mev().await
     }
-    
+
     /// Get current buffer amount
     pub async fn get_buffer_amount(&self) -> U256 {
-        *self.mev_buffer.read().await
+        self.state.read().await.pending_mev
+    }
+
+    /// Last epoch number seen from the contract, without querying it
+    pub async fn cached_epoch(&self) -> u64 {
+        *self.current_epoch.read().await
     }
-    
+
     /// Get current epoch data
     pub async fn get_current_epoch(&self) -> EpochData {
+        if let Some(contract) = &self.contract {
+            match contract.getCurrentEpochInfo().call().await {
+                Ok(info) => {
+                    let epoch = info.epoch.saturating_to::<u64>();
+                    *self.current_epoch.write().await = epoch;
+                    if let Ok(data) = self.get_epoch_info(epoch).await {
+                        return data;
+                    }
+                }
+                Err(e) => warn!("Failed to query current epoch: {}", e),
+            }
+        }
+
         let epoch = *self.current_epoch.read().await;
-        
-        // In production, this would query the smart contract
-        // For now, return mock data
         EpochData {
             epoch,
             total_mev: U256::ZERO,
@@ -291,91 +609,179 @@ impl MevDistributorClient {
             protocol_fee: U256::ZERO,
             treasury_amount: U256::ZERO,
             settled: false,
-            timestamp: SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: unix_now(),
         }
     }
-    
+
     /// Get epoch info for specific epoch number
-    pub async fn get_epoch_info(&self, epoch: u64) -> Result<EpochData, String> {
-        // In production, this would query the smart contract
-        // self.call_contract_get_epoch_data(epoch).await
-        
+    pub async fn get_epoch_info(&self, epoch: u64) -> Result<EpochData, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(EpochData {
+                epoch,
+                total_mev: U256::ZERO,
+                stakers_reward: U256::ZERO,
+                protocol_fee: U256::ZERO,
+                treasury_amount: U256::ZERO,
+                settled: false,
+                timestamp: 0,
+            });
+        };
+
+        let data = contract
+            .getEpochData(U256::from(epoch))
+            .call()
+            .await
+            .map_err(|e| MevDistributorError::Contract(e.to_string()))?;
+
         Ok(EpochData {
             epoch,
-            total_mev: U256::ZERO,
-            stakers_reward: U256::ZERO,
-            protocol_fee: U256::ZERO,
-            treasury_amount: U256::ZERO,
-            settled: false,
-            timestamp: 0,
+            total_mev: data.totalMEV,
+            stakers_reward: data.stakersReward,
+            protocol_fee: data.protocolFee,
+            treasury_amount: data.treasuryAmount,
+            settled: data.settled,
+            timestamp: data.timestamp.saturating_to(),
         })
     }
-    
+
     /// Check if epoch settlement is needed
-    pub async fn check_epoch_settlement(&self) -> Result<bool, String> {
-        let _current_epoch_data = self.get_current_epoch().await;
-        
-        // In production, check if epoch duration has passed
-        // and call settleEpoch() on the contract if needed
-        
-        Ok(false)
-    }
-    
-    /// Settle current epoch (admin function)
-    pub async fn settle_epoch(&self) -> Result<(), String> {
+    ///
+    /// True once the contract reports no time remaining in the current epoch.
+    pub async fn check_epoch_settlement(&self) -> Result<bool, MevDistributorError> {
+        let Some(contract) = &self.contract else {
+            return Ok(false);
+        };
+
+        let info = contract
+            .getCurrentEpochInfo()
+            .call()
+            .await
+            .map_err(|e| MevDistributorError::Contract(e.to_string()))?;
+
+        *self.current_epoch.write().await = info.epoch.saturating_to();
+        Ok(info.timeRemaining == U256::ZERO)
+    }
+
+    /// Settle current epoch
+    ///
+    /// `settleEpoch` is permissionless once the epoch duration has passed.
+    /// Returns once the transaction is sent; the receipt is awaited in a
+    /// background task, which advances the cached epoch.
+    pub async fn settle_epoch(&self) -> Result<(), MevDistributorError> {
         let current = *self.current_epoch.read().await;
-        
+
         info!("Settling epoch {}", current);
-        
-        // In production, this would call the smart contract
-        // self.call_contract_settle_epoch().await?;
-        
-        // Increment epoch
-        let mut epoch = self.current_epoch.write().await;
-        *epoch += 1;
-        
+
+        let Some(contract) = &self.contract else {
+            *self.current_epoch.write().await = current + 1;
+            return Ok(());
+        };
+
+        let pending = contract
+            .settleEpoch()
+            .send()
+            .await
+            .map_err(|e| MevDistributorError::Contract(e.to_string()))?;
+        let current_epoch = Arc::clone(&self.current_epoch);
+        tokio::spawn(async move {
+            let receipt = match pending.get_receipt().await {
+                Ok(receipt) => receipt,
+                Err(e) => {
+                    warn!("Epoch {} settlement not confirmed: {}", current, e);
+                    return;
+                }
+            };
+            if !receipt.status() {
+                error!("Epoch {} settlement reverted: tx={}", current, receipt.transaction_hash);
+                return;
+            }
+
+            if let Some(settled) = receipt.decoded_log::<IMEVDistributor::EpochSettled>() {
+                info!(
+                    "Epoch {} settled: total_mev={}, stakers_reward={}",
+                    settled.epoch, settled.totalMEV, settled.stakersReward
+                );
+            }
+            let mut epoch = current_epoch.write().await;
+            *epoch = (*epoch).max(current + 1);
+        });
+
         Ok(())
     }
-    
+
+    /// Run deposit and epoch settlement until the node shuts down
+    ///
+    /// Spawned by the node as a background task next to bundle settlement,
+    /// which feeds captured MEV in through [`Self::add_mev`].
+    pub async fn run(self: Arc<Self>) {
+        info!(
+            "💰 MEV distributor task started: contract={}, deposit_interval={:?}",
+            self.contract_address, self.deposit_interval
+        );
+
+        // A deposit left unconfirmed by the previous run is settled first
+        if let Err(e) = self.reconcile_pending_deposit().await {
+            warn!("Failed to reconcile pending MEV deposit: {}", e);
+        }
+
+        let mut ticker = tokio::time::interval(MAINTENANCE_TICK.min(self.deposit_interval));
+        loop {
+            ticker.tick().await;
+
+            self.check_and_deposit().await;
+
+            match self.check_epoch_settlement().await {
+                Ok(true) => {
+                    if let Err(e) = self.settle_epoch().await {
+                        error!("Failed to settle epoch: {}", e);
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => debug!("Epoch settlement check failed: {}", e),
+            }
+        }
+    }
+
     /// Get distributor statistics
     pub async fn get_distributor_stats(&self) -> DistributorStats {
-        let buffer = *self.mev_buffer.read().await;
+        let state = self.state.read().await.clone();
         let epoch = *self.current_epoch.read().await;
-        let last_deposit = *self.last_deposit_time.read().await;
-        let time_since_deposit = SystemTime::now()
-            .duration_since(last_deposit)
-            .unwrap_or(Duration::ZERO);
-        
+
         DistributorStats {
             current_epoch: epoch,
-            buffer_amount: buffer,
-            time_since_last_deposit: time_since_deposit,
-            total_deposited: U256::ZERO, // Would track this in production
-            deposits_count: 0,             // Would track this in production
+            buffer_amount: state.pending_mev,
+            time_since_last_deposit: Duration::from_secs(
+                unix_now().saturating_sub(state.last_deposit_time),
+            ),
+            total_deposited: state.total_deposited,
+            deposits_count: state.deposits_count,
         }
     }
-    
+
     /// Get time until next scheduled deposit
     pub async fn time_until_next_deposit(&self) -> Duration {
-        let last_deposit = *self.last_deposit_time.read().await;
-        let elapsed = SystemTime::now()
-            .duration_since(last_deposit)
-            .unwrap_or(Duration::ZERO);
-        
+        let last_deposit = self.state.read().await.last_deposit_time;
+        let elapsed = Duration::from_secs(unix_now().saturating_sub(last_deposit));
+
         self.deposit_interval.saturating_sub(elapsed)
     }
-    
+
     /// Check if deposit is pending
     pub async fn is_deposit_pending(&self) -> bool {
-        let buffer = *self.mev_buffer.read().await;
+        let buffer = self.state.read().await.pending_mev;
         buffer >= self.max_buffer
             || self.time_until_next_deposit().await == Duration::ZERO
     }
 }
 
+/// Current Unix time in seconds
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Distributor statistics
 #[derive(Debug, Clone)]
 pub struct DistributorStats {
@@ -411,7 +817,7 @@ mod tests {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         let buffer = client.get_buffer_amount().await;
         assert_eq!(buffer, U256::ZERO);
     }
@@ -421,47 +827,31 @@ mod tests {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         let amount = U256::from(1000);
         client.add_mev(amount).await;
-        
+
         let buffer = client.get_buffer_amount().await;
         assert_eq!(buffer, amount);
     }
 
     #[tokio::test]
-    async fn test_deposit_mev() {
+    async fn test_deposit_mev_without_provider_keeps_buffer() {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         // Add MEV to buffer
         let amount = U256::from(1000);
         client.add_mev(amount).await;
-        
-        // Deposit
-        let result = client.deposit_mev().await;
-        assert!(result.is_ok());
-        
-        // Buffer should be cleared
-        let buffer = client.get_buffer_amount().await;
-        assert_eq!(buffer, U256::ZERO);
-    }
 
-    #[tokio::test]
-    async fn test_force_deposit() {
-        let contract = Address::random();
-        let sequencer = Address::random();
-        let client = MevDistributorClient::default_config(contract, sequencer);
-        
-        let amount = U256::from(500);
-        client.add_mev(amount).await;
-        
-        let deposited = client.force_deposit().await.unwrap();
-        assert_eq!(deposited, amount);
-        
+        // Nothing can be deposited, the MEV must not be dropped
+        assert!(matches!(client.deposit_mev().await, Err(MevDistributorError::Config(_))));
+        assert!(client.force_deposit().await.is_err());
+
         let buffer = client.get_buffer_amount().await;
-        assert_eq!(buffer, U256::ZERO);
+        assert_eq!(buffer, amount);
+        assert_eq!(client.get_distributor_stats().await.deposits_count, 0);
     }
 
     #[tokio::test]
@@ -469,7 +859,7 @@ mod tests {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         let epoch_data = client.get_current_epoch().await;
         assert_eq!(epoch_data.epoch, 1);
         assert_eq!(epoch_data.total_mev, U256::ZERO);
@@ -480,9 +870,9 @@ mod tests {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         client.add_mev(U256::from(1000)).await;
-        
+
         let stats = client.get_distributor_stats().await;
         assert_eq!(stats.current_epoch, 1);
         assert_eq!(stats.buffer_amount, U256::from(1000));
@@ -493,13 +883,71 @@ mod tests {
         let contract = Address::random();
         let sequencer = Address::random();
         let client = MevDistributorClient::default_config(contract, sequencer);
-        
+
         // Add MEV multiple times
         for i in 1..=5 {
             client.add_mev(U256::from(i * 100)).await;
         }
-        
+
         let buffer = client.get_buffer_amount().await;
         assert_eq!(buffer, U256::from(1500)); // Sum of 100+200+300+400+500
     }
+
+    #[tokio::test]
+    async fn test_buffer_survives_restart() {
+        let path = std::env::temp_dir()
+            .join(format!("ande-mev-buffer-{}", Address::random()))
+            .join(DISTRIBUTOR_BUFFER_FILE);
+        let contract = Address::random();
+        let sequencer = Address::random();
+
+        let pending = PendingDeposit {
+            tx_hash: B256::repeat_byte(0xde),
+            nonce: 7,
+            amount: U256::from(700),
+            raw: Bytes::from_static(&[0x02, 0xf8]),
+        };
+
+        {
+            let client = MevDistributorClient::default_config(contract, sequencer)
+                .with_buffer_path(&path)
+                .unwrap();
+            client.add_mev(U256::from(700)).await;
+            let mut state = client.state.write().await;
+            state.pending_deposit = Some(pending.clone());
+            client.try_persist(&state).unwrap();
+            drop(state);
+            client.add_mev(U256::from(300)).await;
+            // Dropped before the deposit was confirmed, as in a crash
+        }
+
+        let client = MevDistributorClient::default_config(contract, sequencer)
+            .with_buffer_path(&path)
+            .unwrap();
+        // The unconfirmed deposit is still buffered and its marker restored
+        assert_eq!(client.get_buffer_amount().await, U256::from(1000));
+        assert_eq!(client.state.read().await.pending_deposit, Some(pending));
+
+        let stats = client.get_distributor_stats().await;
+        assert_eq!(stats.total_deposited, U256::ZERO);
+        assert_eq!(stats.deposits_count, 0);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_buffer_file_without_pending_deposit() {
+        let state: DistributorBufferState = serde_json::from_str(
+            r#"{"pendingMev":"0x64","lastDepositTime":1,"totalDeposited":"0x0","depositsCount":0}"#,
+        )
+        .unwrap();
+        assert_eq!(state.pending_mev, U256::from(100));
+        assert_eq!(state.pending_deposit, None);
+    }
+
+    #[test]
+    fn test_missing_buffer_file_is_empty() {
+        let path = std::env::temp_dir().join(format!("ande-mev-missing-{}.json", Address::random()));
+        assert_eq!(DistributorBufferState::load(&path).unwrap(), DistributorBufferState::default());
+    }
 }
@@ -27,7 +27,7 @@ use reth_revm::{
         state::EvmState,
     },
 };
-use tracing::{debug, info};
+use tracing::{debug, info, trace};
 
 /// Handler wrapper that applies ANDE-specific MEV distribution policies.
 ///
@@ -48,12 +48,13 @@ impl<EVM, ERROR, FRAME> AndeHandler<EVM, ERROR, FRAME> {
     ///
     /// * `mev_redirect` - Optional MEV distribution policy. If `None`, behaves like standard handler.
     pub fn new(mev_redirect: Option<AndeMevRedirect>) -> Self {
+        // Handlers are created per transaction, keep this quiet
         if mev_redirect.is_some() {
-            info!("🎯 AndeHandler initialized with MEV redistribution enabled");
+            trace!("AndeHandler initialized with MEV redistribution enabled");
         } else {
-            debug!("AndeHandler initialized in standard mode (no MEV redirect)");
+            trace!("AndeHandler initialized in standard mode (no MEV redirect)");
         }
-        
+
         Self {
             inner: MainnetHandler::default(),
             mev_redirect,
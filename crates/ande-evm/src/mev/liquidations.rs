@@ -0,0 +1,462 @@
+//! Liquidation Reconstruction from Receipts
+//!
+//! Decodes `Liquidation` logs from `AndeLend` and `PositionLiquidated` logs
+//! from `AndePerpetuals` and values the bonus the liquidator captured:
+//!
+//! ```text
+//! AndeLend            collateralAmount − debtAmount at oracle prices  ─┐
+//! AndePerpetuals      liquidationFee (in the collateral token)        ─┤
+//!                                                                      ↓
+//!                  bonus (token) ── AndeOracleAggregator.getPrice ──→ USD ──→ ANDE
+//! ```
+//!
+//! Prices are read from the oracle the liquidating contract itself uses
+//! (its `priceOracle()`), at the post-block state, through
+//! [`PriceOracleReader`]. Only logs emitted by known lending contracts are
+//! decoded, so arbitrary contracts cannot fabricate liquidations.
+
+use super::swaps::EvmPoolStateReader;
+use alloy::sol_types::{sol, SolEvent};
+use alloy_evm::Evm;
+use alloy_primitives::{Address, Log, B256, U256, U512};
+
+/// Oracle prices are USD with 18 decimals
+const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// AndeLend liquidation ABI
+pub mod lend {
+    use super::sol;
+
+    sol! {
+        #[derive(Debug)]
+        event Liquidation(
+            address indexed liquidator,
+            address indexed borrower,
+            address indexed collateralToken,
+            address debtToken,
+            uint256 debtAmount,
+            uint256 collateralAmount
+        );
+
+        function liquidate(address borrower, address debtToken, address collateralToken, uint256 debtAmount) external;
+        function priceOracle() external view returns (address);
+    }
+
+    /// `LIQUIDATION_BONUS` in basis points
+    pub const LIQUIDATION_BONUS_BPS: u64 = 500;
+}
+
+/// AndePerpetuals liquidation ABI
+pub mod perps {
+    use super::sol;
+
+    sol! {
+        #[derive(Debug)]
+        event PositionLiquidated(
+            address indexed user,
+            address indexed market,
+            address indexed liquidator,
+            uint256 liquidationPrice,
+            uint256 liquidationFee
+        );
+
+        function liquidate(address user, address market) external;
+        function collateralToken() external view returns (address);
+        function priceOracle() external view returns (address);
+    }
+}
+
+/// AndeOracleAggregator and ERC20 views
+pub mod aggregator {
+    use super::sol;
+
+    sol! {
+        function getPrice(address asset) external view returns (uint256 price);
+        function decimals() external view returns (uint8);
+    }
+}
+
+/// Read-only access to lending contracts and their price oracle
+pub trait PriceOracleReader {
+    /// Oracle used by a lending contract (`priceOracle()`)
+    fn price_oracle(&mut self, contract: Address) -> Option<Address>;
+
+    /// USD price of `asset` with 18 decimals, `None` if unpriced
+    fn price(&mut self, oracle: Address, asset: Address) -> Option<U256>;
+
+    /// ERC20 decimals of `token`
+    fn decimals(&mut self, token: Address) -> Option<u8>;
+
+    /// Collateral token of an AndePerpetuals contract
+    fn perp_collateral_token(&mut self, perps: Address) -> Option<Address>;
+}
+
+impl<E: Evm> PriceOracleReader for EvmPoolStateReader<E> {
+    fn price_oracle(&mut self, contract: Address) -> Option<Address> {
+        self.call(contract, lend::priceOracleCall {}).filter(|oracle| !oracle.is_zero())
+    }
+
+    fn price(&mut self, oracle: Address, asset: Address) -> Option<U256> {
+        self.call(oracle, aggregator::getPriceCall { asset }).filter(|price| !price.is_zero())
+    }
+
+    fn decimals(&mut self, token: Address) -> Option<u8> {
+        self.call(token, aggregator::decimalsCall {})
+    }
+
+    fn perp_collateral_token(&mut self, perps: Address) -> Option<Address> {
+        self.call(perps, perps::collateralTokenCall {})
+    }
+}
+
+/// What was liquidated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiquidationKind {
+    /// AndeLend borrow position
+    Lend {
+        /// Token seized from the borrower
+        collateral_token: Address,
+        /// Token repaid by the liquidator
+        debt_token: Address,
+        /// Debt repaid
+        debt_amount: U256,
+        /// Collateral seized, bonus included
+        collateral_amount: U256,
+    },
+    /// AndePerpetuals position
+    Perpetual {
+        /// Perpetual market
+        market: Address,
+        /// Mark price at liquidation
+        price: U256,
+        /// Fee paid to the liquidator in the collateral token
+        fee: U256,
+    },
+}
+
+/// Liquidation decoded from a receipt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationEvent {
+    /// Index of the transaction in the block
+    pub tx_index: usize,
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Lending contract that emitted the event
+    pub contract: Address,
+    /// Account that received the bonus
+    pub liquidator: Address,
+    /// Liquidated account
+    pub borrower: Address,
+    /// Liquidation details
+    pub kind: LiquidationKind,
+}
+
+impl LiquidationEvent {
+    /// Protocol name for reporting
+    pub fn protocol(&self) -> &'static str {
+        match self.kind {
+            LiquidationKind::Lend { .. } => "AndeLend",
+            LiquidationKind::Perpetual { .. } => "AndePerpetuals",
+        }
+    }
+}
+
+/// Liquidation bonus priced through the oracle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationBonus {
+    /// Token the bonus was paid in
+    pub token: Address,
+    /// Bonus in `token` units
+    pub amount: U256,
+    /// Bonus in USD (18 decimals), if the oracle priced it
+    pub usd: Option<U256>,
+}
+
+/// Decode the liquidations of one transaction from its logs
+///
+/// Logs from contracts for which `is_lending` is false are ignored.
+pub fn decode_liquidations(
+    tx_index: usize,
+    tx_hash: B256,
+    logs: &[Log],
+    is_lending: impl Fn(&Address) -> bool,
+) -> Vec<LiquidationEvent> {
+    let mut liquidations = Vec::new();
+
+    for log in logs {
+        let Some(topic0) = log.topics().first() else {
+            continue;
+        };
+        if !is_lending(&log.address) {
+            continue;
+        }
+
+        let (liquidator, borrower, kind) = if *topic0 == lend::Liquidation::SIGNATURE_HASH {
+            let Ok(event) = lend::Liquidation::decode_log_data(&log.data) else {
+                continue;
+            };
+            (
+                event.liquidator,
+                event.borrower,
+                LiquidationKind::Lend {
+                    collateral_token: event.collateralToken,
+                    debt_token: event.debtToken,
+                    debt_amount: event.debtAmount,
+                    collateral_amount: event.collateralAmount,
+                },
+            )
+        } else if *topic0 == perps::PositionLiquidated::SIGNATURE_HASH {
+            let Ok(event) = perps::PositionLiquidated::decode_log_data(&log.data) else {
+                continue;
+            };
+            (
+                event.liquidator,
+                event.user,
+                LiquidationKind::Perpetual {
+                    market: event.market,
+                    price: event.liquidationPrice,
+                    fee: event.liquidationFee,
+                },
+            )
+        } else {
+            continue;
+        };
+
+        liquidations.push(LiquidationEvent {
+            tx_index,
+            tx_hash,
+            contract: log.address,
+            liquidator,
+            borrower,
+            kind,
+        });
+    }
+
+    liquidations
+}
+
+/// Bonus captured by the liquidator
+///
+/// For AndeLend this is the seized collateral minus the repaid debt at
+/// oracle prices, expressed in the collateral token. For AndePerpetuals it
+/// is the liquidation fee.
+pub fn liquidation_bonus<R: PriceOracleReader>(
+    liquidation: &LiquidationEvent,
+    reader: &mut R,
+) -> Option<LiquidationBonus> {
+    let oracle = reader.price_oracle(liquidation.contract);
+
+    match &liquidation.kind {
+        LiquidationKind::Lend { collateral_token, debt_token, debt_amount, collateral_amount } => {
+            let oracle = oracle?;
+            let debt_usd = usd_value(reader, oracle, *debt_token, *debt_amount)?;
+            let collateral_usd = usd_value(reader, oracle, *collateral_token, *collateral_amount)?;
+            let repaid = token_amount(reader, oracle, *collateral_token, debt_usd)?;
+
+            Some(LiquidationBonus {
+                token: *collateral_token,
+                amount: collateral_amount.saturating_sub(repaid),
+                usd: Some(collateral_usd.saturating_sub(debt_usd)),
+            })
+        }
+        LiquidationKind::Perpetual { fee, .. } => {
+            let token = reader.perp_collateral_token(liquidation.contract)?;
+            let usd = oracle.and_then(|oracle| usd_value(reader, oracle, token, *fee));
+            Some(LiquidationBonus { token, amount: *fee, usd })
+        }
+    }
+}
+
+/// USD value (18 decimals) of `amount` of `token`
+pub fn usd_value<R: PriceOracleReader>(
+    reader: &mut R,
+    oracle: Address,
+    token: Address,
+    amount: U256,
+) -> Option<U256> {
+    let price = reader.price(oracle, token)?;
+    let normalized = normalize(amount, reader.decimals(token).unwrap_or(18));
+    Some(mul_div(normalized, price, U256::from(PRICE_PRECISION)))
+}
+
+/// Amount of `token` worth `usd` (18 decimals)
+fn token_amount<R: PriceOracleReader>(
+    reader: &mut R,
+    oracle: Address,
+    token: Address,
+    usd: U256,
+) -> Option<U256> {
+    let price = reader.price(oracle, token)?;
+    let normalized = mul_div(usd, U256::from(PRICE_PRECISION), price);
+    Some(denormalize(normalized, reader.decimals(token).unwrap_or(18)))
+}
+
+/// Scale a token amount to 18 decimals, as AndeLend does
+fn normalize(amount: U256, decimals: u8) -> U256 {
+    match decimals {
+        d if d < 18 => amount.saturating_mul(U256::from(10).pow(U256::from(18 - d))),
+        d => amount / U256::from(10).pow(U256::from(d - 18)),
+    }
+}
+
+/// Inverse of [`normalize`]
+fn denormalize(amount: U256, decimals: u8) -> U256 {
+    match decimals {
+        d if d < 18 => amount / U256::from(10).pow(U256::from(18 - d)),
+        d => amount.saturating_mul(U256::from(10).pow(U256::from(d - 18))),
+    }
+}
+
+/// `a * b / c` without intermediate overflow
+fn mul_div(a: U256, b: U256, c: U256) -> U256 {
+    U256::saturating_from(U512::from(a) * U512::from(b) / U512::from(c))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::mev::swaps::tests::MockPools;
+    use crate::mev::swaps::PoolStateReader;
+    use std::collections::HashMap;
+
+    /// In-memory oracle state
+    #[derive(Debug, Default)]
+    pub(crate) struct MockOracle {
+        pub(crate) oracles: HashMap<Address, Address>,
+        pub(crate) prices: HashMap<Address, U256>,
+        pub(crate) decimals: HashMap<Address, u8>,
+        pub(crate) collateral: HashMap<Address, Address>,
+    }
+
+    impl PriceOracleReader for MockOracle {
+        fn price_oracle(&mut self, contract: Address) -> Option<Address> {
+            self.oracles.get(&contract).copied()
+        }
+
+        fn price(&mut self, _oracle: Address, asset: Address) -> Option<U256> {
+            self.prices.get(&asset).copied()
+        }
+
+        fn decimals(&mut self, token: Address) -> Option<u8> {
+            self.decimals.get(&token).copied()
+        }
+
+        fn perp_collateral_token(&mut self, perps: Address) -> Option<Address> {
+            self.collateral.get(&perps).copied()
+        }
+    }
+
+    impl PoolStateReader for MockOracle {
+        fn pool_tokens(&mut self, _pool: Address) -> Option<(Address, Address)> {
+            None
+        }
+
+        fn v2_reserves(&mut self, _pool: Address) -> Option<(U256, U256)> {
+            None
+        }
+    }
+
+    /// Pools without any oracle
+    impl PriceOracleReader for MockPools {
+        fn price_oracle(&mut self, _contract: Address) -> Option<Address> {
+            None
+        }
+
+        fn price(&mut self, _oracle: Address, _asset: Address) -> Option<U256> {
+            None
+        }
+
+        fn decimals(&mut self, _token: Address) -> Option<u8> {
+            None
+        }
+
+        fn perp_collateral_token(&mut self, _perps: Address) -> Option<Address> {
+            None
+        }
+    }
+
+    /// USD price with 18 decimals
+    pub(crate) fn usd(dollars: u64) -> U256 {
+        U256::from(dollars) * U256::from(PRICE_PRECISION)
+    }
+
+    /// `Liquidation` log of AndeLend
+    pub(crate) fn lend_liquidation_log(
+        lend: Address,
+        liquidator: Address,
+        collateral_token: Address,
+        debt_token: Address,
+        debt_amount: U256,
+        collateral_amount: U256,
+    ) -> Log {
+        let event = lend::Liquidation {
+            liquidator,
+            borrower: Address::repeat_byte(0xbb),
+            collateralToken: collateral_token,
+            debtToken: debt_token,
+            debtAmount: debt_amount,
+            collateralAmount: collateral_amount,
+        };
+        Log { address: lend, data: event.encode_log_data() }
+    }
+
+    #[test]
+    fn test_lend_bonus_from_oracle_prices() {
+        let (lend, oracle) = (Address::repeat_byte(0x10), Address::repeat_byte(0x11));
+        let (weth, usdc) = (Address::repeat_byte(0xe0), Address::repeat_byte(0xc0));
+        let liquidator = Address::repeat_byte(0xaa);
+
+        let mut reader = MockOracle::default();
+        reader.oracles.insert(lend, oracle);
+        reader.prices.insert(weth, usd(2_000));
+        reader.prices.insert(usdc, usd(1));
+        reader.decimals.insert(usdc, 6);
+
+        // Repay 1,000 USDC, seize 0.525 WETH (1,000 USD + 5%)
+        let log = lend_liquidation_log(
+            lend,
+            liquidator,
+            weth,
+            usdc,
+            U256::from(1_000_000_000u64),
+            U256::from(525_000_000_000_000_000u64),
+        );
+        let unknown = Log { address: Address::repeat_byte(0x99), ..log.clone() };
+
+        let liquidations = decode_liquidations(0, B256::ZERO, &[log, unknown], |a| *a == lend);
+        assert_eq!(liquidations.len(), 1);
+        assert_eq!(liquidations[0].liquidator, liquidator);
+
+        let bonus = liquidation_bonus(&liquidations[0], &mut reader).unwrap();
+        assert_eq!(bonus.token, weth);
+        assert_eq!(bonus.amount, U256::from(25_000_000_000_000_000u64));
+        assert_eq!(bonus.usd, Some(usd(50)));
+    }
+
+    #[test]
+    fn test_perpetual_fee_bonus() {
+        let (perp, oracle, usdc) =
+            (Address::repeat_byte(0x20), Address::repeat_byte(0x21), Address::repeat_byte(0xc0));
+        let mut reader = MockOracle::default();
+        reader.oracles.insert(perp, oracle);
+        reader.collateral.insert(perp, usdc);
+        reader.prices.insert(usdc, usd(1));
+        reader.decimals.insert(usdc, 6);
+
+        let event = perps::PositionLiquidated {
+            user: Address::repeat_byte(0xbb),
+            market: Address::repeat_byte(0x30),
+            liquidator: Address::repeat_byte(0xaa),
+            liquidationPrice: usd(1_500),
+            liquidationFee: U256::from(5_000_000u64),
+        };
+        let log = Log { address: perp, data: event.encode_log_data() };
+
+        let liquidations = decode_liquidations(3, B256::ZERO, &[log], |_| true);
+        assert_eq!(liquidations[0].protocol(), "AndePerpetuals");
+
+        let bonus = liquidation_bonus(&liquidations[0], &mut reader).unwrap();
+        assert_eq!((bonus.token, bonus.amount), (usdc, U256::from(5_000_000u64)));
+        assert_eq!(bonus.usd, Some(usd(5)));
+    }
+}
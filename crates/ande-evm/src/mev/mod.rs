@@ -8,6 +8,13 @@
 //! - `redirect`: MEV redirect policy and detection
 //! - `handler`: Execution handler with MEV interception
 //! - `config`: MEV configuration from environment
+//! - `auction`: MEVAuctionManager client and bundle lane for the payload builder
+//! - `simulation`: bundle simulation shared by the bundle RPC and payload builder
+//! - `distributor`: MEVDistributor deposits and epoch settlement
+//! - `swaps`: swap reconstruction from receipts for sandwich/arbitrage detection
+//! - `liquidations`: AndeLend / AndePerpetuals liquidation bonuses from receipts
+//! - `store`: persistent, indexed history of MEV events
+//! - `registry`: hot-reloaded DEX, lending and oracle addresses for the detector
 //!
 //! ## Usage
 //!
@@ -28,8 +35,34 @@ pub mod redirect;
 pub mod handler;
 pub mod config;
 pub mod detector;
+pub mod auction;
+pub mod simulation;
+pub mod distributor;
+pub mod swaps;
+pub mod liquidations;
+pub mod store;
+pub mod registry;
 
 pub use redirect::{AndeMevRedirect, MevDetection, MevRedirectError, MevType};
 pub use handler::AndeHandler;
 pub use config::{MevConfig, MevConfigError};
-pub use detector::MevDetector;
+pub use detector::{DetectorConfig, MevDetector, MevOpportunity};
+pub use store::{
+    EpochMevTotals, MevEventFilter, MevEventRecord, MevEventSource, MevEventStore,
+    MevEventStoreError, MAX_QUERY_LIMIT, MEV_EVENT_STORE_DIR,
+};
+pub use liquidations::{LiquidationBonus, LiquidationEvent, LiquidationKind, PriceOracleReader};
+pub use registry::{
+    ProtocolRegistry, ProtocolRegistryError, ProtocolRegistryFile, DISCOVERED_POOLS_FILE,
+    PROTOCOL_REGISTRY_ENV,
+};
+pub use swaps::{EvmPoolStateReader, PoolKind, PoolStateReader, SwapEvent};
+pub use auction::{
+    AuctionStats, BundleExecutionResult, BundleLane, BundleSubmission, MevAuctionClient,
+    MevAuctionError,
+};
+pub use simulation::{simulate_bundle, BundleSimulation, BundleSimulationError, SimulatedTx};
+pub use distributor::{
+    DistributorBufferState, DistributorStats, EpochData, MevDistributorClient,
+    MevDistributorError, DISTRIBUTOR_BUFFER_FILE,
+};
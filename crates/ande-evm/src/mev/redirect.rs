@@ -150,31 +150,9 @@ impl AndeMevRedirect {
         <CTX::Db as Database>::Error: std::error::Error,
     {
         // Get base fee from block context
-        let base_fee = ctx.block().basefee();
-        
-        if gas_used == 0 {
-            return Ok(MevDetection {
-                mev_type: MevType::BaseFeeOnly,
-                profit: U256::ZERO,
-                gas_used: 0,
-            });
-        }
-
-        // Calculate base fee portion
-        let base_fee_amount = U256::from(base_fee) * U256::from(gas_used);
-
-        // TODO: Implement MEV detection logic
-        // For now, we only redirect base fees
-        // Future: Analyze transaction patterns to detect sandwich, arbitrage, liquidations
-        let mev_type = MevType::BaseFeeOnly;
-        let total_amount = base_fee_amount;
-
-        if total_amount.is_zero() {
-            return Ok(MevDetection {
-                mev_type,
-                profit: U256::ZERO,
-                gas_used,
-            });
+        let detection = self.detect(ctx.block().basefee(), gas_used);
+        if detection.profit.is_zero() {
+            return Ok(detection);
         }
 
         // Credit the MEV sink account
@@ -183,14 +161,25 @@ impl AndeMevRedirect {
             .load_account(self.mev_sink)
             .map_err(MevRedirectError::Database)?;
         journal
-            .balance_incr(self.mev_sink, total_amount)
+            .balance_incr(self.mev_sink, detection.profit)
             .map_err(MevRedirectError::Database)?;
 
-        Ok(MevDetection {
-            mev_type,
-            profit: total_amount,
+        Ok(detection)
+    }
+
+    /// Computes what [`apply`](Self::apply) credits for a transaction.
+    ///
+    /// Pure function of the block base fee and the gas used, so the same
+    /// detection can be reconstructed from receipts after execution.
+    pub fn detect(&self, base_fee: u64, gas_used: u64) -> MevDetection {
+        // TODO: Implement MEV detection logic
+        // For now, we only redirect base fees
+        // Future: Analyze transaction patterns to detect sandwich, arbitrage, liquidations
+        MevDetection {
+            mev_type: MevType::BaseFeeOnly,
+            profit: U256::from(base_fee) * U256::from(gas_used),
             gas_used,
-        })
+        }
     }
 
     /// Detects MEV type based on transaction context.
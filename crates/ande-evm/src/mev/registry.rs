@@ -0,0 +1,603 @@
+//! Protocol Registry for the MEV Detector
+//!
+//! Declarative list of the DEXes, lending markets and oracles the detector
+//! should understand, loaded from a JSON file:
+//!
+//! ```json
+//! {
+//!   "dex": [{
+//!     "name": "AndeSwap",
+//!     "kind": "v2",
+//!     "factory": "0x…",
+//!     "routers": ["0x…"],
+//!     "pools": [],
+//!     "events": ["Swap(address,uint256,uint256,uint256,uint256,address)"]
+//!   }],
+//!   "lending": [{
+//!     "name": "AndeLend",
+//!     "markets": ["0x…"],
+//!     "events": ["Liquidation(address,address,address,address,uint256,uint256)"]
+//!   }],
+//!   "oracles": [{
+//!     "name": "AndeOracleAggregator",
+//!     "address": "0x…",
+//!     "events": ["PriceUpdated(address,uint256,uint8,uint256)"]
+//!   }]
+//! }
+//! ```
+//!
+//! ```text
+//! registry file ── mtime changed ──→ reload ──→ RegistrySnapshot (Arc, swapped atomically)
+//! receipts ── PairCreated from a registered factory ──→ discovered pools (kept across reloads)
+//!                                                          ↓ persisted
+//!                                              <datadir>/mev_discovered_pools.json
+//! swap logs ── pool(): declared or discovered, same kind ──→ decoded
+//! ```
+//!
+//! Once the registry declares a factory or a pool, swaps are only decoded
+//! from declared or discovered pools, so an arbitrary contract emitting
+//! `Swap` logs cannot fabricate MEV. A registry without pools accepts any
+//! contract that answers `token0()`/`token1()`.
+
+use super::swaps::PoolKind;
+use alloy::sol_types::{sol, SolEvent};
+use alloy_primitives::{keccak256, Address, Log, B256};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Environment variable pointing at the registry file
+pub const PROTOCOL_REGISTRY_ENV: &str = "ANDE_PROTOCOL_REGISTRY";
+
+/// How often the registry file is checked for changes
+pub const REGISTRY_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// File in the node data directory holding the discovered pools
+pub const DISCOVERED_POOLS_FILE: &str = "mev_discovered_pools.json";
+
+sol! {
+    /// AndeSwapFactory pair deployment
+    #[derive(Debug)]
+    event PairCreated(address indexed token0, address indexed token1, address pair, uint256 pairIndex);
+}
+
+/// Errors loading the protocol registry
+#[derive(Debug, Error)]
+pub enum ProtocolRegistryError {
+    /// The registry file could not be read
+    #[error("failed to read protocol registry {path}: {source}")]
+    Io {
+        /// Registry file
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// The registry file is not valid JSON for the registry schema
+    #[error("invalid protocol registry: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    /// The discovered pools could not be read or written
+    #[error("failed to access discovered pools {path}: {source}")]
+    DiscoveredPools {
+        /// Discovered pools file
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// An event signature is not of the form `Name(type,...)`
+    #[error("invalid event signature `{signature}` for {protocol}")]
+    InvalidEventSignature {
+        /// Protocol declaring the event
+        protocol: String,
+        /// Offending signature
+        signature: String,
+    },
+}
+
+/// DEX entry of the registry file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DexProtocol {
+    /// Protocol name
+    pub name: String,
+    /// Pool implementation, `v2` or `v3`
+    #[serde(default = "default_pool_kind", with = "pool_kind_serde")]
+    pub kind: PoolKind,
+    /// Factory whose `PairCreated` events register new pools
+    #[serde(default)]
+    pub factory: Option<Address>,
+    /// Router contracts
+    #[serde(default)]
+    pub routers: Vec<Address>,
+    /// Known pools
+    #[serde(default)]
+    pub pools: Vec<Address>,
+    /// Event signatures emitted by the protocol
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Lending entry of the registry file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LendingProtocol {
+    /// Protocol name
+    pub name: String,
+    /// Lending market contracts
+    #[serde(default)]
+    pub markets: Vec<Address>,
+    /// Event signatures emitted by the protocol
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Oracle entry of the registry file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleEntry {
+    /// Oracle name
+    pub name: String,
+    /// Oracle contract
+    pub address: Address,
+    /// Event signatures emitted by the oracle
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Contents of the registry file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolRegistryFile {
+    /// DEX protocols
+    #[serde(default)]
+    pub dex: Vec<DexProtocol>,
+    /// Lending protocols
+    #[serde(default)]
+    pub lending: Vec<LendingProtocol>,
+    /// Price oracles
+    #[serde(default)]
+    pub oracles: Vec<OracleEntry>,
+}
+
+/// Registered pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolInfo {
+    /// Protocol the pool belongs to
+    pub protocol: String,
+    /// Pool implementation
+    #[serde(with = "pool_kind_serde")]
+    pub kind: PoolKind,
+}
+
+/// Registered event signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventInfo {
+    /// Protocol declaring the event
+    pub protocol: String,
+    /// Canonical signature, e.g. `Swap(address,uint256,…)`
+    pub signature: String,
+}
+
+/// Lookup tables built from a registry file
+#[derive(Debug, Clone, Default)]
+pub struct RegistrySnapshot {
+    /// DEX routers
+    pub dex_routers: HashSet<Address>,
+    /// DEX factories, by address
+    pub factories: HashMap<Address, PoolInfo>,
+    /// Declared pools
+    pub pools: HashMap<Address, PoolInfo>,
+    /// Lending markets
+    pub lending_markets: HashSet<Address>,
+    /// Oracles, by address
+    pub oracles: HashMap<Address, String>,
+    /// Event signatures, by topic0
+    pub events: HashMap<B256, EventInfo>,
+}
+
+impl RegistrySnapshot {
+    /// Build lookup tables, validating every event signature
+    pub fn from_file(file: &ProtocolRegistryFile) -> Result<Self, ProtocolRegistryError> {
+        let mut snapshot = Self::default();
+
+        for dex in &file.dex {
+            let info = PoolInfo { protocol: dex.name.clone(), kind: dex.kind };
+            snapshot.dex_routers.extend(dex.routers.iter().copied());
+            if let Some(factory) = dex.factory {
+                snapshot.factories.insert(factory, info.clone());
+            }
+            for pool in &dex.pools {
+                snapshot.pools.insert(*pool, info.clone());
+            }
+            snapshot.add_events(&dex.name, &dex.events)?;
+        }
+
+        for lending in &file.lending {
+            snapshot.lending_markets.extend(lending.markets.iter().copied());
+            snapshot.add_events(&lending.name, &lending.events)?;
+        }
+
+        for oracle in &file.oracles {
+            snapshot.oracles.insert(oracle.address, oracle.name.clone());
+            snapshot.add_events(&oracle.name, &oracle.events)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    fn add_events(&mut self, protocol: &str, events: &[String]) -> Result<(), ProtocolRegistryError> {
+        for signature in events {
+            let signature: String = signature.chars().filter(|c| !c.is_whitespace()).collect();
+            let valid = signature
+                .split_once('(')
+                .is_some_and(|(name, rest)| !name.is_empty() && rest.ends_with(')'));
+            if !valid {
+                return Err(ProtocolRegistryError::InvalidEventSignature {
+                    protocol: protocol.to_string(),
+                    signature,
+                });
+            }
+
+            self.events.insert(
+                keccak256(signature.as_bytes()),
+                EventInfo { protocol: protocol.to_string(), signature },
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Hot-reloadable protocol registry
+#[derive(Debug, Default)]
+pub struct ProtocolRegistry {
+    /// Registry file, if loaded from disk
+    path: Option<PathBuf>,
+    /// Modification time of the loaded file
+    modified: RwLock<Option<SystemTime>>,
+    /// Tables from the file
+    snapshot: RwLock<Arc<RegistrySnapshot>>,
+    /// Pools found through factory `PairCreated` events
+    discovered: RwLock<HashMap<Address, PoolInfo>>,
+    /// File the discovered pools are persisted to
+    discovered_path: Option<PathBuf>,
+}
+
+impl ProtocolRegistry {
+    /// Registry with fixed contents
+    pub fn from_registry_file(file: &ProtocolRegistryFile) -> Result<Self, ProtocolRegistryError> {
+        Ok(Self {
+            snapshot: RwLock::new(Arc::new(RegistrySnapshot::from_file(file)?)),
+            ..Default::default()
+        })
+    }
+
+    /// Load the registry from a JSON file
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ProtocolRegistryError> {
+        let registry = Self { path: Some(path.into()), ..Default::default() };
+        registry.reload()?;
+        Ok(registry)
+    }
+
+    /// Load the file named by [`PROTOCOL_REGISTRY_ENV`], if set
+    pub fn from_env() -> Result<Option<Self>, ProtocolRegistryError> {
+        match std::env::var(PROTOCOL_REGISTRY_ENV) {
+            Ok(path) => Self::load(path).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Persist discovered pools to `path`, loading those already there
+    pub fn with_discovered_pools(mut self, path: impl Into<PathBuf>) -> Result<Self, ProtocolRegistryError> {
+        let path = path.into();
+        match std::fs::read(&path) {
+            Ok(bytes) => *self.discovered.get_mut() = serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(source) => return Err(ProtocolRegistryError::DiscoveredPools { path, source }),
+        }
+        self.discovered_path = Some(path);
+        Ok(self)
+    }
+
+    /// Re-read the file if it changed, returning whether it was reloaded
+    ///
+    /// A file that fails to parse leaves the previous tables in place.
+    pub fn reload(&self) -> Result<bool, ProtocolRegistryError> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+
+        let io_err = |source| ProtocolRegistryError::Io { path: path.clone(), source };
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).map_err(io_err)?;
+        if *self.modified.read() == Some(modified) {
+            return Ok(false);
+        }
+
+        let contents = std::fs::read_to_string(path).map_err(io_err)?;
+        let file: ProtocolRegistryFile = serde_json::from_str(&contents)?;
+        let snapshot = RegistrySnapshot::from_file(&file)?;
+
+        *self.snapshot.write() = Arc::new(snapshot);
+        *self.modified.write() = Some(modified);
+        Ok(true)
+    }
+
+    /// Reload the file whenever it changes
+    pub async fn watch(self: Arc<Self>) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+
+        let mut interval = tokio::time::interval(REGISTRY_RELOAD_INTERVAL);
+        loop {
+            interval.tick().await;
+            match self.reload() {
+                Ok(true) => info!("🔄 Protocol registry reloaded from {}", path.display()),
+                Ok(false) => {}
+                Err(e) => warn!("Keeping previous protocol registry: {}", e),
+            }
+        }
+    }
+
+    /// Path of the registry file, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Current tables from the file
+    pub fn snapshot(&self) -> Arc<RegistrySnapshot> {
+        Arc::clone(&self.snapshot.read())
+    }
+
+    /// Whether `address` is a registered DEX router
+    pub fn is_dex_router(&self, address: &Address) -> bool {
+        self.snapshot.read().dex_routers.contains(address)
+    }
+
+    /// Whether `address` is a registered lending market
+    pub fn is_lending_market(&self, address: &Address) -> bool {
+        self.snapshot.read().lending_markets.contains(address)
+    }
+
+    /// Registered or discovered pool
+    pub fn pool(&self, address: &Address) -> Option<PoolInfo> {
+        self.snapshot
+            .read()
+            .pools
+            .get(address)
+            .cloned()
+            .or_else(|| self.discovered.read().get(address).cloned())
+    }
+
+    /// Whether swaps of a `kind` pool at `address` should be decoded
+    ///
+    /// Declared and discovered pools must match their registered kind;
+    /// other contracts are only accepted while no pool is registered.
+    pub fn accepts_pool(&self, address: &Address, kind: PoolKind) -> bool {
+        match self.pool(address) {
+            Some(info) => info.kind == kind,
+            None => {
+                let snapshot = self.snapshot.read();
+                snapshot.factories.is_empty() && snapshot.pools.is_empty() && self.discovered.read().is_empty()
+            }
+        }
+    }
+
+    /// Number of pools found through factory `PairCreated` events
+    pub fn discovered_pools(&self) -> usize {
+        self.discovered.read().len()
+    }
+
+    /// Protocol event with this topic0
+    pub fn event(&self, topic0: &B256) -> Option<EventInfo> {
+        self.snapshot.read().events.get(topic0).cloned()
+    }
+
+    /// Register pools deployed by registered factories, returning how many
+    /// were new
+    ///
+    /// New pools are written to the discovered pools file, if any.
+    pub fn discover_pools(&self, logs: &[Log]) -> usize {
+        let snapshot = self.snapshot();
+        let mut discovered = self.discovered.write();
+        let mut added = 0;
+
+        for log in logs {
+            let Some(factory) = snapshot.factories.get(&log.address) else {
+                continue;
+            };
+            if log.topics().first() != Some(&PairCreated::SIGNATURE_HASH) {
+                continue;
+            }
+            let Ok(event) = PairCreated::decode_log_data(&log.data) else {
+                continue;
+            };
+
+            if !snapshot.pools.contains_key(&event.pair) &&
+                discovered.insert(event.pair, factory.clone()).is_none()
+            {
+                info!("🆕 Discovered {} pool {} ({} / {})", factory.protocol, event.pair, event.token0, event.token1);
+                added += 1;
+            }
+        }
+
+        if added > 0
+            && let Some(path) = &self.discovered_path
+            && let Err(e) = persist_pools(path, &discovered)
+        {
+            warn!("Failed to persist discovered pools to {}: {}", path.display(), e);
+        }
+        added
+    }
+}
+
+/// Write the discovered pools atomically (temp file, fsync, rename)
+fn persist_pools(path: &Path, pools: &HashMap<Address, PoolInfo>) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        serde_json::to_writer(&mut file, pools)?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp_path, path)
+}
+
+fn default_pool_kind() -> PoolKind {
+    PoolKind::V2
+}
+
+/// `"v2"` / `"v3"` encoding of [`PoolKind`]
+mod pool_kind_serde {
+    use super::PoolKind;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(kind: &PoolKind, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match kind {
+            PoolKind::V2 => "v2",
+            PoolKind::V3 => "v3",
+        })
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PoolKind, D::Error> {
+        match String::deserialize(deserializer)?.to_ascii_lowercase().as_str() {
+            "v2" => Ok(PoolKind::V2),
+            "v3" => Ok(PoolKind::V3),
+            other => Err(serde::de::Error::custom(format!("unknown pool kind `{other}`"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    const REGISTRY: &str = r#"{
+        "dex": [{
+            "name": "AndeSwap",
+            "kind": "v2",
+            "factory": "0x00000000000000000000000000000000000000f1",
+            "routers": ["0x00000000000000000000000000000000000000a1"],
+            "events": ["Swap(address, uint256,uint256,uint256,uint256,address)"]
+        }],
+        "lending": [{
+            "name": "AndeLend",
+            "markets": ["0x00000000000000000000000000000000000000b1"],
+            "events": ["Liquidation(address,address,address,address,uint256,uint256)"]
+        }],
+        "oracles": [{ "name": "Aggregator", "address": "0x00000000000000000000000000000000000000c1" }]
+    }"#;
+
+    #[test]
+    fn test_registry_tables() {
+        let file: ProtocolRegistryFile = serde_json::from_str(REGISTRY).unwrap();
+        let registry = ProtocolRegistry::from_registry_file(&file).unwrap();
+
+        assert!(registry.is_dex_router(&Address::with_last_byte(0xa1)));
+        assert!(registry.is_lending_market(&Address::with_last_byte(0xb1)));
+
+        let swap = registry
+            .event(&keccak256("Swap(address,uint256,uint256,uint256,uint256,address)"))
+            .unwrap();
+        assert_eq!(swap.protocol, "AndeSwap");
+    }
+
+    #[test]
+    fn test_invalid_event_signature_rejected() {
+        let file = ProtocolRegistryFile {
+            oracles: vec![OracleEntry {
+                name: "broken".to_string(),
+                address: Address::ZERO,
+                events: vec!["PriceUpdated".to_string()],
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            ProtocolRegistry::from_registry_file(&file),
+            Err(ProtocolRegistryError::InvalidEventSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pair_created_discovery() {
+        let file: ProtocolRegistryFile = serde_json::from_str(REGISTRY).unwrap();
+        let registry = ProtocolRegistry::from_registry_file(&file).unwrap();
+        let pair = Address::with_last_byte(0xd1);
+
+        let event = PairCreated {
+            token0: Address::with_last_byte(1),
+            token1: Address::with_last_byte(2),
+            pair,
+            pairIndex: U256::ZERO,
+        };
+        let from_factory = Log { address: Address::with_last_byte(0xf1), data: event.encode_log_data() };
+        let from_stranger = Log { address: Address::with_last_byte(0xf2), data: event.encode_log_data() };
+
+        // A registered factory restricts decoding to the pools it deployed
+        assert!(!registry.accepts_pool(&pair, PoolKind::V2));
+
+        let dir = std::env::temp_dir().join(format!("ande-registry-pools-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(DISCOVERED_POOLS_FILE);
+        let _ = std::fs::remove_file(&path);
+        let registry = registry.with_discovered_pools(&path).unwrap();
+
+        assert_eq!(registry.discover_pools(&[from_stranger]), 0);
+        assert_eq!(registry.discover_pools(std::slice::from_ref(&from_factory)), 1);
+        assert_eq!(registry.discover_pools(&[from_factory]), 0);
+        assert_eq!(registry.pool(&pair).unwrap().protocol, "AndeSwap");
+
+        // Only discovered pools of the factory's kind are decoded
+        assert!(registry.accepts_pool(&pair, PoolKind::V2));
+        assert!(!registry.accepts_pool(&pair, PoolKind::V3));
+        assert!(!registry.accepts_pool(&Address::with_last_byte(0xee), PoolKind::V2));
+
+        // Discovered pools survive a restart
+        let restarted = ProtocolRegistry::from_registry_file(&file).unwrap().with_discovered_pools(&path).unwrap();
+        assert_eq!(restarted.pool(&pair).unwrap().kind, PoolKind::V2);
+    }
+
+    #[test]
+    fn test_hot_reload_keeps_discovered_pools() {
+        let dir = std::env::temp_dir().join(format!("ande-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("protocols.json");
+        std::fs::write(&path, REGISTRY).unwrap();
+
+        let registry = ProtocolRegistry::load(&path).unwrap();
+        assert!(!registry.reload().unwrap());
+
+        let pair = Address::with_last_byte(0xd2);
+        let event = PairCreated {
+            token0: Address::with_last_byte(1),
+            token1: Address::with_last_byte(2),
+            pair,
+            pairIndex: U256::from(1),
+        };
+        registry.discover_pools(&[Log { address: Address::with_last_byte(0xf1), data: event.encode_log_data() }]);
+
+        // Drop the router; make sure the mtime differs from the first load
+        let updated = REGISTRY.replace("\"0x00000000000000000000000000000000000000a1\"", "");
+        std::thread::sleep(Duration::from_millis(20));
+        std::fs::write(&path, updated).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(1)).unwrap();
+
+        assert!(registry.reload().unwrap());
+        assert!(!registry.is_dex_router(&Address::with_last_byte(0xa1)));
+        assert!(registry.pool(&pair).is_some());
+
+        // A broken file keeps the last good tables
+        std::fs::write(&path, "{ not json").unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(2)).unwrap();
+        assert!(registry.reload().is_err());
+        assert!(registry.is_lending_market(&Address::with_last_byte(0xb1)));
+    }
+}
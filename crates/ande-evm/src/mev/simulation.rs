@@ -0,0 +1,223 @@
+//! Bundle Simulation
+//!
+//! Executes a bundle in order on a caller-provided EVM, committing each
+//! transaction so later transactions see earlier state changes. Used by the
+//! bundle RPC (against the latest state) and by the payload builder (against
+//! the parent of the block being built), so both run the same ANDE EVM,
+//! including the Token Duality precompile at 0xFD.
+
+use alloy_consensus::{transaction::Recovered, Transaction};
+use alloy_evm::{Evm, FromRecoveredTx};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_primitives::TransactionSigned;
+use reth_ethereum::evm::revm::{context_interface::result::ExecutionResult, Database, DatabaseCommit};
+use thiserror::Error;
+
+/// Result of simulating a single bundle transaction
+#[derive(Debug, Clone)]
+pub struct SimulatedTx {
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Sender
+    pub from: Address,
+    /// Recipient (`None` for contract creation)
+    pub to: Option<Address>,
+    /// Gas used
+    pub gas_used: u64,
+    /// Priority fees paid to the block beneficiary
+    pub gas_fees: U256,
+    /// Whether the transaction succeeded
+    pub success: bool,
+    /// Return or revert data
+    pub output: Bytes,
+}
+
+/// Result of simulating a whole bundle
+#[derive(Debug, Clone, Default)]
+pub struct BundleSimulation {
+    /// Per-transaction results, in bundle order
+    pub results: Vec<SimulatedTx>,
+    /// Total gas used by the bundle
+    pub total_gas_used: u64,
+    /// Total priority fees paid by the bundle
+    pub gas_fees: U256,
+    /// Balance change of the block beneficiary (fees plus direct payments)
+    pub coinbase_diff: U256,
+}
+
+/// Errors that invalidate a bundle during simulation
+#[derive(Debug, Error)]
+pub enum BundleSimulationError {
+    /// Transaction failed validation (nonce, balance, gas limit...)
+    #[error("Bundle tx {index} ({tx_hash}) invalid: {reason}")]
+    InvalidTransaction {
+        /// Position in the bundle
+        index: usize,
+        /// Transaction hash
+        tx_hash: B256,
+        /// EVM error
+        reason: String,
+    },
+
+    /// Transaction reverted and is not in `revertingTxHashes`
+    #[error("Bundle tx {index} ({tx_hash}) reverted")]
+    Reverted {
+        /// Position in the bundle
+        index: usize,
+        /// Transaction hash
+        tx_hash: B256,
+        /// Revert data
+        output: Bytes,
+    },
+
+    /// State could not be read
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+/// Simulate a bundle on `evm`
+///
+/// Every transaction must execute successfully unless its hash is listed in
+/// `reverting_tx_hashes`. State changes are committed to the EVM database.
+pub fn simulate_bundle<E>(
+    evm: &mut E,
+    txs: &[Recovered<TransactionSigned>],
+    reverting_tx_hashes: &[B256],
+) -> Result<BundleSimulation, BundleSimulationError>
+where
+    E: Evm<DB: DatabaseCommit>,
+    E::Tx: FromRecoveredTx<TransactionSigned>,
+{
+    let beneficiary = evm.block().beneficiary;
+    let base_fee = evm.block().basefee;
+    let coinbase_before = coinbase_balance(evm, beneficiary)?;
+
+    let mut simulation = BundleSimulation::default();
+    for (index, tx) in txs.iter().enumerate() {
+        let tx_hash = *tx.hash();
+        let result = evm.transact(tx).map_err(|e| BundleSimulationError::InvalidTransaction {
+            index,
+            tx_hash,
+            reason: e.to_string(),
+        })?;
+
+        let gas_used = result.result.gas_used();
+        let (success, output) = match &result.result {
+            ExecutionResult::Success { output, .. } => (true, output.data().clone()),
+            ExecutionResult::Revert { output, .. } => (false, output.clone()),
+            ExecutionResult::Halt { .. } => (false, Bytes::new()),
+        };
+
+        if !success && !reverting_tx_hashes.contains(&tx_hash) {
+            return Err(BundleSimulationError::Reverted { index, tx_hash, output });
+        }
+
+        let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+        let gas_fees = U256::from(tip) * U256::from(gas_used);
+
+        evm.db_mut().commit(result.state);
+
+        simulation.total_gas_used += gas_used;
+        simulation.gas_fees += gas_fees;
+        simulation.results.push(SimulatedTx {
+            tx_hash,
+            from: tx.signer(),
+            to: tx.to(),
+            gas_used,
+            gas_fees,
+            success,
+            output,
+        });
+    }
+
+    let coinbase_after = coinbase_balance(evm, beneficiary)?;
+    simulation.coinbase_diff = coinbase_after.saturating_sub(coinbase_before);
+
+    Ok(simulation)
+}
+
+/// Current balance of the block beneficiary
+fn coinbase_balance<E>(evm: &mut E, beneficiary: Address) -> Result<U256, BundleSimulationError>
+where
+    E: Evm,
+{
+    evm.db_mut()
+        .basic(beneficiary)
+        .map(|account| account.map(|info| info.balance).unwrap_or_default())
+        .map_err(|e| BundleSimulationError::Database(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm_config::AndeEvmFactory;
+    use alloy_consensus::{SignableTransaction, TxLegacy};
+    use alloy_evm::{EvmEnv, EvmFactory};
+    use alloy_primitives::{Signature, TxKind};
+    use reth_ethereum::evm::revm::{
+        context::BlockEnv,
+        database::{CacheDB, EmptyDB},
+        inspector::NoOpInspector,
+        primitives::hardfork::SpecId,
+        state::AccountInfo,
+    };
+
+    type TestEvm<'a> = <AndeEvmFactory as EvmFactory>::Evm<&'a mut CacheDB<EmptyDB>, NoOpInspector>;
+
+    fn legacy_transfer(nonce: u64, gas_limit: u64) -> Recovered<TransactionSigned> {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 10,
+            gas_limit,
+            to: TxKind::Call(Address::repeat_byte(0x22)),
+            value: U256::from(1),
+            input: Bytes::new(),
+        };
+        let signed = tx.into_signed(Signature::test_signature());
+        Recovered::new_unchecked(signed.into(), Address::repeat_byte(0x11))
+    }
+
+    fn test_evm(db: &mut CacheDB<EmptyDB>) -> TestEvm<'_> {
+        let mut env = EvmEnv::<SpecId>::default();
+        env.block_env = BlockEnv { beneficiary: Address::repeat_byte(0xcb), ..Default::default() };
+        env.cfg_env.chain_id = 1;
+        AndeEvmFactory::new(SpecId::CANCUN).create_evm(db, env)
+    }
+
+    fn funded_db() -> CacheDB<EmptyDB> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            Address::repeat_byte(0x11),
+            AccountInfo { balance: U256::from(10u64.pow(18)), ..Default::default() },
+        );
+        db
+    }
+
+    #[test]
+    fn test_bundle_simulation_commits_in_order() {
+        let mut db = funded_db();
+        let mut evm = test_evm(&mut db);
+
+        let txs = vec![legacy_transfer(0, 21_000), legacy_transfer(1, 21_000)];
+        let simulation = simulate_bundle(&mut evm, &txs, &[]).unwrap();
+
+        assert_eq!(simulation.results.len(), 2);
+        assert_eq!(simulation.total_gas_used, 42_000);
+        assert!(simulation.results.iter().all(|r| r.success));
+        assert_eq!(simulation.gas_fees, U256::from(10 * 42_000));
+        assert_eq!(simulation.coinbase_diff, simulation.gas_fees);
+    }
+
+    #[test]
+    fn test_bundle_simulation_rejects_invalid_tx() {
+        let mut db = funded_db();
+        let mut evm = test_evm(&mut db);
+
+        // Second transaction reuses the first nonce
+        let txs = vec![legacy_transfer(0, 21_000), legacy_transfer(0, 21_000)];
+        let err = simulate_bundle(&mut evm, &txs, &[]).unwrap_err();
+
+        assert!(matches!(err, BundleSimulationError::InvalidTransaction { index: 1, .. }));
+    }
+}
@@ -0,0 +1,936 @@
+//! Persistent MEV Event Store
+//!
+//! Every detected opportunity, per-block base-fee redirection total and
+//! settled auction bundle is appended to a JSON-lines segment in the node's
+//! data directory. Each segment covers [`SEGMENT_BLOCKS`] blocks; only a
+//! small summary per segment is kept in memory, while the per-address
+//! indexes of sealed segments stay on disk and are read when a query needs
+//! them. Old segments are rotated out, their epoch totals are kept.
+//!
+//! ```text
+//! MevDetector / AndeMevRedirect (per block) / settle_block
+//!        ↓ MevEventRecord
+//! mev_events/
+//!   seg-<start>.jsonl     ── append + fsync, one line per event
+//!   seg-<start>.idx.json  ── sealed: summary + searcher/address → line offsets
+//!   archived_epochs.json  ── totals of rotated-out segments
+//!
+//! memory: start → summary (block range, types, sources, blooms, epoch totals)
+//! query:  summaries ─→ candidate segments ─→ index offsets or scan ─→ records
+//! ```
+//!
+//! A segment is sealed once events [`SEAL_LAG`] blocks past its end arrive,
+//! which leaves room for settled bundles recorded behind the tip. Reorgs
+//! rewrite the affected segments without the reverted events.
+//!
+//! Events are only counted in epoch totals when their MEVDistributor epoch
+//! is known; without a distributor they are stored with no epoch.
+
+use super::auction::BundleExecutionResult;
+use super::detector::{MevOpportunity, MevType};
+use alloy_primitives::{Address, Bloom, BloomInput, B256, U256};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Directory of the event store inside the node data directory
+pub const MEV_EVENT_STORE_DIR: &str = "mev_events";
+
+/// Default maximum number of records returned by a query
+pub const DEFAULT_QUERY_LIMIT: usize = 1_000;
+
+/// Upper bound on the records of one query, whatever limit is requested
+pub const MAX_QUERY_LIMIT: usize = 10_000;
+
+/// Blocks covered by one segment file
+pub const SEGMENT_BLOCKS: u64 = 10_000;
+
+/// Blocks past a segment's end before it is sealed
+pub const SEAL_LAG: u64 = 256;
+
+/// Default number of segments kept before the oldest is rotated out
+pub const DEFAULT_RETAINED_SEGMENTS: usize = 100;
+
+/// File holding the epoch totals of rotated-out segments
+const ARCHIVED_EPOCHS_FILE: &str = "archived_epochs.json";
+
+/// Errors of the MEV event store
+#[derive(Debug, Error)]
+pub enum MevEventStoreError {
+    /// Reading or writing the log failed
+    #[error("MEV event log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A log entry could not be encoded
+    #[error("MEV event encoding error: {0}")]
+    Encoding(#[from] serde_json::Error),
+}
+
+/// Where a recorded MEV event came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MevEventSource {
+    /// Found by the [`MevDetector`](super::MevDetector) in executed receipts
+    Detector,
+    /// Base fee credited to the MEV sink by [`AndeMevRedirect`](super::AndeMevRedirect),
+    /// one event per block
+    Redirect,
+    /// Bundle executed through the MEV auction
+    Auction,
+}
+
+/// One persisted MEV event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevEventRecord {
+    /// Block the event happened in
+    pub block_number: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+    /// MEVDistributor epoch the value belongs to, if known
+    #[serde(default)]
+    pub epoch: Option<u64>,
+    /// Transaction, bundle or (for redirect totals) block hash
+    pub tx_hash: B256,
+    /// Kind of MEV
+    pub mev_type: MevType,
+    /// Component that reported the event
+    pub source: MevEventSource,
+    /// Extracted value in wei of ANDE
+    pub value: U256,
+    /// Account that captured the value, if known
+    pub searcher: Option<Address>,
+    /// Other affected accounts and contracts
+    pub addresses: Vec<Address>,
+    /// Free-form details (counterpart transactions, profit token, ...)
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl MevEventRecord {
+    /// Record a detector opportunity; its first address is the searcher
+    pub fn from_opportunity(opportunity: &MevOpportunity, timestamp: u64, epoch: Option<u64>) -> Self {
+        Self {
+            block_number: opportunity.block_number,
+            timestamp,
+            epoch,
+            tx_hash: opportunity.tx_hash,
+            mev_type: opportunity.mev_type,
+            source: MevEventSource::Detector,
+            value: opportunity.value,
+            searcher: opportunity.addresses.first().copied(),
+            addresses: opportunity.addresses.iter().skip(1).copied().collect(),
+            metadata: opportunity.metadata.clone().into_iter().collect(),
+        }
+    }
+
+    /// Record the base fee redirected in block `block_hash`
+    ///
+    /// One event per block keeps the store proportional to blocks rather
+    /// than transactions; `gas_used` and `transactions` are kept as metadata.
+    pub fn from_redirected_block(
+        block_number: u64,
+        block_hash: B256,
+        timestamp: u64,
+        epoch: Option<u64>,
+        value: U256,
+        gas_used: u64,
+        transactions: usize,
+    ) -> Self {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("gas_used".to_string(), gas_used.to_string());
+        metadata.insert("transactions".to_string(), transactions.to_string());
+
+        Self {
+            block_number,
+            timestamp,
+            epoch,
+            tx_hash: block_hash,
+            mev_type: MevType::Other,
+            source: MevEventSource::Redirect,
+            value,
+            searcher: None,
+            addresses: Vec::new(),
+            metadata,
+        }
+    }
+
+    /// Record an executed auction bundle won by `searcher`
+    pub fn from_bundle(
+        bundle_hash: B256,
+        result: &BundleExecutionResult,
+        searcher: Address,
+        block_number: u64,
+        timestamp: u64,
+        epoch: Option<u64>,
+    ) -> Self {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("bid_paid".to_string(), result.bid_paid.to_string());
+
+        Self {
+            block_number,
+            timestamp,
+            epoch,
+            tx_hash: bundle_hash,
+            mev_type: MevType::Other,
+            source: MevEventSource::Auction,
+            value: result.mev_captured,
+            searcher: Some(searcher),
+            addresses: Vec::new(),
+            metadata,
+        }
+    }
+}
+
+/// Query over stored events; all set fields must match
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MevEventFilter {
+    /// First block (inclusive)
+    pub from_block: Option<u64>,
+    /// Last block (inclusive)
+    pub to_block: Option<u64>,
+    /// Kind of MEV
+    pub mev_type: Option<MevType>,
+    /// Reporting component
+    pub source: Option<MevEventSource>,
+    /// Searcher that captured the value
+    pub searcher: Option<Address>,
+    /// Searcher, victim, pool or other affected address
+    pub address: Option<Address>,
+    /// Maximum records returned (default [`DEFAULT_QUERY_LIMIT`], at most
+    /// [`MAX_QUERY_LIMIT`])
+    pub limit: Option<usize>,
+}
+
+impl MevEventFilter {
+    /// Whether `record` satisfies the filter
+    pub fn matches(&self, record: &MevEventRecord) -> bool {
+        self.from_block.is_none_or(|from| record.block_number >= from)
+            && self.to_block.is_none_or(|to| record.block_number <= to)
+            && self.mev_type.is_none_or(|t| record.mev_type == t)
+            && self.source.is_none_or(|s| record.source == s)
+            && self.searcher.is_none_or(|s| record.searcher == Some(s))
+            && self.address.is_none_or(|a| {
+                record.searcher == Some(a) || record.addresses.contains(&a)
+            })
+    }
+}
+
+/// Extracted value of one epoch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochMevTotals {
+    /// Epoch number
+    pub epoch: u64,
+    /// Number of events
+    pub events: u64,
+    /// First block with an event
+    pub first_block: u64,
+    /// Last block with an event
+    pub last_block: u64,
+    /// Value of all events
+    pub total_value: U256,
+    /// Value per reporting component
+    pub by_source: BTreeMap<String, U256>,
+    /// Value per kind of MEV
+    pub by_type: BTreeMap<String, U256>,
+}
+
+impl EpochMevTotals {
+    /// Fold the totals of `other` (same epoch) into these
+    fn merge(&mut self, other: &Self) {
+        if other.events == 0 {
+            return;
+        }
+        if self.events == 0 || other.first_block < self.first_block {
+            self.first_block = other.first_block;
+        }
+        self.last_block = self.last_block.max(other.last_block);
+        self.events += other.events;
+        self.total_value += other.total_value;
+        for (source, value) in &other.by_source {
+            *self.by_source.entry(source.clone()).or_default() += *value;
+        }
+        for (mev_type, value) in &other.by_type {
+            *self.by_type.entry(mev_type.clone()).or_default() += *value;
+        }
+    }
+
+    fn add(&mut self, record: &MevEventRecord) {
+        if self.events == 0 || record.block_number < self.first_block {
+            self.first_block = record.block_number;
+        }
+        self.last_block = self.last_block.max(record.block_number);
+        self.events += 1;
+        self.total_value += record.value;
+        *self.by_source.entry(format!("{:?}", record.source)).or_default() += record.value;
+        *self.by_type.entry(record.mev_type.name().to_string()).or_default() += record.value;
+    }
+}
+
+/// In-memory summary of one segment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentSummary {
+    /// Bytes of the segment file covered
+    bytes: u64,
+    /// Number of events
+    records: u64,
+    /// Lowest block with an event
+    min_block: u64,
+    /// Highest block with an event
+    max_block: u64,
+    /// Kinds of MEV present
+    types: BTreeSet<MevType>,
+    /// Reporting components present
+    sources: BTreeSet<MevEventSource>,
+    /// Searchers present
+    searchers: Bloom,
+    /// Affected addresses present (including searchers)
+    addresses: Bloom,
+    /// Totals per epoch
+    epochs: BTreeMap<u64, EpochMevTotals>,
+}
+
+impl SegmentSummary {
+    /// Whether the segment may hold events matching `filter`
+    fn may_match(&self, filter: &MevEventFilter) -> bool {
+        self.records > 0
+            && filter.from_block.is_none_or(|from| self.max_block >= from)
+            && filter.to_block.is_none_or(|to| self.min_block <= to)
+            && filter.mev_type.is_none_or(|t| self.types.contains(&t))
+            && filter.source.is_none_or(|s| self.sources.contains(&s))
+            && filter
+                .searcher
+                .is_none_or(|s| self.searchers.contains_input(BloomInput::Raw(s.as_slice())))
+            && filter
+                .address
+                .is_none_or(|a| self.addresses.contains_input(BloomInput::Raw(a.as_slice())))
+    }
+}
+
+/// Index of one segment, persisted next to it once sealed
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentIndex {
+    /// Summary, the only part kept in memory for sealed segments
+    summary: SegmentSummary,
+    /// Searcher → line offsets
+    by_searcher: BTreeMap<Address, Vec<u64>>,
+    /// Affected address (including searcher) → line offsets
+    by_address: BTreeMap<Address, Vec<u64>>,
+}
+
+impl SegmentIndex {
+    /// Index `record`, stored as a line of `len` bytes at the end of the segment
+    fn add(&mut self, record: &MevEventRecord, len: u64) {
+        let offset = self.summary.bytes;
+        let summary = &mut self.summary;
+        if summary.records == 0 || record.block_number < summary.min_block {
+            summary.min_block = record.block_number;
+        }
+        summary.max_block = summary.max_block.max(record.block_number);
+        summary.records += 1;
+        summary.bytes += len;
+        summary.types.insert(record.mev_type);
+        summary.sources.insert(record.source);
+        if let Some(epoch) = record.epoch {
+            summary
+                .epochs
+                .entry(epoch)
+                .or_insert_with(|| EpochMevTotals { epoch, ..Default::default() })
+                .add(record);
+        }
+
+        if let Some(searcher) = record.searcher {
+            summary.searchers.accrue(BloomInput::Raw(searcher.as_slice()));
+            self.by_searcher.entry(searcher).or_default().push(offset);
+        }
+        for address in record.searcher.iter().chain(&record.addresses) {
+            self.summary.addresses.accrue(BloomInput::Raw(address.as_slice()));
+            let offsets = self.by_address.entry(*address).or_default();
+            if offsets.last() != Some(&offset) {
+                offsets.push(offset);
+            }
+        }
+    }
+
+    /// Offsets of the events a filter can be narrowed to, if indexed
+    fn offsets(&self, filter: &MevEventFilter) -> Option<Vec<u64>> {
+        let offsets = match (filter.searcher, filter.address) {
+            (Some(searcher), _) => self.by_searcher.get(&searcher),
+            (None, Some(address)) => self.by_address.get(&address),
+            (None, None) => return None,
+        };
+        Some(offsets.cloned().unwrap_or_default())
+    }
+
+    /// Rebuild the index of a segment file
+    ///
+    /// A torn final line from a crash is cut off so appends start on a
+    /// line boundary; other malformed lines are skipped.
+    fn scan(path: &Path) -> Result<Self, MevEventStoreError> {
+        let mut index = Self::default();
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let len = reader.read_line(&mut line)? as u64;
+            if len == 0 {
+                break;
+            }
+            if !line.ends_with('\n') {
+                warn!("Truncating torn MEV event at {}:{}", path.display(), index.summary.bytes);
+                OpenOptions::new().write(true).open(path)?.set_len(index.summary.bytes)?;
+                break;
+            }
+            match serde_json::from_str::<MevEventRecord>(&line) {
+                Ok(record) => index.add(&record, len),
+                Err(e) => {
+                    warn!("Skipping malformed MEV event at {}:{}: {}", path.display(), index.summary.bytes, e);
+                    index.summary.bytes += len;
+                }
+            }
+        }
+        Ok(index)
+    }
+}
+
+/// Segment of the store
+#[derive(Debug)]
+enum Segment {
+    /// Sealed: index on disk, summary in memory
+    Sealed(SegmentSummary),
+    /// Still appended to: index in memory
+    Open {
+        /// Segment file, opened for appending
+        file: File,
+        /// Full index
+        index: SegmentIndex,
+    },
+}
+
+impl Segment {
+    fn summary(&self) -> &SegmentSummary {
+        match self {
+            Self::Sealed(summary) => summary,
+            Self::Open { index, .. } => &index.summary,
+        }
+    }
+}
+
+/// Segments and archived totals
+#[derive(Debug, Default)]
+struct Inner {
+    /// Segment start block → segment
+    segments: BTreeMap<u64, Segment>,
+    /// Epoch totals of rotated-out segments
+    archived: BTreeMap<u64, EpochMevTotals>,
+}
+
+/// Append-only, segmented and indexed store of MEV events
+#[derive(Debug)]
+pub struct MevEventStore {
+    /// Store directory
+    dir: PathBuf,
+    /// Segments kept before rotation
+    retained_segments: usize,
+    /// Segments, summaries and open indexes
+    inner: RwLock<Inner>,
+}
+
+impl MevEventStore {
+    /// Open (or create) the store in `dir`
+    ///
+    /// Sealed segments are loaded from their persisted summaries; only
+    /// unsealed segments are scanned.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, MevEventStoreError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut starts = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let name = entry?.file_name();
+            let Some(start) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix("seg-")?.strip_suffix(".jsonl")?.parse().ok())
+            else {
+                continue;
+            };
+            starts.push(start);
+        }
+
+        let store = Self {
+            dir,
+            retained_segments: DEFAULT_RETAINED_SEGMENTS,
+            inner: RwLock::new(Inner::default()),
+        };
+
+        let mut inner = Inner { archived: store.load_archived()?, ..Default::default() };
+        for start in starts {
+            let segment_len = std::fs::metadata(store.segment_path(start))?.len();
+            let sealed = std::fs::read(store.index_path(start))
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<SegmentIndex>(&bytes).ok())
+                .filter(|index| index.summary.bytes == segment_len);
+            let segment = match sealed {
+                Some(index) => Segment::Sealed(index.summary),
+                None => store.open_segment(start, SegmentIndex::scan(&store.segment_path(start))?)?,
+            };
+            inner.segments.insert(start, segment);
+        }
+
+        if let Some(last) = inner.segments.values().map(|s| s.summary().max_block).max() {
+            store.seal_before(&mut inner, last)?;
+        }
+        *store.inner.write() = inner;
+        Ok(store)
+    }
+
+    /// Keep `segments` segments before rotating the oldest out
+    pub fn with_retained_segments(mut self, segments: usize) -> Self {
+        self.retained_segments = segments.max(1);
+        self
+    }
+
+    /// Directory of the store
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist and index events
+    ///
+    /// Writes and fsyncs the segment files; call it from blocking context.
+    pub fn append(&self, records: Vec<MevEventRecord>) -> Result<(), MevEventStoreError> {
+        let Some(last) = records.iter().map(|record| record.block_number).max() else {
+            return Ok(());
+        };
+
+        let mut by_segment: BTreeMap<u64, Vec<MevEventRecord>> = BTreeMap::new();
+        for record in records {
+            by_segment.entry(segment_start(record.block_number)).or_default().push(record);
+        }
+
+        let mut inner = self.inner.write();
+        for (start, records) in by_segment {
+            let (file, index) = self.writable(&mut inner, start)?;
+
+            let mut buf = Vec::new();
+            let mut lines = Vec::with_capacity(records.len());
+            for record in &records {
+                let before = buf.len();
+                serde_json::to_writer(&mut buf, record)?;
+                buf.push(b'\n');
+                lines.push((buf.len() - before) as u64);
+            }
+            file.write_all(&buf)?;
+            file.sync_data()?;
+
+            for (record, len) in records.iter().zip(lines) {
+                index.add(record, len);
+            }
+        }
+
+        self.seal_before(&mut inner, last)?;
+        self.rotate(&mut inner)
+    }
+
+    /// Drop every event at or above `from_block` (reorg)
+    pub fn revert_from(&self, from_block: u64) -> Result<(), MevEventStoreError> {
+        let mut inner = self.inner.write();
+        let affected: Vec<u64> = inner
+            .segments
+            .iter()
+            .filter(|(_, segment)| {
+                let summary = segment.summary();
+                summary.records > 0 && summary.max_block >= from_block
+            })
+            .map(|(start, _)| *start)
+            .collect();
+
+        for start in affected {
+            inner.segments.remove(&start);
+            let path = self.segment_path(start);
+            let _ = std::fs::remove_file(self.index_path(start));
+
+            // Keep the surviving lines; events are not block-ordered within a segment
+            let mut kept = Vec::new();
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                match serde_json::from_str::<MevEventRecord>(&line) {
+                    Ok(record) if record.block_number < from_block => {
+                        kept.extend_from_slice(line.as_bytes());
+                        kept.push(b'\n');
+                    }
+                    _ => {}
+                }
+            }
+
+            if kept.is_empty() {
+                std::fs::remove_file(&path)?;
+                continue;
+            }
+            write_atomic(&path, &kept)?;
+            let segment = self.open_segment(start, SegmentIndex::scan(&path)?)?;
+            inner.segments.insert(start, segment);
+        }
+        Ok(())
+    }
+
+    /// Events matching `filter`, in block order
+    ///
+    /// Reads segment files; call it from blocking context.
+    pub fn query(&self, filter: &MevEventFilter) -> Vec<MevEventRecord> {
+        let limit = filter.limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+        let from = filter.from_block.unwrap_or(0);
+        let to = filter.to_block.unwrap_or(u64::MAX);
+        if from > to {
+            return Vec::new();
+        }
+
+        let inner = self.inner.read();
+        let mut records = Vec::new();
+        for (start, segment) in inner.segments.range(segment_start(from)..=to) {
+            if records.len() >= limit {
+                break;
+            }
+            if !segment.summary().may_match(filter) {
+                continue;
+            }
+            match self.read_segment(*start, segment, filter) {
+                Ok(mut found) => {
+                    found.sort_by_key(|record| record.block_number);
+                    records.extend(found);
+                }
+                Err(e) => warn!("Failed to read MEV event segment {}: {}", start, e),
+            }
+        }
+        records.truncate(limit);
+        records
+    }
+
+    /// Totals of every epoch in `from..=to`, including rotated-out segments
+    pub fn epoch_totals(&self, from: u64, to: u64) -> Vec<EpochMevTotals> {
+        if from > to {
+            return Vec::new();
+        }
+
+        let inner = self.inner.read();
+        let mut totals: BTreeMap<u64, EpochMevTotals> =
+            inner.archived.range(from..=to).map(|(epoch, t)| (*epoch, t.clone())).collect();
+        for segment in inner.segments.values() {
+            for (epoch, segment_totals) in segment.summary().epochs.range(from..=to) {
+                totals
+                    .entry(*epoch)
+                    .or_insert_with(|| EpochMevTotals { epoch: *epoch, ..Default::default() })
+                    .merge(segment_totals);
+            }
+        }
+        totals.into_values().collect()
+    }
+
+    /// Highest block with a stored event
+    pub fn last_block(&self) -> Option<u64> {
+        self.inner
+            .read()
+            .segments
+            .values()
+            .map(Segment::summary)
+            .filter(|summary| summary.records > 0)
+            .map(|summary| summary.max_block)
+            .max()
+    }
+
+    /// Matching events of one segment, through its index where possible
+    fn read_segment(
+        &self,
+        start: u64,
+        segment: &Segment,
+        filter: &MevEventFilter,
+    ) -> Result<Vec<MevEventRecord>, MevEventStoreError> {
+        let offsets = match segment {
+            Segment::Open { index, .. } => index.offsets(filter),
+            Segment::Sealed(_) if filter.searcher.is_some() || filter.address.is_some() => {
+                let bytes = std::fs::read(self.index_path(start))?;
+                serde_json::from_slice::<SegmentIndex>(&bytes)?.offsets(filter)
+            }
+            Segment::Sealed(_) => None,
+        };
+        let mut reader = BufReader::new(File::open(self.segment_path(start))?);
+        let mut found = Vec::new();
+        let mut line = String::new();
+        let mut keep = |line: &str| {
+            if let Ok(record) = serde_json::from_str::<MevEventRecord>(line)
+                && filter.matches(&record)
+            {
+                found.push(record);
+            }
+        };
+
+        match offsets {
+            Some(offsets) => {
+                for offset in offsets {
+                    reader.seek(SeekFrom::Start(offset))?;
+                    line.clear();
+                    reader.read_line(&mut line)?;
+                    keep(&line);
+                }
+            }
+            None => loop {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 {
+                    break;
+                }
+                keep(&line);
+            },
+        }
+        Ok(found)
+    }
+
+    /// Open segment `start` for appending, reopening it if it was sealed
+    fn writable<'a>(
+        &self,
+        inner: &'a mut Inner,
+        start: u64,
+    ) -> Result<(&'a mut File, &'a mut SegmentIndex), MevEventStoreError> {
+        let reopen = match inner.segments.get(&start) {
+            Some(Segment::Open { .. }) => None,
+            Some(Segment::Sealed(_)) => {
+                debug!(segment = start, "Reopening sealed MEV event segment");
+                let bytes = std::fs::read(self.index_path(start))?;
+                let index: SegmentIndex = serde_json::from_slice(&bytes)?;
+                std::fs::remove_file(self.index_path(start))?;
+                Some(index)
+            }
+            None => Some(SegmentIndex::default()),
+        };
+        if let Some(index) = reopen {
+            let segment = self.open_segment(start, index)?;
+            inner.segments.insert(start, segment);
+        }
+
+        match inner.segments.get_mut(&start) {
+            Some(Segment::Open { file, index }) => Ok((file, index)),
+            _ => unreachable!("segment {start} was just opened"),
+        }
+    }
+
+    /// Open the segment file for appending
+    fn open_segment(&self, start: u64, index: SegmentIndex) -> Result<Segment, MevEventStoreError> {
+        let file = OpenOptions::new().create(true).append(true).open(self.segment_path(start))?;
+        Ok(Segment::Open { file, index })
+    }
+
+    /// Seal open segments that ended [`SEAL_LAG`] blocks before `last_block`
+    fn seal_before(&self, inner: &mut Inner, last_block: u64) -> Result<(), MevEventStoreError> {
+        let sealable: Vec<u64> = inner
+            .segments
+            .iter()
+            .filter(|(start, segment)| {
+                matches!(segment, Segment::Open { .. })
+                    && **start + SEGMENT_BLOCKS + SEAL_LAG <= last_block
+            })
+            .map(|(start, _)| *start)
+            .collect();
+
+        for start in sealable {
+            let Some(Segment::Open { index, .. }) = inner.segments.remove(&start) else {
+                continue;
+            };
+            write_atomic(&self.index_path(start), &serde_json::to_vec(&index)?)?;
+            debug!(segment = start, records = index.summary.records, "Sealed MEV event segment");
+            inner.segments.insert(start, Segment::Sealed(index.summary));
+        }
+        Ok(())
+    }
+
+    /// Rotate out the oldest segments beyond the retention, keeping their totals
+    fn rotate(&self, inner: &mut Inner) -> Result<(), MevEventStoreError> {
+        while inner.segments.len() > self.retained_segments {
+            let Some((start, segment)) = inner.segments.pop_first() else {
+                break;
+            };
+            for (epoch, totals) in &segment.summary().epochs {
+                inner
+                    .archived
+                    .entry(*epoch)
+                    .or_insert_with(|| EpochMevTotals { epoch: *epoch, ..Default::default() })
+                    .merge(totals);
+            }
+            // Totals are persisted before the events are deleted
+            write_atomic(&self.dir.join(ARCHIVED_EPOCHS_FILE), &serde_json::to_vec(&inner.archived)?)?;
+            std::fs::remove_file(self.segment_path(start))?;
+            let _ = std::fs::remove_file(self.index_path(start));
+            debug!(segment = start, "Rotated out MEV event segment");
+        }
+        Ok(())
+    }
+
+    /// Epoch totals of rotated-out segments
+    fn load_archived(&self) -> Result<BTreeMap<u64, EpochMevTotals>, MevEventStoreError> {
+        match std::fs::read(self.dir.join(ARCHIVED_EPOCHS_FILE)) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn segment_path(&self, start: u64) -> PathBuf {
+        self.dir.join(format!("seg-{start:012}.jsonl"))
+    }
+
+    fn index_path(&self, start: u64) -> PathBuf {
+        self.dir.join(format!("seg-{start:012}.idx.json"))
+    }
+}
+
+/// First block of the segment holding `block`
+fn segment_start(block: u64) -> u64 {
+    block - block % SEGMENT_BLOCKS
+}
+
+/// Write a file atomically (temp file, fsync, rename)
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), MevEventStoreError> {
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ande-mev-store-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(MEV_EVENT_STORE_DIR)
+    }
+
+    fn record(block: u64, epoch: u64, mev_type: MevType, searcher: u8, value: u64) -> MevEventRecord {
+        MevEventRecord {
+            block_number: block,
+            timestamp: block * 2,
+            epoch: Some(epoch),
+            tx_hash: B256::with_last_byte(block as u8),
+            mev_type,
+            source: MevEventSource::Detector,
+            value: U256::from(value),
+            searcher: Some(Address::repeat_byte(searcher)),
+            addresses: vec![Address::repeat_byte(0xee)],
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_query_by_indexes() {
+        let store = MevEventStore::open(temp_path("query")).unwrap();
+        store
+            .append(vec![
+                record(10, 1, MevType::Sandwich, 1, 100),
+                record(11, 1, MevType::Arbitrage, 2, 50),
+                record(20, 2, MevType::Sandwich, 2, 70),
+            ])
+            .unwrap();
+
+        let range = MevEventFilter { from_block: Some(11), to_block: Some(20), ..Default::default() };
+        assert_eq!(store.query(&range).len(), 2);
+
+        let by_searcher = MevEventFilter { searcher: Some(Address::repeat_byte(2)), ..Default::default() };
+        assert_eq!(store.query(&by_searcher).len(), 2);
+
+        let sandwiches = MevEventFilter {
+            mev_type: Some(MevType::Sandwich),
+            to_block: Some(15),
+            ..Default::default()
+        };
+        let found = store.query(&sandwiches);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].block_number, 10);
+
+        let victim = MevEventFilter { address: Some(Address::repeat_byte(0xee)), limit: Some(1), ..Default::default() };
+        assert_eq!(store.query(&victim).len(), 1);
+
+        // Stored without an epoch: queryable, but not part of any epoch total
+        store.append(vec![MevEventRecord { epoch: None, ..record(21, 0, MevType::Other, 4, 9) }]).unwrap();
+        let unlimited = MevEventFilter { limit: Some(usize::MAX), ..Default::default() };
+        assert_eq!(store.query(&unlimited).len(), 4);
+        let totals = store.epoch_totals(0, u64::MAX);
+        assert_eq!(totals.iter().map(|t| t.events).sum::<u64>(), 3);
+    }
+
+    #[test]
+    fn test_history_survives_restart_and_reorg() {
+        let path = temp_path("restart");
+        {
+            let store = MevEventStore::open(&path).unwrap();
+            store.append(vec![record(10, 1, MevType::Sandwich, 1, 100)]).unwrap();
+            store.append(vec![record(12, 1, MevType::Arbitrage, 1, 40)]).unwrap();
+            store.revert_from(12).unwrap();
+            store.append(vec![record(12, 1, MevType::Liquidation, 3, 5)]).unwrap();
+        }
+
+        let store = MevEventStore::open(&path).unwrap();
+        assert_eq!(store.last_block(), Some(12));
+
+        let totals = store.epoch_totals(0, 10);
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].events, 2);
+        assert_eq!(totals[0].total_value, U256::from(105));
+        assert_eq!(totals[0].by_type.get("Liquidation"), Some(&U256::from(5)));
+        assert!(!totals[0].by_type.contains_key("Arbitrage"));
+
+        let arbs = MevEventFilter { mev_type: Some(MevType::Arbitrage), ..Default::default() };
+        assert!(store.query(&arbs).is_empty());
+    }
+
+    #[test]
+    fn test_segments_seal_rotate_and_reopen() {
+        let path = temp_path("segments");
+        let late = SEGMENT_BLOCKS - 1;
+        {
+            let store = MevEventStore::open(&path).unwrap().with_retained_segments(2);
+            store.append(vec![record(5, 1, MevType::Sandwich, 1, 10)]).unwrap();
+            store.append(vec![record(SEGMENT_BLOCKS + 5, 2, MevType::Arbitrage, 2, 20)]).unwrap();
+            // Seals both earlier segments; with a retention of two the first
+            // is rotated out
+            store.append(vec![record(2 * SEGMENT_BLOCKS + SEAL_LAG, 3, MevType::Other, 3, 30)]).unwrap();
+            assert!(path.join(format!("seg-{SEGMENT_BLOCKS:012}.idx.json")).exists());
+            assert!(!path.join(format!("seg-{:012}.jsonl", 0)).exists());
+            assert_eq!(store.query(&MevEventFilter::default()).len(), 2);
+
+            // A late settled bundle reopens its sealed segment
+            store.append(vec![record(SEGMENT_BLOCKS + late, 2, MevType::Arbitrage, 2, 1)]).unwrap();
+            assert!(!path.join(format!("seg-{SEGMENT_BLOCKS:012}.idx.json")).exists());
+        }
+
+        let store = MevEventStore::open(&path).unwrap();
+        // Sealed again on open, then queried through its on-disk index
+        let by_searcher = MevEventFilter { searcher: Some(Address::repeat_byte(2)), ..Default::default() };
+        let found = store.query(&by_searcher);
+        assert_eq!(found.iter().map(|r| r.block_number).collect::<Vec<_>>(), [
+            SEGMENT_BLOCKS + 5,
+            SEGMENT_BLOCKS + late
+        ]);
+
+        // Totals of the rotated-out segment survive
+        let totals = store.epoch_totals(1, 3);
+        assert_eq!(totals.iter().map(|t| t.total_value).collect::<Vec<_>>(), [
+            U256::from(10),
+            U256::from(21),
+            U256::from(30)
+        ]);
+        assert!(store.query(&MevEventFilter { to_block: Some(100), ..Default::default() }).is_empty());
+    }
+}
@@ -0,0 +1,624 @@
+//! Swap Reconstruction from Receipts
+//!
+//! Decodes `Swap` logs emitted by `AndeSwapPair` (V2) and `AndeSwapV3Pool`
+//! (V3) into normalized [`SwapEvent`]s and finds cross-transaction MEV in
+//! them:
+//!
+//! ```text
+//! receipts ── Swap logs ──→ SwapEvent { pool, token_in, token_out, amounts }
+//!                               ├─ same pool: A(buy) → V(buy) → A(sell)    ⇒ sandwich
+//!                               └─ same tx:   X → Y → … → X, out > in      ⇒ cyclic arbitrage
+//! profit (token) ── V2 reserves / V3 sqrtPrice vs ANDE ──→ value in ANDE
+//! ```
+//!
+//! Pool tokens and reserves are read from state through [`PoolStateReader`],
+//! so only contracts that answer `token0()`/`token1()` are treated as pools.
+
+use alloy::sol_types::{sol, SolCall, SolEvent};
+use alloy_evm::Evm;
+use alloy_primitives::{Address, Log, B256, I256, U256, U512};
+use std::collections::{HashMap, HashSet};
+
+/// AndeSwapPair (Uniswap V2 style) events and views
+pub mod v2 {
+    use super::sol;
+
+    sol! {
+        #[derive(Debug)]
+        event Swap(
+            address indexed sender,
+            uint256 amount0In,
+            uint256 amount1In,
+            uint256 amount0Out,
+            uint256 amount1Out,
+            address indexed to
+        );
+
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+    }
+}
+
+/// AndeSwapV3Pool events
+pub mod v3 {
+    use super::sol;
+
+    sol! {
+        #[derive(Debug)]
+        event Swap(
+            address indexed sender,
+            address indexed recipient,
+            int256 amount0,
+            int256 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick
+        );
+    }
+}
+
+/// Pool implementation a swap came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolKind {
+    /// AndeSwapPair (constant product)
+    V2,
+    /// AndeSwapV3Pool (concentrated liquidity)
+    V3,
+}
+
+/// Normalized swap reconstructed from a receipt log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapEvent {
+    /// Index of the transaction in the block
+    pub tx_index: usize,
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Transaction signer
+    pub from: Address,
+    /// Pool that emitted the swap
+    pub pool: Address,
+    /// Pool implementation
+    pub kind: PoolKind,
+    /// Token sold into the pool
+    pub token_in: Address,
+    /// Token bought from the pool
+    pub token_out: Address,
+    /// Amount sold into the pool
+    pub amount_in: U256,
+    /// Amount bought from the pool
+    pub amount_out: U256,
+    /// V3 pool price after the swap (token1 per token0, Q64.96)
+    pub sqrt_price_x96: Option<U256>,
+}
+
+/// Read-only access to pool state
+pub trait PoolStateReader {
+    /// `(token0, token1)` of a pool, `None` if the address is not a pool
+    fn pool_tokens(&mut self, pool: Address) -> Option<(Address, Address)>;
+
+    /// `(reserve0, reserve1)` of a V2 pool
+    fn v2_reserves(&mut self, pool: Address) -> Option<(U256, U256)>;
+}
+
+/// [`PoolStateReader`] that issues static calls through an EVM
+///
+/// Results are cached per reader, so create one per analyzed block. Calls
+/// are executed without committing, so state is never modified.
+#[derive(Debug)]
+pub struct EvmPoolStateReader<E> {
+    /// EVM over the post-block state
+    evm: E,
+    /// Cached pool tokens
+    tokens: HashMap<Address, Option<(Address, Address)>>,
+    /// Cached V2 reserves
+    reserves: HashMap<Address, Option<(U256, U256)>>,
+}
+
+impl<E: Evm> EvmPoolStateReader<E> {
+    /// Create a reader over `evm`
+    pub fn new(evm: E) -> Self {
+        Self { evm, tokens: HashMap::new(), reserves: HashMap::new() }
+    }
+
+    /// Static call returning the decoded output
+    pub(crate) fn call<C: SolCall>(&mut self, pool: Address, call: C) -> Option<C::Return> {
+        let result = self
+            .evm
+            .transact_system_call(Address::ZERO, pool, call.abi_encode().into())
+            .ok()?;
+        if !result.result.is_success() {
+            return None;
+        }
+        C::abi_decode_returns(result.result.output()?).ok()
+    }
+}
+
+impl<E: Evm> PoolStateReader for EvmPoolStateReader<E> {
+    fn pool_tokens(&mut self, pool: Address) -> Option<(Address, Address)> {
+        if let Some(cached) = self.tokens.get(&pool) {
+            return *cached;
+        }
+
+        let tokens = self
+            .call(pool, v2::token0Call {})
+            .zip(self.call(pool, v2::token1Call {}));
+        self.tokens.insert(pool, tokens);
+        tokens
+    }
+
+    fn v2_reserves(&mut self, pool: Address) -> Option<(U256, U256)> {
+        if let Some(cached) = self.reserves.get(&pool) {
+            return *cached;
+        }
+
+        let reserves = self
+            .call(pool, v2::getReservesCall {})
+            .map(|r| (U256::from(r.reserve0), U256::from(r.reserve1)));
+        self.reserves.insert(pool, reserves);
+        reserves
+    }
+}
+
+/// Decode the swaps of one transaction from its logs
+///
+/// Logs from pools rejected by `accept_pool` are skipped.
+pub fn decode_swaps<R: PoolStateReader>(
+    tx_index: usize,
+    tx_hash: B256,
+    from: Address,
+    logs: &[Log],
+    reader: &mut R,
+    accept_pool: impl Fn(&Address, PoolKind) -> bool,
+) -> Vec<SwapEvent> {
+    let mut swaps = Vec::new();
+
+    for log in logs {
+        let Some(topic0) = log.topics().first() else {
+            continue;
+        };
+
+        // (kind, token0 in?, amount in, amount out, price)
+        let decoded = if *topic0 == v2::Swap::SIGNATURE_HASH {
+            let Ok(swap) = v2::Swap::decode_log_data(&log.data) else {
+                continue;
+            };
+            if swap.amount0In > swap.amount0Out {
+                (
+                    PoolKind::V2,
+                    true,
+                    swap.amount0In - swap.amount0Out,
+                    swap.amount1Out.saturating_sub(swap.amount1In),
+                    None,
+                )
+            } else {
+                (
+                    PoolKind::V2,
+                    false,
+                    swap.amount1In.saturating_sub(swap.amount1Out),
+                    swap.amount0Out - swap.amount0In,
+                    None,
+                )
+            }
+        } else if *topic0 == v3::Swap::SIGNATURE_HASH {
+            let Ok(swap) = v3::Swap::decode_log_data(&log.data) else {
+                continue;
+            };
+            let price = Some(U256::from(swap.sqrtPriceX96));
+            // Positive amounts flow into the pool
+            if swap.amount0 > I256::ZERO {
+                (PoolKind::V3, true, swap.amount0.unsigned_abs(), swap.amount1.unsigned_abs(), price)
+            } else {
+                (PoolKind::V3, false, swap.amount1.unsigned_abs(), swap.amount0.unsigned_abs(), price)
+            }
+        } else {
+            continue;
+        };
+
+        let (kind, zero_for_one, amount_in, amount_out, sqrt_price_x96) = decoded;
+        if !accept_pool(&log.address, kind) {
+            continue;
+        }
+        let Some((token0, token1)) = reader.pool_tokens(log.address) else {
+            continue;
+        };
+        let (token_in, token_out) = if zero_for_one { (token0, token1) } else { (token1, token0) };
+
+        swaps.push(SwapEvent {
+            tx_index,
+            tx_hash,
+            from,
+            pool: log.address,
+            kind,
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            sqrt_price_x96,
+        });
+    }
+
+    swaps
+}
+
+/// Sandwich found in a block: front-run, victim and back-run on one pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandwichMatch {
+    /// Front-running swap
+    pub front_run: SwapEvent,
+    /// Victim swap
+    pub victim: SwapEvent,
+    /// Back-running swap
+    pub back_run: SwapEvent,
+    /// Attacker profit in the front-run input token
+    pub profit: U256,
+}
+
+/// Find front-run/victim/back-run triples
+///
+/// A triple is two swaps by the same signer on the same pool, in opposite
+/// directions, surrounding a swap by another signer in the attacker's first
+/// direction. Only profitable triples are returned; each swap takes part in
+/// at most one triple, so overlapping front-runs sharing a victim or back-run
+/// are counted once.
+pub fn find_sandwiches(swaps: &[SwapEvent]) -> Vec<SandwichMatch> {
+    let mut by_pool: HashMap<Address, Vec<&SwapEvent>> = HashMap::new();
+    for swap in swaps {
+        by_pool.entry(swap.pool).or_default().push(swap);
+    }
+
+    let mut sandwiches = Vec::new();
+    for pool_swaps in by_pool.values() {
+        for (i, front) in pool_swaps.iter().enumerate() {
+            let back = pool_swaps[i + 1..].iter().find(|s| {
+                s.from == front.from
+                    && s.tx_index > front.tx_index
+                    && s.token_in == front.token_out
+                    && s.token_out == front.token_in
+            });
+            let Some(back) = back else {
+                continue;
+            };
+
+            let victim = pool_swaps.iter().find(|s| {
+                s.tx_index > front.tx_index
+                    && s.tx_index < back.tx_index
+                    && s.from != front.from
+                    && s.token_in == front.token_in
+            });
+            let Some(victim) = victim else {
+                continue;
+            };
+
+            if back.amount_out > front.amount_in {
+                sandwiches.push(SandwichMatch {
+                    front_run: (*front).clone(),
+                    victim: (*victim).clone(),
+                    back_run: (*back).clone(),
+                    profit: back.amount_out - front.amount_in,
+                });
+            }
+        }
+    }
+
+    // One match per (front, victim, back) triple, and no swap counted twice:
+    // overlapping fronts sharing a back-run or victim keep only the earliest
+    sandwiches.sort_by_key(sandwich_key);
+    sandwiches.dedup_by_key(|s| sandwich_key(s));
+
+    let mut claimed = HashSet::new();
+    sandwiches.retain(|s| {
+        let pool = s.victim.pool;
+        let swaps = [(pool, s.front_run.tx_index), (pool, s.victim.tx_index), (pool, s.back_run.tx_index)];
+        if swaps.iter().any(|swap| claimed.contains(swap)) {
+            return false;
+        }
+        claimed.extend(swaps);
+        true
+    });
+    sandwiches.sort_by_key(|s| (s.victim.tx_index, s.victim.pool));
+    sandwiches
+}
+
+/// Identity of a sandwich: its pool and the (front, victim, back) triple
+fn sandwich_key(sandwich: &SandwichMatch) -> (Address, usize, usize, usize) {
+    (
+        sandwich.victim.pool,
+        sandwich.front_run.tx_index,
+        sandwich.victim.tx_index,
+        sandwich.back_run.tx_index,
+    )
+}
+
+/// Cyclic arbitrage inside one transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArbitrageMatch {
+    /// Swaps forming the cycle, in execution order
+    pub swaps: Vec<SwapEvent>,
+    /// Token the cycle starts and ends in
+    pub token: Address,
+    /// Profit in `token`
+    pub profit: U256,
+}
+
+/// Find transactions whose swaps form a profitable token cycle
+///
+/// Swaps must chain (`token_out` of one is `token_in` of the next), touch at
+/// least two pools, and end in the token they started with.
+pub fn find_cyclic_arbitrage(swaps: &[SwapEvent]) -> Vec<ArbitrageMatch> {
+    let mut by_tx: Vec<(usize, Vec<&SwapEvent>)> = Vec::new();
+    for swap in swaps {
+        match by_tx.last_mut() {
+            Some((index, tx_swaps)) if *index == swap.tx_index => tx_swaps.push(swap),
+            _ => by_tx.push((swap.tx_index, vec![swap])),
+        }
+    }
+
+    by_tx
+        .into_iter()
+        .filter_map(|(_, tx_swaps)| {
+            let first = tx_swaps.first()?;
+            let last = tx_swaps.last()?;
+
+            let chained = tx_swaps.windows(2).all(|w| w[0].token_out == w[1].token_in);
+            let distinct_pools = tx_swaps.iter().any(|s| s.pool != first.pool);
+            let closed = last.token_out == first.token_in;
+
+            (tx_swaps.len() >= 2
+                && chained
+                && distinct_pools
+                && closed
+                && last.amount_out > first.amount_in)
+                .then(|| ArbitrageMatch {
+                    swaps: tx_swaps.iter().map(|s| (*s).clone()).collect(),
+                    token: first.token_in,
+                    profit: last.amount_out - first.amount_in,
+                })
+        })
+        .collect()
+}
+
+/// Value an amount of `token` in ANDE
+///
+/// Uses, in order: `token` being ANDE itself, a configured pricing pool, or
+/// any pool pairing `token` with ANDE seen in `swaps`. V2 pools are priced
+/// from reserves read from state, V3 pools from their post-swap sqrt price.
+pub fn value_in_ande<R: PoolStateReader>(
+    token: Address,
+    amount: U256,
+    ande_token: Address,
+    pricing_pools: &HashMap<Address, Address>,
+    swaps: &[SwapEvent],
+    reader: &mut R,
+) -> Option<U256> {
+    if token == ande_token {
+        return Some(amount);
+    }
+
+    let pairs_with_ande = |a: Address, b: Address| {
+        (a == token && b == ande_token) || (a == ande_token && b == token)
+    };
+
+    if let Some(pool) = pricing_pools.get(&token) {
+        if let Some(value) = value_via_v2(*pool, token, amount, ande_token, reader) {
+            return Some(value);
+        }
+    }
+
+    for swap in swaps.iter().rev() {
+        if !pairs_with_ande(swap.token_in, swap.token_out) {
+            continue;
+        }
+        let value = match swap.kind {
+            PoolKind::V2 => value_via_v2(swap.pool, token, amount, ande_token, reader),
+            PoolKind::V3 => value_via_v3(swap, token, amount, reader),
+        };
+        if value.is_some() {
+            return value;
+        }
+    }
+
+    None
+}
+
+/// Convert through a V2 pool's reserves
+fn value_via_v2<R: PoolStateReader>(
+    pool: Address,
+    token: Address,
+    amount: U256,
+    ande_token: Address,
+    reader: &mut R,
+) -> Option<U256> {
+    let (token0, token1) = reader.pool_tokens(pool)?;
+    let (reserve0, reserve1) = reader.v2_reserves(pool)?;
+
+    let (reserve_token, reserve_ande) = if token0 == token && token1 == ande_token {
+        (reserve0, reserve1)
+    } else if token1 == token && token0 == ande_token {
+        (reserve1, reserve0)
+    } else {
+        return None;
+    };
+
+    if reserve_token.is_zero() {
+        return None;
+    }
+    Some(mul_div(amount, reserve_ande, reserve_token))
+}
+
+/// Convert through a V3 pool's sqrt price
+fn value_via_v3<R: PoolStateReader>(
+    swap: &SwapEvent,
+    token: Address,
+    amount: U256,
+    reader: &mut R,
+) -> Option<U256> {
+    let sqrt_price = U512::from(swap.sqrt_price_x96?);
+    if sqrt_price.is_zero() {
+        return None;
+    }
+    let (token0, _) = reader.pool_tokens(swap.pool)?;
+    let price_x192 = sqrt_price.checked_mul(sqrt_price)?;
+    let amount = U512::from(amount);
+
+    // price = token1 per token0 = sqrtPrice² / 2^192; an overflow on an
+    // extreme price drops the swap as a price source
+    let value = if token == token0 {
+        amount.checked_mul(price_x192)? >> 192
+    } else {
+        amount.checked_shl(192)?.checked_div(price_x192)?
+    };
+    U256::checked_from_limbs_slice(value.as_limbs())
+}
+
+/// `a * b / c` without intermediate overflow
+fn mul_div(a: U256, b: U256, c: U256) -> U256 {
+    U256::saturating_from(U512::from(a) * U512::from(b) / U512::from(c))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_primitives::LogData;
+
+    /// In-memory pool state
+    #[derive(Debug, Default)]
+    pub(crate) struct MockPools {
+        pub(crate) tokens: HashMap<Address, (Address, Address)>,
+        pub(crate) reserves: HashMap<Address, (U256, U256)>,
+    }
+
+    impl PoolStateReader for MockPools {
+        fn pool_tokens(&mut self, pool: Address) -> Option<(Address, Address)> {
+            self.tokens.get(&pool).copied()
+        }
+
+        fn v2_reserves(&mut self, pool: Address) -> Option<(U256, U256)> {
+            self.reserves.get(&pool).copied()
+        }
+    }
+
+    /// `Swap` log of an AndeSwapPair
+    pub(crate) fn v2_swap_log(
+        pool: Address,
+        amount0_in: u64,
+        amount1_in: u64,
+        amount0_out: u64,
+        amount1_out: u64,
+    ) -> Log {
+        let event = v2::Swap {
+            sender: Address::ZERO,
+            amount0In: U256::from(amount0_in),
+            amount1In: U256::from(amount1_in),
+            amount0Out: U256::from(amount0_out),
+            amount1Out: U256::from(amount1_out),
+            to: Address::ZERO,
+        };
+        Log { address: pool, data: event.encode_log_data() }
+    }
+
+    #[test]
+    fn test_decode_v2_and_v3_swaps() {
+        let (pair, pool_v3) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (t0, t1) = (Address::repeat_byte(0xa0), Address::repeat_byte(0xa1));
+        let mut pools = MockPools::default();
+        pools.tokens.insert(pair, (t0, t1));
+        pools.tokens.insert(pool_v3, (t0, t1));
+
+        let v3_log = Log {
+            address: pool_v3,
+            data: v3::Swap {
+                sender: Address::ZERO,
+                recipient: Address::ZERO,
+                amount0: I256::try_from(-40).unwrap(),
+                amount1: I256::try_from(50).unwrap(),
+                sqrtPriceX96: alloy_primitives::aliases::U160::from(1u128 << 96),
+                liquidity: 0,
+                tick: alloy_primitives::aliases::I24::ZERO,
+            }
+            .encode_log_data(),
+        };
+        let unrelated = Log { address: pair, data: LogData::new_unchecked(vec![B256::ZERO], Default::default()) };
+        let logs = vec![v2_swap_log(pair, 100, 0, 0, 90), v3_log, unrelated];
+
+        let swaps = decode_swaps(0, B256::ZERO, Address::ZERO, &logs, &mut pools, |_, _| true);
+        assert_eq!(swaps.len(), 2);
+        assert_eq!((swaps[0].token_in, swaps[0].amount_in, swaps[0].amount_out), (t0, U256::from(100), U256::from(90)));
+        assert_eq!((swaps[1].token_in, swaps[1].amount_in, swaps[1].amount_out), (t1, U256::from(50), U256::from(40)));
+        assert_eq!(swaps[1].kind, PoolKind::V3);
+    }
+
+    #[test]
+    fn test_value_via_reserves_and_sqrt_price() {
+        let (ande, token) = (Address::repeat_byte(0xfd), Address::repeat_byte(0xaa));
+        let pair = Address::repeat_byte(1);
+        let mut pools = MockPools::default();
+        pools.tokens.insert(pair, (token, ande));
+        pools.reserves.insert(pair, (U256::from(1_000), U256::from(4_000)));
+
+        let mut pricing = HashMap::new();
+        pricing.insert(token, pair);
+        let value = value_in_ande(token, U256::from(10), ande, &pricing, &[], &mut pools);
+        assert_eq!(value, Some(U256::from(40)));
+
+        // V3 pool at price 4 ANDE per token (sqrtPrice = 2 * 2^96)
+        let pool_v3 = Address::repeat_byte(2);
+        pools.tokens.insert(pool_v3, (token, ande));
+        let swap = SwapEvent {
+            tx_index: 0,
+            tx_hash: B256::ZERO,
+            from: Address::ZERO,
+            pool: pool_v3,
+            kind: PoolKind::V3,
+            token_in: token,
+            token_out: ande,
+            amount_in: U256::ZERO,
+            amount_out: U256::ZERO,
+            sqrt_price_x96: Some(U256::from(2u128 << 96)),
+        };
+        let swaps = std::slice::from_ref(&swap);
+        let value = value_in_ande(token, U256::from(10), ande, &HashMap::new(), swaps, &mut pools);
+        assert_eq!(value, Some(U256::from(40)));
+
+        // An extreme sqrt price overflows and is not used as a price source
+        let extreme = SwapEvent { sqrt_price_x96: Some(U256::MAX), ..swap };
+        let value = value_in_ande(token, U256::MAX, ande, &HashMap::new(), &[extreme], &mut pools);
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_overlapping_sandwiches_counted_once() {
+        let (pool, t0, t1) = (Address::repeat_byte(1), Address::repeat_byte(0xa0), Address::repeat_byte(0xa1));
+        let (attacker, victim) = (Address::repeat_byte(0xbb), Address::repeat_byte(0xcc));
+        let swap = |tx_index, from, sell_t0: bool, amount_in: u64, amount_out: u64| SwapEvent {
+            tx_index,
+            tx_hash: B256::with_last_byte(tx_index as u8),
+            from,
+            pool,
+            kind: PoolKind::V2,
+            token_in: if sell_t0 { t0 } else { t1 },
+            token_out: if sell_t0 { t1 } else { t0 },
+            amount_in: U256::from(amount_in),
+            amount_out: U256::from(amount_out),
+            sqrt_price_x96: None,
+        };
+
+        // A buys, V buys, A buys again, V buys, A sells once: both buys pair
+        // with the single back-run, which must only be counted once
+        let swaps = vec![
+            swap(0, attacker, true, 100, 90),
+            swap(1, victim, true, 100, 80),
+            swap(2, attacker, true, 100, 70),
+            swap(3, victim, true, 100, 60),
+            swap(4, attacker, false, 90, 120),
+        ];
+        let sandwiches = find_sandwiches(&swaps);
+        assert_eq!(sandwiches.len(), 1);
+        let sandwich = &sandwiches[0];
+        assert_eq!(
+            (sandwich.front_run.tx_index, sandwich.victim.tx_index, sandwich.back_run.tx_index),
+            (0, 1, 4)
+        );
+        assert_eq!(sandwich.profit, U256::from(20));
+    }
+}
@@ -0,0 +1,662 @@
+//! Bundle RPC for MEV searchers
+//!
+//! Flashbots-compatible `eth_sendBundle`, `eth_callBundle` and
+//! `eth_cancelBundle`. Bundles are simulated for their target block with the
+//! node's EVM configuration (AndeEvmFactory, including the Token Duality
+//! precompile at 0xFD) before they are handed to the MEV auction.
+//!
+//! The API is served on its own endpoint ([`serve_bundle_rpc`]) behind
+//! [`FlashbotsSignatureLayer`], which checks the `X-Flashbots-Signature`
+//! header against the request body. Every method requires it: the signer is
+//! the searcher that committed the bundle on MEVAuctionManager, and
+//! `replacementUuid`s are scoped to it. `eth_callBundle` runs a full EVM
+//! simulation per call, so each signer may make at most
+//! [`MAX_CALL_BUNDLES_PER_WINDOW`] calls per [`CALL_BUNDLE_WINDOW`].
+//!
+//! ```text
+//! X-Flashbots-Signature ─→ FlashbotsSignatureLayer ─→ BundleSigner (request extension)
+//! eth_sendBundle ─→ decode + recover ─→ simulate at target ─→ MevAuctionClient::submit_bundle
+//! eth_callBundle ─→ rate limit per signer ─→ decode + recover ─→ simulate at blockNumber ─→ results
+//! eth_cancelBundle ─→ (signer, replacementUuid) ─→ MevAuctionClient::cancel_bundle
+//! ```
+
+use crate::mev::{
+    auction::decode_bundle_transactions, simulate_bundle, BundleSimulation, BundleSubmission,
+    MevAuctionClient,
+};
+use alloy_consensus::{transaction::Recovered, Header};
+use alloy_primitives::{hex, keccak256, Address, Bytes, Signature, B256, U256, U64};
+use async_trait::async_trait;
+use http::StatusCode;
+use http_body_util::{BodyExt, Full, Limited};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse, Server, ServerHandle};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObjectOwned,
+};
+use jsonrpsee::{Extensions, Methods};
+use jsonrpsee_core::{BoxError, RpcResult};
+use jsonrpsee_proc_macros::rpc;
+use parking_lot::Mutex;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_primitives::TransactionSigned;
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_storage_api::{BlockNumReader, BlockReaderIdExt, StateProviderFactory};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service, ServiceBuilder};
+use tracing::{debug, info};
+
+/// Header carrying the searcher's signature over the request body
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "x-flashbots-signature";
+
+/// Largest request body checked by [`FlashbotsSignatureLayer`] (10 MiB)
+pub const MAX_BUNDLE_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// JSON-RPC error code for requests without a valid signature
+pub const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// JSON-RPC error code for signers over their `eth_callBundle` rate
+pub const RATE_LIMITED_CODE: i32 = -32005;
+
+/// `eth_callBundle` calls allowed per signer and window
+pub const MAX_CALL_BUNDLES_PER_WINDOW: u32 = 20;
+
+/// Window of the `eth_callBundle` rate limit
+pub const CALL_BUNDLE_WINDOW: Duration = Duration::from_secs(1);
+
+/// `eth_sendBundle` parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleRequest {
+    /// EIP-2718 encoded signed transactions, in execution order
+    pub txs: Vec<Bytes>,
+    /// Block the bundle targets
+    pub block_number: U64,
+    /// Earliest block timestamp the bundle may be included at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_timestamp: Option<u64>,
+    /// Latest block timestamp the bundle may be included at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_timestamp: Option<u64>,
+    /// Transactions allowed to revert
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reverting_tx_hashes: Vec<B256>,
+    /// Identifier used to replace or cancel the bundle, scoped to the signer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement_uuid: Option<String>,
+}
+
+/// `eth_sendBundle` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleResponse {
+    /// Bundle hash (`keccak256` of the concatenated transaction hashes)
+    pub bundle_hash: B256,
+}
+
+/// `eth_callBundle` parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleRequest {
+    /// EIP-2718 encoded signed transactions, in execution order
+    pub txs: Vec<Bytes>,
+    /// Block to simulate the bundle in, on the state of its parent
+    /// (or of the latest block, if the parent is not known yet)
+    pub block_number: U64,
+    /// Timestamp to simulate with (defaults to parent + 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+/// Simulation result of a single bundle transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTxResult {
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Sender
+    pub from_address: Address,
+    /// Recipient
+    pub to_address: Option<Address>,
+    /// Gas used
+    pub gas_used: u64,
+    /// Priority fees paid
+    pub gas_fees: U256,
+    /// Return data, if the transaction succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Revert data, if the transaction reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<Bytes>,
+}
+
+/// `eth_callBundle` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResponse {
+    /// Bundle hash
+    pub bundle_hash: B256,
+    /// Block beneficiary balance change
+    pub coinbase_diff: U256,
+    /// Total priority fees paid
+    pub gas_fees: U256,
+    /// Total gas used
+    pub total_gas_used: u64,
+    /// Block whose state the bundle was simulated on
+    pub state_block_number: u64,
+    /// Per-transaction results
+    pub results: Vec<CallBundleTxResult>,
+}
+
+/// `eth_cancelBundle` parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelBundleRequest {
+    /// `replacementUuid` the bundle was submitted with
+    pub replacement_uuid: String,
+}
+
+/// Searcher that signed the request, set by [`FlashbotsSignatureLayer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleSigner(pub Address);
+
+/// Bundle RPC API trait
+#[rpc(server, namespace = "eth")]
+pub trait EthBundleApi {
+    /// Submit a bundle to the MEV auction
+    #[method(name = "sendBundle", with_extensions)]
+    async fn send_bundle(&self, bundle: SendBundleRequest) -> RpcResult<SendBundleResponse>;
+
+    /// Simulate a bundle in a given block
+    #[method(name = "callBundle", with_extensions)]
+    async fn call_bundle(&self, bundle: CallBundleRequest) -> RpcResult<CallBundleResponse>;
+
+    /// Cancel a bundle the signer submitted with a `replacementUuid`
+    #[method(name = "cancelBundle", with_extensions)]
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<bool>;
+}
+
+/// Implementation of the bundle RPC API
+#[derive(Debug)]
+pub struct EthBundleApiImpl<Client, EvmConfig> {
+    /// State access
+    client: Client,
+    /// EVM configuration used for simulation
+    evm_config: EvmConfig,
+    /// Auction receiving accepted bundles
+    auction: Arc<MevAuctionClient>,
+    /// (signer, `replacementUuid`) → bundle hash
+    replacements: Mutex<HashMap<(Address, String), B256>>,
+    /// `eth_callBundle` rate limit per signer
+    call_limiter: SignerRateLimiter,
+}
+
+impl<Client, EvmConfig> EthBundleApiImpl<Client, EvmConfig> {
+    /// Creates a new bundle API
+    pub fn new(client: Client, evm_config: EvmConfig, auction: Arc<MevAuctionClient>) -> Self {
+        Self {
+            client,
+            evm_config,
+            auction,
+            replacements: Mutex::new(HashMap::new()),
+            call_limiter: SignerRateLimiter::new(MAX_CALL_BUNDLES_PER_WINDOW, CALL_BUNDLE_WINDOW),
+        }
+    }
+}
+
+/// Fixed-window rate limit per signer
+#[derive(Debug)]
+pub struct SignerRateLimiter {
+    /// Calls allowed per window
+    max_calls: u32,
+    /// Window length
+    window: Duration,
+    /// Signer → (window start, calls in the window)
+    windows: Mutex<HashMap<Address, (Instant, u32)>>,
+}
+
+impl SignerRateLimiter {
+    /// Allow `max_calls` per signer every `window`
+    pub fn new(max_calls: u32, window: Duration) -> Self {
+        Self { max_calls, window, windows: Mutex::new(HashMap::new()) }
+    }
+
+    /// Count a call by `signer` at `now`, returning whether it is allowed
+    pub fn check(&self, signer: Address, now: Instant) -> bool {
+        let mut windows = self.windows.lock();
+        let (start, calls) = windows.entry(signer).or_insert((now, 0));
+        if now.duration_since(*start) >= self.window {
+            *start = now;
+            *calls = 0;
+        }
+        if *calls >= self.max_calls {
+            return false;
+        }
+        *calls += 1;
+
+        // Forget signers whose window has passed
+        if windows.len() > 1024 {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+        }
+        true
+    }
+}
+
+impl<Client, EvmConfig> EthBundleApiImpl<Client, EvmConfig>
+where
+    Client: StateProviderFactory + BlockReaderIdExt<Header = Header> + BlockNumReader,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+{
+    /// Simulate a bundle in `block_number`
+    ///
+    /// Uses the state after the parent block, or after the latest block if
+    /// the parent is not known yet. Returns the number of the block whose
+    /// state was used.
+    fn simulate(
+        &self,
+        txs: &[Recovered<TransactionSigned>],
+        reverting_tx_hashes: &[B256],
+        block_number: u64,
+        timestamp: Option<u64>,
+    ) -> RpcResult<(u64, BundleSimulation)> {
+        let parent_number = block_number
+            .checked_sub(1)
+            .ok_or_else(|| invalid_params("cannot simulate the genesis block"))?;
+        let latest = self.client.best_block_number().map_err(internal_error)?;
+
+        let (parent, state) = if parent_number < latest {
+            let parent = self
+                .client
+                .header_by_number(parent_number)
+                .map_err(internal_error)?
+                .ok_or_else(|| internal_error(format!("header {parent_number} not found")))?;
+            (parent, self.client.history_by_block_number(parent_number).map_err(internal_error)?)
+        } else {
+            let mut parent = self
+                .client
+                .latest_header()
+                .map_err(internal_error)?
+                .ok_or_else(|| internal_error("latest header not found"))?
+                .unseal();
+            // Blocks between latest and the target are assumed empty
+            parent.number = parent_number;
+            (parent, self.client.latest().map_err(internal_error)?)
+        };
+        let mut db = State::builder().with_database(StateProviderDatabase::new(&state)).build();
+
+        let next_block = NextBlockEnvAttributes {
+            timestamp: timestamp.unwrap_or_default().max(parent.timestamp + 1),
+            suggested_fee_recipient: parent.beneficiary,
+            prev_randao: parent.mix_hash,
+            gas_limit: parent.gas_limit,
+            parent_beacon_block_root: parent.parent_beacon_block_root,
+            withdrawals: None,
+        };
+        let evm_env = self.evm_config.next_evm_env(&parent, &next_block).map_err(internal_error)?;
+        let mut evm = self.evm_config.evm_with_env(&mut db, evm_env);
+
+        let simulation =
+            simulate_bundle(&mut evm, txs, reverting_tx_hashes).map_err(invalid_params)?;
+        Ok((parent_number.min(latest), simulation))
+    }
+}
+
+#[async_trait]
+impl<Client, EvmConfig> EthBundleApiServer for EthBundleApiImpl<Client, EvmConfig>
+where
+    Client: StateProviderFactory
+        + BlockReaderIdExt<Header = Header>
+        + BlockNumReader
+        + Send
+        + Sync
+        + 'static,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + 'static,
+{
+    async fn send_bundle(
+        &self,
+        ext: &Extensions,
+        request: SendBundleRequest,
+    ) -> RpcResult<SendBundleResponse> {
+        let searcher = signer(ext)?;
+        if !self.auction.has_provider() {
+            return Err(internal_error(
+                "bundles need MEVAuctionManager commitments, no provider configured",
+            ));
+        }
+        if request.txs.is_empty() {
+            return Err(invalid_params("bundle must contain at least one transaction"));
+        }
+
+        let target_block = request.block_number.to::<u64>();
+        let latest = self.client.best_block_number().map_err(internal_error)?;
+        if target_block <= latest {
+            return Err(invalid_params(format!(
+                "target block {target_block} is not after latest block {latest}"
+            )));
+        }
+
+        let txs = decode_bundle_transactions(&request.txs).map_err(invalid_params)?;
+        let (_, simulation) = self.simulate(
+            &txs,
+            &request.reverting_tx_hashes,
+            target_block,
+            request.min_timestamp,
+        )?;
+
+        // The bid is taken from the on-chain commitment, which must be the signer's
+        let bundle = BundleSubmission::from_raw(searcher, U256::ZERO, target_block, request.txs)
+            .map_err(invalid_params)?
+            .with_timestamps(request.min_timestamp, request.max_timestamp)
+            .with_reverting_tx_hashes(request.reverting_tx_hashes);
+        let bundle_hash = bundle.bundle_hash;
+
+        self.auction.submit_bundle(bundle).await.map_err(invalid_params)?;
+
+        // A bundle with the same signer and replacementUuid replaces the previous one
+        if let Some(uuid) = request.replacement_uuid {
+            if let Some(previous) = self.replacements.lock().insert((searcher, uuid), bundle_hash) {
+                self.auction.cancel_bundle(previous);
+            }
+        }
+
+        info!(
+            "Bundle accepted: hash={}, target_block={}, txs={}, gas_used={}, coinbase_diff={}",
+            bundle_hash,
+            target_block,
+            txs.len(),
+            simulation.total_gas_used,
+            simulation.coinbase_diff
+        );
+
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    async fn call_bundle(
+        &self,
+        ext: &Extensions,
+        request: CallBundleRequest,
+    ) -> RpcResult<CallBundleResponse> {
+        let searcher = signer(ext)?;
+        if !self.call_limiter.check(searcher, Instant::now()) {
+            return Err(ErrorObjectOwned::owned(
+                RATE_LIMITED_CODE,
+                format!("at most {MAX_CALL_BUNDLES_PER_WINDOW} eth_callBundle per {CALL_BUNDLE_WINDOW:?}"),
+                None::<()>,
+            ));
+        }
+        if request.txs.is_empty() {
+            return Err(invalid_params("bundle must contain at least one transaction"));
+        }
+
+        let txs = decode_bundle_transactions(&request.txs).map_err(invalid_params)?;
+        let tx_hashes: Vec<B256> = txs.iter().map(|tx| *tx.hash()).collect();
+
+        // Reverts are reported per transaction instead of failing the call
+        let (state_block_number, simulation) = self.simulate(
+            &txs,
+            &tx_hashes,
+            request.block_number.to::<u64>(),
+            request.timestamp,
+        )?;
+
+        debug!(
+            "callBundle: block={}, txs={}, gas_used={}",
+            request.block_number,
+            txs.len(),
+            simulation.total_gas_used
+        );
+
+        let results = simulation
+            .results
+            .into_iter()
+            .map(|result| CallBundleTxResult {
+                tx_hash: result.tx_hash,
+                from_address: result.from,
+                to_address: result.to,
+                gas_used: result.gas_used,
+                gas_fees: result.gas_fees,
+                value: result.success.then(|| result.output.clone()),
+                revert: (!result.success).then_some(result.output),
+            })
+            .collect();
+
+        Ok(CallBundleResponse {
+            bundle_hash: BundleSubmission::compute_bundle_hash(&tx_hashes),
+            coinbase_diff: simulation.coinbase_diff,
+            gas_fees: simulation.gas_fees,
+            total_gas_used: simulation.total_gas_used,
+            state_block_number,
+            results,
+        })
+    }
+
+    async fn cancel_bundle(&self, ext: &Extensions, request: CancelBundleRequest) -> RpcResult<bool> {
+        let key = (signer(ext)?, request.replacement_uuid);
+        let Some(bundle_hash) = self.replacements.lock().remove(&key) else {
+            return Ok(false);
+        };
+
+        Ok(self.auction.cancel_bundle(bundle_hash))
+    }
+}
+
+/// Serve the bundle API on its own HTTP endpoint behind [`FlashbotsSignatureLayer`]
+pub async fn serve_bundle_rpc(
+    addr: SocketAddr,
+    methods: impl Into<Methods>,
+) -> std::io::Result<ServerHandle> {
+    let server = Server::builder()
+        .http_only()
+        .max_request_body_size(MAX_BUNDLE_REQUEST_BYTES as u32)
+        .set_http_middleware(ServiceBuilder::new().layer(FlashbotsSignatureLayer))
+        .build(addr)
+        .await?;
+    Ok(server.start(methods))
+}
+
+/// Signer of `body` according to an `X-Flashbots-Signature` header value
+///
+/// The header is `<address>:<signature>`, the signature an EIP-191 personal
+/// signature over the `0x`-prefixed hex of `keccak256(body)`.
+pub fn verify_flashbots_signature(header: &[u8], body: &[u8]) -> Option<Address> {
+    let (address, signature) = std::str::from_utf8(header).ok()?.split_once(':')?;
+    let address: Address = address.trim().parse().ok()?;
+    let signature: Signature = signature.trim().parse().ok()?;
+
+    let message = hex::encode_prefixed(keccak256(body));
+    let recovered = signature.recover_address_from_msg(message).ok()?;
+    (recovered == address).then_some(address)
+}
+
+/// Tower layer checking `X-Flashbots-Signature` on HTTP requests
+///
+/// Requests with a valid signature carry the [`BundleSigner`] extension;
+/// requests with an invalid one are refused with `403`. Unsigned requests
+/// pass through and are refused by the methods that need a signer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlashbotsSignatureLayer;
+
+impl<S> Layer<S> for FlashbotsSignatureLayer {
+    type Service = FlashbotsSignatureService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FlashbotsSignatureService { inner }
+    }
+}
+
+/// Service of [`FlashbotsSignatureLayer`]
+#[derive(Debug, Clone)]
+pub struct FlashbotsSignatureService<S> {
+    /// Wrapped JSON-RPC service
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for FlashbotsSignatureService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse, Error = BoxError> + Clone + Send + 'static,
+    S::Future: Send,
+    B: http_body::Body<Data = alloy_primitives::bytes::Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<HttpResponse, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        // Use the service that was driven to readiness, leave a fresh clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let body = match Limited::new(body, MAX_BUNDLE_REQUEST_BYTES).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(_) => {
+                    return Ok(http_error(StatusCode::PAYLOAD_TOO_LARGE, "request body too large"));
+                }
+            };
+
+            if let Some(header) = parts.headers.get(FLASHBOTS_SIGNATURE_HEADER) {
+                match verify_flashbots_signature(header.as_bytes(), &body) {
+                    Some(signer) => {
+                        parts.extensions.insert(BundleSigner(signer));
+                    }
+                    None => {
+                        return Ok(http_error(
+                            StatusCode::FORBIDDEN,
+                            "invalid X-Flashbots-Signature",
+                        ));
+                    }
+                }
+            }
+
+            inner.call(HttpRequest::from_parts(parts, HttpBody::new(Full::new(body)))).await
+        })
+    }
+}
+
+/// Plain-text HTTP error response
+fn http_error(status: StatusCode, message: &'static str) -> HttpResponse {
+    let mut response = HttpResponse::new(HttpBody::from(message));
+    *response.status_mut() = status;
+    response
+}
+
+/// Verified signer of the request
+fn signer(ext: &Extensions) -> RpcResult<Address> {
+    ext.get::<BundleSigner>().map(|signer| signer.0).ok_or_else(|| {
+        ErrorObjectOwned::owned(
+            UNAUTHORIZED_CODE,
+            "missing X-Flashbots-Signature header",
+            None::<()>,
+        )
+    })
+}
+
+/// Invalid params RPC error
+fn invalid_params(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>)
+}
+
+/// Internal RPC error
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn test_send_bundle_request_flashbots_format() {
+        let request: SendBundleRequest = serde_json::from_value(serde_json::json!({
+            "txs": ["0x02f8"],
+            "blockNumber": "0x64",
+            "minTimestamp": 1700000000,
+            "revertingTxHashes": [
+                "0x1111111111111111111111111111111111111111111111111111111111111111"
+            ],
+            "replacementUuid": "4a1d-uuid"
+        }))
+        .unwrap();
+
+        assert_eq!(request.block_number.to::<u64>(), 100);
+        assert_eq!(request.min_timestamp, Some(1_700_000_000));
+        assert_eq!(request.max_timestamp, None);
+        assert_eq!(
+            request.reverting_tx_hashes,
+            vec![b256!("0x1111111111111111111111111111111111111111111111111111111111111111")]
+        );
+        assert_eq!(request.replacement_uuid.as_deref(), Some("4a1d-uuid"));
+    }
+
+    #[test]
+    fn test_flashbots_signature() {
+        use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+        let signer = PrivateKeySigner::random();
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"eth_sendBundle","params":[]}"#;
+        let signature =
+            signer.sign_message_sync(hex::encode_prefixed(keccak256(body)).as_bytes()).unwrap();
+        let header = format!("{}:{}", signer.address(), signature);
+
+        assert_eq!(verify_flashbots_signature(header.as_bytes(), body), Some(signer.address()));
+        // Another body, or another claimed address, does not verify
+        assert_eq!(verify_flashbots_signature(header.as_bytes(), b"{}"), None);
+        let forged = format!("{}:{}", Address::repeat_byte(1), signature);
+        assert_eq!(verify_flashbots_signature(forged.as_bytes(), body), None);
+    }
+
+    #[test]
+    fn test_call_bundle_rate_limit_per_signer() {
+        let limiter = SignerRateLimiter::new(2, CALL_BUNDLE_WINDOW);
+        let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let now = Instant::now();
+
+        assert!(limiter.check(alice, now));
+        assert!(limiter.check(alice, now));
+        assert!(!limiter.check(alice, now));
+        // Limits are per signer, and reset with the window
+        assert!(limiter.check(bob, now));
+        assert!(limiter.check(alice, now + CALL_BUNDLE_WINDOW));
+    }
+
+    #[test]
+    fn test_call_bundle_response_serialization() {
+        let response = CallBundleResponse {
+            bundle_hash: B256::ZERO,
+            coinbase_diff: U256::from(42),
+            gas_fees: U256::from(42),
+            total_gas_used: 21_000,
+            state_block_number: 7,
+            results: vec![CallBundleTxResult {
+                tx_hash: B256::ZERO,
+                from_address: Address::ZERO,
+                to_address: None,
+                gas_used: 21_000,
+                gas_fees: U256::from(42),
+                value: Some(Bytes::new()),
+                revert: None,
+            }],
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["coinbaseDiff"], "0x2a");
+        assert_eq!(json["stateBlockNumber"], 7);
+        assert!(json["results"][0].get("revert").is_none());
+    }
+}
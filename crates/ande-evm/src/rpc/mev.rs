@@ -0,0 +1,149 @@
+//! MEV History RPC
+//!
+//! Read-only `mev_*` namespace over the persistent [`MevEventStore`]:
+//!
+//! - `mev_getEvents`: events over a block range, filtered by type, source,
+//!   searcher or affected address, at most [`MAX_QUERY_LIMIT`] per call
+//! - `mev_getEpochTotals`: extracted value per epoch next to what
+//!   MEVDistributor recorded for the same epoch
+
+use crate::mev::{
+    EpochMevTotals, MevDistributorClient, MevEventFilter, MevEventRecord, MevEventStore, MAX_QUERY_LIMIT,
+};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObjectOwned,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Maximum number of epochs per `mev_getEpochTotals` call
+pub const MAX_EPOCH_RANGE: u64 = 1_000;
+
+/// Epoch totals with the distributor's view of the same epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochMevReport {
+    /// Value extracted according to the local event store
+    #[serde(flatten)]
+    pub extracted: EpochMevTotals,
+    /// `totalMEV` recorded by MEVDistributor, if it could be queried
+    pub distributor_total_mev: Option<U256>,
+    /// Whether MEVDistributor has settled the epoch
+    pub distributor_settled: Option<bool>,
+}
+
+/// MEV history RPC API
+#[rpc(server, namespace = "mev")]
+pub trait MevHistoryApi {
+    /// Stored MEV events matching `filter`
+    #[method(name = "getEvents")]
+    async fn get_events(&self, filter: MevEventFilter) -> RpcResult<Vec<MevEventRecord>>;
+
+    /// Extracted value per epoch in `from_epoch..=to_epoch`
+    #[method(name = "getEpochTotals")]
+    async fn get_epoch_totals(&self, from_epoch: u64, to_epoch: u64) -> RpcResult<Vec<EpochMevReport>>;
+}
+
+/// Implementation of [`MevHistoryApiServer`]
+#[derive(Debug)]
+pub struct MevHistoryApiImpl {
+    /// Event store
+    store: Arc<MevEventStore>,
+    /// Distributor used to cross-check epoch totals
+    distributor: Option<Arc<MevDistributorClient>>,
+}
+
+impl MevHistoryApiImpl {
+    /// Create the API over `store`
+    pub fn new(store: Arc<MevEventStore>, distributor: Option<Arc<MevDistributorClient>>) -> Self {
+        Self { store, distributor }
+    }
+}
+
+#[async_trait]
+impl MevHistoryApiServer for MevHistoryApiImpl {
+    async fn get_events(&self, filter: MevEventFilter) -> RpcResult<Vec<MevEventRecord>> {
+        // Segment reads are blocking file I/O
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || store.query(&filter))
+            .await
+            .map_err(|e| ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))
+    }
+
+    async fn get_epoch_totals(&self, from_epoch: u64, to_epoch: u64) -> RpcResult<Vec<EpochMevReport>> {
+        if to_epoch < from_epoch || to_epoch - from_epoch >= MAX_EPOCH_RANGE {
+            return Err(ErrorObjectOwned::owned(
+                INVALID_PARAMS_CODE,
+                format!("epoch range must be ascending and span at most {MAX_EPOCH_RANGE} epochs"),
+                None::<()>,
+            ));
+        }
+
+        let mut reports = Vec::new();
+        for extracted in self.store.epoch_totals(from_epoch, to_epoch) {
+            let onchain = match self.distributor.as_ref().filter(|d| d.has_provider()) {
+                Some(distributor) => match distributor.get_epoch_info(extracted.epoch).await {
+                    Ok(data) => Some(data),
+                    Err(e) => {
+                        warn!(epoch = extracted.epoch, "Failed to query distributor epoch: {}", e);
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            reports.push(EpochMevReport {
+                distributor_total_mev: onchain.as_ref().map(|data| data.total_mev),
+                distributor_settled: onchain.as_ref().map(|data| data.settled),
+                extracted,
+            });
+        }
+
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mev::{MevEventSource, MevType, MEV_EVENT_STORE_DIR};
+    use alloy_primitives::{Address, B256};
+
+    #[tokio::test]
+    async fn test_epoch_totals_without_distributor() {
+        let dir = std::env::temp_dir().join(format!("ande-mev-rpc-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = Arc::new(MevEventStore::open(dir.join(MEV_EVENT_STORE_DIR)).unwrap());
+        store
+            .append(vec![MevEventRecord {
+                block_number: 5,
+                timestamp: 10,
+                epoch: Some(3),
+                tx_hash: B256::ZERO,
+                mev_type: MevType::Sandwich,
+                source: MevEventSource::Detector,
+                value: U256::from(7),
+                searcher: Some(Address::repeat_byte(1)),
+                addresses: Vec::new(),
+                metadata: Default::default(),
+            }])
+            .unwrap();
+
+        let api = MevHistoryApiImpl::new(store, None);
+        let reports = api.get_epoch_totals(0, 10).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].extracted.total_value, U256::from(7));
+        assert_eq!(reports[0].distributor_total_mev, None);
+
+        let json = serde_json::to_value(&reports[0]).unwrap();
+        assert_eq!(json["epoch"], 3);
+        assert!(api.get_epoch_totals(10, 0).await.is_err());
+        assert!(api.get_epoch_totals(0, MAX_EPOCH_RANGE).await.is_err());
+    }
+}
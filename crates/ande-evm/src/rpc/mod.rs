@@ -1,4 +1,15 @@
 /// Evolve RPC modules
 pub mod txpool;
 
-pub use txpool::{create_evolve_txpool_module, EvolveTxpoolApiImpl};
+/// MEV bundle RPC (`eth_sendBundle`, `eth_callBundle`, `eth_cancelBundle`)
+pub mod bundle;
+
+/// MEV history RPC (`mev_getEvents`, `mev_getEpochTotals`)
+pub mod mev;
+
+pub use bundle::{
+    serve_bundle_rpc, verify_flashbots_signature, BundleSigner, EthBundleApiImpl,
+    EthBundleApiServer, FlashbotsSignatureLayer,
+};
+pub use mev::{EpochMevReport, MevHistoryApiImpl, MevHistoryApiServer};
+pub use txpool::{create_evolve_txpool_module, EvolveTxpoolApiImpl, EvolveTxpoolApiServer};
@@ -1,10 +1,23 @@
 use crate::config::current_block_gas_limit;
-use alloy_primitives::Bytes;
+use crate::fair_ordering::{
+    order_by_arrival, DecryptionShare, EncryptedMempool, EncryptedTx, SealedBatchInfo,
+    ThresholdEncryptionConfig, TxOrdering,
+};
+use alloy_consensus::transaction::SignerRecoverable;
+use alloy_consensus::Transaction;
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{Bytes, Signature, TxHash, B256};
 use async_trait::async_trait;
-use jsonrpsee::tracing::debug;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned};
 use jsonrpsee_core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
-use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_primitives::TransactionSigned;
+use reth_transaction_pool::{
+    PoolTransaction, TransactionOrigin, TransactionPool, ValidPoolTransaction,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, warn};
 
 /// Evolve txpool RPC API trait
 #[rpc(server, namespace = "txpoolExt")]
@@ -12,6 +25,33 @@ pub trait EvolveTxpoolApi {
     /// Get transactions from the pool up to the configured `max_bytes` limit
     #[method(name = "getTxs")]
     async fn get_txs(&self) -> RpcResult<Vec<Bytes>>;
+
+    /// Committee key that encrypted transactions must use (commit-reveal only)
+    #[method(name = "encryptionKey")]
+    async fn encryption_key(&self) -> RpcResult<ThresholdEncryptionConfig>;
+
+    /// Submit a threshold-encrypted transaction (commit-reveal only)
+    ///
+    /// `signature` is the submitter's EIP-191 signature over the envelope id.
+    #[method(name = "submitEncryptedTransaction")]
+    async fn submit_encrypted_transaction(
+        &self,
+        envelope: EncryptedTx,
+        signature: Bytes,
+    ) -> RpcResult<B256>;
+
+    /// Sealed batches waiting for decryption shares (commit-reveal only)
+    #[method(name = "getSealedBatches")]
+    async fn get_sealed_batches(&self) -> RpcResult<Vec<SealedBatchInfo>>;
+
+    /// Submit a keyholder's proven decryption shares for a sealed batch,
+    /// one per envelope (commit-reveal only)
+    #[method(name = "submitDecryptionShares")]
+    async fn submit_decryption_shares(
+        &self,
+        batch_id: u64,
+        shares: Vec<DecryptionShare>,
+    ) -> RpcResult<()>;
 }
 
 /// Implementation of the Evolve txpool RPC API
@@ -21,12 +61,32 @@ pub struct EvolveTxpoolApiImpl<Pool> {
     pool: Pool,
     /// Maximum bytes allowed for transaction selection
     max_bytes: u64,
+    /// Ordering policy for selection
+    ordering: TxOrdering,
 }
 
 impl<Pool> EvolveTxpoolApiImpl<Pool> {
     /// Creates a new instance of `TxpoolApi`.
     pub const fn new(pool: Pool, max_bytes: u64) -> Self {
-        Self { pool, max_bytes }
+        Self { pool, max_bytes, ordering: TxOrdering::Priority }
+    }
+
+    /// Use `ordering` instead of priority-fee ordering
+    pub fn with_ordering(mut self, ordering: TxOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Encrypted mempool, if the commit-reveal ordering is active
+    fn encrypted_mempool(&self) -> RpcResult<&Arc<EncryptedMempool>> {
+        match &self.ordering {
+            TxOrdering::CommitReveal(mempool) => Ok(mempool),
+            other => Err(ErrorObjectOwned::owned(
+                INVALID_PARAMS_CODE,
+                format!("encrypted transactions are disabled ({:?} ordering)", other.mode()),
+                None::<()>,
+            )),
+        }
     }
 }
 
@@ -38,66 +98,245 @@ pub const fn create_evolve_txpool_module<Pool>(
 where
     Pool: TransactionPool + Send + Sync + 'static,
 {
-    EvolveTxpoolApiImpl { pool, max_bytes }
+    EvolveTxpoolApiImpl { pool, max_bytes, ordering: TxOrdering::Priority }
+}
+
+/// Running byte and gas totals of a selection
+#[derive(Debug)]
+struct Selection {
+    /// Byte cap, 0 for none
+    max_bytes: u64,
+    /// Gas cap, 0 for none
+    gas_cap: u64,
+    /// Bytes selected so far
+    total_bytes: u64,
+    /// Gas selected so far
+    total_gas: u64,
+    /// Selected raw transactions
+    txs: Vec<Bytes>,
+}
+
+impl Selection {
+    const fn new(max_bytes: u64, gas_cap: u64) -> Self {
+        Self { max_bytes, gas_cap, total_bytes: 0, total_gas: 0, txs: Vec::new() }
+    }
+
+    /// Give back space reserved for a transaction that was not selected
+    fn release(&mut self, sz: u64, gas: u64) {
+        self.total_bytes -= sz;
+        self.total_gas -= gas;
+    }
+
+    /// Account for a transaction if it fits under both caps
+    fn reserve(&mut self, sz: u64, gas: u64) -> bool {
+        // Enforce byte cap if configured (> 0)
+        if self.max_bytes > 0 && self.total_bytes + sz > self.max_bytes {
+            return false;
+        }
+        // Enforce gas cap if configured (> 0)
+        if self.gas_cap > 0 && self.total_gas + gas > self.gas_cap {
+            return false;
+        }
+
+        self.total_bytes += sz;
+        self.total_gas += gas;
+        true
+    }
+
+    /// Add a pool transaction, returning `false` once a cap is hit
+    fn push_pool_tx<T: PoolTransaction>(&mut self, pool_tx: &ValidPoolTransaction<T>) -> bool {
+        // Size and gas of this tx
+        let sz = pool_tx.encoded_length() as u64;
+        let gas = pool_tx.gas_limit();
+        if !self.reserve(sz, gas) {
+            return false;
+        }
+
+        // Convert for encoding
+        let tx = pool_tx.transaction.clone().into_consensus_with2718();
+        self.txs.push(tx.encoded_bytes().clone());
+        true
+    }
+}
+
+impl<Pool> EvolveTxpoolApiImpl<Pool>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Run a revealed transaction through pool validation
+    ///
+    /// Decryption only proves the sender encrypted it; signature, nonce,
+    /// balance and fee checks are the same as for plaintext transactions.
+    /// Returns the hash and gas limit of a valid transaction.
+    async fn validate_revealed(&self, raw: &Bytes) -> Result<(TxHash, u64), String> {
+        let tx = TransactionSigned::decode_2718(&mut raw.as_ref()).map_err(|e| e.to_string())?;
+        let gas_limit = tx.gas_limit();
+        let recovered = tx.try_into_recovered().map_err(|e| e.to_string())?;
+        let pool_tx = Pool::Transaction::try_from_consensus(recovered)
+            .map_err(|_| "not a pool transaction".to_string())?;
+        let hash = *pool_tx.hash();
+
+        self.pool
+            .add_transaction(TransactionOrigin::External, pool_tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok((hash, gas_limit))
+    }
 }
 
 #[async_trait]
 impl<Pool> EvolveTxpoolApiServer for EvolveTxpoolApiImpl<Pool>
 where
-    Pool: TransactionPool + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
 {
     /// Returns a Geth-style `TxpoolContent` with raw RLP hex strings.
     async fn get_txs(&self) -> RpcResult<Vec<Bytes>> {
-        //------------------------------------------------------------------//
-        // 1. Iterate best txs (sorted by priority) and stop once we hit    //
-        //    the byte cap                                                   //
-        //------------------------------------------------------------------//
-        let mut total_bytes = 0u64;
-        let mut total_gas = 0u64;
-        let mut selected_txs: Vec<Bytes> = Vec::new();
-
         // Determine the active gas cap for selection
-        let gas_cap = current_block_gas_limit();
+        let mut selection = Selection::new(self.max_bytes, current_block_gas_limit());
 
-        // Use best_transactions() which returns an iterator of transactions
-        // ordered by their priority (gas price/priority fee)
-        for best_tx in self.pool.best_transactions() {
-            // Convert for gas introspection and encoding
-            let tx = best_tx.transaction.clone().into_consensus_with2718();
-
-            // Size and gas of this tx
-            let sz = best_tx.encoded_length() as u64;
-            let gas = best_tx.gas_limit();
-
-            // Enforce byte cap if configured (> 0)
-            if self.max_bytes > 0 && total_bytes + sz > self.max_bytes {
-                break;
+        match &self.ordering {
+            TxOrdering::Priority => {
+                //----------------------------------------------------------//
+                // Iterate best txs (sorted by priority) and stop once we   //
+                // hit the byte cap                                         //
+                //----------------------------------------------------------//
+                for best_tx in self.pool.best_transactions() {
+                    if !selection.push_pool_tx(&best_tx) {
+                        break;
+                    }
+                }
             }
-            // Enforce gas cap if configured (> 0)
-            if gas_cap > 0 && total_gas + gas > gas_cap {
-                break;
+            TxOrdering::Fcfs => {
+                for pool_tx in order_by_arrival(self.pool.pending_transactions()) {
+                    if !selection.push_pool_tx(&pool_tx) {
+                        break;
+                    }
+                }
             }
+            TxOrdering::CommitReveal(mempool) => {
+                //----------------------------------------------------------//
+                // 1. Fix the order of everything encrypted so far          //
+                // 2. Fail batches stuck below the threshold for too long   //
+                //    and reveal those that reached it, in order            //
+                // 3. Validate revealed txs like any pool tx; only those    //
+                //    that are executable now keep their sealed position    //
+                // 4. Plaintext txs follow by arrival, so none of them can  //
+                //    be placed ahead of a transaction it has not seen      //
+                //----------------------------------------------------------//
+                mempool.seal();
+                let failed = mempool.expire_stuck();
+                if !failed.is_empty() {
+                    warn!(envelopes = ?failed, "Encrypted transactions failed: batch expired without decryption");
+                }
+                mempool.reveal();
+
+                let revealed = mempool.take_revealed(|raw| {
+                    match TransactionSigned::decode_2718(&mut raw.as_ref()) {
+                        Ok(tx) => selection.reserve(raw.len() as u64, tx.gas_limit()),
+                        // Consumed and dropped below
+                        Err(_) => true,
+                    }
+                });
 
-            let bz = tx.encoded_bytes();
-            selected_txs.push(bz.clone());
+                let mut validated = Vec::with_capacity(revealed.len());
+                for raw in revealed {
+                    match self.validate_revealed(&raw).await {
+                        Ok((hash, gas_limit)) => validated.push((hash, gas_limit, raw)),
+                        Err(e) => {
+                            debug!("Dropping revealed transaction: {}", e);
+                            if let Ok(tx) = TransactionSigned::decode_2718(&mut raw.as_ref()) {
+                                selection.release(raw.len() as u64, tx.gas_limit());
+                            }
+                        }
+                    }
+                }
 
-            total_bytes += sz;
-            total_gas += gas;
+                // Queued revealed txs (nonce gaps) stay in the pool for later blocks
+                let pending = self.pool.pending_transactions();
+                let executable: HashSet<TxHash> = pending.iter().map(|tx| *tx.hash()).collect();
+                let mut included = HashSet::new();
+                for (hash, gas_limit, raw) in validated {
+                    if executable.contains(&hash) {
+                        included.insert(hash);
+                        selection.txs.push(raw);
+                    } else {
+                        selection.release(raw.len() as u64, gas_limit);
+                    }
+                }
+
+                for pool_tx in order_by_arrival(pending) {
+                    if included.contains(pool_tx.hash()) {
+                        continue;
+                    }
+                    if !selection.push_pool_tx(&pool_tx) {
+                        break;
+                    }
+                }
+            }
         }
 
         debug!(
-            "get_txs returning {} transactions ({} bytes, {} gas)",
-            selected_txs.len(),
-            total_bytes,
-            total_gas
+            "get_txs returning {} transactions ({} bytes, {} gas, {:?} ordering)",
+            selection.txs.len(),
+            selection.total_bytes,
+            selection.total_gas,
+            self.ordering.mode()
         );
-        Ok(selected_txs)
+        Ok(selection.txs)
+    }
+
+    async fn encryption_key(&self) -> RpcResult<ThresholdEncryptionConfig> {
+        let public_key = self.encrypted_mempool()?.public_key();
+        Ok(ThresholdEncryptionConfig {
+            threshold: public_key.threshold,
+            public_key: public_key.to_bytes(),
+            verification_keys: public_key.verification_keys(),
+        })
+    }
+
+    async fn submit_encrypted_transaction(
+        &self,
+        envelope: EncryptedTx,
+        signature: Bytes,
+    ) -> RpcResult<B256> {
+        let signature = Signature::try_from(signature.as_ref()).map_err(|e| {
+            ErrorObjectOwned::owned(INVALID_PARAMS_CODE, e.to_string(), None::<()>)
+        })?;
+        self.encrypted_mempool()?.submit(envelope, &signature).map_err(|e| {
+            ErrorObjectOwned::owned(INVALID_PARAMS_CODE, e.to_string(), None::<()>)
+        })
+    }
+
+    async fn get_sealed_batches(&self) -> RpcResult<Vec<SealedBatchInfo>> {
+        Ok(self.encrypted_mempool()?.sealed_batches())
+    }
+
+    async fn submit_decryption_shares(
+        &self,
+        batch_id: u64,
+        shares: Vec<DecryptionShare>,
+    ) -> RpcResult<()> {
+        self.encrypted_mempool()?.add_shares(batch_id, shares).map_err(|e| {
+            ErrorObjectOwned::owned(INVALID_PARAMS_CODE, e.to_string(), None::<()>)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::config::{EvolveConfig, DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS};
+    use crate::fair_ordering::deal_committee;
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+    use alloy_primitives::Address;
+    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction, TestPool};
+    use reth_transaction_pool::TransactionOrigin;
 
     #[test]
     fn test_default_config_value() {
@@ -127,4 +366,86 @@ mod tests {
         assert_eq!(custom_config.max_txpool_bytes, 1000);
         assert_eq!(custom_config.max_txpool_gas, 1_000_000);
     }
+
+    /// EIP-1559 mock transaction with the given tip
+    fn mock_tx(sender: Address, nonce: u64, tip: u128) -> MockTransaction {
+        MockTransaction::eip1559()
+            .with_sender(sender)
+            .with_nonce(nonce)
+            .with_gas_limit(100_000)
+            .with_max_fee(1_000_000_000)
+            .with_priority_fee(tip)
+    }
+
+    /// Raw bytes `get_txs` returns for a mock transaction
+    fn raw(tx: &MockTransaction) -> Bytes {
+        tx.clone().into_consensus_with2718().encoded_bytes().clone()
+    }
+
+    /// Victim swaps with a low tip; the attacker, having seen it, bids a
+    /// high tip to run first and a zero tip to run right after.
+    async fn sandwich_pool() -> (TestPool, [MockTransaction; 3]) {
+        let pool = testing_pool();
+        let (victim, attacker) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+
+        let victim_swap = mock_tx(victim, 0, 1_000);
+        let front_run = mock_tx(attacker, 0, 1_000_000);
+        let back_run = mock_tx(attacker, 1, 0);
+
+        for tx in [&victim_swap, &front_run, &back_run] {
+            pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        }
+        (pool, [victim_swap, front_run, back_run])
+    }
+
+    #[tokio::test]
+    async fn test_priority_ordering_allows_sandwich() {
+        let (pool, [victim, front, back]) = sandwich_pool().await;
+        let api = EvolveTxpoolApiImpl::new(pool, DEFAULT_MAX_TXPOOL_BYTES);
+
+        let txs = api.get_txs().await.unwrap();
+        assert_eq!(txs, vec![raw(&front), raw(&victim), raw(&back)]);
+    }
+
+    #[tokio::test]
+    async fn test_fcfs_ordering_prevents_sandwich() {
+        let (pool, [victim, front, back]) = sandwich_pool().await;
+        let api = EvolveTxpoolApiImpl::new(pool, DEFAULT_MAX_TXPOOL_BYTES)
+            .with_ordering(TxOrdering::Fcfs);
+
+        // The tip cannot buy a place ahead of the victim
+        let txs = api.get_txs().await.unwrap();
+        assert_eq!(txs, vec![raw(&victim), raw(&front), raw(&back)]);
+    }
+
+    #[tokio::test]
+    async fn test_commit_reveal_prevents_sandwich() {
+        let (public_key, shares) = deal_committee(B256::repeat_byte(5), 2, 3);
+        let mempool = Arc::new(EncryptedMempool::new(public_key.clone(), shares[..2].to_vec()));
+        let pool = testing_pool();
+        let api = EvolveTxpoolApiImpl::new(pool.clone(), DEFAULT_MAX_TXPOOL_BYTES)
+            .with_ordering(TxOrdering::CommitReveal(mempool.clone()));
+
+        // The victim only publishes a ciphertext
+        let victim = mock_tx(Address::repeat_byte(0x11), 0, 1_000);
+        let envelope = public_key.encrypt(&raw(&victim));
+        let signature = PrivateKeySigner::random().sign_message_sync(envelope.id().as_slice()).unwrap();
+        api.submit_encrypted_transaction(envelope.clone(), signature.as_bytes().into()).await.unwrap();
+        assert_ne!(envelope.ciphertext, raw(&victim));
+
+        // A blind high-tip front-run still lands after the sealed batch
+        let front = mock_tx(Address::repeat_byte(0x22), 0, 1_000_000);
+        pool.add_transaction(TransactionOrigin::External, front.clone()).await.unwrap();
+
+        let txs = api.get_txs().await.unwrap();
+        assert_eq!(txs, vec![raw(&victim), raw(&front)]);
+        assert!(mempool.sealed_batches().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_methods_disabled_without_commit_reveal() {
+        let api = EvolveTxpoolApiImpl::new(testing_pool(), DEFAULT_MAX_TXPOOL_BYTES);
+        assert!(api.get_sealed_batches().await.is_err());
+        assert!(api.encryption_key().await.is_err());
+    }
 }
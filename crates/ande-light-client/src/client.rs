@@ -0,0 +1,163 @@
+//! Light client following the validator set across epochs
+//!
+//! The client keeps the set of the latest epoch it trusts, open-ended, and
+//! the set it handed over from, closed at the block before the latest one
+//! starts, so blocks at the end of the previous epoch still verify:
+//!
+//! ```text
+//! previous: epoch E   [start E, start E+1 - 1]
+//! trusted:  epoch E+1 [start E+1, ...)
+//! ```
+
+use crate::{
+    error::LightClientError,
+    proof::{FinalityProof, FinalizedBlock, ValidatorSetChangeProof},
+    validator_set::ValidatorSet,
+};
+use alloy_primitives::B256;
+
+/// Trusted validator set of one chain, advanced by change proofs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClient {
+    /// Chain proofs are verified for
+    chain_id: u64,
+
+    /// Validator set of the current epoch
+    trusted: ValidatorSet,
+
+    /// Hash of `trusted`
+    trusted_hash: B256,
+
+    /// Validator set of the epoch before, closed where `trusted` starts
+    previous: Option<ValidatorSet>,
+}
+
+impl LightClient {
+    /// Start from a validator set obtained out of band
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `trusted` is not canonical
+    pub fn new(chain_id: u64, mut trusted: ValidatorSet) -> Result<Self, LightClientError> {
+        trusted.validate()?;
+        trusted.end_block = None;
+        let trusted_hash = trusted.hash();
+        Ok(Self { chain_id, trusted, trusted_hash, previous: None })
+    }
+
+    /// Chain proofs are verified for
+    pub const fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Epoch of the trusted validator set
+    pub const fn epoch(&self) -> u64 {
+        self.trusted.epoch
+    }
+
+    /// Trusted validator set
+    pub const fn validator_set(&self) -> &ValidatorSet {
+        &self.trusted
+    }
+
+    /// Hash of the trusted validator set
+    pub const fn validator_set_hash(&self) -> B256 {
+        self.trusted_hash
+    }
+
+    /// Validator set of the epoch before the trusted one, if the client
+    /// handed over from it
+    pub const fn previous_validator_set(&self) -> Option<&ValidatorSet> {
+        self.previous.as_ref()
+    }
+
+    /// Verify that a block of the trusted or the previous epoch is final
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the proof does not verify against the set of the
+    /// epoch it names
+    pub fn verify_finality(&self, proof: &FinalityProof) -> Result<FinalizedBlock, LightClientError> {
+        let set = match &self.previous {
+            Some(previous) if previous.epoch == proof.validator_set.epoch => previous,
+            _ => &self.trusted,
+        };
+        proof.verify(self.chain_id, set)
+    }
+
+    /// Move to the next epoch's validator set
+    ///
+    /// The trusted set is closed at the block before the new one starts
+    /// and kept as the previous set. Nothing changes if the proof does not
+    /// verify.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the proof is not a handover from the trusted set
+    pub fn apply_change(&mut self, proof: &ValidatorSetChangeProof) -> Result<(), LightClientError> {
+        proof.verify(self.chain_id, &self.trusted)?;
+
+        // The proof only verifies for a set starting after the trusted one
+        let mut previous = self.trusted.clone();
+        previous.close(proof.new_set.start_block - 1)?;
+
+        self.trusted = ValidatorSet { end_block: None, ..proof.new_set.clone() };
+        self.trusted_hash = self.trusted.hash();
+        self.previous = Some(previous);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        digest::validator_set_digest,
+        proof::tests::{finality_proof, keys, set_of, sign, CHAIN_ID},
+    };
+
+    #[test]
+    fn test_follows_validator_set_across_epochs() {
+        let keys = keys(6);
+        let genesis = set_of(1, &keys[..3], &[10, 10, 10]);
+        let mut client = LightClient::new(CHAIN_ID, genesis.clone()).unwrap();
+        assert!(client.verify_finality(&finality_proof(10, &genesis, &keys[..3])).is_ok());
+
+        // Epoch 2 is run by entirely new validators
+        let next = set_of(2, &keys[3..], &[5, 5, 5]);
+        let digest = validator_set_digest(CHAIN_ID, 2, next.hash());
+        client
+            .apply_change(&ValidatorSetChangeProof { new_set: next.clone(), signatures: sign(&keys[..3], digest) })
+            .unwrap();
+        assert_eq!((client.epoch(), client.validator_set_hash()), (2, next.hash()));
+
+        // Only the new validators finalize blocks from now on
+        assert!(client.verify_finality(&finality_proof(20, &next, &keys[3..])).is_ok());
+        assert!(client.verify_finality(&finality_proof(20, &genesis, &keys[..3])).is_err());
+
+        // The old validators still prove blocks of their own epoch, up to its end
+        assert_eq!(client.previous_validator_set().and_then(|set| set.end_block), Some(9));
+        assert!(client.verify_finality(&finality_proof(9, &genesis, &keys[..3])).is_ok());
+        assert!(matches!(
+            client.verify_finality(&finality_proof(10, &genesis, &keys[..3])),
+            Err(LightClientError::BlockOutsideEpoch { block_number: 10, epoch: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_rejected_change_keeps_trusted_set() {
+        let keys = keys(4);
+        let genesis = set_of(1, &keys[..3], &[10, 10, 10]);
+        let mut client = LightClient::new(CHAIN_ID, genesis.clone()).unwrap();
+
+        // Two of three validators are 20 of 30, short of the threshold of 21
+        let next = set_of(2, &keys[3..], &[100]);
+        let digest = validator_set_digest(CHAIN_ID, 2, next.hash());
+        let proof = ValidatorSetChangeProof { new_set: next, signatures: sign(&keys[..2], digest) };
+        assert_eq!(
+            client.apply_change(&proof),
+            Err(LightClientError::InsufficientVotingPower { have: 20, need: 21 })
+        );
+        assert_eq!(client.validator_set(), &genesis);
+    }
+}
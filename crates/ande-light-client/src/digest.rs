@@ -0,0 +1,62 @@
+//! Messages signed by validators
+//!
+//! ```text
+//! attestation:   keccak256("ANDE_ATTESTATION_V2" ‖ uint64 chainId ‖ uint64 epoch ‖ uint64 number ‖ bytes32 blockHash)
+//! validator set: keccak256("ANDE_VALIDATOR_SET_V1" ‖ uint64 chainId ‖ uint64 epoch ‖ bytes32 setHash)
+//! ```
+//!
+//! Attestations commit to the epoch of the set that casts them, so votes of
+//! validators that serve in several epochs only count for their own one.
+//!
+//! Signatures are 65-byte `r ‖ s ‖ v` over the digest itself, without an
+//! EIP-191 prefix.
+
+use crate::error::LightClientError;
+use alloc::vec::Vec;
+use alloy_primitives::{keccak256, Address, Signature, B256};
+
+/// Domain separator of block attestations
+const ATTESTATION_DOMAIN: &[u8] = b"ANDE_ATTESTATION_V2";
+
+/// Domain separator of validator set handovers
+const VALIDATOR_SET_DOMAIN: &[u8] = b"ANDE_VALIDATOR_SET_V1";
+
+/// Digest the validators of `epoch` sign to attest a block
+pub fn attestation_digest(chain_id: u64, epoch: u64, block_number: u64, block_hash: B256) -> B256 {
+    let mut preimage = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 56);
+    preimage.extend_from_slice(ATTESTATION_DOMAIN);
+    preimage.extend_from_slice(&chain_id.to_be_bytes());
+    preimage.extend_from_slice(&epoch.to_be_bytes());
+    preimage.extend_from_slice(&block_number.to_be_bytes());
+    preimage.extend_from_slice(block_hash.as_slice());
+    keccak256(preimage)
+}
+
+/// Digest the validators of `epoch - 1` sign to hand over to the set of
+/// `epoch` with hash `set_hash`
+pub fn validator_set_digest(chain_id: u64, epoch: u64, set_hash: B256) -> B256 {
+    keccak256(validator_set_message(chain_id, epoch, set_hash))
+}
+
+/// Preimage of [`validator_set_digest`], for signers that hash what they sign
+pub fn validator_set_message(chain_id: u64, epoch: u64, set_hash: B256) -> Vec<u8> {
+    let mut message = Vec::with_capacity(VALIDATOR_SET_DOMAIN.len() + 48);
+    message.extend_from_slice(VALIDATOR_SET_DOMAIN);
+    message.extend_from_slice(&chain_id.to_be_bytes());
+    message.extend_from_slice(&epoch.to_be_bytes());
+    message.extend_from_slice(set_hash.as_slice());
+    message
+}
+
+/// Address that signed `digest`
+///
+/// # Errors
+///
+/// Returns [`LightClientError::InvalidSignature`] naming `claimed` if the
+/// signature is malformed or does not recover
+pub fn recover_signer(digest: B256, signature: &[u8], claimed: Address) -> Result<Address, LightClientError> {
+    Signature::try_from(signature)
+        .ok()
+        .and_then(|sig| sig.recover_address_from_prehash(&digest).ok())
+        .ok_or(LightClientError::InvalidSignature { validator: claimed })
+}
@@ -0,0 +1,84 @@
+//! Proof verification errors
+
+use alloy_primitives::{Address, B256};
+use core::fmt;
+
+/// Why a proof was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LightClientError {
+    /// The proof is for a validator set other than the trusted one
+    UntrustedValidatorSet {
+        /// Hash of the trusted set
+        expected: B256,
+        /// Hash of the set in the proof
+        actual: B256,
+    },
+
+    /// A set change does not follow the trusted epoch
+    UnexpectedEpoch {
+        /// Epoch after the trusted one
+        expected: u64,
+        /// Epoch of the proposed set
+        actual: u64,
+    },
+
+    /// The block lies outside the epoch of the validator set
+    BlockOutsideEpoch {
+        /// Block number of the header
+        block_number: u64,
+        /// Epoch of the validator set
+        epoch: u64,
+    },
+
+    /// The validator set is not in canonical form
+    InvalidValidatorSet(&'static str),
+
+    /// The header is not a valid RLP-encoded block header
+    InvalidHeader,
+
+    /// A signature is malformed or not from the validator it claims
+    InvalidSignature {
+        /// Claimed signer
+        validator: Address,
+    },
+
+    /// A signer is not in the validator set
+    UnknownValidator(Address),
+
+    /// A validator signed twice
+    DuplicateSignature(Address),
+
+    /// The signers hold less than 2/3+1 of the voting power
+    InsufficientVotingPower {
+        /// Voting power of the signers
+        have: u64,
+        /// BFT threshold of the set
+        need: u64,
+    },
+}
+
+impl fmt::Display for LightClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UntrustedValidatorSet { expected, actual } => {
+                write!(f, "validator set {actual} is not the trusted set {expected}")
+            }
+            Self::UnexpectedEpoch { expected, actual } => {
+                write!(f, "expected a validator set for epoch {expected}, got epoch {actual}")
+            }
+            Self::BlockOutsideEpoch { block_number, epoch } => {
+                write!(f, "block {block_number} is not in epoch {epoch}")
+            }
+            Self::InvalidValidatorSet(reason) => write!(f, "invalid validator set: {reason}"),
+            Self::InvalidHeader => f.write_str("invalid block header"),
+            Self::InvalidSignature { validator } => write!(f, "invalid signature from {validator}"),
+            Self::UnknownValidator(validator) => write!(f, "{validator} is not in the validator set"),
+            Self::DuplicateSignature(validator) => write!(f, "duplicate signature from {validator}"),
+            Self::InsufficientVotingPower { have, need } => {
+                write!(f, "insufficient voting power: have {have}, need {need}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for LightClientError {}
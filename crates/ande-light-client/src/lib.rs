@@ -0,0 +1,49 @@
+//! # Ande Light Client
+//!
+//! Verifies that an ANDE Chain block is final without running a node.
+//!
+//! A block is final once validators holding 2/3+1 of the voting power of
+//! its epoch's validator set have attested it. A [`FinalityProof`] carries
+//! everything needed to check that: the RLP-encoded header, the validator
+//! set with its voting powers, and the attestation signatures. A light
+//! client only has to trust one validator set; a
+//! [`ValidatorSetChangeProof`] signed by 2/3+1 of that set hands trust
+//! over to the set of the next epoch.
+//!
+//! ```text
+//! trusted set (epoch E) ──┬─ FinalityProof(block in E)
+//!                         │     keccak(header) = block hash
+//!                         │     start(E) ≤ number ≤ end(E)
+//!                         │     Σ power(signers of attestation digest of E) ≥ 2/3+1
+//!                         │     → FinalizedBlock { number, hash, header }
+//!                         │
+//!                         └─ ValidatorSetChangeProof(set E+1)
+//!                               Σ power(signers of validator set digest) ≥ 2/3+1
+//!                               → trusted set (epoch E+1), E closed at start(E+1) - 1
+//! ```
+//!
+//! Digests are `keccak256` of fixed-width big-endian fields, exactly as
+//! validators sign them in `ande-consensus`.
+//!
+//! The crate is `no_std` and only needs `alloc`. The `serde` feature
+//! derives (de)serialization of the proof types, as served over JSON-RPC by
+//! `ande-consensus`.
+
+#![no_std]
+#![warn(missing_docs, unreachable_pub, unused_crate_dependencies)]
+#![deny(unused_must_use, rust_2018_idioms)]
+
+extern crate alloc;
+
+pub mod client;
+pub mod digest;
+pub mod error;
+pub mod proof;
+pub mod validator_set;
+
+pub use alloy_consensus::Header;
+pub use client::LightClient;
+pub use digest::{attestation_digest, recover_signer, validator_set_digest, validator_set_message};
+pub use error::LightClientError;
+pub use proof::{decode_header, FinalityProof, FinalizedBlock, ValidatorSetChangeProof, ValidatorSignature};
+pub use validator_set::ValidatorSet;
@@ -0,0 +1,321 @@
+//! Finality and validator set change proofs
+//!
+//! Both proofs carry the validator set they are checked against. The
+//! verifier compares it with the set it trusts by hash and takes the
+//! epoch's block range from its own copy, since the end of an epoch is not
+//! covered by the hash.
+
+use crate::{
+    digest::{attestation_digest, recover_signer, validator_set_digest},
+    error::LightClientError,
+    validator_set::ValidatorSet,
+};
+use alloc::{collections::BTreeSet, vec::Vec};
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use alloy_rlp::Decodable;
+
+/// Signature of one validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ValidatorSignature {
+    /// Signing validator
+    pub validator: Address,
+
+    /// 65-byte recoverable signature over the signed digest
+    pub signature: Bytes,
+}
+
+/// Proof that a block is final
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FinalityProof {
+    /// RLP-encoded block header; its keccak256 is the block hash
+    pub header: Bytes,
+
+    /// Validator set of the block's epoch
+    pub validator_set: ValidatorSet,
+
+    /// Attestations of the block, sorted by validator address
+    pub signatures: Vec<ValidatorSignature>,
+}
+
+/// A block a [`FinalityProof`] showed to be final
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalizedBlock {
+    /// Block number
+    pub number: u64,
+
+    /// Block hash
+    pub hash: B256,
+
+    /// Decoded header, for state, transaction and receipt roots
+    pub header: Header,
+
+    /// Epoch of the validator set that attested the block
+    pub epoch: u64,
+
+    /// Voting power of the attesting validators
+    pub voting_power: u64,
+}
+
+impl FinalityProof {
+    /// Verify the proof against the trusted validator set
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the set is not the trusted one, the header does not
+    /// decode or lies outside the trusted epoch, a signature is invalid,
+    /// duplicated or from outside the set, or the signers hold less than
+    /// 2/3+1 of the voting power
+    pub fn verify(&self, chain_id: u64, trusted: &ValidatorSet) -> Result<FinalizedBlock, LightClientError> {
+        let (trusted_hash, set_hash) = (trusted.hash(), self.validator_set.hash());
+        if set_hash != trusted_hash {
+            return Err(LightClientError::UntrustedValidatorSet { expected: trusted_hash, actual: set_hash });
+        }
+        trusted.validate()?;
+
+        let header = decode_header(&self.header)?;
+        if !trusted.contains(header.number) {
+            return Err(LightClientError::BlockOutsideEpoch { block_number: header.number, epoch: trusted.epoch });
+        }
+
+        let hash = keccak256(&self.header);
+        let digest = attestation_digest(chain_id, trusted.epoch, header.number, hash);
+        let voting_power = tally(trusted, digest, &self.signatures)?;
+
+        Ok(FinalizedBlock { number: header.number, hash, header, epoch: trusted.epoch, voting_power })
+    }
+}
+
+/// Proof that the validators of an epoch handed over to the next epoch's set
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ValidatorSetChangeProof {
+    /// Validator set of the next epoch
+    pub new_set: ValidatorSet,
+
+    /// Signatures of the previous epoch's validators over the new set,
+    /// sorted by validator address
+    pub signatures: Vec<ValidatorSignature>,
+}
+
+impl ValidatorSetChangeProof {
+    /// Verify the handover from `trusted` and return the signed power
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the new set is not for the epoch after `trusted`,
+    /// does not start after it or is not canonical, or its signatures do
+    /// not hold 2/3+1 of the voting power of `trusted`
+    pub fn verify(&self, chain_id: u64, trusted: &ValidatorSet) -> Result<u64, LightClientError> {
+        let expected = trusted.epoch.saturating_add(1);
+        if self.new_set.epoch != expected {
+            return Err(LightClientError::UnexpectedEpoch { expected, actual: self.new_set.epoch });
+        }
+        self.new_set.validate()?;
+        let follows = match trusted.end_block {
+            Some(end_block) => self.new_set.start_block == end_block.saturating_add(1),
+            None => self.new_set.start_block > trusted.start_block,
+        };
+        if !follows {
+            return Err(LightClientError::InvalidValidatorSet("epoch does not start after the trusted one"));
+        }
+
+        let digest = validator_set_digest(chain_id, self.new_set.epoch, self.new_set.hash());
+        tally(trusted, digest, &self.signatures)
+    }
+}
+
+/// Decode an RLP-encoded block header, rejecting trailing bytes
+///
+/// # Errors
+///
+/// Returns [`LightClientError::InvalidHeader`] if `rlp` is not exactly one
+/// header
+pub fn decode_header(rlp: &[u8]) -> Result<Header, LightClientError> {
+    let mut buf = rlp;
+    let header = Header::decode(&mut buf).map_err(|_| LightClientError::InvalidHeader)?;
+    if !buf.is_empty() {
+        return Err(LightClientError::InvalidHeader);
+    }
+    Ok(header)
+}
+
+/// Voting power of `set` that signed `digest`, at least its BFT threshold
+fn tally(set: &ValidatorSet, digest: B256, signatures: &[ValidatorSignature]) -> Result<u64, LightClientError> {
+    let mut signers = BTreeSet::new();
+    let mut power = 0u64;
+    for entry in signatures {
+        let signer = recover_signer(digest, &entry.signature, entry.validator)?;
+        if signer != entry.validator {
+            return Err(LightClientError::InvalidSignature { validator: entry.validator });
+        }
+        if !signers.insert(signer) {
+            return Err(LightClientError::DuplicateSignature(signer));
+        }
+        power = power.saturating_add(set.power_of(&signer).ok_or(LightClientError::UnknownValidator(signer))?);
+    }
+
+    let need = set.bft_threshold();
+    if power < need {
+        return Err(LightClientError::InsufficientVotingPower { have: power, need });
+    }
+    Ok(power)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_primitives::Signature;
+    use k256::ecdsa::SigningKey;
+
+    pub(crate) const CHAIN_ID: u64 = 6174;
+
+    pub(crate) fn keys(n: u8) -> Vec<SigningKey> {
+        (1..=n).map(|i| SigningKey::from_slice(&[i; 32]).unwrap()).collect()
+    }
+
+    /// Set of `epoch`, which starts at block `10 * (epoch - 1)`
+    pub(crate) fn set_of(epoch: u64, keys: &[SigningKey], powers: &[u64]) -> ValidatorSet {
+        let validators: Vec<Address> = keys.iter().map(Address::from_private_key).collect();
+        ValidatorSet::new(epoch, epoch.saturating_sub(1) * 10, &validators, powers).unwrap()
+    }
+
+    pub(crate) fn sign(keys: &[SigningKey], digest: B256) -> Vec<ValidatorSignature> {
+        let mut signatures: Vec<ValidatorSignature> = keys
+            .iter()
+            .map(|key| {
+                let (sig, recovery) = key.sign_prehash_recoverable(digest.as_slice()).unwrap();
+                let signature = Signature::from_signature_and_parity(sig, recovery.is_y_odd());
+                ValidatorSignature {
+                    validator: Address::from_private_key(key),
+                    signature: Bytes::copy_from_slice(&signature.as_bytes()),
+                }
+            })
+            .collect();
+        signatures.sort_by_key(|entry| entry.validator);
+        signatures
+    }
+
+    pub(crate) fn finality_proof(number: u64, set: &ValidatorSet, signers: &[SigningKey]) -> FinalityProof {
+        let header = Header { number, state_root: B256::repeat_byte(0xaa), ..Default::default() };
+        let header = Bytes::from(alloy_rlp::encode(&header));
+        let digest = attestation_digest(CHAIN_ID, set.epoch, number, keccak256(&header));
+        FinalityProof { header, validator_set: set.clone(), signatures: sign(signers, digest) }
+    }
+
+    #[test]
+    fn test_finality_proof_verifies() {
+        let keys = keys(4);
+        let set = set_of(3, &keys, &[10, 10, 10, 10]);
+
+        let proof = finality_proof(42, &set, &keys[..3]);
+        let block = proof.verify(CHAIN_ID, &set).unwrap();
+        assert_eq!((block.number, block.epoch, block.voting_power), (42, 3, 30));
+        assert_eq!(block.hash, block.header.hash_slow());
+        assert_eq!(block.header.state_root, B256::repeat_byte(0xaa));
+
+        // Signed for another chain
+        assert!(proof.verify(CHAIN_ID + 1, &set).is_err());
+
+        // Another header under the same signatures
+        let mut forged = proof.clone();
+        forged.header = finality_proof(43, &set, &[]).header;
+        assert!(matches!(forged.verify(CHAIN_ID, &set), Err(LightClientError::InvalidSignature { .. })));
+    }
+
+    #[test]
+    fn test_finality_proof_needs_quorum_of_trusted_set() {
+        let keys = keys(4);
+        let set = set_of(3, &keys, &[10, 10, 10, 10]);
+
+        let proof = finality_proof(42, &set, &keys[..2]);
+        assert_eq!(
+            proof.verify(CHAIN_ID, &set),
+            Err(LightClientError::InsufficientVotingPower { have: 20, need: 27 })
+        );
+
+        let mut duplicated = finality_proof(42, &set, &keys[..2]);
+        duplicated.signatures.push(duplicated.signatures[0].clone());
+        assert!(matches!(duplicated.verify(CHAIN_ID, &set), Err(LightClientError::DuplicateSignature(_))));
+
+        // A set the signers control, claiming to be the trusted one
+        let rogue = set_of(3, &keys[..1], &[100]);
+        let proof = finality_proof(42, &rogue, &keys[..1]);
+        assert!(proof.verify(CHAIN_ID, &rogue).is_ok());
+        assert!(matches!(
+            proof.verify(CHAIN_ID, &set),
+            Err(LightClientError::UntrustedValidatorSet { .. })
+        ));
+    }
+
+    #[test]
+    fn test_finality_proof_bound_to_epoch() {
+        let keys = keys(4);
+        let mut set = set_of(3, &keys, &[10, 10, 10, 10]);
+
+        // Blocks before the epoch started or after it ended are not its own
+        assert_eq!(
+            finality_proof(15, &set, &keys[..3]).verify(CHAIN_ID, &set),
+            Err(LightClientError::BlockOutsideEpoch { block_number: 15, epoch: 3 })
+        );
+        set.close(29).unwrap();
+        assert!(finality_proof(29, &set, &keys[..3]).verify(CHAIN_ID, &set).is_ok());
+        assert!(matches!(
+            finality_proof(30, &set, &keys[..3]).verify(CHAIN_ID, &set),
+            Err(LightClientError::BlockOutsideEpoch { .. })
+        ));
+
+        // The same validators' votes from epoch 3 do not count in epoch 4
+        let next = set_of(4, &keys, &[10, 10, 10, 10]);
+        let mut replayed = finality_proof(35, &set, &keys[..3]);
+        replayed.validator_set = next.clone();
+        assert!(matches!(replayed.verify(CHAIN_ID, &next), Err(LightClientError::InvalidSignature { .. })));
+
+        // A handover must start right after the closed epoch
+        let mut early = next;
+        early.start_block = 25;
+        let digest = validator_set_digest(CHAIN_ID, early.epoch, early.hash());
+        let proof = ValidatorSetChangeProof { new_set: early, signatures: sign(&keys[..3], digest) };
+        assert!(matches!(proof.verify(CHAIN_ID, &set), Err(LightClientError::InvalidValidatorSet(_))));
+    }
+
+    #[test]
+    fn test_validator_set_change_proof() {
+        let keys = keys(5);
+        let trusted = set_of(3, &keys[..4], &[10, 10, 10, 10]);
+        let next = set_of(4, &keys[1..], &[10, 10, 10, 20]);
+
+        let digest = validator_set_digest(CHAIN_ID, next.epoch, next.hash());
+        let proof = ValidatorSetChangeProof { new_set: next.clone(), signatures: sign(&keys[..3], digest) };
+        assert_eq!(proof.verify(CHAIN_ID, &trusted), Ok(30));
+
+        // Signatures of the incoming validators alone do not count
+        let proof = ValidatorSetChangeProof { new_set: next.clone(), signatures: sign(&keys[3..], digest) };
+        assert!(matches!(proof.verify(CHAIN_ID, &trusted), Err(LightClientError::UnknownValidator(_))));
+
+        // Handover signatures do not carry over to another set
+        let other = set_of(4, &keys[4..], &[100]);
+        let proof = ValidatorSetChangeProof { new_set: other, signatures: sign(&keys[..3], digest) };
+        assert!(matches!(proof.verify(CHAIN_ID, &trusted), Err(LightClientError::InvalidSignature { .. })));
+
+        // Epochs cannot be skipped
+        let skipped = set_of(5, &keys[1..], &[10, 10, 10, 20]);
+        let digest = validator_set_digest(CHAIN_ID, skipped.epoch, skipped.hash());
+        let proof = ValidatorSetChangeProof { new_set: skipped, signatures: sign(&keys[..4], digest) };
+        assert_eq!(proof.verify(CHAIN_ID, &trusted), Err(LightClientError::UnexpectedEpoch { expected: 4, actual: 5 }));
+    }
+
+    #[test]
+    fn test_decode_header_rejects_trailing_bytes() {
+        let mut rlp = alloy_rlp::encode(Header::default());
+        assert!(decode_header(&rlp).is_ok());
+        rlp.push(0);
+        assert_eq!(decode_header(&rlp), Err(LightClientError::InvalidHeader));
+        assert_eq!(decode_header(&[]), Err(LightClientError::InvalidHeader));
+    }
+}
@@ -0,0 +1,211 @@
+//! Validator sets as light clients trust them
+//!
+//! A set is canonical when its validators are sorted by address without
+//! duplicates and every validator has non-zero voting power. Only canonical
+//! sets are hashed, so every node derives the same hash for an epoch:
+//!
+//! ```text
+//! hash = keccak256(uint64 epoch ‖ uint64 startBlock ‖ (address validator ‖ uint64 power)*)
+//! ```
+//!
+//! A set only finalizes blocks from its epoch's start block on. The end
+//! block is not known when the handover to the set is signed, so it is left
+//! out of the hash; a verifier closes the set once it trusts the next one.
+
+use crate::error::LightClientError;
+use alloc::vec::Vec;
+use alloy_primitives::{keccak256, Address, B256};
+
+/// Validators and voting powers of one epoch
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ValidatorSet {
+    /// Epoch the set is in force for
+    pub epoch: u64,
+
+    /// First block of the epoch
+    pub start_block: u64,
+
+    /// Last block of the epoch, `None` while it is the latest one
+    pub end_block: Option<u64>,
+
+    /// Validator addresses, sorted ascending
+    pub validators: Vec<Address>,
+
+    /// Voting power of each validator, in `validators` order
+    pub powers: Vec<u64>,
+}
+
+impl ValidatorSet {
+    /// Canonical set of the epoch starting at `start_block`, from
+    /// validators and powers in any order
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the lengths differ, the set is empty, a validator
+    /// appears twice or has no voting power
+    pub fn new(epoch: u64, start_block: u64, validators: &[Address], powers: &[u64]) -> Result<Self, LightClientError> {
+        if validators.len() != powers.len() {
+            return Err(LightClientError::InvalidValidatorSet("validators and powers differ in length"));
+        }
+
+        let mut entries: Vec<(Address, u64)> = validators.iter().copied().zip(powers.iter().copied()).collect();
+        entries.sort_unstable_by_key(|(validator, _)| *validator);
+
+        let set = Self {
+            epoch,
+            start_block,
+            end_block: None,
+            validators: entries.iter().map(|(validator, _)| *validator).collect(),
+            powers: entries.iter().map(|(_, power)| *power).collect(),
+        };
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Check that the set is canonical
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the set is empty, unsorted, has duplicates, a
+    /// validator without power, its total power overflows or it ends before
+    /// it starts
+    pub fn validate(&self) -> Result<(), LightClientError> {
+        if self.validators.len() != self.powers.len() {
+            return Err(LightClientError::InvalidValidatorSet("validators and powers differ in length"));
+        }
+        if self.validators.is_empty() {
+            return Err(LightClientError::InvalidValidatorSet("no validators"));
+        }
+        if self.validators.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(LightClientError::InvalidValidatorSet("validators not sorted or duplicated"));
+        }
+        if self.powers.contains(&0) {
+            return Err(LightClientError::InvalidValidatorSet("validator without voting power"));
+        }
+        if self.powers.iter().try_fold(0u64, |total, power| total.checked_add(*power)).is_none() {
+            return Err(LightClientError::InvalidValidatorSet("total voting power overflows"));
+        }
+        if self.end_block.is_some_and(|end_block| end_block < self.start_block) {
+            return Err(LightClientError::InvalidValidatorSet("epoch ends before it starts"));
+        }
+        Ok(())
+    }
+
+    /// Hash light clients trust the set by
+    pub fn hash(&self) -> B256 {
+        let mut preimage = Vec::with_capacity(16 + self.validators.len() * 28);
+        preimage.extend_from_slice(&self.epoch.to_be_bytes());
+        preimage.extend_from_slice(&self.start_block.to_be_bytes());
+        for (validator, power) in self.validators.iter().zip(&self.powers) {
+            preimage.extend_from_slice(validator.as_slice());
+            preimage.extend_from_slice(&power.to_be_bytes());
+        }
+        keccak256(preimage)
+    }
+
+    /// Whether `block_number` lies within the epoch
+    pub fn contains(&self, block_number: u64) -> bool {
+        block_number >= self.start_block && self.end_block.is_none_or(|end_block| block_number <= end_block)
+    }
+
+    /// Close the set at `end_block`, the block before the next epoch starts
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the set starts after `end_block`
+    pub fn close(&mut self, end_block: u64) -> Result<(), LightClientError> {
+        if end_block < self.start_block {
+            return Err(LightClientError::InvalidValidatorSet("epoch ends before it starts"));
+        }
+        self.end_block = Some(end_block);
+        Ok(())
+    }
+
+    /// Total voting power
+    pub fn total_power(&self) -> u64 {
+        self.powers.iter().fold(0u64, |total, power| total.saturating_add(*power))
+    }
+
+    /// Voting power needed for finality (2/3 + 1)
+    ///
+    /// Equal to `total * 2 / 3 + 1` without overflowing.
+    pub fn bft_threshold(&self) -> u64 {
+        let total = self.total_power();
+        total / 3 * 2 + total % 3 * 2 / 3 + 1
+    }
+
+    /// Voting power of `validator`, `None` if not in the set
+    pub fn power_of(&self, validator: &Address) -> Option<u64> {
+        self.validators
+            .binary_search(validator)
+            .ok()
+            .and_then(|index| self.powers.get(index).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_canonical_order_and_hash() {
+        let (a, b, c) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+        let set = ValidatorSet::new(7, 100, &[c, a, b], &[30, 10, 20]).unwrap();
+        assert_eq!(set.validators, vec![a, b, c]);
+        assert_eq!(set.powers, vec![10, 20, 30]);
+        assert_eq!(set.power_of(&c), Some(30));
+        assert_eq!(set.power_of(&Address::ZERO), None);
+
+        // Input order does not change the hash, the epoch and its start do
+        assert_eq!(set.hash(), ValidatorSet::new(7, 100, &[a, b, c], &[10, 20, 30]).unwrap().hash());
+        assert_ne!(set.hash(), ValidatorSet::new(8, 100, &[a, b, c], &[10, 20, 30]).unwrap().hash());
+        assert_ne!(set.hash(), ValidatorSet::new(7, 101, &[a, b, c], &[10, 20, 30]).unwrap().hash());
+
+        // The end is learned later and does not change the hash
+        let mut closed = set.clone();
+        closed.close(199).unwrap();
+        assert_eq!(closed.hash(), set.hash());
+        assert!(!closed.contains(99) && closed.contains(100) && closed.contains(199) && !closed.contains(200));
+        assert!(set.contains(u64::MAX));
+        assert!(closed.close(99).is_err());
+    }
+
+    #[test]
+    fn test_threshold_matches_engine() {
+        for total in [1u64, 2, 3, 4, 10, 100, 301, u64::MAX] {
+            let set = ValidatorSet {
+                epoch: 0,
+                start_block: 0,
+                end_block: None,
+                validators: vec![Address::ZERO],
+                powers: vec![total],
+            };
+            assert_eq!(u128::from(set.bft_threshold()), u128::from(total) * 2 / 3 + 1);
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_sets() {
+        let a = Address::repeat_byte(1);
+        assert!(ValidatorSet::new(1, 0, &[], &[]).is_err());
+        assert!(ValidatorSet::new(1, 0, &[a, a], &[1, 1]).is_err());
+        assert!(ValidatorSet::new(1, 0, &[a], &[0]).is_err());
+        assert!(ValidatorSet::new(1, 0, &[a], &[1, 2]).is_err());
+
+        let unsorted = ValidatorSet {
+            epoch: 1,
+            start_block: 0,
+            end_block: None,
+            validators: vec![Address::repeat_byte(2), a],
+            powers: vec![1, 1],
+        };
+        assert!(unsorted.validate().is_err());
+
+        let mut reversed = ValidatorSet::new(1, 10, &[a], &[1]).unwrap();
+        reversed.end_block = Some(9);
+        assert!(reversed.validate().is_err());
+    }
+}
@@ -16,8 +16,37 @@
 //!       ├─ ProposerSelection (weighted round-robin)
 //!       └─ BlockAttestation (2/3+ signatures)
 //! ```
-
-use ande_consensus::{ConsensusConfig, ConsensusEngine};
+//!
+//! Every canonical block is attested with this validator's key; once 2/3+1
+//! of the voting power has attested, the engine holds a quorum certificate
+//! for it. Headers conflicting with a certified block are rejected; the
+//! certificate does not by itself advance reth's finalized head.
+//!
+//! Header validation is synchronous, so proposers are checked against the
+//! engine's precomputed schedule. The proposer seals each block it builds
+//! (see [`ande_consensus::seal`]) and a block whose seal does not recover to
+//! its beneficiary and the proposer scheduled for its height is rejected at
+//! import. Heights the schedule does not cover yet are refused too, and the
+//! block is imported again once the schedule has caught up. Sealed headers
+//! carry up to 97 bytes of extra data, so the Ethereum 32-byte limit is
+//! raised accordingly.
+//!
+//! With `CONSENSUS_DATA_SOURCE=state` (the default) the engine reads the
+//! consensus contracts from this node's own state through
+//! [`NodeStateCaller`], so it needs neither a JSON-RPC hop nor this node's
+//! RPC server to be up when it starts.
+//!
+//! With `ANDE_CONSENSUS_ADDRESS` and a sequencer key set, every canonical
+//! block is also attested on the AndeConsensus contract by
+//! [`submit_attestations`]; failed submissions are retried on later blocks.
+
+use alloy_primitives::{Address, Bytes};
+use ande_consensus::{
+    seal::MAX_SEALED_EXTRA_DATA, ConsensusConfig, ConsensusEngine, DataSourceKind, StateReader, ViewCaller,
+    ConsensusError as BftError,
+};
+use ande_evm::{attestation::BlockAttester, consensus_config::ConsensusConfig as AttestationConfig};
+use futures::StreamExt;
 use reth_chainspec::ChainSpec;
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
 use reth_consensus_common::validation::{
@@ -26,13 +55,159 @@ use reth_consensus_common::validation::{
 };
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_primitives::{Block, BlockBody, EthPrimitives, Receipt};
+use reth_evm::{ConfigureEvm, Evm};
+use reth_evm_ethereum::EthEvmConfig;
 use reth_execution_types::BlockExecutionResult;
 use reth_node_api::{FullNodeTypes, NodeTypes};
 use reth_node_builder::{BuilderContext, components::ConsensusBuilder};
 use reth_primitives::{RecoveredBlock, SealedBlock, SealedHeader};
-use std::sync::Arc;
+use reth_primitives_traits::BlockHeader;
+use reth_provider::{BlockNumReader, CanonStateSubscriptions, HeaderProvider, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use revm::context::result::ExecutionResult;
+use std::{path::Path, sync::Arc};
+use tokio::sync::OnceCell;
 use tracing::{debug, info, warn};
 
+/// Shared consensus engine, initialized by the consensus builder
+static CONSENSUS_ENGINE: OnceCell<Option<Arc<ConsensusEngine>>> = OnceCell::const_new();
+
+/// Get the shared consensus engine, if BFT consensus is configured
+pub fn consensus_engine() -> Option<Arc<ConsensusEngine>> {
+    CONSENSUS_ENGINE.get().cloned().flatten()
+}
+
+/// Advance the proposer schedule, track validator performance, announce our
+/// own blocks and attest every block that becomes canonical
+pub async fn attest_canonical_blocks<P>(provider: P, engine: Arc<ConsensusEngine>)
+where
+    P: CanonStateSubscriptions<Primitives = EthPrimitives>,
+{
+    info!("✍️  Block attestation started");
+
+    let mut notifications = provider.canonical_state_stream();
+    while let Some(notification) = notifications.next().await {
+        for block in notification.committed().blocks_iter() {
+            let number = block.header().number();
+            if let Err(e) = engine.advance_schedule(number) {
+                warn!(block = number, "Failed to extend proposer schedule: {}", e);
+            }
+            engine.observe_canonical_block(
+                number,
+                block.hash(),
+                block.header().beneficiary(),
+                block.header().timestamp(),
+            );
+            if let Err(e) = engine.announce_block(number, block.hash(), block.header().beneficiary()).await {
+                warn!(block = number, "Failed to announce block proposal: {}", e);
+            }
+            if let Err(e) = engine.attest_block(number, block.hash()).await {
+                warn!(block = number, "Failed to attest block: {}", e);
+            }
+        }
+    }
+
+    warn!("Block attestation stopped: canonical state stream closed");
+}
+
+/// Attest every canonical block on the AndeConsensus contract
+///
+/// Submission status is tracked by the attester, which retries failed
+/// attestations and stays idle while this node is not an active validator.
+pub async fn submit_attestations<P>(provider: P, attester: Arc<BlockAttester>)
+where
+    P: CanonStateSubscriptions<Primitives = EthPrimitives>,
+{
+    info!("📝 On-chain attestation started for {:?}", attester.address());
+
+    let mut notifications = provider.canonical_state_stream();
+    while let Some(notification) = notifications.next().await {
+        for block in notification.committed().blocks_iter() {
+            attester.on_canonical_block(block.header().number(), block.hash()).await;
+        }
+    }
+
+    warn!("On-chain attestation stopped: canonical state stream closed");
+}
+
+/// Build the on-chain attester if AndeConsensus attestation is configured
+fn block_attester(data_dir: &Path) -> Option<Arc<BlockAttester>> {
+    let config = AttestationConfig::from_env().ok()?;
+    if !config.enabled || !config.attestation_enabled {
+        info!("ℹ️  On-chain attestation disabled");
+        return None;
+    }
+
+    match BlockAttester::from_config(&config, data_dir) {
+        Ok(attester) => Some(Arc::new(attester)),
+        Err(e) => {
+            warn!("⚠️  On-chain attestation not started: {}", e);
+            None
+        }
+    }
+}
+
+/// Executes contract views on this node's state for the consensus engine
+///
+/// Views run as system calls on the state after the requested block, the
+/// in-process equivalent of `eth_call` pinned to that block.
+#[derive(Debug, Clone)]
+pub struct NodeStateCaller<P> {
+    /// Node state provider
+    provider: P,
+
+    /// EVM configuration used to run the views
+    evm_config: EthEvmConfig,
+}
+
+impl<P> NodeStateCaller<P> {
+    /// Create a caller over `provider`
+    pub fn new(provider: P, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { provider, evm_config: EthEvmConfig::new(chain_spec) }
+    }
+}
+
+impl<P> ViewCaller for NodeStateCaller<P>
+where
+    P: StateProviderFactory
+        + BlockNumReader
+        + HeaderProvider<Header = alloy_consensus::Header>
+        + Send
+        + Sync,
+{
+    fn latest_block(&self) -> ande_consensus::Result<u64> {
+        self.provider.best_block_number().map_err(|e| BftError::Internal(e.to_string()))
+    }
+
+    fn call(&self, block: u64, contract: Address, input: Bytes) -> ande_consensus::Result<Bytes> {
+        let header = self
+            .provider
+            .header_by_number(block)
+            .map_err(|e| BftError::Internal(e.to_string()))?
+            .ok_or_else(|| BftError::Internal(format!("Header of block {block} not found")))?;
+        let state = self
+            .provider
+            .history_by_block_number(block)
+            .map_err(|e| BftError::Internal(format!("State of block {block} unavailable: {e}")))?;
+
+        let evm_env = self.evm_config.evm_env(&header).map_err(|e| BftError::Internal(e.to_string()))?;
+        let mut evm = self.evm_config.evm_with_env(StateProviderDatabase::new(state), evm_env);
+        let result = evm
+            .transact_system_call(Address::ZERO, contract, input)
+            .map_err(|e| BftError::ContractError(e.to_string()))?;
+
+        match result.result {
+            ExecutionResult::Success { output, .. } => Ok(output.into_data()),
+            ExecutionResult::Revert { output, .. } => {
+                Err(BftError::ContractError(format!("View call to {contract} reverted: {output}")))
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                Err(BftError::ContractError(format!("View call to {contract} halted: {reason:?}")))
+            }
+        }
+    }
+}
+
 /// ANDE Consensus Builder
 ///
 /// Creates AndeConsensus instances with BFT integration
@@ -51,7 +226,7 @@ impl AndeConsensusBuilder {
         chain_spec: Arc<ChainSpec>,
         consensus_config: Option<ConsensusConfig>,
     ) -> eyre::Result<Arc<AndeConsensus>> {
-        let inner = EthBeaconConsensus::new(chain_spec);
+        let inner = EthBeaconConsensus::new(chain_spec).with_max_extra_data_size(MAX_SEALED_EXTRA_DATA);
         
         // Initialize consensus engine if config provided
         let consensus_engine = if let Some(config) = consensus_config {
@@ -88,14 +263,51 @@ where
             info!("ℹ️  Using standard Ethereum consensus (BFT disabled)");
         }
 
-        // Initialize consensus engine if config provided
-        let consensus_engine = if let Some(config) = consensus_config {
-            info!("🔧 Initializing BFT consensus engine");
-            Some(Arc::new(ConsensusEngine::new(config).await?))
-        } else {
-            warn!("⚠️  No consensus config - BFT validation disabled");
-            None
-        };
+        // Initialize the shared consensus engine if config provided
+        let consensus_engine = CONSENSUS_ENGINE
+            .get_or_try_init(|| async {
+                let Some(config) = consensus_config else {
+                    warn!("⚠️  No consensus config - BFT validation disabled");
+                    return Ok::<_, eyre::Report>(None);
+                };
+                info!("🔧 Initializing BFT consensus engine");
+                let engine = match config.data_source {
+                    DataSourceKind::State => {
+                        info!("📖 Reading consensus contracts from local state");
+                        let reader = StateReader::new(
+                            NodeStateCaller::new(ctx.provider().clone(), ctx.chain_spec()),
+                            config.consensus_contract,
+                            config.coordinator_contract,
+                        );
+                        ConsensusEngine::with_data_source(config, Arc::new(reader)).await?
+                    }
+                    DataSourceKind::Rpc => {
+                        info!("🌐 Reading consensus contracts over RPC: {}", config.rpc_url);
+                        ConsensusEngine::new(config).await?
+                    }
+                };
+                Ok(Some(Arc::new(engine)))
+            })
+            .await?
+            .clone();
+
+        if let Some(engine) = &consensus_engine {
+            // Without a synced validator set every import would be refused
+            // as unscheduled, so a node that cannot start the engine stops
+            engine
+                .start()
+                .await
+                .map_err(|e| eyre::eyre!("Failed to start consensus engine: {}", e))?;
+
+            ctx.task_executor().spawn(Box::pin(attest_canonical_blocks(
+                ctx.provider().clone(),
+                Arc::clone(engine),
+            )));
+        }
+
+        if let Some(attester) = block_attester(ctx.config().datadir().data_dir()) {
+            ctx.task_executor().spawn(Box::pin(submit_attestations(ctx.provider().clone(), attester)));
+        }
 
         Ok(Arc::new(AndeConsensus::new(ctx.chain_spec(), consensus_engine)) as Self::Consensus)
     }
@@ -130,11 +342,11 @@ impl std::fmt::Debug for AndeConsensus {
 
 impl AndeConsensus {
     /// Create a new ANDE consensus instance
-    pub const fn new(
+    pub fn new(
         chain_spec: Arc<ChainSpec>,
         consensus_engine: Option<Arc<ConsensusEngine>>,
     ) -> Self {
-        let inner = EthBeaconConsensus::new(chain_spec);
+        let inner = EthBeaconConsensus::new(chain_spec).with_max_extra_data_size(MAX_SEALED_EXTRA_DATA);
         Self {
             inner,
             consensus_engine,
@@ -146,52 +358,54 @@ impl AndeConsensus {
         self.consensus_engine.as_ref()
     }
 
-    /// Validate block proposer is authorized
-    async fn validate_proposer(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+    /// Validate the block's proposer seal against the precomputed schedule
+    fn validate_proposer(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
         let Some(engine) = &self.consensus_engine else {
             // BFT disabled, allow any proposer
             return Ok(());
         };
 
-        let block_number = header.number;
-        
-        // Get current proposer from consensus engine
-        let expected_proposer = engine
-            .get_current_proposer(block_number)
-            .await
-            .map_err(|e| ConsensusError::Other(format!("Failed to get proposer: {e}").into()))?;
-
-        let actual_proposer = header.beneficiary;
-
-        if actual_proposer != expected_proposer {
-            return Err(ConsensusError::Other(
-                format!(
-                    "Invalid proposer: expected {expected_proposer}, got {actual_proposer}"
-                )
-                .into(),
-            ));
-        }
+        let proposer = engine
+            .verify_sealed_proposer(header.header())
+            .map_err(|e| ConsensusError::Other(format!("Block {}: {e}", header.number).into()))?;
 
         debug!(
-            block = block_number,
-            proposer = ?actual_proposer,
-            "✅ Proposer validated"
+            block = header.number,
+            proposer = ?proposer,
+            "✅ Proposer seal validated"
         );
 
         Ok(())
     }
 
-    /// Validate block has sufficient validator attestations
-    async fn validate_attestations(&self, _header: &SealedHeader) -> Result<(), ConsensusError> {
-        let Some(_engine) = &self.consensus_engine else {
-            // BFT disabled, no attestation required
+    /// Reject headers that conflict with a block finalized by a quorum certificate
+    ///
+    /// This only guards against replacing a certified block. It does not
+    /// move reth's finalized head: forkchoice finality still comes from the
+    /// engine API driver, and a block without a certificate is accepted.
+    fn validate_finality(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        let Some(engine) = &self.consensus_engine else {
+            // BFT disabled, no certificates
             return Ok(());
         };
 
-        // TODO: Implement attestation validation
-        // For now, we'll implement this in a future update when we have
-        // the attestation collection mechanism in place
-        
+        let Some(certificate) = engine.quorum_certificate(header.number) else {
+            return Ok(());
+        };
+
+        if certificate.block_hash != header.hash() {
+            return Err(ConsensusError::Other(
+                format!(
+                    "Block {} conflicts with finalized {} (quorum certificate of epoch {})",
+                    header.hash(),
+                    certificate.block_hash,
+                    certificate.epoch
+                )
+                .into(),
+            ));
+        }
+
+        debug!(block = header.number, "✅ Block matches quorum certificate");
         Ok(())
     }
 }
@@ -199,7 +413,13 @@ impl AndeConsensus {
 impl HeaderValidator for AndeConsensus {
     fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
         // Delegate to inner consensus for standard validation
-        self.inner.validate_header(header)
+        self.inner.validate_header(header)?;
+
+        // Only the scheduled proposer may produce the block
+        self.validate_proposer(header)?;
+
+        // Finalized blocks cannot be replaced
+        self.validate_finality(header)
     }
 
     fn validate_header_against_parent(
@@ -229,9 +449,6 @@ impl HeaderValidator for AndeConsensus {
             self.inner.chain_spec(),
         )?;
 
-        // BFT proposer validation (async, but we'll handle it in validate_block)
-        // Can't do async here due to trait constraints
-
         Ok(())
     }
 }
@@ -250,16 +467,8 @@ impl Consensus<Block> for AndeConsensus {
     fn validate_block_pre_execution(&self, block: &SealedBlock) -> Result<(), Self::Error> {
         self.inner.validate_block_pre_execution(block)?;
 
-        // TODO: Add async proposer validation here once we refactor for async support
-        // For now, log validation info
-        if self.consensus_engine.is_some() {
-            debug!(
-                block = block.number,
-                proposer = ?block.beneficiary,
-                "🔍 BFT validation deferred to block processing"
-            );
-        }
-
+        // Proposer is checked in validate_header, which also runs for
+        // headers imported ahead of their bodies
         Ok(())
     }
 }
@@ -0,0 +1,231 @@
+//! ANDE Engine API Validator - accepts proposer-sealed payloads
+//!
+//! Sealed headers carry the proposer signature in `extra_data`, which makes
+//! it longer than the 32 bytes the stock payload conversion allows:
+//!
+//! ```text
+//! engine_newPayload(payload)
+//!     ↓
+//! extra_data = vanity (≤ 32) ‖ seal (65)   → taken out, at most 97 bytes
+//!     ↓
+//! block_hash = hash(block without extra_data)
+//!     ↓
+//! EthereumExecutionPayloadValidator (fork fields, sidecar, hash)
+//!     ↓
+//! extra_data restored, block resealed     → must hash to the payload's block_hash
+//! ```
+//!
+//! The seal itself is checked by `AndeConsensus` on import; this validator
+//! only lets the sealed payload through the Engine API.
+
+use alloy_rpc_types_engine::{ExecutionData, PayloadError};
+use ande_consensus::seal::MAX_SEALED_EXTRA_DATA;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_ethereum_engine_primitives::EthPayloadAttributes;
+use reth_ethereum_payload_builder::EthereumExecutionPayloadValidator;
+use reth_ethereum_primitives::{Block, EthPrimitives, TransactionSigned};
+use reth_node_api::{
+    AddOnsContext, EngineApiMessageVersion, EngineApiValidator, EngineObjectValidationError,
+    EngineTypes, FullNodeComponents, NewPayloadError, NodeTypes, PayloadOrAttributes, PayloadTypes,
+    PayloadValidator,
+};
+use reth_node_builder::rpc::PayloadValidatorBuilder;
+use reth_node_ethereum::EthereumEngineValidator;
+use reth_primitives::{RecoveredBlock, SealedBlock};
+use std::sync::Arc;
+
+/// Engine API validator accepting `extra_data` up to [`MAX_SEALED_EXTRA_DATA`]
+#[derive(Debug, Clone)]
+pub struct AndeEngineValidator<ChainSpec = reth_chainspec::ChainSpec> {
+    /// Stock validator, for payload attributes and version-specific fields
+    inner: EthereumEngineValidator<ChainSpec>,
+
+    /// Stock payload checks, run on the payload without its seal
+    payload: EthereumExecutionPayloadValidator<ChainSpec>,
+}
+
+impl<ChainSpec> AndeEngineValidator<ChainSpec> {
+    /// Create a validator for `chain_spec`
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self {
+            inner: EthereumEngineValidator::new(chain_spec.clone()),
+            payload: EthereumExecutionPayloadValidator::new(chain_spec),
+        }
+    }
+}
+
+impl<ChainSpec: EthChainSpec + EthereumHardforks> AndeEngineValidator<ChainSpec> {
+    /// Check `payload` the way the stock validator does, except for the
+    /// length of its `extra_data`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PayloadError::ExtraData`] above [`MAX_SEALED_EXTRA_DATA`],
+    /// [`PayloadError::BlockHash`] if the sealed block does not hash to the
+    /// payload's block hash, or any error of the stock checks
+    pub fn ensure_well_formed_sealed_payload(
+        &self,
+        mut payload: ExecutionData,
+    ) -> Result<SealedBlock<Block>, PayloadError> {
+        let expected_hash = payload.payload.block_hash();
+        let extra_data = std::mem::take(&mut payload.payload.as_v1_mut().extra_data);
+        if extra_data.len() > MAX_SEALED_EXTRA_DATA {
+            return Err(PayloadError::ExtraData(extra_data));
+        }
+
+        // The stock checks compare against the hash of the block they convert
+        let unsealed_hash = payload
+            .payload
+            .clone()
+            .try_into_block_with_sidecar::<TransactionSigned>(&payload.sidecar)?
+            .header
+            .hash_slow();
+        payload.payload.as_v1_mut().block_hash = unsealed_hash;
+
+        let mut block = self.payload.ensure_well_formed_payload::<TransactionSigned>(payload)?.into_block();
+        block.header.extra_data = extra_data;
+        let sealed_block = SealedBlock::seal_slow(block);
+        if sealed_block.hash() != expected_hash {
+            return Err(PayloadError::BlockHash { execution: sealed_block.hash(), consensus: expected_hash });
+        }
+        Ok(sealed_block)
+    }
+}
+
+impl<ChainSpec, Types> PayloadValidator<Types> for AndeEngineValidator<ChainSpec>
+where
+    ChainSpec: EthChainSpec + EthereumHardforks + 'static,
+    Types: PayloadTypes<ExecutionData = ExecutionData>,
+{
+    type Block = Block;
+
+    fn ensure_well_formed_payload(
+        &self,
+        payload: ExecutionData,
+    ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
+        let sealed_block = self.ensure_well_formed_sealed_payload(payload)?;
+        sealed_block.try_recover().map_err(|e| NewPayloadError::Other(e.into()))
+    }
+}
+
+impl<ChainSpec, Types> EngineApiValidator<Types> for AndeEngineValidator<ChainSpec>
+where
+    ChainSpec: EthChainSpec + EthereumHardforks + 'static,
+    Types: PayloadTypes<PayloadAttributes = EthPayloadAttributes, ExecutionData = ExecutionData>,
+{
+    fn validate_version_specific_fields(
+        &self,
+        version: EngineApiMessageVersion,
+        payload_or_attrs: PayloadOrAttributes<'_, Types::ExecutionData, EthPayloadAttributes>,
+    ) -> Result<(), EngineObjectValidationError> {
+        EngineApiValidator::<Types>::validate_version_specific_fields(&self.inner, version, payload_or_attrs)
+    }
+
+    fn ensure_well_formed_attributes(
+        &self,
+        version: EngineApiMessageVersion,
+        attributes: &EthPayloadAttributes,
+    ) -> Result<(), EngineObjectValidationError> {
+        EngineApiValidator::<Types>::ensure_well_formed_attributes(&self.inner, version, attributes)
+    }
+}
+
+/// Builder for [`AndeEngineValidator`]
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct AndeEngineValidatorBuilder;
+
+impl<Node, Types> PayloadValidatorBuilder<Node> for AndeEngineValidatorBuilder
+where
+    Types: NodeTypes<
+        ChainSpec: EthChainSpec + EthereumHardforks + Clone + 'static,
+        Payload: EngineTypes<ExecutionData = ExecutionData>
+                     + PayloadTypes<PayloadAttributes = EthPayloadAttributes>,
+        Primitives = EthPrimitives,
+    >,
+    Node: FullNodeComponents<Types = Types>,
+{
+    type Validator = AndeEngineValidator<Types::ChainSpec>;
+
+    async fn build(self, ctx: &AddOnsContext<'_, Node>) -> eyre::Result<Self::Validator> {
+        Ok(AndeEngineValidator::new(ctx.config.chain.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_primitives::Bytes;
+    use ande_consensus::{
+        seal::{apply_seal, recover_sealer, seal_message, EXTRA_SEAL},
+        LocalKey, SigningBackend,
+    };
+    use reth_chainspec::ChainSpec;
+    use reth_node_ethereum::EthEngineTypes;
+
+    const CHAIN_ID: u64 = 6174;
+
+    /// Payload of a block sealed by `key`
+    async fn sealed_payload(key: &LocalKey) -> ExecutionData {
+        let mut header = Header {
+            number: 1,
+            beneficiary: key.address(),
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            transactions_root: EMPTY_ROOT_HASH,
+            extra_data: Bytes::from_static(b"reth/v1.8.2"),
+            ..Header::default()
+        };
+        let signature = key.sign_message(&seal_message(CHAIN_ID, &header)).await.unwrap();
+        apply_seal(&mut header, &signature);
+
+        let block = SealedBlock::seal_slow(Block { header, body: BlockBody::default() });
+        <EthEngineTypes as PayloadTypes>::block_to_payload(block)
+    }
+
+    #[tokio::test]
+    async fn test_sealed_payload_passes_new_payload() {
+        let key = LocalKey::from_bytes(&[7; 32]).unwrap();
+        let payload = sealed_payload(&key).await;
+        let expected_hash = payload.payload.block_hash();
+        let chain_spec = Arc::new(ChainSpec::default());
+
+        // The stock validator rejects the seal's extra data
+        let stock = EthereumEngineValidator::new(chain_spec.clone());
+        assert!(matches!(
+            PayloadValidator::<EthEngineTypes>::ensure_well_formed_payload(&stock, payload.clone()),
+            Err(NewPayloadError::Eth(PayloadError::ExtraData(_)))
+        ));
+
+        let validator = AndeEngineValidator::new(chain_spec);
+        let block = PayloadValidator::<EthEngineTypes>::ensure_well_formed_payload(&validator, payload).unwrap();
+        assert_eq!(block.hash(), expected_hash);
+        assert_eq!(block.header().extra_data.len(), b"reth/v1.8.2".len() + EXTRA_SEAL);
+        assert_eq!(recover_sealer(CHAIN_ID, block.header()).unwrap(), key.address());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_or_oversized_extra_data_rejected() {
+        let key = LocalKey::from_bytes(&[7; 32]).unwrap();
+        let validator = AndeEngineValidator::new(Arc::new(ChainSpec::default()));
+
+        // A changed seal no longer matches the block hash
+        let mut tampered = sealed_payload(&key).await;
+        let mut extra_data = tampered.payload.as_v1().extra_data.to_vec();
+        extra_data[20] ^= 1;
+        tampered.payload.as_v1_mut().extra_data = extra_data.into();
+        assert!(matches!(
+            validator.ensure_well_formed_sealed_payload(tampered),
+            Err(PayloadError::BlockHash { .. })
+        ));
+
+        let mut oversized = sealed_payload(&key).await;
+        oversized.payload.as_v1_mut().extra_data = Bytes::from(vec![0; MAX_SEALED_EXTRA_DATA + 1]);
+        assert!(matches!(
+            validator.ensure_well_formed_sealed_payload(oversized),
+            Err(PayloadError::ExtraData(_))
+        ));
+    }
+}
@@ -2,10 +2,10 @@
 //!
 //! Custom executor builder that integrates ANDE Chain personalizaciones:
 //! - Token Duality Precompile (0xFD) via AndeEvmConfig
+//! - MEV redirection via AndeHandler (activation height from the chainspec)
 //! - (Future) Parallel EVM execution via Block-STM
-//! - (Future) MEV-aware execution ordering
 
-use ande_evm::AndeEvmFactory;
+use ande_evm::{AndeChainConfig, AndeEvmFactory};
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_ethereum::evm::EthEvmConfig;
 use reth_ethereum_primitives::EthPrimitives;
@@ -22,15 +22,16 @@ use reth_node_builder::{BuilderContext, components::ExecutorBuilder, FullNodeTyp
 ///   - Gas: 3000 base + 100/word
 ///   - Security: Balance checks, overflow protection
 ///
+/// - ✅ MEV Redirection
+///   - Base fees credited to the MEV sink through `AndeHandler`
+///   - Enabled with `config.ande.mevEnabled`/`mevSink` in the genesis
+///   - Active from `config.ande.mevActivationBlock`
+///
 /// ## Planned Features (v2.0):
 /// - ⏳ Parallel EVM Execution (Block-STM)
 ///   - Multi-version concurrency control
 ///   - Automatic conflict detection
 ///   - 10-15x throughput improvement
-/// 
-/// - ⏳ MEV-Aware Execution
-///   - Bundle execution support
-///   - Fair MEV distribution (80% stakers, 20% treasury)
 ///
 /// ## Integration Points:
 /// - Integrated via `AndeNode::components()` → `executor(AndeExecutorBuilder)`
@@ -52,27 +53,41 @@ where
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
         use reth_ethereum::evm::revm::primitives::hardfork::SpecId;
-        use ande_evm::MevConfig;
         
         tracing::info!("🔧 Building ANDE EVM with custom configuration");
         
         // Determine spec ID from chain spec (default to CANCUN)
         let spec_id = SpecId::CANCUN;
         
-        // Check if MEV redistribution is configured
-        if let Ok(Some(mev_config)) = MevConfig::from_env() {
-            tracing::info!("💰 MEV Redistribution enabled via smart contract:");
-            tracing::info!("   • MEV Sink: {:?}", mev_config.mev_sink);
-            tracing::info!("   • Min Threshold: {} wei", mev_config.min_threshold);
-            tracing::info!("   • Distribution: 80% stakers, 20% treasury");
-            tracing::info!("   • Implementation: Smart contract based (see docs/MEV_INTEGRATION_STRATEGY.md)");
-        } else {
-            tracing::debug!("MEV redistribution not configured (set ANDE_MEV_ENABLED=true to enable)");
-        }
-        
         // Create ANDE EVM factory with Token Duality precompile
-        let ande_factory = AndeEvmFactory::new(spec_id);
+        let mut ande_factory = AndeEvmFactory::new(spec_id);
+
+        // Install MEV redirection if the chainspec enables it. The sink credit
+        // is part of the state transition, so sink, switch and activation
+        // height all come from the genesis for every node to agree on them.
+        let chain_config = AndeChainConfig::from_genesis(ctx.chain_spec().genesis())?;
+        match chain_config.mev_redirect()? {
+            Some((redirect, activation_block)) => {
+                tracing::info!("💰 MEV Redistribution enabled:");
+                tracing::info!("   • MEV Sink: {:?}", redirect.mev_sink());
+                tracing::info!("   • Min Threshold: {} wei", redirect.min_threshold());
+                tracing::info!("   • Activation Block: {}", activation_block);
+
+                ande_factory = ande_factory.with_mev_redirect(redirect, activation_block);
+            }
+            None => {
+                tracing::debug!("MEV redistribution not enabled (set `config.ande.mevEnabled` in the genesis)");
+            }
+        }
+        if std::env::var_os("ANDE_MEV_ENABLED").is_some() || std::env::var_os("ANDE_MEV_SINK").is_some() {
+            tracing::warn!(
+                "⚠️  ANDE_MEV_ENABLED/ANDE_MEV_SINK do not affect execution - \
+                 MEV redirection is configured by `config.ande` in the genesis"
+            );
+        }
         
+        let mev_enabled = ande_factory.mev_redirect().is_some();
+
         // Create EthEvmConfig with our ANDE factory
         let evm_config = EthEvmConfig::new_with_evm_factory(
             ctx.chain_spec().clone(),
@@ -84,6 +99,10 @@ where
         tracing::info!("   • Spec ID: {:?}", spec_id);
         tracing::info!("   • Factory: AndeEvmFactory");
         tracing::info!("   • Precompiles: Standard Ethereum + ANDE Token Duality (0xFD)");
+        tracing::info!(
+            "   • MEV Redirect: {}",
+            if mev_enabled { "enabled" } else { "disabled" }
+        );
         
         Ok(evm_config)
     }
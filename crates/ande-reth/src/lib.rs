@@ -22,11 +22,12 @@
 //! - **Token Duality Precompile (0xFD)**: Native ANDE token as ERC20
 //! - **Custom EVM Execution**: AndeEvmFactory with full context access
 //! - **Custom Executor**: AndeExecutorBuilder integrating ANDE features
+//! - **MEV Redirection**: AndeHandler credits base fees to the MEV sink
+//! - **MEV Bundle Lane**: winning MEVAuctionManager bundle at the top of the block
 //!
 //! ## Planned Features ⏳
 //!
 //! - **Parallel EVM Execution**: Block-STM algorithm
-//! - **MEV Detection**: Fair MEV distribution
 //! - **Enhanced Consensus**: Custom validator selection
 //!
 //! ## Components
@@ -46,6 +47,15 @@ pub mod executor;
 /// ANDE custom consensus builder
 pub mod consensus;
 
+/// ANDE payload builder with the MEV bundle lane
+pub mod payload;
+
+/// ANDE Engine API validator for sealed payloads
+pub mod engine;
+
+/// MEV auction client and bundle settlement
+pub mod mev;
+
 /// Re-export main node type
 pub use node::AndeNode;
 
@@ -54,3 +64,9 @@ pub use executor::AndeExecutorBuilder;
 
 /// Re-export consensus builder
 pub use consensus::AndeConsensusBuilder;
+
+/// Re-export engine validator builder
+pub use engine::{AndeEngineValidator, AndeEngineValidatorBuilder};
+
+/// Re-export payload builder
+pub use payload::{AndePayloadBuilder, AndePayloadBuilderBuilder};
@@ -23,12 +23,22 @@
 
 use reth::chainspec::EthereumChainSpecParser;
 use reth::cli::Cli;
+use ande_evm::fair_ordering::TxOrdering;
+use ande_evm::rpc::{
+    serve_bundle_rpc, EthBundleApiImpl, EthBundleApiServer, EvolveTxpoolApiImpl,
+    EvolveTxpoolApiServer, MevHistoryApiImpl, MevHistoryApiServer,
+};
+use ande_consensus::rpc::{AttestationApiImpl, AttestationApiServer};
+use ande_evm::{AndeChainConfig, DEFAULT_MAX_TXPOOL_BYTES};
+use reth_node_api::FullNodeComponents;
 use tracing::{info, warn};
 
 // Import ANDE custom components
 mod node;
 mod executor;
 mod consensus;
+mod mev;
+mod payload;
 
 use node::AndeNode;
 
@@ -59,6 +69,50 @@ fn main() {
         // ✅ ANDE CUSTOM NODE - Not EthereumNode!
         let handle = builder
             .node(AndeNode::new())
+            .extend_rpc_modules(|ctx| {
+                // Sequencer txpool RPC with the network's ordering policy
+                let chain_config = AndeChainConfig::from_genesis(ctx.config().chain.genesis())?;
+                let ordering = TxOrdering::from_chain_config(&chain_config)?;
+                info!("   Txpool ordering: {:?}", ordering.mode());
+                let txpool_api =
+                    EvolveTxpoolApiImpl::new(ctx.node().pool().clone(), DEFAULT_MAX_TXPOOL_BYTES)
+                        .with_ordering(ordering);
+                ctx.modules.merge_configured(txpool_api.into_rpc())?;
+
+                // Bundle RPC for searchers, only when the MEV auction is configured.
+                // Served on its own endpoint so X-Flashbots-Signature can be checked
+                if let Some(auction) = mev::auction_client() {
+                    let bundle_api = EthBundleApiImpl::new(
+                        ctx.provider().clone(),
+                        ctx.node().evm_config().clone(),
+                        auction,
+                    );
+                    let addr = mev::bundle_rpc_addr();
+                    ctx.node().task_executor().spawn(Box::pin(async move {
+                        match serve_bundle_rpc(addr, bundle_api.into_rpc()).await {
+                            Ok(server) => server.stopped().await,
+                            Err(e) => warn!("⚠️  Bundle RPC failed to bind {}: {}", addr, e),
+                        }
+                    }));
+                    info!("   Bundle RPC enabled on {}: eth_sendBundle, eth_callBundle, eth_cancelBundle", addr);
+                }
+
+                // MEV history over the persistent event store
+                let datadir = ctx.config().datadir();
+                if let Some(store) = mev::event_store(datadir.data_dir()) {
+                    let history_api =
+                        MevHistoryApiImpl::new(store, mev::distributor_client(datadir.data_dir()));
+                    ctx.modules.merge_configured(history_api.into_rpc())?;
+                    info!("   MEV history RPC enabled: mev_getEvents, mev_getEpochTotals");
+                }
+
+                // Attestation gossip and finality, only with BFT consensus
+                if let Some(engine) = consensus::consensus_engine() {
+                    ctx.modules.merge_configured(AttestationApiImpl::new(engine).into_rpc())?;
+                    info!("   Attestation RPC enabled: ande_submitAttestation, ande_getQuorumCertificate, ande_getFinalityProof");
+                }
+                Ok(())
+            })
             .launch()
             .await?;
 
@@ -66,12 +120,18 @@ fn main() {
         info!("   🎯 Custom Features Active:");
         info!("      • Token Duality Precompile at 0xFD");
         info!("      • Custom EVM Configuration");
+        info!("      • MEV Auction Bundle Lane (MEV_AUCTION_ADDRESS)");
+        info!("      • Fair Txpool Ordering (genesis `txOrdering`)");
+        info!("      • MEV Event History (mev_getEvents)");
         info!("      • Evolve Sequencer Integration");
         info!("");
         info!("   🌐 Endpoints:");
         info!("      • Engine API: http://0.0.0.0:8551");
         info!("      • HTTP RPC:   http://0.0.0.0:8545");
         info!("      • WebSocket:  ws://0.0.0.0:8546");
+        if mev::auction_client().is_some() {
+            info!("      • Bundle RPC: http://{} (X-Flashbots-Signature)", mev::bundle_rpc_addr());
+        }
         info!("");
         info!("   📊 Monitoring:");
         info!("      • Metrics:    http://0.0.0.0:9001");
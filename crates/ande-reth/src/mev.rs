@@ -0,0 +1,506 @@
+//! MEV Auction and Distributor Wiring
+//!
+//! Process-wide [`MevAuctionClient`] shared by the payload builder (which
+//! reads the bundle lane) and the settlement task (which reports included
+//! and rejected bundles to MEVAuctionManager once blocks are canonical).
+//! MEV captured by executed bundles is handed to the [`MevDistributorClient`]
+//! for deposit into MEVDistributor. Every MEV event of a canonical block is
+//! recorded in the [`MevEventStore`] for the `mev_*` history RPC; the
+//! recording task is the store's only writer and follows reorgs.
+//!
+//! ```text
+//! payload builder ── winning_bundle ──→ BundleLane ←── submit_bundle ── searchers (signed, :8547)
+//!                                          ↑
+//! canonical blocks ── settle_block ────────┘──→ markBundleExecuted / markBundleRejected
+//!        │            │ (SETTLEMENT_DEPTH deep)
+//!        │            ├── mev_captured ──→ MevDistributorClient ──→ depositMEV / settleEpoch
+//!        │            └── executed bundles ─────────────┐
+//!        │                                              ↓
+//!        └── MevDetector + base-fee redirect ──→ recording task ──→ MevEventStore
+//!                 ↑
+//!          ProtocolRegistry (ANDE_PROTOCOL_REGISTRY, hot reloaded)
+//! ```
+
+use alloy_consensus::TxReceipt;
+use ande_evm::mev::{
+    AndeMevRedirect, DetectorConfig, EvmPoolStateReader, MevAuctionClient, MevDetector,
+    MevDistributorClient, MevEventRecord, MevEventStore, ProtocolRegistry, DISCOVERED_POOLS_FILE,
+    DISTRIBUTOR_BUFFER_FILE, MEV_EVENT_STORE_DIR,
+};
+use ande_evm::AndeChainConfig;
+use alloy_primitives::{B256, U256};
+use futures::StreamExt;
+use reth_ethereum_primitives::{Block, EthPrimitives, Receipt};
+use reth_evm::ConfigureEvm;
+use reth_primitives::RecoveredBlock;
+use reth_primitives_traits::{BlockBody, BlockHeader};
+use reth_provider::{CanonStateSubscriptions, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tracing::{debug, info, warn};
+
+/// Number of blocks of bundle history kept by the settlement task
+const BUNDLE_HISTORY_BLOCKS: u64 = 256;
+
+/// Canonical blocks built on top of a block before its bundles are settled
+pub const SETTLEMENT_DEPTH: u64 = 6;
+
+/// Default address of the signed bundle RPC endpoint
+pub const DEFAULT_BUNDLE_RPC_ADDR: &str = "0.0.0.0:8547";
+
+/// Shared auction client, initialized on first use
+static AUCTION_CLIENT: OnceLock<Option<Arc<MevAuctionClient>>> = OnceLock::new();
+
+/// Shared distributor client, initialized on first use
+static DISTRIBUTOR_CLIENT: OnceLock<Option<Arc<MevDistributorClient>>> = OnceLock::new();
+
+/// Shared MEV event store, initialized on first use
+static EVENT_STORE: OnceLock<Option<Arc<MevEventStore>>> = OnceLock::new();
+
+/// Shared protocol registry, initialized on first use
+static PROTOCOL_REGISTRY: OnceLock<Option<Arc<ProtocolRegistry>>> = OnceLock::new();
+
+/// Bundle records of a settled block, sent to the recording task
+#[derive(Debug)]
+pub struct SettledBundles {
+    /// Settled block
+    pub block_number: u64,
+    /// Hash of the block when it was settled
+    pub block_hash: B256,
+    /// Executed bundles of the block
+    pub records: Vec<MevEventRecord>,
+}
+
+/// Canonical block waiting for [`SETTLEMENT_DEPTH`] confirmations
+#[derive(Debug)]
+struct UnsettledBlock {
+    /// Block hash
+    hash: B256,
+    /// Block timestamp
+    timestamp: u64,
+    /// Transactions of the block, in order
+    tx_hashes: Vec<B256>,
+}
+
+/// Get the shared auction client
+///
+/// Returns `None` unless `MEV_AUCTION_ADDRESS` is set. See
+/// [`MevAuctionClient::from_env`] for the remaining variables.
+pub fn auction_client() -> Option<Arc<MevAuctionClient>> {
+    AUCTION_CLIENT
+        .get_or_init(|| {
+            if std::env::var("MEV_AUCTION_ADDRESS").is_err() {
+                return None;
+            }
+
+            match MevAuctionClient::from_env() {
+                Ok(client) => Some(Arc::new(client)),
+                Err(e) => {
+                    warn!("⚠️  MEV auction client disabled: {}", e);
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Address of the signed bundle RPC endpoint
+///
+/// `ANDE_BUNDLE_RPC_ADDR`, or [`DEFAULT_BUNDLE_RPC_ADDR`] when unset or invalid.
+pub fn bundle_rpc_addr() -> SocketAddr {
+    let default = DEFAULT_BUNDLE_RPC_ADDR.parse().expect("valid default address");
+    match std::env::var("ANDE_BUNDLE_RPC_ADDR") {
+        Ok(addr) => addr.parse().unwrap_or_else(|e| {
+            warn!("⚠️  Invalid ANDE_BUNDLE_RPC_ADDR {}: {}, using {}", addr, e, default);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// Get the shared distributor client
+///
+/// Returns `None` unless `MEV_DISTRIBUTOR_ADDRESS` is set. The deposit buffer
+/// is persisted under `data_dir` so captured MEV survives restarts.
+pub fn distributor_client(data_dir: &Path) -> Option<Arc<MevDistributorClient>> {
+    DISTRIBUTOR_CLIENT
+        .get_or_init(|| {
+            if std::env::var("MEV_DISTRIBUTOR_ADDRESS").is_err() {
+                return None;
+            }
+
+            match MevDistributorClient::from_env()
+                .and_then(|client| client.with_buffer_path(data_dir.join(DISTRIBUTOR_BUFFER_FILE)))
+            {
+                Ok(client) => Some(Arc::new(client)),
+                Err(e) => {
+                    warn!("⚠️  MEV distributor disabled: {}", e);
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Get the shared MEV event store under `data_dir`
+///
+/// Enabled unless `ANDE_ENABLE_MEV_DETECTION=false`.
+pub fn event_store(data_dir: &Path) -> Option<Arc<MevEventStore>> {
+    EVENT_STORE
+        .get_or_init(|| {
+            let enabled = std::env::var("ANDE_ENABLE_MEV_DETECTION")
+                .ok()
+                .and_then(|v| v.parse::<bool>().ok())
+                .unwrap_or(true);
+            if !enabled {
+                return None;
+            }
+
+            match MevEventStore::open(data_dir.join(MEV_EVENT_STORE_DIR)) {
+                Ok(store) => Some(Arc::new(store)),
+                Err(e) => {
+                    warn!("⚠️  MEV event store disabled: {}", e);
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Get the shared protocol registry
+///
+/// Returns `None` unless `ANDE_PROTOCOL_REGISTRY` names a readable registry
+/// file. See [`ProtocolRegistry`] for the file format. Discovered pools are
+/// persisted under `data_dir`.
+pub fn protocol_registry(data_dir: &Path) -> Option<Arc<ProtocolRegistry>> {
+    PROTOCOL_REGISTRY
+        .get_or_init(|| {
+            let registry = ProtocolRegistry::from_env()
+                .transpose()?
+                .and_then(|registry| registry.with_discovered_pools(data_dir.join(DISCOVERED_POOLS_FILE)));
+            match registry {
+                Ok(registry) => Some(Arc::new(registry)),
+                Err(e) => {
+                    warn!("⚠️  Protocol registry disabled: {}", e);
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Base-fee redirect active on this network, with its activation block
+///
+/// Read from the chainspec like the EVM factory setup in the executor, so
+/// recorded redirections match what the handler credited.
+pub fn active_redirect(genesis: &alloy_genesis::Genesis) -> Option<(AndeMevRedirect, u64)> {
+    AndeChainConfig::from_genesis(genesis).ok()?.mev_redirect().ok().flatten()
+}
+
+/// Settle auction bundles for every block that becomes canonical
+///
+/// Settlement transactions cannot be undone, so a block is settled once
+/// [`SETTLEMENT_DEPTH`] canonical blocks are built on top of it; blocks
+/// reverted before that are dropped from the queue. MEV captured by
+/// executed bundles is buffered in `distributor`, and the bundle records
+/// are handed to the recording task through `settled`, if any.
+pub async fn settle_canonical_blocks<P>(
+    provider: P,
+    client: Arc<MevAuctionClient>,
+    distributor: Option<Arc<MevDistributorClient>>,
+    settled: Option<UnboundedSender<SettledBundles>>,
+) where
+    P: CanonStateSubscriptions<Primitives = EthPrimitives>,
+{
+    info!("💰 MEV bundle settlement task started");
+
+    let mut queue: BTreeMap<u64, UnsettledBlock> = BTreeMap::new();
+    let mut last_settled: Option<u64> = None;
+    let mut notifications = provider.canonical_state_stream();
+    while let Some(notification) = notifications.next().await {
+        if let Some(reverted) = notification.reverted() {
+            let from_block = reverted.first().header().number();
+            let _reverted = queue.split_off(&from_block);
+            if last_settled.is_some_and(|settled| settled >= from_block) {
+                warn!(
+                    from_block,
+                    depth = SETTLEMENT_DEPTH,
+                    "Reorg below the settlement depth, bundles already settled on-chain stay settled"
+                );
+            }
+        }
+
+        let committed = notification.committed();
+        for block in committed.blocks_iter() {
+            let number = block.header().number();
+            if last_settled.is_some_and(|settled| number <= settled) {
+                continue;
+            }
+            queue.insert(
+                number,
+                UnsettledBlock {
+                    hash: block.hash(),
+                    timestamp: block.header().timestamp(),
+                    tx_hashes: block.body().transactions().iter().map(|tx| *tx.hash()).collect(),
+                },
+            );
+        }
+
+        let tip = committed.tip().header().number();
+        while let Some(entry) = queue.first_entry() {
+            if entry.key().saturating_add(SETTLEMENT_DEPTH) > tip {
+                break;
+            }
+            let (number, block) = entry.remove_entry();
+
+            debug!(block = number, txs = block.tx_hashes.len(), "Settling MEV bundles");
+            let finalized = client.settle_block(number, &block.tx_hashes).await;
+            last_settled = Some(number);
+
+            let epoch = current_epoch(distributor.as_deref()).await;
+            let mut records = Vec::new();
+            for (bundle, result) in finalized.iter().filter(|(_, r)| r.executed) {
+                if let Some(distributor) = &distributor {
+                    distributor.add_mev(result.mev_captured).await;
+                }
+                records.push(MevEventRecord::from_bundle(
+                    bundle.bundle_hash,
+                    result,
+                    bundle.searcher,
+                    number,
+                    block.timestamp,
+                    epoch,
+                ));
+            }
+
+            if let Some(settled) = settled.as_ref().filter(|_| !records.is_empty()) {
+                let _ = settled.send(SettledBundles { block_number: number, block_hash: block.hash, records });
+            }
+        }
+
+        client.cleanup_old_bundles(tip, BUNDLE_HISTORY_BLOCKS).await;
+    }
+
+    warn!("MEV bundle settlement task stopped: canonical state stream closed");
+}
+
+/// Record the MEV events of every block that becomes canonical
+///
+/// Detector opportunities are found in the block receipts, with pool state
+/// read at the block. Base-fee redirections are reconstructed from gas used.
+/// Reverted blocks are dropped from the store before new ones are added.
+///
+/// This task is the only writer of `store`: bundle records from the
+/// settlement task arrive through `settled` and are stored only while their
+/// block is canonical in this task's view, so they cannot outlive a revert.
+pub async fn record_canonical_mev<P, E>(
+    provider: P,
+    evm_config: E,
+    store: Arc<MevEventStore>,
+    redirect: Option<(AndeMevRedirect, u64)>,
+    distributor: Option<Arc<MevDistributorClient>>,
+    registry: Option<Arc<ProtocolRegistry>>,
+    mut settled: UnboundedReceiver<SettledBundles>,
+) where
+    P: CanonStateSubscriptions<Primitives = EthPrimitives> + StateProviderFactory,
+    E: ConfigureEvm<Primitives = EthPrimitives>,
+{
+    info!("📚 MEV event recording started ({})", store.path().display());
+
+    let config = match registry {
+        Some(registry) => DetectorConfig::default().with_registry(registry),
+        None => DetectorConfig::default(),
+    };
+    let detector = MevDetector::new(config);
+    // Recent canonical hashes, and settled bundles of blocks not seen yet
+    let mut canonical: BTreeMap<u64, B256> = BTreeMap::new();
+    let mut early: Vec<SettledBundles> = Vec::new();
+    let mut notifications = provider.canonical_state_stream();
+    loop {
+        let notification = tokio::select! {
+            notification = notifications.next() => match notification {
+                Some(notification) => notification,
+                None => break,
+            },
+            Some(bundles) = settled.recv() => {
+                store_settled(&store, &canonical, &mut early, bundles).await;
+                continue;
+            }
+        };
+
+        if let Some(reverted) = notification.reverted() {
+            let from_block = reverted.first().header().number();
+            let _reverted = canonical.split_off(&from_block);
+            if let Some(Err(e)) = with_store(&store, move |store| store.revert_from(from_block)).await {
+                warn!("Failed to revert MEV events from block {}: {}", from_block, e);
+            }
+        }
+
+        let committed = notification.committed();
+        for block in committed.blocks_iter() {
+            canonical.insert(block.header().number(), block.hash());
+            let Some(receipts) = committed.receipts_by_block_hash(block.hash()) else {
+                continue;
+            };
+            let receipts: Vec<Receipt> = receipts.into_iter().cloned().collect();
+            let epoch = current_epoch(distributor.as_deref()).await;
+
+            let mut records = block_redirections(block, &receipts, redirect, epoch);
+            match provider.state_by_block_hash(block.hash()) {
+                Ok(state) => {
+                    let db = StateProviderDatabase::new(state);
+                    match evm_config.evm_for_block(db, block.header()) {
+                        Ok(evm) => {
+                            let mut reader = EvmPoolStateReader::new(evm);
+                            records.extend(
+                                detector
+                                    .analyze_executed_block(block, &receipts, &mut reader)
+                                    .iter()
+                                    .map(|opp| {
+                                        MevEventRecord::from_opportunity(
+                                            opp,
+                                            block.header().timestamp(),
+                                            epoch,
+                                        )
+                                    }),
+                            );
+                        }
+                        Err(e) => warn!(block = block.header().number(), "No EVM for MEV detection: {}", e),
+                    }
+                }
+                Err(e) => warn!(block = block.header().number(), "No state for MEV detection: {}", e),
+            }
+
+            if let Some(Err(e)) = with_store(&store, move |store| store.append(records)).await {
+                warn!(block = block.header().number(), "Failed to record MEV events: {}", e);
+            }
+        }
+
+        let tip = committed.tip().header().number();
+        canonical = canonical.split_off(&tip.saturating_sub(BUNDLE_HISTORY_BLOCKS));
+        for bundles in std::mem::take(&mut early) {
+            store_settled(&store, &canonical, &mut early, bundles).await;
+        }
+    }
+
+    warn!("MEV event recording stopped: canonical state stream closed");
+}
+
+/// Register the pools deployed in every block that becomes canonical
+///
+/// Runs instead of [`record_canonical_mev`] when the event store is
+/// disabled, so the persisted pools stay complete.
+pub async fn discover_canonical_pools<P>(provider: P, registry: Arc<ProtocolRegistry>)
+where
+    P: CanonStateSubscriptions<Primitives = EthPrimitives>,
+{
+    let mut notifications = provider.canonical_state_stream();
+    while let Some(notification) = notifications.next().await {
+        let committed = notification.committed();
+        for block in committed.blocks_iter() {
+            let Some(receipts) = committed.receipts_by_block_hash(block.hash()) else {
+                continue;
+            };
+            for receipt in receipts.into_iter().filter(|receipt| receipt.success) {
+                registry.discover_pools(&receipt.logs);
+            }
+        }
+    }
+
+    warn!("Pool discovery stopped: canonical state stream closed");
+}
+
+/// Store settled bundle records if their block is still canonical
+///
+/// Records for a block beyond the known tip are kept in `early` until the
+/// block is seen; records for a replaced or forgotten block are dropped.
+async fn store_settled(
+    store: &Arc<MevEventStore>,
+    canonical: &BTreeMap<u64, B256>,
+    early: &mut Vec<SettledBundles>,
+    bundles: SettledBundles,
+) {
+    match canonical.get(&bundles.block_number) {
+        Some(hash) if *hash == bundles.block_hash => {
+            let records = bundles.records;
+            if let Some(Err(e)) = with_store(store, move |store| store.append(records)).await {
+                warn!(block = bundles.block_number, "Failed to record settled bundles: {}", e);
+            }
+        }
+        None if canonical.last_key_value().is_none_or(|(tip, _)| bundles.block_number > *tip) => {
+            early.push(bundles);
+        }
+        _ => debug!(block = bundles.block_number, "Dropping settled bundles of a non-canonical block"),
+    }
+}
+
+/// Run blocking store I/O off the async runtime, in call order
+async fn with_store<T: Send + 'static>(
+    store: &Arc<MevEventStore>,
+    f: impl FnOnce(&MevEventStore) -> T + Send + 'static,
+) -> Option<T> {
+    let store = store.clone();
+    match tokio::task::spawn_blocking(move || f(&store)).await {
+        Ok(result) => Some(result),
+        Err(e) => {
+            warn!("MEV event store task failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Base fee redirected in `block`, as one aggregate event
+fn block_redirections(
+    block: &RecoveredBlock<Block>,
+    receipts: &[Receipt],
+    redirect: Option<(AndeMevRedirect, u64)>,
+    epoch: Option<u64>,
+) -> Vec<MevEventRecord> {
+    let header = block.header();
+    let Some((redirect, activation_block)) = redirect else {
+        return Vec::new();
+    };
+    if header.number() < activation_block {
+        return Vec::new();
+    }
+    let base_fee = header.base_fee_per_gas().unwrap_or_default();
+
+    let mut previous_cumulative = 0;
+    let (mut value, mut gas_used, mut transactions) = (U256::ZERO, 0, 0);
+    for receipt in receipts {
+        let tx_gas = receipt.cumulative_gas_used() - previous_cumulative;
+        previous_cumulative = receipt.cumulative_gas_used();
+
+        let detection = redirect.detect(base_fee, tx_gas);
+        if !detection.profit.is_zero() {
+            value += detection.profit;
+            gas_used += tx_gas;
+            transactions += 1;
+        }
+    }
+
+    if value.is_zero() {
+        return Vec::new();
+    }
+    vec![MevEventRecord::from_redirected_block(
+        header.number(),
+        block.hash(),
+        header.timestamp(),
+        epoch,
+        value,
+        gas_used,
+        transactions,
+    )]
+}
+
+/// Epoch to attribute new events to, if a connected distributor knows it
+async fn current_epoch(distributor: Option<&MevDistributorClient>) -> Option<u64> {
+    match distributor.filter(|distributor| distributor.has_provider()) {
+        Some(distributor) => Some(distributor.cached_epoch().await),
+        None => None,
+    }
+}
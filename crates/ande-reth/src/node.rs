@@ -12,6 +12,8 @@
 
 use crate::executor::AndeExecutorBuilder;
 use crate::consensus::AndeConsensusBuilder;
+use crate::engine::AndeEngineValidatorBuilder;
+use crate::payload::AndePayloadBuilderBuilder;
 use reth_chainspec::ChainSpec;
 use reth_ethereum::{
     node::{
@@ -29,9 +31,7 @@ use reth_ethereum_primitives::EthPrimitives;
 use reth_node_builder::NodeAdapter;
 use reth_node_ethereum::{
     EthEngineTypes,
-    EthereumEngineValidatorBuilder,
     EthereumNetworkBuilder,
-    EthereumPayloadBuilder,
 };
 use reth_provider::EthStorage;
 
@@ -47,6 +47,7 @@ use reth_provider::EthStorage;
 /// ✅ Token Duality Precompile (0xFD)
 /// ✅ Custom EVM Factory (AndeEvmFactory)
 /// ✅ Custom Precompile Provider (AndePrecompileProvider)
+/// ✅ MEV Auction Bundle Lane (AndePayloadBuilder)
 ///
 /// ## Future Features:
 /// ⏳ Parallel EVM Execution (Block-STM)
@@ -76,8 +77,9 @@ impl NodeTypes for AndeNode {
 
 /// ANDE node add-ons - RPC and validation configuration
 /// 
-/// Uses standard Ethereum RPC API builder and engine validator for compatibility.
-pub type AndeAddOns<N> = RpcAddOns<N, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>;
+/// Uses the standard Ethereum RPC API builder, and an engine validator that
+/// accepts the proposer seal in `extra_data`.
+pub type AndeAddOns<N> = RpcAddOns<N, EthereumEthApiBuilder, AndeEngineValidatorBuilder>;
 
 /// ANDE Chain Node Implementation
 ///
@@ -85,7 +87,8 @@ pub type AndeAddOns<N> = RpcAddOns<N, EthereumEthApiBuilder, EthereumEngineValid
 /// 
 /// 1. **Custom Executor**: Uses `AndeExecutorBuilder` instead of `EthereumExecutorBuilder`
 /// 2. **Custom EVM**: AndeEvmFactory with Token Duality Precompile
-/// 3. **Same architecture**: Compatible with Reth v1.8.2 and Evolve sequencer
+/// 3. **Custom Payload Builder**: winning auction bundle at the top of each block
+/// 4. **Same architecture**: Compatible with Reth v1.8.2 and Evolve sequencer
 ///
 /// The component structure follows Reth patterns but with ANDE customizations.
 impl<N> Node<N> for AndeNode
@@ -95,7 +98,7 @@ where
     type ComponentsBuilder = ComponentsBuilder<
         N,
         EthereumPoolBuilder,
-        BasicPayloadServiceBuilder<EthereumPayloadBuilder>,
+        BasicPayloadServiceBuilder<AndePayloadBuilderBuilder>,
         EthereumNetworkBuilder,
         AndeExecutorBuilder,
         AndeConsensusBuilder,
@@ -108,7 +111,7 @@ where
             .node_types::<N>()
             .pool(EthereumPoolBuilder::default())
             .executor(AndeExecutorBuilder::default())
-            .payload(BasicPayloadServiceBuilder::new(EthereumPayloadBuilder::default()))
+            .payload(BasicPayloadServiceBuilder::new(AndePayloadBuilderBuilder::default()))
             .network(EthereumNetworkBuilder::default())
             .consensus(AndeConsensusBuilder::default())
     }
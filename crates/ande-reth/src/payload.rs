@@ -0,0 +1,579 @@
+//! ANDE Payload Builder - MEV Bundle Lane
+//!
+//! Wraps the standard Ethereum payload builder and places the winning
+//! MEVAuctionManager bundle for the block at the top of the payload.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! try_build(args)
+//!   ├─ BundleLane::winning_bundle(parent + 1) ── none ──→ EthereumPayloadBuilder
+//!   ├─ check the bundle's timestamp window
+//!   ├─ simulate bundle on the parent state (every tx must succeed
+//!   │  unless listed in revertingTxHashes)
+//!   │      └─ failed ──→ exclude bundle ──→ EthereumPayloadBuilder
+//!   ├─ execute bundle txs first, in order (top of block)
+//!   ├─ fill the rest from the pool, skipping the bundle's txs
+//!   └─ seal the header with the validator key (BFT consensus only)
+//! ```
+//!
+//! Bundles are all-or-nothing: a bundle that cannot be executed in full is
+//! excluded from the lane and rejected on-chain when its block is settled.
+
+use crate::consensus::consensus_engine;
+use crate::mev::{
+    active_redirect, auction_client, discover_canonical_pools, distributor_client, event_store,
+    protocol_registry, record_canonical_mev, settle_canonical_blocks,
+};
+use alloy_consensus::Transaction;
+use ande_evm::mev::{simulate_bundle, BundleSubmission, MevAuctionClient};
+use alloy_primitives::{B256, U256};
+use reth::core::cli::config::PayloadBuilderConfig;
+use reth_basic_payload_builder::{
+    is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
+    PayloadConfig,
+};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_ethereum_engine_primitives::{
+    EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
+};
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, EthereumPayloadBuilder};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{
+    block::CommitChanges,
+    execute::{BlockBuilder, BlockBuilderOutcome},
+    ConfigureEvm, Evm, NextBlockEnvAttributes,
+};
+use reth_node_api::{FullNodeTypes, NodeTypes, PayloadTypes, PrimitivesTy, TxTy};
+use reth_node_builder::{components::PayloadBuilderBuilder, BuilderContext};
+use reth_payload_primitives::PayloadBuilderError;
+use reth_primitives::{InvalidTransactionError, Recovered, SealedBlock, SealedHeader};
+use reth_provider::{ChainSpecProvider, StateProviderFactory};
+use reth_revm::{cached::CachedReads, database::StateProviderDatabase, db::State};
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, BestTransactions, BestTransactionsAttributes,
+    PoolTransaction, TransactionPool,
+};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::mpsc;
+use tracing::{debug, info, trace, warn};
+
+/// ANDE Payload Builder Builder
+///
+/// Component builder used by `AndeNode` in place of reth's
+/// `EthereumPayloadBuilder` component. When `MEV_AUCTION_ADDRESS` is set it
+/// also spawns the task that settles bundles on-chain once blocks are
+/// canonical, and when `MEV_DISTRIBUTOR_ADDRESS` is set the task that
+/// deposits captured MEV into MEVDistributor. Unless MEV detection is
+/// disabled, canonical blocks are also recorded in the MEV event store.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct AndePayloadBuilderBuilder;
+
+impl<Types, Node, Pool, EvmConfig> PayloadBuilderBuilder<Node, Pool, EvmConfig>
+    for AndePayloadBuilderBuilder
+where
+    Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    EvmConfig: ConfigureEvm<Primitives = PrimitivesTy<Types>, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+    Types::Payload: PayloadTypes<
+        BuiltPayload = EthBuiltPayload,
+        PayloadAttributes = EthPayloadAttributes,
+        PayloadBuilderAttributes = EthPayloadBuilderAttributes,
+    >,
+{
+    type PayloadBuilder = AndePayloadBuilder<Pool, Node::Provider, EvmConfig>;
+
+    async fn build_payload_builder(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+        evm_config: EvmConfig,
+    ) -> eyre::Result<Self::PayloadBuilder> {
+        let conf = ctx.payload_builder_config();
+        let chain = ctx.chain_spec().chain();
+        let builder_config = EthereumBuilderConfig::new()
+            .with_gas_limit(conf.gas_limit_for(chain))
+            .with_max_blobs_per_block(conf.max_blobs_per_block())
+            .with_extra_data(conf.extra_data_bytes());
+
+        let distributor = distributor_client(ctx.config().datadir().data_dir());
+        if let Some(distributor) = &distributor {
+            info!("💰 MEV distributor enabled:");
+            info!("   • Distributor: {:?}", distributor.contract_address());
+            info!("   • Pending buffer: {}", distributor.get_buffer_amount().await);
+
+            ctx.task_executor().spawn(Box::pin(Arc::clone(distributor).run()));
+        }
+
+        let registry = protocol_registry(ctx.config().datadir().data_dir());
+        if let Some(registry) = &registry {
+            let snapshot = registry.snapshot();
+            info!("🗂️  Protocol registry loaded:");
+            info!("   • File: {}", registry.path().map(|p| p.display().to_string()).unwrap_or_default());
+            info!("   • DEX routers: {}", snapshot.dex_routers.len());
+            info!("   • Pools: {} declared, {} discovered", snapshot.pools.len(), registry.discovered_pools());
+            info!("   • Lending markets: {}", snapshot.lending_markets.len());
+            info!("   • Oracles: {}", snapshot.oracles.len());
+
+            ctx.task_executor().spawn(Box::pin(Arc::clone(registry).watch()));
+        }
+
+        let store = event_store(ctx.config().datadir().data_dir());
+        let mut settled_tx = None;
+        if let Some(store) = &store {
+            info!("📚 MEV event store: {}", store.path().display());
+
+            let (tx, settled_rx) = mpsc::unbounded_channel();
+            settled_tx = Some(tx);
+            ctx.task_executor().spawn(Box::pin(record_canonical_mev(
+                ctx.provider().clone(),
+                evm_config.clone(),
+                Arc::clone(store),
+                active_redirect(ctx.chain_spec().genesis()),
+                distributor.clone(),
+                registry,
+                settled_rx,
+            )));
+        } else if let Some(registry) = registry {
+            // Nothing is recorded, but pool discovery must not miss deployments
+            ctx.task_executor().spawn(Box::pin(discover_canonical_pools(ctx.provider().clone(), registry)));
+        }
+
+        let auction = auction_client();
+        match &auction {
+            Some(client) => {
+                info!("🎯 MEV bundle lane enabled:");
+                info!("   • Auction Manager: {:?}", client.contract_address());
+                info!("   • Sequencer: {:?}", client.sequencer_address());
+
+                ctx.task_executor().spawn(Box::pin(settle_canonical_blocks(
+                    ctx.provider().clone(),
+                    Arc::clone(client),
+                    distributor,
+                    settled_tx,
+                )));
+            }
+            None => {
+                debug!("MEV bundle lane not configured (set MEV_AUCTION_ADDRESS to enable)");
+            }
+        }
+
+        Ok(AndePayloadBuilder::new(
+            ctx.provider().clone(),
+            pool,
+            evm_config,
+            builder_config,
+            auction,
+        ))
+    }
+}
+
+/// ANDE Payload Builder
+///
+/// Delegates to [`EthereumPayloadBuilder`] unless the auction lane holds an
+/// executable bundle for the block being built.
+#[derive(Debug, Clone)]
+pub struct AndePayloadBuilder<Pool, Client, EvmConfig> {
+    /// Standard Ethereum payload builder
+    inner: EthereumPayloadBuilder<Pool, Client, EvmConfig>,
+    /// Client used to access the parent state
+    client: Client,
+    /// Transaction pool
+    pool: Pool,
+    /// EVM configuration (AndeEvmFactory)
+    evm_config: EvmConfig,
+    /// Payload builder configuration
+    builder_config: EthereumBuilderConfig,
+    /// Auction client holding the bundle lane
+    auction: Option<Arc<MevAuctionClient>>,
+}
+
+impl<Pool, Client, EvmConfig> AndePayloadBuilder<Pool, Client, EvmConfig>
+where
+    Pool: Clone,
+    Client: Clone,
+    EvmConfig: Clone,
+{
+    /// Create a new ANDE payload builder
+    pub fn new(
+        client: Client,
+        pool: Pool,
+        evm_config: EvmConfig,
+        builder_config: EthereumBuilderConfig,
+        auction: Option<Arc<MevAuctionClient>>,
+    ) -> Self {
+        let inner = EthereumPayloadBuilder::new(
+            client.clone(),
+            pool.clone(),
+            evm_config.clone(),
+            builder_config.clone(),
+        );
+
+        Self { inner, client, pool, evm_config, builder_config, auction }
+    }
+}
+
+impl<Pool, Client, EvmConfig> AndePayloadBuilder<Pool, Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    /// Block environment attributes for the payload being built
+    fn next_block_env(
+        &self,
+        parent: &SealedHeader,
+        attributes: &EthPayloadBuilderAttributes,
+    ) -> NextBlockEnvAttributes {
+        NextBlockEnvAttributes {
+            timestamp: attributes.timestamp,
+            suggested_fee_recipient: attributes.suggested_fee_recipient,
+            prev_randao: attributes.prev_randao,
+            gas_limit: self.builder_config.gas_limit(parent.gas_limit),
+            parent_beacon_block_root: attributes.parent_beacon_block_root,
+            withdrawals: Some(attributes.withdrawals.clone()),
+        }
+    }
+
+    /// Simulate a bundle on top of the parent state
+    ///
+    /// Returns the beneficiary balance change caused by the bundle, which is
+    /// the MEV value reported to MEVAuctionManager on settlement.
+    fn simulate_bundle(
+        &self,
+        parent: &SealedHeader,
+        attributes: &EthPayloadBuilderAttributes,
+        bundle: &BundleSubmission,
+        txs: &[Recovered<TransactionSigned>],
+    ) -> Result<U256, String> {
+        let state_provider =
+            self.client.state_by_block_hash(parent.hash()).map_err(|e| e.to_string())?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(&state_provider))
+            .with_bundle_update()
+            .build();
+
+        let evm_env = self
+            .evm_config
+            .next_evm_env(parent, &self.next_block_env(parent, attributes))
+            .map_err(|e| e.to_string())?;
+        let mut evm = self.evm_config.evm_with_env(&mut db, evm_env);
+
+        simulate_bundle(&mut evm, txs, &bundle.reverting_tx_hashes)
+            .map(|simulation| simulation.coinbase_diff)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Build a payload with the bundle at the top of the block
+    ///
+    /// Returns `Ok(None)` if the bundle failed to execute in full, in which
+    /// case nothing from this attempt is used.
+    fn build_with_bundle(
+        &self,
+        cached_reads: &mut CachedReads,
+        config: &PayloadConfig<EthPayloadBuilderAttributes>,
+        cancel_requested: impl Fn() -> bool,
+        best_payload: Option<&EthBuiltPayload>,
+        bundle: &BundleSubmission,
+        bundle_txs: &[Recovered<TransactionSigned>],
+    ) -> Result<Option<BuildOutcome<EthBuiltPayload>>, PayloadBuilderError> {
+        let PayloadConfig { parent_header, attributes } = config;
+        let chain_spec = self.client.chain_spec();
+
+        let state_provider = self.client.state_by_block_hash(parent_header.hash())?;
+        let state = StateProviderDatabase::new(&state_provider);
+        let mut db = State::builder()
+            .with_database(cached_reads.as_db_mut(state))
+            .with_bundle_update()
+            .build();
+
+        let mut builder = self
+            .evm_config
+            .builder_for_next_block(
+                &mut db,
+                parent_header,
+                self.next_block_env(parent_header, attributes),
+            )
+            .map_err(PayloadBuilderError::other)?;
+
+        let block_gas_limit: u64 = builder.evm_mut().block().gas_limit;
+        let base_fee = builder.evm_mut().block().basefee;
+
+        builder.apply_pre_execution_changes().map_err(|err| {
+            warn!(target: "payload_builder", %err, "failed to apply pre-execution changes");
+            PayloadBuilderError::Internal(err.into())
+        })?;
+
+        let mut cumulative_gas_used = 0;
+        let mut total_fees = U256::ZERO;
+
+        // Bundle lane: every transaction must succeed, in order, unless the
+        // searcher allowed it to revert
+        for tx in bundle_txs {
+            let may_revert = bundle.reverting_tx_hashes.contains(tx.hash());
+            let gas_used = builder
+                .execute_transaction_with_commit_condition(tx.clone(), |result| {
+                    if result.is_success() || may_revert {
+                        CommitChanges::Yes
+                    } else {
+                        CommitChanges::No
+                    }
+                })
+                .ok()
+                .flatten();
+
+            let Some(gas_used) = gas_used else {
+                return Ok(None);
+            };
+
+            let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            total_fees += U256::from(tip) * U256::from(gas_used);
+            cumulative_gas_used += gas_used;
+        }
+
+        // Public lane: best pool transactions, without the bundle's own txs
+        let bundle_hashes: HashSet<B256> = bundle_txs.iter().map(|tx| *tx.hash()).collect();
+        let mut best_txs = self
+            .pool
+            .best_transactions_with_attributes(BestTransactionsAttributes::new(base_fee, None));
+
+        while let Some(pool_tx) = best_txs.next() {
+            if bundle_hashes.contains(pool_tx.hash()) {
+                continue
+            }
+
+            // Blob transactions are left for blocks without a bundle
+            if pool_tx.is_eip4844() {
+                continue
+            }
+
+            if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+                best_txs.mark_invalid(
+                    &pool_tx,
+                    InvalidPoolTransactionError::ExceedsGasLimit(
+                        pool_tx.gas_limit(),
+                        block_gas_limit,
+                    ),
+                );
+                continue
+            }
+
+            if cancel_requested() {
+                return Ok(Some(BuildOutcome::Cancelled))
+            }
+
+            let tx = pool_tx.to_consensus();
+            let gas_used = match builder.execute_transaction(tx.clone()) {
+                Ok(gas_used) => gas_used,
+                Err(reth_evm::execute::BlockExecutionError::Validation(
+                    reth_evm::execute::BlockValidationError::InvalidTx { error, .. },
+                )) => {
+                    if error.is_nonce_too_low() {
+                        trace!(target: "payload_builder", %error, ?tx, "skipping nonce too low transaction");
+                    } else {
+                        trace!(target: "payload_builder", %error, ?tx, "skipping invalid transaction and its descendants");
+                        best_txs.mark_invalid(
+                            &pool_tx,
+                            InvalidPoolTransactionError::Consensus(
+                                InvalidTransactionError::TxTypeNotSupported,
+                            ),
+                        );
+                    }
+                    continue
+                }
+                Err(err) => return Err(PayloadBuilderError::evm(err)),
+            };
+
+            let miner_fee = tx
+                .effective_tip_per_gas(base_fee)
+                .expect("fee is always valid; execution succeeded");
+            total_fees += U256::from(miner_fee) * U256::from(gas_used);
+            cumulative_gas_used += gas_used;
+        }
+
+        if !is_better_payload(best_payload, total_fees) {
+            drop(builder);
+            return Ok(Some(BuildOutcome::Aborted {
+                fees: total_fees,
+                cached_reads: std::mem::take(cached_reads),
+            }))
+        }
+
+        let BlockBuilderOutcome { execution_result, block, .. } =
+            builder.finish(&state_provider)?;
+
+        let requests = chain_spec
+            .is_prague_active_at_timestamp(attributes.timestamp)
+            .then_some(execution_result.requests);
+
+        let sealed_block = Arc::new(block.sealed_block().clone());
+        debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "sealed built block with bundle");
+
+        let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests);
+
+        Ok(Some(BuildOutcome::Better { payload, cached_reads: std::mem::take(cached_reads) }))
+    }
+
+    /// Winning bundle with a known body for the next block
+    fn next_bundle(&self, block_number: u64) -> Option<(Arc<MevAuctionClient>, BundleSubmission)> {
+        let auction = self.auction.as_ref()?;
+        let bundle = auction.lane().winning_bundle(block_number)?;
+        Some((Arc::clone(auction), bundle))
+    }
+}
+
+impl<Pool, Client, EvmConfig> PayloadBuilder for AndePayloadBuilder<Pool, Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    type Attributes = EthPayloadBuilderAttributes;
+    type BuiltPayload = EthBuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        Ok(match self.build_unsealed(args)? {
+            BuildOutcome::Better { payload, cached_reads } => {
+                BuildOutcome::Better { payload: seal_payload(payload)?, cached_reads }
+            }
+            BuildOutcome::Freeze(payload) => BuildOutcome::Freeze(seal_payload(payload)?),
+            outcome => outcome,
+        })
+    }
+
+    fn on_missing_payload(
+        &self,
+        args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
+    ) -> MissingPayloadBehaviour<Self::BuiltPayload> {
+        self.inner.on_missing_payload(args)
+    }
+
+    fn build_empty_payload(
+        &self,
+        config: PayloadConfig<Self::Attributes>,
+    ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        seal_payload(self.inner.build_empty_payload(config)?)
+    }
+}
+
+/// Seal a built payload with this node's validator signer
+///
+/// Without BFT consensus or a validator signer blocks are left unsealed. The
+/// seal changes the block hash, so the payload is rebuilt around the sealed
+/// header.
+///
+/// Payloads are built on blocking threads while the signer may be remote, so
+/// the seal is signed on the runtime and waited for here.
+fn seal_payload(payload: EthBuiltPayload) -> Result<EthBuiltPayload, PayloadBuilderError> {
+    let Some(engine) = consensus_engine() else {
+        return Ok(payload)
+    };
+    if !engine.can_seal() {
+        debug!(target: "payload_builder", id=%payload.id(), "no validator signer, payload left unsealed");
+        return Ok(payload)
+    }
+
+    let block = payload.block();
+    let mut header = block.header().clone();
+    let runtime = tokio::runtime::Handle::try_current().map_err(PayloadBuilderError::other)?;
+    let (sealed_tx, sealed_rx) = std::sync::mpsc::sync_channel(1);
+    runtime.spawn(async move {
+        let sealed = engine.seal_header(&mut header).await.map(|()| header);
+        let _ = sealed_tx.send(sealed);
+    });
+    let header = sealed_rx
+        .recv()
+        .map_err(PayloadBuilderError::other)?
+        .map_err(PayloadBuilderError::other)?;
+    let sealed_block = Arc::new(SealedBlock::seal_parts(header, block.body().clone()));
+    trace!(target: "payload_builder", id=%payload.id(), hash=?sealed_block.hash(), "sealed payload header");
+
+    Ok(EthBuiltPayload::new(payload.id(), sealed_block, payload.fees(), payload.requests())
+        .with_sidecars(payload.sidecars().clone()))
+}
+
+impl<Pool, Client, EvmConfig> AndePayloadBuilder<Pool, Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    /// Build a payload, with the winning bundle on top if it executes
+    fn build_unsealed(
+        &self,
+        args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        let block_number = args.config.parent_header.number + 1;
+        let Some((auction, bundle)) = self.next_bundle(block_number) else {
+            return self.inner.try_build(args)
+        };
+
+        let lane = auction.lane();
+        if !bundle.is_valid_at(args.config.attributes.timestamp) {
+            lane.exclude(bundle.bundle_hash, "block timestamp outside bundle window".to_string());
+            return self.inner.try_build(args)
+        }
+
+        let bundle_txs = match bundle.decode_transactions() {
+            Ok(txs) if !txs.is_empty() => txs,
+            Ok(_) => {
+                lane.exclude(bundle.bundle_hash, "bundle body not provided".to_string());
+                return self.inner.try_build(args)
+            }
+            Err(e) => {
+                lane.exclude(bundle.bundle_hash, e.to_string());
+                return self.inner.try_build(args)
+            }
+        };
+
+        let mev_value = match self.simulate_bundle(
+            &args.config.parent_header,
+            &args.config.attributes,
+            &bundle,
+            &bundle_txs,
+        ) {
+            Ok(value) => value,
+            Err(reason) => {
+                warn!(bundle = ?bundle.bundle_hash, %reason, "Bundle simulation failed, excluding from block");
+                lane.exclude(bundle.bundle_hash, format!("simulation failed: {reason}"));
+                return self.inner.try_build(args)
+            }
+        };
+
+        let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+        let outcome = self.build_with_bundle(
+            &mut cached_reads,
+            &config,
+            || cancel.is_cancelled(),
+            best_payload.as_ref(),
+            &bundle,
+            &bundle_txs,
+        )?;
+
+        match outcome {
+            Some(outcome) => {
+                if matches!(outcome, BuildOutcome::Better { .. }) {
+                    info!(
+                        "🎯 Bundle placed at top of block {}: hash={}, bid={}, mev={}",
+                        block_number, bundle.bundle_hash, bundle.bid_amount, mev_value
+                    );
+                    lane.record_built(bundle.bundle_hash, mev_value);
+                }
+                Ok(outcome)
+            }
+            None => {
+                warn!(bundle = ?bundle.bundle_hash, "Bundle failed during block building, excluding from block");
+                lane.exclude(bundle.bundle_hash, "execution failed at top of block".to_string());
+                self.inner.try_build(BuildArguments::new(cached_reads, config, cancel, best_payload))
+            }
+        }
+    }
+}
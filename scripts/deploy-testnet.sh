@@ -478,6 +478,37 @@ deploy_sequencer_coordinator() {
     exit 1
 }
 
+# Grant SLASHER_ROLE to the sequencer nodes
+#
+# Nodes submit the slashing evidence they detect from their own keys, and
+# slashUnscheduledProposal trusts the scheduled proposer the reporter
+# computed off-chain, so only the operated sequencers get the role.
+grant_slasher_roles() {
+    local consensus_address=$1
+
+    if [ "$(get_state "slasher_roles_granted")" == "true" ]; then
+        log_info "SLASHER_ROLE already granted, skipping"
+        return 0
+    fi
+
+    log_info "Granting SLASHER_ROLE to sequencer nodes..."
+
+    local slasher_role=$(cast keccak "SLASHER_ROLE")
+    for sequencer in "$SEQUENCER_1_ADDRESS" "$SEQUENCER_2_ADDRESS" "$SEQUENCER_3_ADDRESS"; do
+        if ! retry_with_backoff $MAX_RETRIES cast send \
+            --rpc-url "$CONSENSUS_RPC_URL" \
+            --private-key "$SEQUENCER_1_PRIVATE_KEY" \
+            "$consensus_address" \
+            "grantRole(bytes32,address)" "$slasher_role" "$sequencer" >> "$LOG_FILE" 2>&1; then
+            log_error "Failed to grant SLASHER_ROLE to $sequencer"
+            exit 1
+        fi
+        log_success "SLASHER_ROLE granted to $sequencer"
+    done
+
+    save_state "slasher_roles_granted" "true"
+}
+
 # Deploy all contracts
 deploy_contracts() {
     log_step "STEP 7: Deploying smart contracts to $CONSENSUS_RPC_URL"
@@ -488,6 +519,9 @@ deploy_contracts() {
     # Deploy AndeSequencerCoordinator
     COORDINATOR_ADDRESS=$(deploy_sequencer_coordinator "$CONSENSUS_ADDRESS")
 
+    # Let the sequencer nodes submit slashing evidence
+    grant_slasher_roles "$CONSENSUS_ADDRESS"
+
     # Update environment file with contract addresses
     log_info "Updating .env.testnet.local with deployed addresses..."
 